@@ -0,0 +1,22 @@
+/* TypeScript file generated from EvmTypes.res by genType. */
+
+/* eslint-disable */
+/* tslint:disable */
+
+import type {t as Address_t} from './Address.gen';
+
+export type accessListItem = { readonly address: Address_t; readonly storageKeys: string[] };
+
+export type withdrawal = {
+  readonly index: string; 
+  readonly validatorIndex: string; 
+  readonly address: Address_t; 
+  readonly amount: string
+};
+
+export type log = {
+  readonly address: Address_t; 
+  readonly data: string; 
+  readonly topics: string[]; 
+  readonly logIndex: number
+};
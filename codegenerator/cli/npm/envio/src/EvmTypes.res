@@ -12,3 +12,48 @@ module Hex = {
 module Abi = {
   type t
 }
+
+/**An item of the access list of an EIP-2930 transaction*/
+@genType
+type accessListItem = {
+  address: Address.t,
+  storageKeys: array<string>,
+}
+
+let accessListItemSchema = S.object((s): accessListItem => {
+  address: s.field("address", Address.schema),
+  storageKeys: s.field("storageKeys", S.array(S.string)),
+})
+
+/**A validator withdrawal included in a block since the Shanghai upgrade.
+The quantities are kept as hex strings, the same way HyperSync returns them*/
+@genType
+type withdrawal = {
+  index: string,
+  validatorIndex: string,
+  address: Address.t,
+  amount: string,
+}
+
+let withdrawalSchema = S.object((s): withdrawal => {
+  index: s.field("index", S.string),
+  validatorIndex: s.field("validatorIndex", S.string),
+  address: s.field("address", Address.schema),
+  amount: s.field("amount", S.string),
+})
+
+/**A log emitted by the transaction, as it's included in the transaction receipt*/
+@genType
+type log = {
+  address: Address.t,
+  data: string,
+  topics: array<string>,
+  logIndex: int,
+}
+
+let logSchema = S.object((s): log => {
+  address: s.field("address", Address.schema),
+  data: s.field("data", S.string),
+  topics: s.field("topics", S.array(S.string)),
+  logIndex: s.field("logIndex", S.int),
+})
@@ -38,6 +38,18 @@ export type genericHandlerWithLoader<loader,handler,eventFilters> = {
   readonly preRegisterDynamicContracts?: boolean
 };
 
+export type genericCall<inputs,outputs,block,transaction> = {
+  readonly inputs: inputs; 
+  readonly outputs: outputs; 
+  readonly from: Address_t; 
+  readonly to: Address_t; 
+  readonly chainId: number; 
+  readonly block: block; 
+  readonly transaction: transaction
+};
+
+export type genericCallHandlerArgs<call,context> = { readonly call: call; readonly context: context };
+
 export type fuelSupplyParams = { readonly subId: string; readonly amount: bigint };
 
 export type fuelTransferParams = {
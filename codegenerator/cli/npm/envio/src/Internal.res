@@ -82,6 +82,45 @@ type eventItem = {
   hasRegisteredDynamicContracts?: bool,
 }
 
+type callInputs
+type callOutputs
+
+@genType
+type genericCall<'inputs, 'outputs, 'block, 'transaction> = {
+  inputs: 'inputs,
+  outputs: 'outputs,
+  from: Address.t,
+  to: Address.t,
+  chainId: int,
+  block: 'block,
+  transaction: 'transaction,
+}
+
+type call = genericCall<callInputs, callOutputs, eventBlock, eventTransaction>
+
+@genType
+type genericCallHandlerArgs<'call, 'context> = {
+  call: 'call,
+  context: 'context,
+}
+
+type callHandlerArgs = genericCallHandlerArgs<call, handlerContext>
+type callHandler = genericHandler<callHandlerArgs>
+
+type callConfig = {
+  name: string,
+  contractName: string,
+  // The function selector, which is also used as the id of the call in the fetch state
+  sighash: string,
+  // The name of the function in the contract abi, which might differ from the call name
+  functionName: string,
+  // Used to convert the positional decoded values to the inputs and outputs records
+  inputNames: array<string>,
+  outputNames: array<string>,
+  paramsRawEventSchema: S.schema<eventParams>,
+  handler: option<callHandler>,
+}
+
 type fuelEventKind =
   | LogData({logId: string, decode: string => eventParams})
   | Mint
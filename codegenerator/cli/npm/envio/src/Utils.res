@@ -300,6 +300,24 @@ module Schema = {
     ->(magic: S.t<Js.Json.t> => S.t<Js.Date.t>)
     ->S.preprocess(_ => {serializer: date => date->magic->Js.Date.toISOString})
 
+  @send external bufferToString: (unknown, string) => string = "toString"
+
+  // Hex strings stored in a bytea column. Postgres returns a Buffer for the column,
+  // or the "\\x" prefixed hex string for rows serialized to json in the db
+  let dbBytea =
+    S.json(~validate=false)
+    ->(magic: S.t<Js.Json.t> => S.t<string>)
+    ->S.preprocess(_ => {
+      parser: bytes => {
+        let hex =
+          Js.typeof(bytes) === "string"
+            ? bytes->(magic: unknown => string)->Js.String2.sliceToEnd(~from=2)
+            : bytes->bufferToString("hex")
+        ("0x" ++ hex)->magic
+      },
+      serializer: hex => ("\\x" ++ hex->magic->Js.String2.sliceToEnd(~from=2))->magic,
+    })
+
   // When trying to serialize data to Json pg type, it will fail with
   // PostgresError: column "params" is of type json but expression is of type boolean
   // If there's bool or null on the root level. It works fine as object field values.
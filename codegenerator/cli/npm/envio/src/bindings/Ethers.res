@@ -102,6 +102,24 @@ module Network = {
   external fromChainId: (~chainId: int) => t = "from"
 }
 
+module FetchRequest = {
+  type t
+
+  @module("ethers") @scope("ethers") @new
+  external make: (~url: string) => t = "FetchRequest"
+
+  @send
+  external setHeader: (t, ~key: string, ~value: string) => unit = "setHeader"
+
+  let makeWithHeaders = (~url, ~headers: dict<string>) => {
+    let request = make(~url)
+    headers
+    ->Js.Dict.entries
+    ->Js.Array2.forEach(((key, value)) => request->setHeader(~key, ~value))
+    request
+  }
+}
+
 module JsonRpcProvider = {
   type t
 
@@ -146,18 +164,96 @@ module JsonRpcProvider = {
     ~options: fallbackProviderOptions,
   ) => t = "FallbackProvider"
 
-  let makeStatic = (~rpcUrl: string, ~network: Network.t, ~priority=?, ~stallTimeout=?): t => {
-    makeWithOptions(~rpcUrl, ~network, ~options={staticNetwork: network, ?priority, ?stallTimeout})
+  @module("ethers") @scope("ethers") @new
+  external makeWithRequest: (
+    ~request: FetchRequest.t,
+    ~network: Network.t,
+    ~options: rpcOptions,
+  ) => t = "JsonRpcProvider"
+
+  type ethersModule
+  @module("ethers") external ethers: ethersModule = "ethers"
+
+  // A plain WebSocketProvider never reconnects once its socket is closed,
+  // so requests are forwarded to a socket which is recreated on the next request after a drop.
+  // Requests in flight when the socket drops are covered by the source's query timeout.
+  let makeReconnectingWebSocket: (
+    ethersModule,
+    ~rpcUrl: string,
+    ~network: Network.t,
+    ~options: rpcOptions,
+  ) => t = %raw(`(ethers, rpcUrl, network, options) => {
+    class ReconnectingWebSocketProvider extends ethers.JsonRpcApiProvider {
+      constructor() {
+        super(network, options);
+        this.socketProvider = null;
+      }
+      getSocketProvider() {
+        if (this.socketProvider === null) {
+          const socketProvider = new ethers.WebSocketProvider(rpcUrl, network, options);
+          const drop = () => {
+            if (this.socketProvider === socketProvider) {
+              this.socketProvider = null;
+              socketProvider.destroy().catch(() => {});
+            }
+          };
+          socketProvider.websocket.addEventListener("close", drop);
+          socketProvider.websocket.addEventListener("error", drop);
+          this.socketProvider = socketProvider;
+        }
+        return this.socketProvider;
+      }
+      _send(payload) {
+        return this.getSocketProvider()._send(payload);
+      }
+      destroy() {
+        if (this.socketProvider !== null) {
+          this.socketProvider.destroy().catch(() => {});
+          this.socketProvider = null;
+        }
+        super.destroy();
+      }
+    }
+    return new ReconnectingWebSocketProvider();
+  }`)
+
+  let isWebSocketUrl = (url: string) =>
+    url->Js.String2.startsWith("ws://") || url->Js.String2.startsWith("wss://")
+
+  let makeStatic = (
+    ~rpcUrl: string,
+    ~network: Network.t,
+    ~priority=?,
+    ~stallTimeout=?,
+    ~headers=?,
+  ): t => {
+    let options = {staticNetwork: network, ?priority, ?stallTimeout}
+    switch headers {
+    | _ if rpcUrl->isWebSocketUrl => ethers->makeReconnectingWebSocket(~rpcUrl, ~network, ~options)
+    | Some(headers) =>
+      makeWithRequest(
+        ~request=FetchRequest.makeWithHeaders(~url=rpcUrl, ~headers),
+        ~network,
+        ~options,
+      )
+    | None => makeWithOptions(~rpcUrl, ~network, ~options)
+    }
   }
 
-  let make = (~rpcUrls: array<string>, ~chainId: int, ~fallbackStallTimeout): t => {
+  let make = (~rpcUrls: array<string>, ~chainId: int, ~fallbackStallTimeout, ~headers=?): t => {
     let network = Network.fromChainId(~chainId)
     switch rpcUrls {
-    | [rpcUrl] => makeStatic(~rpcUrl, ~network)
+    | [rpcUrl] => makeStatic(~rpcUrl, ~network, ~headers?)
     | rpcUrls =>
       makeFallbackProvider(
         ~providers=rpcUrls->Js.Array2.mapi((rpcUrl, index) =>
-          makeStatic(~rpcUrl, ~network, ~priority=index, ~stallTimeout=fallbackStallTimeout)
+          makeStatic(
+            ~rpcUrl,
+            ~network,
+            ~priority=index,
+            ~stallTimeout=fallbackStallTimeout,
+            ~headers?,
+          )
         ),
         ~network,
         ~options={
@@ -185,6 +281,32 @@ module JsonRpcProvider = {
     fields->Obj.magic
   }
 
+  type transactionReceiptLog = {
+    address: Address.t,
+    data: string,
+    topics: array<string>,
+    index: int,
+  }
+
+  type transactionReceipt = {logs: array<transactionReceiptLog>}
+
+  @send
+  external getTransactionReceipt: (t, ~transactionHash: string) => promise<transactionReceipt> =
+    "getTransactionReceipt"
+
+  let makeGetTransactionLogs = (~getTransactionReceiptByHash) => async (log: log): array<
+    EvmTypes.log,
+  > => {
+    let receipt = await getTransactionReceiptByHash(log.transactionHash)
+    // Make it compatible with the transaction logs from HyperSync
+    receipt.logs->Js.Array2.map((receiptLog): EvmTypes.log => {
+      address: receiptLog.address,
+      data: receiptLog.data,
+      topics: receiptLog.topics,
+      logIndex: receiptLog.index,
+    })
+  }
+
   @send
   external getBlockNumber: t => promise<int> = "getBlockNumber"
 
@@ -206,3 +328,29 @@ module JsonRpcProvider = {
   @send
   external getBlock: (t, int) => promise<Js.nullable<block>> = "getBlock"
 }
+
+module WebSocketProvider = {
+  type t
+
+  // The underlying WebSocket connection of the provider
+  type websocket
+
+  @module("ethers") @scope("ethers") @new
+  external make: (~wsUrl: string, ~network: Network.t, ~options: JsonRpcProvider.rpcOptions) => t =
+    "WebSocketProvider"
+
+  // Subscribes to newHeads and calls the listener with every new block number
+  @send
+  external onBlock: (t, @as("block") _, int => unit) => promise<t> = "on"
+
+  @send
+  external onError: (t, @as("error") _, exn => unit) => promise<t> = "on"
+
+  @get external websocket: t => websocket = "websocket"
+
+  @send
+  external onClose: (websocket, @as("close") _, unit => unit) => unit = "addEventListener"
+
+  @send
+  external destroy: t => promise<unit> = "destroy"
+}
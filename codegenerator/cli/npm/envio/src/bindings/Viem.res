@@ -11,6 +11,33 @@ type decodedEvent<'a> = {
 
 @module("viem") external decodeEventLogOrThrow: eventLog => decodedEvent<'a> = "decodeEventLog"
 
+type functionData = {
+  abi: EvmTypes.Abi.t,
+  data: string,
+}
+
+type decodedFunctionData<'a> = {
+  functionName: string,
+  // Positional, undefined for functions without inputs
+  args: option<array<'a>>,
+}
+
+@module("viem")
+external decodeFunctionDataOrThrow: functionData => decodedFunctionData<'a> = "decodeFunctionData"
+
+type functionResult<'a> = {
+  abi: EvmTypes.Abi.t,
+  functionName: string,
+  // Used to pick the overloaded function
+  args: option<array<'a>>,
+  data: string,
+}
+
+// Returns undefined for functions without outputs, the value of a single output
+// and an array of the values for several outputs
+@module("viem")
+external decodeFunctionResultOrThrow: functionResult<'a> => unknown = "decodeFunctionResult"
+
 type hex = EvmTypes.Hex.t
 @module("viem") external toHex: 'a => hex = "toHex"
 @module("viem") external keccak256: hex => hex = "keccak256"
@@ -43,7 +43,9 @@ let currentHistoryFieldsSchema = S.object(s => {
   log_index: s.field("entity_history_log_index", S.int),
 })
 
-let makeHistoryRowSchema: S.t<'entity> => S.t<historyRow<'entity>> = entitySchema => {
+let makeHistoryRowSchema = (entitySchema: S.t<'entity>, ~entityTable): S.t<
+  historyRow<'entity>,
+> => {
   //Maps a schema object for the given entity with all fields nullable except for the id field
   //Keeps any original nullable fields
   let nullableEntitySchema: S.t<Js.Dict.t<unknown>> = S.schema(s =>
@@ -51,6 +53,7 @@ let makeHistoryRowSchema: S.t<'entity> => S.t<historyRow<'entity>> = entitySchem
     | Object({items}) =>
       let nulldict = Js.Dict.empty()
       items->Belt.Array.forEach(({location, schema}) => {
+        let schema = entityTable->isJsonField(location) ? schema->stringifyJson : schema
         let nullableFieldSchema = switch (location, schema->S.classify) {
         | ("id", _)
         | (_, Null(_)) => schema //TODO double check this works for array types
@@ -333,7 +336,61 @@ let fromTable = (table: table, ~schema: S.t<'entity>): t<'entity> => {
   let insertFn: (Postgres.sql, Js.Json.t, ~shouldCopyCurrentEntity: bool) => promise<unit> =
     insertFnString->Table.PostgresInterop.eval
 
-  let schema = makeHistoryRowSchema(schema)
+  let schema = makeHistoryRowSchema(schema, ~entityTable=table)
 
   {table, createInsertFnQuery, schema, schemaRows: S.array(schema), insertFn}
 }
+
+//Immutable entities have no history table. Their rows keep the event which inserted them
+//and the order they were inserted in instead, so the rows of the rolled back events can be
+//deleted on reorgs
+module Immutable = {
+  let chainIdFieldName = "envio_chain_id"
+  let blockTimestampFieldName = "envio_block_timestamp"
+  let blockNumberFieldName = "envio_block_number"
+  let logIndexFieldName = "envio_log_index"
+  let serialFieldName = "envio_serial"
+
+  let fields = [
+    mkField(chainIdFieldName, Integer),
+    mkField(blockTimestampFieldName, Integer),
+    mkField(blockNumberFieldName, Integer),
+    mkField(logIndexFieldName, Integer),
+    mkField(serialFieldName, Serial, ~isIndex=true),
+  ]
+
+  //An entity with the fields of the event which inserted it
+  type row<'entity>
+
+  let makeRow = (entity: 'entity, ~event: historyFields): row<'entity> =>
+    Utils.Dict.merge(
+      entity->(Utils.magic: 'entity => Js.Dict.t<int>),
+      Js.Dict.fromArray([
+        (chainIdFieldName, event.chain_id),
+        (blockTimestampFieldName, event.block_timestamp),
+        (blockNumberFieldName, event.block_number),
+        (logIndexFieldName, event.log_index),
+      ]),
+    )->(Utils.magic: Js.Dict.t<int> => row<'entity>)
+
+  //The serial is left out, since it's set by the db
+  let makeRowSchema = (entitySchema: S.t<'entity>): S.t<row<'entity>> =>
+    S.schema(s =>
+      switch entitySchema->S.classify {
+      | Object({items}) =>
+        let dict = Js.Dict.empty()
+        items->Belt.Array.forEach(({location, schema}) => {
+          dict->Js.Dict.set(location, s.matches(schema))
+        })
+        [chainIdFieldName, blockTimestampFieldName, blockNumberFieldName, logIndexFieldName]
+        ->Belt.Array.forEach(fieldName => {
+          dict->Js.Dict.set(fieldName, s.matches(S.int->S.toUnknown))
+        })
+        dict
+      | _ =>
+        Js.Exn.raiseError(
+          "Failed creating immutable row schema. Expected an object schema for entity",
+        )
+      }
+    )->(Utils.magic: S.t<Js.Dict.t<unknown>> => S.t<row<'entity>>)
+}
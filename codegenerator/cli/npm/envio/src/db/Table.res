@@ -5,6 +5,7 @@ type derived
 @unboxed
 type fieldType =
   | @as("INTEGER") Integer
+  | @as("BIGINT") Int8
   | @as("BOOLEAN") Boolean
   | @as("NUMERIC") Numeric
   | @as("DOUBLE PRECISION") DoublePrecision
@@ -14,8 +15,11 @@ type fieldType =
   | @as("TIMESTAMP WITH TIME ZONE") Timestamp
   | @as("TIMESTAMP") TimestampWithoutTimezone
   | @as("TIMESTAMP WITH TIME ZONE NULL") TimestampWithNullTimezone
+  | @as("BYTEA") Bytea
   | Custom(string)
 
+type indexType = | @as("GIN") Gin
+
 type field = {
   fieldName: string,
   fieldType: fieldType,
@@ -23,6 +27,8 @@ type field = {
   isNullable: bool,
   isPrimaryKey: bool,
   isIndex: bool,
+  //The access method of the index, the default btree when it's not set
+  indexType: option<indexType>,
   linkedEntity: option<string>,
   defaultValue: option<string>,
 }
@@ -41,6 +47,7 @@ let mkField = (
   ~isNullable=false,
   ~isPrimaryKey=false,
   ~isIndex=false,
+  ~indexType=?,
   ~linkedEntity=?,
   fieldName,
   fieldType,
@@ -52,6 +59,7 @@ let mkField = (
     isNullable,
     isPrimaryKey,
     isIndex,
+    indexType,
     linkedEntity,
     defaultValue: default,
   }->Field
@@ -126,6 +134,22 @@ let getNonDefaultFields = table =>
     }
   )
 
+//Hasura serves bytea columns as "\x" prefixed hex strings, so the non array ones are
+//also served as 0x prefixed hex strings by a computed field with the Hex suffix
+let getByteaFieldNames = table =>
+  table
+  ->getFields
+  ->Array.keepMap(field =>
+    switch field {
+    | {fieldType: Bytea, isArray: false} => Some(field->getDbFieldName)
+    | _ => None
+    }
+  )
+
+let getByteaHexFunctionName = (~tableName, ~fieldName) => `${tableName}_${fieldName}_hex`
+
+let getByteaHexComputedFieldName = fieldName => fieldName ++ "Hex"
+
 let getLinkedEntityFields = table =>
   table.fields->Array.keepMap(field =>
     switch field {
@@ -184,6 +208,47 @@ type sqlParams<'entity> = {
   hasArrayField: bool,
 }
 
+//Json values are stringified explicitly, since the driver would write a string
+//as it is instead of a json string and an array as a postgres array
+let jsonDbSchema =
+  S.json(~validate=false)
+  ->S.transform(_ => {
+    parser: json => json,
+    serializer: json => json->Js.Json.stringify->Js.Json.string,
+  })
+  ->S.toUnknown
+
+let rec stringifyJson = (schema: S.t<unknown>): S.t<unknown> =>
+  switch schema->S.classify {
+  | JSON(_) => jsonDbSchema
+  | Null(child) => S.null(child->stringifyJson)->S.toUnknown
+  | _ => schema
+  }
+
+//Checked by the column type, since the Timestamp and bytea schemas
+//are json schemas as well
+let isJsonField = (table, dbFieldName) =>
+  switch table->getFieldByDbName(dbFieldName) {
+  | Some(Field({fieldType: JsonB})) => true
+  | _ => false
+  }
+
+//The entity schema with its json fields stringified, for writing the entities
+//without the unnest query
+let stringifyJsonFields = (schema: S.t<'entity>, ~table): S.t<'entity> =>
+  S.schema(s =>
+    switch schema->S.classify {
+    | Object({items}) =>
+      let dict = Js.Dict.empty()
+      items->Belt.Array.forEach(({location, schema}) => {
+        let schema = table->isJsonField(location) ? schema->stringifyJson : schema
+        dict->Js.Dict.set(location, s.matches(schema))
+      })
+      dict
+    | _ => Js.Exn.raiseError("Failed stringifying json fields. Expected an object schema")
+    }
+  )->(Utils.magic: S.t<dict<unknown>> => S.t<'entity>)
+
 let toSqlParams = (table: table, ~schema) => {
   let quotedFieldNames = []
   let quotedNonPrimaryFieldNames = []
@@ -195,6 +260,11 @@ let toSqlParams = (table: table, ~schema) => {
     | Object({items}) =>
       let dict = Js.Dict.empty()
       items->Belt.Array.forEach(({location, inlinedLocation, schema}) => {
+        let field = switch table->getFieldByDbName(location) {
+        | Some(field) => field
+        | None => raise(NonExistingTableField(location))
+        }
+
         let rec coerceSchema = schema =>
           switch schema->S.classify {
           | BigInt => BigInt.schema->S.toUnknown
@@ -205,9 +275,13 @@ let toSqlParams = (table: table, ~schema) => {
               hasArrayField := true
               S.array(child->coerceSchema)->S.toUnknown
             }
-          | JSON(_) => {
-              hasArrayField := true
-              schema
+          | JSON(_) =>
+            switch field {
+            | Field({fieldType: JsonB}) => jsonDbSchema
+            | _ => {
+                hasArrayField := true
+                schema
+              }
             }
           | Bool =>
             // Workaround for https://github.com/porsager/postgres/issues/471
@@ -215,11 +289,6 @@ let toSqlParams = (table: table, ~schema) => {
           | _ => schema
           }
 
-        let field = switch table->getFieldByDbName(location) {
-        | Some(field) => field
-        | None => raise(NonExistingTableField(location))
-        }
-
         quotedFieldNames
         ->Js.Array2.push(inlinedLocation)
         ->ignore
@@ -261,13 +330,13 @@ let toSqlParams = (table: table, ~schema) => {
 }
 
 /*
-Gets all single indicies
+Gets all single indicies using the default btree
 And maps the fields defined to their actual db name (some have _id suffix)
 */
 let getSingleIndices = (table): array<string> => {
   let indexFields = table.fields->Array.keepMap(field =>
     switch field {
-    | Field(field) if field.isIndex => Some(field->getDbFieldName)
+    | Field({isIndex: true, indexType: None} as field) => Some(field->getDbFieldName)
     | _ => None
     }
   )
@@ -284,6 +353,20 @@ let getSingleIndices = (table): array<string> => {
   ->Js.Array2.sortInPlace
 }
 
+/*
+Gets all single indicies with an index type
+And maps the fields defined to their actual db name (some have _id suffix)
+*/
+let getTypedSingleIndices = (table): array<(string, indexType)> => {
+  table.fields->Array.keepMap(field =>
+    switch field {
+    | Field({isIndex: true, indexType: Some(indexType)} as field) =>
+      Some((field->getDbFieldName, indexType))
+    | _ => None
+    }
+  )
+}
+
 /*
 Gets all composite indicies
 And maps the fields defined to their actual db name (some have _id suffix)
@@ -251,6 +251,12 @@ module QueryTypes = {
      *  it won't overshoot by too much.
      */
     maxNumTraces?: int,
+    /**
+     * Whether to include all blocks regardless of if they are related to a returned transaction or log.
+     *  Normally the server will return only the blocks that are related to the transaction or logs in the response.
+     *  But if this is set to true, the server will return data for all blocks in the requested range [from_block, to_block).
+     */
+    includeAllBlocks?: bool,
     /**
      * Selects join mode for the query,
      * Default: join in this order logs -> transactions -> traces -> blocks
@@ -356,6 +362,30 @@ module ResponseTypes = {
     topics?: array<Js.Nullable.t<EvmTypes.Hex.t>>,
   }
 
+  type trace = {
+    from?: Address.t,
+    @as("to") to_?: Address.t,
+    callType?: string,
+    gas?: bigint,
+    input?: string,
+    init?: string,
+    value?: bigint,
+    author?: Address.t,
+    rewardType?: string,
+    blockHash?: string,
+    blockNumber?: int,
+    address?: Address.t,
+    code?: string,
+    gasUsed?: bigint,
+    output?: string,
+    subtraces?: int,
+    traceAddress?: array<int>,
+    transactionHash?: string,
+    transactionPosition?: int,
+    kind?: string,
+    error?: string,
+  }
+
   type event = {
     transaction?: transaction,
     block?: block,
@@ -399,14 +429,38 @@ module ResponseTypes = {
     /** Rollback guard, supposed to be used to detect rollbacks */
     rollbackGuard: option<rollbackGuard>,
   }
+
+  type queryResponseData = {
+    blocks: array<block>,
+    transactions: array<transaction>,
+    logs: array<log>,
+    traces: array<trace>,
+  }
+
+  type queryResponse = {
+    /** Current height of the source hypersync instance */
+    archiveHeight: option<int>,
+    /**
+     * Next block to query for, the responses are paginated so,
+     *  the caller should continue the query from this block if they
+     *  didn't get responses up to the to_block they specified in the Query.
+     */
+    nextBlock: int,
+    /** Total time it took the hypersync instance to execute the query. */
+    totalExecutionTime: int,
+    /** Response data */
+    data: queryResponseData,
+    /** Rollback guard, supposed to be used to detect rollbacks */
+    rollbackGuard: option<rollbackGuard>,
+  }
 }
 
 type query = QueryTypes.query
 type eventResponse = ResponseTypes.eventResponse
+type queryResponse = ResponseTypes.queryResponse
 
 //Todo, add bindings for these types
 type streamConfig
-type queryResponse
 type queryResponseStream
 type eventStream
 type t = {
@@ -1,6 +1,8 @@
-use crate::constants::project_paths::{DEFAULT_CONFIG_PATH, DEFAULT_GENERATED_PATH};
+use crate::constants::project_paths::{
+    DEFAULT_CONFIG_PATH, DEFAULT_FIXTURE_PATH, DEFAULT_GENERATED_PATH,
+};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use clap_markdown::MarkdownOptions;
 use strum::{Display, EnumIter, EnumString};
 use subenum::subenum;
@@ -38,6 +40,11 @@ pub struct ProjectPaths {
     ///The file in the project containing config.
     #[arg(global = true, long, default_value_t=String::from(DEFAULT_CONFIG_PATH))]
     pub config: String,
+
+    ///The config profile to apply, eg. dev, staging or prod. Can also be set with the
+    ///ENVIO_PROFILE env var
+    #[arg(global = true, long)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -46,7 +53,7 @@ pub enum CommandType {
     Init(InitArgs),
 
     /// Development commands for starting, stopping, and restarting the indexer with automatic codegen for any changed files
-    Dev,
+    Dev(DevArgs),
 
     /// Stop the local environment - delete the database and stop all processes (including Docker) for the current directory
     Stop,
@@ -54,6 +61,13 @@ pub enum CommandType {
     ///Generate indexing code from user-defined configuration & schema files
     Codegen,
 
+    ///Validate the configuration & schema files and report all errors without running codegen
+    Validate(ValidateArgs),
+
+    ///Record the logs of the configured contracts with their selected block and transaction
+    ///fields into a fixture that can be replayed offline with the file_config network option
+    Record(RecordArgs),
+
     ///Prints a summary of the benchmark data after running the indexer
     ///with envio start --bench flag or setting 'ENVIO_SAVE_BENCHMARK_DATA=true'
     BenchmarkSummary,
@@ -98,6 +112,45 @@ pub struct StartArgs {
     pub bench: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct DevArgs {
+    ///Answer yes to prompts, like resetting the database for schema changes that can't be
+    ///migrated. Without a terminal, prompts are answered with no unless this is set
+    #[arg(short = 'y', long, action)]
+    pub yes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    ///The format that diagnostics are printed in
+    #[arg(long, default_value_t = ValidateFormat::Human)]
+    #[clap(value_enum)]
+    pub format: ValidateFormat,
+}
+
+#[derive(Debug, Clone, ValueEnum, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ValidateFormat {
+    Human,
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct RecordArgs {
+    ///The id of the network to record. Required when the config has multiple networks
+    #[arg(long)]
+    pub chain_id: Option<u64>,
+    ///The first block to record. Defaults to the start_block of the network
+    #[arg(long)]
+    pub from_block: Option<u64>,
+    ///The last block to record (inclusive)
+    #[arg(long)]
+    pub to_block: u64,
+    ///The directory within the project that the fixture is written to
+    #[arg(long, default_value_t = String::from(DEFAULT_FIXTURE_PATH))]
+    pub fixture_directory: String,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum LocalCommandTypes {
     /// Local Envio and ganache environment commands
@@ -124,6 +177,8 @@ pub enum DbMigrateSubcommands {
     Down,
     ///Setup database by dropping schema and then running migrations
     Setup,
+    ///Print the SQL that would migrate the database to the latest schema without a reset
+    Plan,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -169,11 +224,14 @@ pub enum InitFlow {
 
 pub mod evm {
     use crate::{
-        config_parsing::chain_helpers::{Network, NetworkWithExplorer},
+        config_parsing::{
+            chain_helpers::{Network, NetworkWithExplorer},
+            chain_registry,
+        },
         evm, init_config,
     };
 
-    use anyhow::Context;
+    use anyhow::anyhow;
     use clap::{Args, Subcommand};
     use std::str::FromStr;
     use strum::{Display, EnumIter, EnumString};
@@ -235,13 +293,20 @@ pub mod evm {
     pub enum NetworkOrChainId {
         NetworkName(Network),
         ChainId(u64),
+        ///The name of a chain from a chains.yaml file. It's resolved after parsing
+        ///the args, once the chains of the project directory are loaded
+        CustomChainName(String),
     }
 
-    impl From<NetworkOrChainId> for u64 {
-        fn from(value: NetworkOrChainId) -> Self {
-            match value {
-                NetworkOrChainId::ChainId(val) => val,
-                NetworkOrChainId::NetworkName(name) => name as u64,
+    impl NetworkOrChainId {
+        pub fn get_chain_id(&self) -> anyhow::Result<u64> {
+            match self {
+                NetworkOrChainId::ChainId(chain_id) => Ok(*chain_id),
+                NetworkOrChainId::NetworkName(network) => Ok(network.get_network_id()),
+                NetworkOrChainId::CustomChainName(name) => chain_registry::get()
+                    .get_by_name(name)
+                    .map(|chain| chain.id)
+                    .ok_or_else(|| anyhow!("Invalid network name or id \"{}\"", name)),
             }
         }
     }
@@ -250,14 +315,11 @@ pub mod evm {
         type Err = anyhow::Error;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let res_network: Result<Network, _> = s.parse();
-
-            match res_network {
-                Ok(n) => Ok(NetworkOrChainId::NetworkName(n)),
-                Err(_) => {
-                    let chain_id: u64 = s.parse().context("Invalid network name or id")?;
-                    Ok(NetworkOrChainId::ChainId(chain_id))
-                }
+            match (s.parse::<Network>(), s.parse::<u64>()) {
+                (Ok(network), _) => Ok(NetworkOrChainId::NetworkName(network)),
+                (Err(_), Ok(chain_id)) => Ok(NetworkOrChainId::ChainId(chain_id)),
+                (Err(_), Err(_)) if s.is_empty() => Err(anyhow!("Invalid network name or id")),
+                (Err(_), Err(_)) => Ok(NetworkOrChainId::CustomChainName(s.to_string())),
             }
         }
     }
@@ -373,4 +435,21 @@ mod test {
             "Please run 'make update-generated-docs'"
         );
     }
+
+    #[test]
+    fn custom_chain_names_are_resolved_after_parsing() {
+        use evm::NetworkOrChainId;
+
+        let chain_id = |s: &str| s.parse::<NetworkOrChainId>().unwrap().get_chain_id().ok();
+
+        assert_eq!(chain_id("ethereum-mainnet"), Some(1));
+        assert_eq!(chain_id("7777777701"), Some(7777777701));
+        //Unknown names aren't in the chain registry, which isn't initialized in tests
+        assert!(matches!(
+            "my-appchain".parse::<NetworkOrChainId>().unwrap(),
+            NetworkOrChainId::CustomChainName(name) if name == "my-appchain"
+        ));
+        assert_eq!(chain_id("my-appchain"), None);
+        assert!("".parse::<NetworkOrChainId>().is_err());
+    }
 }
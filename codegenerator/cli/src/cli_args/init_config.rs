@@ -58,6 +58,7 @@ pub mod evm {
                         event: EvmAbi::event_signature_from_abi_event(&event),
                         name: None,
                         field_selection: None,
+                        filters: None,
                     })
                     .collect();
 
@@ -72,6 +73,9 @@ pub mod evm {
                             abi_file_path: None,
                             handler,
                             events,
+                            calls: None,
+                            block_handlers: None,
+                            factory: None,
                         },
                     };
 
@@ -92,6 +96,9 @@ pub mod evm {
                         abi_file_path: None,
                         handler,
                         events,
+                        calls: None,
+                        block_handlers: None,
+                        factory: None,
                     })
                 };
 
@@ -107,39 +114,48 @@ pub mod evm {
                         .entry(selected_network.network.get_network_id())
                         .or_insert({
                             let rpc_config = match &selected_network.network {
-                                NetworkKind::Supported(_) => None,
+                                NetworkKind::Supported(_) | NetworkKind::Custom(_) => None,
                                 NetworkKind::Unsupported { rpc_url, .. } => Some(RpcConfig {
                                     url: rpc_url.clone().into(),
+                                    ws_url: None,
+                                    headers: None,
                                     sync_config: None,
+                                    role: None,
+                                    priority: None,
                                 }),
                             };
 
-                            let end_block = match selected_network.network {
+                            let end_block = match &selected_network.network {
                                 NetworkKind::Supported(network) => {
-                                    chain_helpers::Network::from(network).get_finite_end_block()
+                                    chain_helpers::Network::from(*network).get_finite_end_block()
                                 }
+                                NetworkKind::Custom(chain) => chain.end_block,
                                 NetworkKind::Unsupported { network_id, .. } => {
-                                    chain_helpers::Network::from_network_id(network_id)
-                                        .ok()
-                                        .map(|network| network.get_finite_end_block())
-                                        .flatten()
+                                    chain_helpers::get_finite_end_block_from_id(*network_id)
                                 }
                             };
 
                             Network {
                                 id: selected_network.network.get_network_id(),
                                 hypersync_config: None,
+                                file_config: None,
+                                source_stall_timeout: None,
                                 rpc_config,
                                 start_block: selected_network.network.get_start_block(),
                                 end_block,
                                 confirmed_block_threshold: None,
                                 contracts: Vec::new(),
+                                block_handlers: None,
+                                field_selection: None,
+                                raw_events: None,
                             }
                         });
 
                     let contract = NetworkContract {
                         name: selected_contract.name.clone(),
                         address,
+                        start_block: None,
+                        end_block: None,
                         config: config.clone(),
                     };
 
@@ -161,6 +177,8 @@ pub mod evm {
                 description: None,
                 ecosystem: None,
                 schema: None,
+                include: None,
+                profiles: None,
                 contracts,
                 networks: networks_map.into_values().sorted_by_key(|v| v.id).collect(),
                 unordered_multichain_mode: Some(true),
@@ -282,6 +300,8 @@ pub mod fuel {
                                     .map(|a| a.to_string())
                                     .collect::<Vec<String>>()
                                     .into(),
+                                start_block: None,
+                                end_block: None,
                                 config: Some(ContractConfig {
                                     abi_file_path: selected_contract.get_vendored_abi_file_path(),
                                     handler: init_config.language.get_event_handler_directory(),
@@ -298,6 +318,8 @@ pub mod fuel {
                 description: None,
                 ecosystem: EcosystemTag::Fuel,
                 schema: None,
+                include: None,
+                profiles: None,
                 contracts: None,
                 raw_events: None,
                 networks: network_configs,
@@ -13,10 +13,11 @@ use crate::{
     cli_args::interactive_init::validation::filter_duplicate_events,
     config_parsing::{
         chain_helpers::{HypersyncNetwork, Network, NetworkWithExplorer},
+        chain_registry::{self, CustomChain},
         contract_import::{
             contract_import,
             converters::{self, ContractImportNetworkSelection, SelectedContract},
-            ContractImportResult,
+            explorer_contract_import, ContractImportResult,
         },
         system_config::EvmAbi,
     },
@@ -82,10 +83,20 @@ impl ContractImportArgs {
 
     async fn get_selected_contract(
         &self,
-        network: &NetworkWithExplorer,
+        network: &ExplorerChain,
         contract_address: Address,
     ) -> anyhow::Result<SelectedContract> {
-        let result = match contract_import(network, &contract_address, 0).await {
+        let import_result = match network {
+            ExplorerChain::Network(network) => contract_import(network, &contract_address, 0).await,
+            ExplorerChain::Custom(chain) => {
+                explorer_contract_import(
+                    chain.explorer_api_url.as_deref().unwrap_or_default(),
+                    &contract_address,
+                )
+                .await
+            }
+        };
+        let result = match import_result {
             Ok(ContractImportResult::Contract(contract_data)) => Ok(contract_data),
             Ok(ContractImportResult::NotVerified) => {
                 Err("Failed to find the verified contract on a block explorer.".to_string())
@@ -108,7 +119,7 @@ impl ContractImportArgs {
                     ..self.clone()
                 })
                 .get_contract_import_selection_from_local_import_args(&LocalImportArgs {
-                    blockchain: Some(NetworkOrChainId::ChainId(network.clone() as u64)),
+                    blockchain: Some(NetworkOrChainId::ChainId(network.get_network_id())),
                     ..LocalImportArgs::default()
                 })
                 .await;
@@ -120,7 +131,7 @@ impl ContractImportArgs {
             abi_events = prompt_abi_events_selection(abi_events)?;
         }
 
-        let network_kind = get_converter_network_u64(network.clone() as u64, &None, &None)?;
+        let network_kind = get_converter_network_u64(network.get_network_id(), &None, &None)?;
 
         let network_selection = ContractImportNetworkSelection::new(network_kind, contract_address);
 
@@ -141,7 +152,7 @@ impl ContractImportArgs {
         &self,
         explorer_import_args: &ExplorerImportArgs,
     ) -> Result<SelectedContract> {
-        let network_with_explorer: NetworkWithExplorer = explorer_import_args
+        let network_with_explorer: ExplorerChain = explorer_import_args
             .get_network_with_explorer()
             .context("Failed getting NetworkWithExplorer")?;
 
@@ -189,6 +200,7 @@ impl ContractImportArgs {
 enum NetworkSelection {
     EnterNetworkId,
     Network(HypersyncNetwork),
+    Custom(CustomChain),
 }
 
 impl fmt::Display for NetworkSelection {
@@ -196,6 +208,33 @@ impl fmt::Display for NetworkSelection {
         match self {
             Self::EnterNetworkId => write!(f, "<Enter Network Id>"),
             Self::Network(network) => write!(f, "{}", network.get_pretty_name()),
+            Self::Custom(chain) => write!(f, "{} ({})", chain.name, chain.id),
+        }
+    }
+}
+
+///A chain to import a contract from. Either one of our supported networks or
+///a chain from a chains.yaml file with an explorer API
+#[derive(Debug, Clone)]
+enum ExplorerChain {
+    Network(NetworkWithExplorer),
+    Custom(CustomChain),
+}
+
+impl ExplorerChain {
+    fn get_network_id(&self) -> u64 {
+        match self {
+            Self::Network(network) => *network as u64,
+            Self::Custom(chain) => chain.id,
+        }
+    }
+}
+
+impl fmt::Display for ExplorerChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(network) => write!(f, "{}", network),
+            Self::Custom(chain) => write!(f, "{} ({})", chain.name, chain.id),
         }
     }
 }
@@ -219,6 +258,16 @@ fn prompt_for_network_id(
             !already_selected_ids.contains(&network_id)
         })
         .map(|n| NetworkSelection::Network(n))
+        //Chains from a chains.yaml file with a HyperSync url
+        .chain(
+            chain_registry::get()
+                .iter()
+                .filter(|chain| {
+                    chain.hypersync_url.is_some() && !already_selected_ids.contains(&chain.id)
+                })
+                .cloned()
+                .map(NetworkSelection::Custom),
+        )
         .collect::<Vec<_>>();
 
     //User's options to either enter an id or select a supported network
@@ -248,6 +297,7 @@ fn prompt_for_network_id(
         //parse it back to a supported network since it was serialized as a
         //string
         NetworkSelection::Network(network) => converters::NetworkKind::Supported(network),
+        NetworkSelection::Custom(chain) => converters::NetworkKind::Custom(chain),
     };
 
     Ok(selected)
@@ -264,9 +314,14 @@ fn get_converter_network_u64(
     let maybe_supported_network =
         Network::from_network_id(network_id).and_then(|n| Ok(HypersyncNetwork::try_from(n)?));
 
-    let network = match maybe_supported_network {
-        Ok(s) => converters::NetworkKind::Supported(s),
-        Err(_) => {
+    let custom_chain = chain_registry::get()
+        .get_by_id(network_id)
+        .filter(|chain| chain.hypersync_url.is_some());
+
+    let network = match (maybe_supported_network, custom_chain) {
+        (Ok(s), _) => converters::NetworkKind::Supported(s),
+        (Err(_), Some(chain)) => converters::NetworkKind::Custom(chain.clone()),
+        (Err(_), None) => {
             let rpc_url = match rpc_url {
                 Some(r) => r.clone(),
                 None => prompt_for_rpc_url()?,
@@ -313,11 +368,20 @@ fn prompt_for_rpc_url() -> Result<String> {
 impl ExplorerImportArgs {
     ///Either take the NetworkWithExplorer value from the cli args or prompt
     ///for a user to select one.
-    fn get_network_with_explorer(&self) -> Result<NetworkWithExplorer> {
+    fn get_network_with_explorer(&self) -> Result<ExplorerChain> {
         let chosen_network = match &self.blockchain {
-            Some(chain) => chain.clone(),
+            Some(chain) => ExplorerChain::Network(chain.clone()),
             None => {
-                let options = NetworkWithExplorer::iter().collect();
+                let options = NetworkWithExplorer::iter()
+                    .map(ExplorerChain::Network)
+                    .chain(
+                        chain_registry::get()
+                            .iter()
+                            .filter(|chain| chain.explorer_api_url.is_some())
+                            .cloned()
+                            .map(ExplorerChain::Custom),
+                    )
+                    .collect();
 
                 Select::new(
                     "Which blockchain would you like to import a contract from?",
@@ -380,7 +444,7 @@ impl LocalImportArgs {
     fn get_network(&self) -> Result<converters::NetworkKind> {
         match &self.blockchain {
             Some(b) => {
-                let network_id: u64 = (b.clone()).into();
+                let network_id = b.get_chain_id()?;
                 get_converter_network_u64(network_id, &self.rpc_url, &self.start_block)
             }
             None => prompt_for_network_id(&self.rpc_url, &self.start_block, vec![]),
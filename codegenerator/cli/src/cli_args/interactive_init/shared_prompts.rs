@@ -113,8 +113,8 @@ enum AddNewContractOption {
 }
 
 fn prompt_add_new_contract_option(
-    contract_name: &String,
-    network: &String,
+    contract_name: &str,
+    network: &str,
     can_add_network: bool,
 ) -> Result<AddNewContractOption> {
     let mut options = AddNewContractOption::iter().collect::<Vec<_>>();
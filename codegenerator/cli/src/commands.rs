@@ -32,14 +32,14 @@ async fn execute_command(
 pub mod rescript {
     use super::execute_command;
     use anyhow::Result;
-    use std::path::PathBuf;
+    use std::path::Path;
 
-    pub async fn clean(path: &PathBuf) -> Result<std::process::ExitStatus> {
+    pub async fn clean(path: &Path) -> Result<std::process::ExitStatus> {
         let args = vec!["rescript", "clean"];
         execute_command("pnpm", args, path).await
     }
 
-    pub async fn build(path: &PathBuf) -> Result<std::process::ExitStatus> {
+    pub async fn build(path: &Path) -> Result<std::process::ExitStatus> {
         let args = vec!["rescript"];
         execute_command("pnpm", args, path).await
     }
@@ -51,12 +51,12 @@ pub mod codegen {
         config_parsing::system_config::SystemConfig, hbs_templating, template_dirs::TemplateDirs,
     };
     use anyhow::{self, Context, Result};
-    use std::path::PathBuf;
+    use std::path::Path;
 
     use crate::project_paths::ParsedProjectPaths;
     use tokio::fs;
 
-    pub async fn remove_files_except_git(directory: &PathBuf) -> Result<()> {
+    pub async fn remove_files_except_git(directory: &Path) -> Result<()> {
         let mut entries = fs::read_dir(directory).await?;
         while let Some(entry) = entries.next_entry().await? {
             let file_type = entry.file_type().await?;
@@ -76,7 +76,7 @@ pub mod codegen {
         Ok(())
     }
 
-    pub async fn check_and_install_pnpm(current_dir: &PathBuf) -> Result<()> {
+    pub async fn check_and_install_pnpm(current_dir: &Path) -> Result<()> {
         // Check if pnpm is already installed
         let check_pnpm = execute_command("pnpm", vec!["--version"], current_dir).await;
 
@@ -228,7 +228,12 @@ pub mod db_migrate {
     use std::process::ExitStatus;
 
     use super::execute_command;
-    use crate::{persisted_state::PersistedState, project_paths::ParsedProjectPaths};
+    use crate::{
+        persisted_state::{
+            get_public_schema, PersistedState, PersistedStateExists, SchemaMigrationPlan,
+        },
+        project_paths::ParsedProjectPaths,
+    };
 
     pub async fn run_up_migrations(
         project_paths: &ParsedProjectPaths,
@@ -273,6 +278,93 @@ pub mod db_migrate {
             .context("Failed to upsert persisted state table")?;
         Ok(())
     }
+
+    ///Applies non breaking schema changes and then runs the up migrations
+    ///to create any new tables, enums and entity history functions
+    pub async fn run_schema_migration(
+        project_paths: &ParsedProjectPaths,
+        persisted_state: &PersistedState,
+        schema_migration_plan: &SchemaMigrationPlan,
+    ) -> anyhow::Result<()> {
+        schema_migration_plan
+            .apply_to_db()
+            .await
+            .context("Failed applying schema migration statements")?;
+
+        run_up_migrations(project_paths, persisted_state).await
+    }
+
+    pub fn print_breaking_changes(schema_migration_plan: &SchemaMigrationPlan) {
+        println!("The following schema changes are breaking and require resetting the database:");
+        for change in schema_migration_plan.get_breaking_changes() {
+            println!("  - {}", change);
+        }
+    }
+
+    pub async fn print_schema_migration_plan(
+        persisted_state: &PersistedState,
+    ) -> anyhow::Result<()> {
+        let persisted_state_db = PersistedStateExists::read_from_db()
+            .await
+            .context("Failed to read persisted state from the DB")?;
+
+        let persisted_state_db = match persisted_state_db {
+            PersistedStateExists::NotExists => {
+                println!("Db migrations have not been run, use `envio local db-migrate setup`");
+                return Ok(());
+            }
+            PersistedStateExists::Corrupted => {
+                println!(
+                    "Invalid DB persisted state, the database needs to be reset with `envio local \
+                     db-migrate setup`"
+                );
+                return Ok(());
+            }
+            PersistedStateExists::Exists(persisted_state_db) => persisted_state_db,
+        };
+
+        let (should_run_db_migrations, changes_detected) =
+            persisted_state.should_run_db_migrations(&persisted_state_db);
+
+        if !should_run_db_migrations {
+            println!("Database is up to date");
+            return Ok(());
+        }
+
+        let schema_migration_plan =
+            match persisted_state.get_schema_migration_plan(&persisted_state_db) {
+                Some(schema_migration_plan) => schema_migration_plan,
+                None => {
+                    println!(
+                    "Changes to {} require resetting the database with `envio local db-migrate \
+                     setup`",
+                    changes_detected
+                        .iter()
+                        .map(|f| f.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                    return Ok(());
+                }
+            };
+
+        if schema_migration_plan.is_breaking() {
+            print_breaking_changes(&schema_migration_plan);
+            return Ok(());
+        }
+
+        println!("Schema changes:");
+        for change in &schema_migration_plan.changes {
+            println!("  - {}", change);
+        }
+
+        println!("\n-- Run before the up migrations, which create any new tables and enums");
+        for statement in schema_migration_plan.to_sql(&get_public_schema()) {
+            println!("{}", statement);
+        }
+
+        Ok(())
+    }
 }
 
 pub mod benchmark {
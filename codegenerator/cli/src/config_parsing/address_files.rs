@@ -0,0 +1,355 @@
+use super::{
+    human_config::{Addresses, AddressesFile, AddressesJson},
+    system_config::Ecosystem,
+    validation::is_valid_ethereum_address,
+};
+use crate::{
+    fuel,
+    project_paths::{path_utils, ParsedProjectPaths},
+};
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::{collections::HashSet, fs, path::PathBuf};
+
+///Resolves the addresses of a network contract, loading them from a file when it's referenced.
+///Loaded addresses are validated for the ecosystem and duplicates are removed
+pub fn load_addresses(
+    addresses: Addresses,
+    ecosystem: &Ecosystem,
+    project_paths: &ParsedProjectPaths,
+) -> Result<Vec<String>> {
+    let (file_path, loaded_addresses) = match addresses {
+        Addresses::List(addresses) => return Ok(dedup(addresses.into())),
+        Addresses::File(AddressesFile { file }) => {
+            let raw = read_file(&file, project_paths)?;
+            let addresses = if file.to_lowercase().ends_with(".json") {
+                parse_json(&raw).and_then(|value| from_json_file(&value))
+            } else {
+                from_csv(&raw)
+            }
+            .context(format!("EE122: Failed to load addresses from \"{}\"", file))?;
+            (file, addresses)
+        }
+        Addresses::Json(AddressesJson { json, path }) => {
+            let raw = read_file(&json, project_paths)?;
+            let addresses = parse_json(&raw)
+                .and_then(|value| from_json_path(&value, &path))
+                .context(format!(
+                    "EE122: Failed to load addresses at \"{}\" from \"{}\"",
+                    path, json
+                ))?;
+            (json, addresses)
+        }
+    };
+
+    for address in loaded_addresses.iter() {
+        let is_valid = match ecosystem {
+            Ecosystem::Evm => is_valid_ethereum_address(address),
+            Ecosystem::Fuel => fuel::address::Address::new(address).is_ok(),
+        };
+        if !is_valid {
+            return Err(anyhow!(
+                "EE122: The address \"{}\" loaded from \"{}\" isn't valid",
+                address,
+                file_path
+            ));
+        }
+    }
+
+    Ok(dedup(loaded_addresses))
+}
+
+fn read_file(file_path: &str, project_paths: &ParsedProjectPaths) -> Result<String> {
+    let path =
+        path_utils::get_config_path_relative_to_root(project_paths, PathBuf::from(file_path))
+            .context(
+                "Failed to get path to the addresses file relative to the root of the project",
+            )?;
+    fs::read_to_string(&path).context(format!(
+        "EE122: Failed to read the addresses file at \"{}\"",
+        file_path
+    ))
+}
+
+fn parse_json(raw: &str) -> Result<Value> {
+    serde_json::from_str(raw).context("Failed to decode the file as JSON")
+}
+
+///Keeps the first occurrence of every address. Addresses are hex, so the comparison
+///ignores the case to also remove checksummed duplicates
+fn dedup(addresses: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    addresses
+        .into_iter()
+        .filter(|address| seen.insert(address.to_lowercase()))
+        .collect()
+}
+
+///Takes an address per row. When the first row is a header with an "address" column,
+///the column is used, otherwise the first one
+fn from_csv(raw: &str) -> Result<Vec<String>> {
+    let mut rows = raw
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split(',')
+                .map(|cell| cell.trim().trim_matches('"').to_string())
+                .collect::<Vec<_>>()
+        })
+        .peekable();
+
+    let header_column = rows.peek().and_then(|header| {
+        header
+            .iter()
+            .position(|cell| cell.eq_ignore_ascii_case("address"))
+    });
+    let column = match header_column {
+        Some(column) => {
+            rows.next();
+            column
+        }
+        None => 0,
+    };
+
+    rows.enumerate()
+        .map(|(i, row)| {
+            row.get(column)
+                .filter(|cell| !cell.is_empty())
+                .cloned()
+                .ok_or_else(|| anyhow!("Row {} doesn't have an address", i + 1))
+        })
+        .collect()
+}
+
+///Supports a JSON array of addresses and Foundry broadcast files,
+///where the addresses of the deployed contracts are collected
+fn from_json_file(value: &Value) -> Result<Vec<String>> {
+    match value {
+        Value::Array(_) => to_addresses(vec![value]),
+        Value::Object(object) if object.contains_key("transactions") => {
+            let deployed = select(value, "$.transactions[*].contractAddress")?
+                .into_iter()
+                .chain(select(
+                    value,
+                    "$.transactions[*].additionalContracts[*].address",
+                )?)
+                .filter(|address| !address.is_null())
+                .collect();
+            to_addresses(deployed)
+        }
+        _ => Err(anyhow!(
+            "Expected a JSON array of addresses or a Foundry broadcast file. Use the json and \
+             path options to select the addresses from other JSON files"
+        )),
+    }
+}
+
+fn from_json_path(value: &Value, path: &str) -> Result<Vec<String>> {
+    let selected = select(value, path)?;
+    if selected.is_empty() {
+        return Err(anyhow!("Nothing matches the path"));
+    }
+    to_addresses(selected)
+}
+
+fn to_addresses(values: Vec<&Value>) -> Result<Vec<String>> {
+    let mut addresses = vec![];
+    for value in values {
+        match value {
+            Value::String(address) => addresses.push(address.clone()),
+            Value::Array(items) => addresses.extend(to_addresses(items.iter().collect())?),
+            value => return Err(anyhow!("Expected an address string, but got {}", value)),
+        }
+    }
+    Ok(addresses)
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+///Parses the subset of JSONPath needed to point at addresses:
+///$.key, $['key'], $[0], $[*] and $.*
+fn parse_path(path: &str) -> Result<Vec<PathSegment>> {
+    let invalid_path = || {
+        anyhow!(
+            "Invalid path \"{}\". Use a JSONPath like $.Vault.address or $.pools[*].address",
+            path
+        )
+    };
+    let mut rest = path.trim().strip_prefix('$').ok_or_else(invalid_path)?;
+    let mut segments = vec![];
+
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            let key = &after_dot[..end];
+            segments.push(match key {
+                "" => return Err(invalid_path()),
+                "*" => PathSegment::Wildcard,
+                key => PathSegment::Key(key.to_string()),
+            });
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket.find(']').ok_or_else(invalid_path)?;
+            let selector = after_bracket[..end].trim();
+            segments.push(match selector {
+                "*" => PathSegment::Wildcard,
+                selector
+                    if selector.len() >= 2
+                        && (selector.starts_with('\'') || selector.starts_with('"')) =>
+                {
+                    PathSegment::Key(selector[1..selector.len() - 1].to_string())
+                }
+                selector => PathSegment::Index(selector.parse().map_err(|_| invalid_path())?),
+            });
+            rest = &after_bracket[end + 1..];
+        } else {
+            return Err(invalid_path());
+        }
+    }
+
+    Ok(segments)
+}
+
+fn select<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>> {
+    let mut selected = vec![value];
+    for segment in parse_path(path)? {
+        selected = selected
+            .into_iter()
+            .flat_map(|value| match (&segment, value) {
+                (PathSegment::Key(key), Value::Object(object)) => {
+                    object.get(key).into_iter().collect()
+                }
+                (PathSegment::Index(index), Value::Array(items)) => {
+                    items.get(*index).into_iter().collect()
+                }
+                (PathSegment::Wildcard, Value::Array(items)) => items.iter().collect(),
+                (PathSegment::Wildcard, Value::Object(object)) => object.values().collect(),
+                _ => vec![],
+            })
+            .collect();
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dedup, from_csv, from_json_file, from_json_path, parse_path, PathSegment};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    const ADDRESS_1: &str = "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC";
+    const ADDRESS_2: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+
+    #[test]
+    fn parses_json_paths() {
+        assert_eq!(
+            parse_path("$.Vault['address'].pools[0][*]").unwrap(),
+            vec![
+                PathSegment::Key("Vault".to_string()),
+                PathSegment::Key("address".to_string()),
+                PathSegment::Key("pools".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Wildcard,
+            ]
+        );
+        assert_eq!(parse_path("$").unwrap(), vec![]);
+        assert!(parse_path("Vault.address").is_err());
+        assert!(parse_path("$.pools[first]").is_err());
+        assert!(parse_path("$..address").is_err());
+    }
+
+    #[test]
+    fn loads_addresses_from_json_path() {
+        let deployments = json!({
+            "Vault": {"address": ADDRESS_1},
+            "pools": [{"address": ADDRESS_1}, {"address": ADDRESS_2}],
+        });
+
+        assert_eq!(
+            from_json_path(&deployments, "$.Vault.address").unwrap(),
+            vec![ADDRESS_1.to_string()]
+        );
+        assert_eq!(
+            from_json_path(&deployments, "$.pools[*].address").unwrap(),
+            vec![ADDRESS_1.to_string(), ADDRESS_2.to_string()]
+        );
+        assert_eq!(
+            from_json_path(&deployments, "$.Token.address")
+                .unwrap_err()
+                .to_string(),
+            "Nothing matches the path"
+        );
+        assert_eq!(
+            from_json_path(&deployments, "$.Vault")
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Expected an address string, but got {{\"address\":\"{}\"}}",
+                ADDRESS_1
+            )
+        );
+    }
+
+    #[test]
+    fn loads_addresses_from_json_files() {
+        assert_eq!(
+            from_json_file(&json!([ADDRESS_1, ADDRESS_2])).unwrap(),
+            vec![ADDRESS_1.to_string(), ADDRESS_2.to_string()]
+        );
+
+        let foundry_broadcast = json!({
+            "transactions": [
+                {"transactionType": "CREATE", "contractAddress": ADDRESS_1, "additionalContracts": []},
+                {"transactionType": "CALL", "contractAddress": null, "additionalContracts": [
+                    {"transactionType": "CREATE2", "address": ADDRESS_2}
+                ]},
+            ],
+            "receipts": [],
+        });
+        assert_eq!(
+            from_json_file(&foundry_broadcast).unwrap(),
+            vec![ADDRESS_1.to_string(), ADDRESS_2.to_string()]
+        );
+
+        assert!(from_json_file(&json!({"Vault": ADDRESS_1})).is_err());
+    }
+
+    #[test]
+    fn loads_addresses_from_csv() {
+        assert_eq!(
+            from_csv(&format!("{ADDRESS_1}\n\n{ADDRESS_2}\n")).unwrap(),
+            vec![ADDRESS_1.to_string(), ADDRESS_2.to_string()]
+        );
+        assert_eq!(
+            from_csv(&format!(
+                "name,address\n# Pools of the registry\nWETH/DAI,{ADDRESS_1}\n\"DAI/USDC\",\"{ADDRESS_2}\""
+            ))
+            .unwrap(),
+            vec![ADDRESS_1.to_string(), ADDRESS_2.to_string()]
+        );
+        assert_eq!(
+            from_csv(&format!("name,address\nWETH/DAI,{ADDRESS_1}\nDAI/USDC"))
+                .unwrap_err()
+                .to_string(),
+            "Row 2 doesn't have an address"
+        );
+    }
+
+    #[test]
+    fn dedups_addresses_ignoring_case() {
+        assert_eq!(
+            dedup(vec![
+                ADDRESS_1.to_string(),
+                ADDRESS_2.to_string(),
+                ADDRESS_1.to_lowercase(),
+            ]),
+            vec![ADDRESS_1.to_string(), ADDRESS_2.to_string()]
+        );
+    }
+}
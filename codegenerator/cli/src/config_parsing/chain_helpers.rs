@@ -6,6 +6,7 @@ use std::fmt;
 use strum::IntoEnumIterator;
 use subenum::subenum;
 
+use super::chain_registry;
 use crate::constants::DEFAULT_CONFIRMED_BLOCK_THRESHOLD;
 
 #[derive(strum::Display)]
@@ -582,9 +583,24 @@ impl fmt::Display for NetworkWithExplorer {
 }
 
 pub fn get_confirmed_block_threshold_from_id(id: u64) -> i32 {
-    Network::from_network_id(id).map_or(DEFAULT_CONFIRMED_BLOCK_THRESHOLD, |n| {
-        n.get_confirmed_block_threshold()
-    })
+    match Network::from_network_id(id) {
+        Ok(network) => network.get_confirmed_block_threshold(),
+        Err(_) => chain_registry::get()
+            .get_by_id(id)
+            .and_then(|chain| chain.confirmed_block_threshold)
+            .unwrap_or(DEFAULT_CONFIRMED_BLOCK_THRESHOLD),
+    }
+}
+
+/// Returns the end block for the network with the id if it is finite,
+/// including the chains declared in a chains.yaml file
+pub fn get_finite_end_block_from_id(id: u64) -> Option<u64> {
+    match Network::from_network_id(id) {
+        Ok(network) => network.get_finite_end_block(),
+        Err(_) => chain_registry::get()
+            .get_by_id(id)
+            .and_then(|chain| chain.end_block),
+    }
 }
 
 #[cfg(test)]
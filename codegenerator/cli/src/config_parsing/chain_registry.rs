@@ -0,0 +1,264 @@
+use super::chain_helpers::Network;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+pub const CHAINS_FILE_NAME: &str = "chains.yaml";
+
+///A chain declared in a chains.yaml file, so chains which aren't built into the CLI yet
+///can be indexed with their own HyperSync and explorer endpoints
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomChain {
+    pub name: String,
+    pub id: u64,
+    pub hypersync_url: Option<String>,
+    pub explorer_api_url: Option<String>,
+    pub confirmed_block_threshold: Option<i32>,
+    pub end_block: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChainsFile {
+    #[serde(default)]
+    chains: Vec<CustomChain>,
+}
+
+///The chains declared in the user-level ~/.envio/chains.yaml and the project-level
+///chains.yaml. They are used in addition to the chains built into the CLI
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChainRegistry {
+    chains: Vec<CustomChain>,
+}
+
+fn get_user_chains_file_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".envio").join(CHAINS_FILE_NAME))
+}
+
+impl ChainRegistry {
+    ///Loads the user-level chains first, so the project-level ones replace the chains
+    ///with the same id
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let paths = get_user_chains_file_path()
+            .into_iter()
+            .chain([project_root.join(CHAINS_FILE_NAME)]);
+
+        let mut files = vec![];
+        for path in paths {
+            if !path.is_file() {
+                continue;
+            }
+            let source = fs::read_to_string(&path).context(format!(
+                "EE125: Failed to read the chains file at \"{}\"",
+                path.to_string_lossy()
+            ))?;
+            files.push((path, source));
+        }
+        Self::from_files(files)
+    }
+
+    fn from_files(files: Vec<(PathBuf, String)>) -> Result<Self> {
+        let mut registry = Self::default();
+        for (path, source) in files {
+            let file_name = path.to_string_lossy();
+            let chains_file: ChainsFile = serde_yaml::from_str(&source).context(format!(
+                "EE125: Failed to deserialize the chains file \"{}\"",
+                file_name
+            ))?;
+
+            let mut file_ids = vec![];
+            for chain in chains_file.chains {
+                if file_ids.contains(&chain.id) {
+                    return Err(anyhow!(
+                        "EE125: The chain {} is declared more than once in \"{}\"",
+                        chain.id,
+                        file_name
+                    ));
+                }
+                file_ids.push(chain.id);
+                Self::validate_chain(&chain)
+                    .context(format!("EE125: Invalid chain in \"{}\"", file_name))?;
+                registry.chains.retain(|existing| existing.id != chain.id);
+                registry.chains.push(chain);
+            }
+        }
+
+        for (i, chain) in registry.chains.iter().enumerate() {
+            if registry.chains[..i]
+                .iter()
+                .any(|other| other.name == chain.name)
+            {
+                return Err(anyhow!(
+                    "EE125: The chain name \"{}\" is used by more than one chain",
+                    chain.name
+                ));
+            }
+        }
+
+        Ok(registry)
+    }
+
+    fn validate_chain(chain: &CustomChain) -> Result<()> {
+        if chain.name.is_empty() || chain.name.parse::<u64>().is_ok() {
+            return Err(anyhow!(
+                "The chain {} should have a name which isn't a number",
+                chain.id
+            ));
+        }
+        if let Ok(network) = Network::from_network_id(chain.id) {
+            return Err(anyhow!(
+                "The chain {} is already supported as \"{}\"",
+                chain.id,
+                network
+            ));
+        }
+        if let Ok(network) = chain.name.parse::<Network>() {
+            return Err(anyhow!(
+                "The chain name \"{}\" is already used by the chain {}",
+                chain.name,
+                network.get_network_id()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get_by_id(&self, id: u64) -> Option<&CustomChain> {
+        self.chains.iter().find(|chain| chain.id == id)
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&CustomChain> {
+        self.chains.iter().find(|chain| chain.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CustomChain> {
+        self.chains.iter()
+    }
+}
+
+static CHAIN_REGISTRY: OnceLock<ChainRegistry> = OnceLock::new();
+static EMPTY_CHAIN_REGISTRY: ChainRegistry = ChainRegistry { chains: Vec::new() };
+
+///Loads the chain registry of the project directory. Needs to run before anything
+///resolves chains, since the loaded registry can't be replaced afterwards
+pub fn init(project_root: &Path) -> Result<()> {
+    let registry = ChainRegistry::load(project_root)?;
+    CHAIN_REGISTRY
+        .set(registry)
+        .map_err(|_| anyhow!("The chain registry is already initialized"))
+}
+
+///Gets the chain registry loaded by init. Before it's initialized, eg. when the
+///config is parsed without the cli, only the built-in chains are available
+pub fn get() -> &'static ChainRegistry {
+    CHAIN_REGISTRY.get().unwrap_or(&EMPTY_CHAIN_REGISTRY)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChainRegistry, CustomChain};
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn file(name: &str, source: &str) -> (PathBuf, String) {
+        (PathBuf::from(name), source.to_string())
+    }
+
+    #[test]
+    fn project_chains_replace_user_chains_with_the_same_id() {
+        let registry = ChainRegistry::from_files(vec![
+            file(
+                "~/.envio/chains.yaml",
+                r#"
+chains:
+  - name: my-appchain
+    id: 7777777701
+    hypersync_url: https://old.hypersync.example
+  - name: other-appchain
+    id: 7777777702
+"#,
+            ),
+            file(
+                "chains.yaml",
+                r#"
+chains:
+  - name: my-appchain
+    id: 7777777701
+    hypersync_url: https://7777777701.hypersync.example
+    explorer_api_url: https://explorer.example/api
+    confirmed_block_threshold: 0
+    end_block: 1000
+"#,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            registry.get_by_name("my-appchain"),
+            Some(&CustomChain {
+                name: "my-appchain".to_string(),
+                id: 7777777701,
+                hypersync_url: Some("https://7777777701.hypersync.example".to_string()),
+                explorer_api_url: Some("https://explorer.example/api".to_string()),
+                confirmed_block_threshold: Some(0),
+                end_block: Some(1000),
+            })
+        );
+        assert_eq!(
+            registry
+                .get_by_id(7777777702)
+                .map(|chain| chain.name.as_str()),
+            Some("other-appchain")
+        );
+        assert_eq!(registry.iter().count(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_chains() {
+        let err = |source: &str| {
+            format!(
+                "{:#}",
+                ChainRegistry::from_files(vec![file("chains.yaml", source)]).unwrap_err()
+            )
+        };
+
+        assert_eq!(
+            err("chains:\n  - name: my-mainnet\n    id: 1\n"),
+            "EE125: Invalid chain in \"chains.yaml\": The chain 1 is already supported as \
+             \"ethereum-mainnet\""
+        );
+        assert_eq!(
+            err("chains:\n  - name: optimism\n    id: 7777777701\n"),
+            "EE125: Invalid chain in \"chains.yaml\": The chain name \"optimism\" is already \
+             used by the chain 10"
+        );
+        assert_eq!(
+            err("chains:\n  - name: a\n    id: 7777777701\n  - name: b\n    id: 7777777701\n"),
+            "EE125: The chain 7777777701 is declared more than once in \"chains.yaml\""
+        );
+        assert_eq!(
+            err("chains:\n  - name: a\n    id: 7777777701\n  - name: a\n    id: 7777777702\n"),
+            "EE125: The chain name \"a\" is used by more than one chain"
+        );
+    }
+
+    #[test]
+    fn load_fails_for_an_invalid_project_chains_file() {
+        let project_dir = tempdir::TempDir::new("chain_registry_test").unwrap();
+        std::fs::write(
+            project_dir.path().join(super::CHAINS_FILE_NAME),
+            "chains:\n  - id: 7777777701\n",
+        )
+        .unwrap();
+
+        let err = ChainRegistry::load(project_dir.path()).unwrap_err();
+
+        assert!(format!("{:#}", err).starts_with("EE125: Failed to deserialize the chains file"));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{config_parsing::chain_helpers::HypersyncNetwork, evm::address::Address};
+use crate::{
+    config_parsing::{chain_helpers::HypersyncNetwork, chain_registry::CustomChain},
+    evm::address::Address,
+};
 use anyhow::{Context, Result};
 use std::fmt::{self, Display};
 
@@ -51,6 +54,8 @@ impl SelectedContract {
 #[derive(Clone, Debug)]
 pub enum NetworkKind {
     Supported(HypersyncNetwork),
+    ///A chain from a chains.yaml file with a HyperSync url
+    Custom(CustomChain),
     Unsupported {
         network_id: u64,
         rpc_url: String,
@@ -62,18 +67,19 @@ impl NetworkKind {
     pub fn get_network_id(&self) -> u64 {
         match self {
             Self::Supported(n) => n.clone() as u64,
+            Self::Custom(chain) => chain.id,
             Self::Unsupported { network_id, .. } => *network_id,
         }
     }
     pub fn get_start_block(&self) -> u64 {
         match self {
-            Self::Supported(_) => 0,
+            Self::Supported(_) | Self::Custom(_) => 0,
             Self::Unsupported { start_block, .. } => *start_block,
         }
     }
     pub fn uses_hypersync(&self) -> bool {
         match self {
-            Self::Supported(_) => true,
+            Self::Supported(_) | Self::Custom(_) => true,
             Self::Unsupported { .. } => false,
         }
     }
@@ -83,6 +89,7 @@ impl Display for NetworkKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
             Self::Supported(n) => write!(f, "{}", n),
+            Self::Custom(chain) => write!(f, "{}", chain.name),
             Self::Unsupported { network_id, .. } => write!(f, "{}", network_id),
         }
     }
@@ -38,6 +38,73 @@ enum ContractImportResponse {
     },
 }
 
+#[derive(Deserialize, Debug)]
+struct ExplorerSourceCode {
+    #[serde(rename = "ContractName")]
+    name: Option<String>,
+    #[serde(rename = "ABI")]
+    abi: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum ExplorerResponse {
+    SourceCode { result: Vec<ExplorerSourceCode> },
+    Error { result: Option<String> },
+}
+
+///Imports the contract from an Etherscan compatible explorer API,
+///for chains declared in a chains.yaml file
+pub async fn explorer_contract_import(
+    explorer_api_url: &str,
+    address: &Address,
+) -> anyhow::Result<ContractImportResult> {
+    let response = reqwest::Client::new()
+        .get(explorer_api_url)
+        .query(&[
+            ("module", "contract"),
+            ("action", "getsourcecode"),
+            ("address", &address.to_checksum_hex_string()),
+        ])
+        .send()
+        .await
+        .context(format!(
+            "Failed to fetch the contract from the explorer API at {}",
+            explorer_api_url
+        ))?;
+
+    let explorer_response: ExplorerResponse = response
+        .json()
+        .await
+        .context("Failed to parse the explorer API response")?;
+
+    let source_code = match explorer_response {
+        ExplorerResponse::SourceCode { result } => result.into_iter().next(),
+        ExplorerResponse::Error { result } => {
+            return Err(anyhow!(
+                "The explorer API responded with an error: {}",
+                result.unwrap_or_else(|| "Unknown error".to_string())
+            ))
+        }
+    };
+
+    match source_code {
+        //Unverified contracts come with a message instead of the ABI
+        Some(ExplorerSourceCode { name, abi }) if abi.starts_with('[') => {
+            let mut abi: ethers::abi::Contract =
+                serde_json::from_str(&abi).context("Failed parsing contract ABI")?;
+
+            abi.events = filter_duplicate_events(abi.events);
+
+            Ok(ContractImportResult::Contract(ContractData {
+                name: name.filter(|name| !name.is_empty()),
+                abi,
+            }))
+        }
+        _ => Ok(ContractImportResult::NotVerified),
+    }
+}
+
 #[async_recursion]
 pub async fn contract_import(
     network: &NetworkWithExplorer,
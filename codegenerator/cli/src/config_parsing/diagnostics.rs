@@ -0,0 +1,1103 @@
+use super::{
+    entity_parsing::Schema,
+    human_config::{self, HumanConfig},
+    profiles,
+    system_config::{
+        BlockHandler, Call, Event, EvmAbi, FieldSelection, NetworkSource, Secrets, SystemConfig,
+    },
+    validation,
+};
+use crate::{
+    constants::project_paths::DEFAULT_SCHEMA_PATH,
+    fuel::abi::FuelAbi,
+    project_paths::{path_utils, ParsedProjectPaths},
+};
+use anyhow::anyhow;
+use colored::Colorize;
+use regex::Regex;
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fmt,
+    path::{Path, PathBuf},
+};
+use yaml_rust2::{
+    parser::{Event as YamlEvent, MarkedEventReceiver, Parser as YamlParser},
+    scanner::{Marker, TScalarStyle},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+///A position in a config or schema file. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceSpan {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceSpan {
+    fn new(file: &Path, line: usize, column: usize) -> Self {
+        SourceSpan {
+            file: file.to_string_lossy().to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub code: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Option<SourceSpan>,
+}
+
+impl Diagnostic {
+    fn from_error(err: &anyhow::Error, span: Option<SourceSpan>) -> Self {
+        let code_regex = Regex::new(r"^(EE\d{3})\b").unwrap();
+        let code = err.chain().find_map(|cause| {
+            code_regex
+                .captures(&cause.to_string())
+                .map(|captures| captures[1].to_string())
+        });
+
+        Diagnostic {
+            code,
+            severity: Severity::Error,
+            message: format!("{:#}", err),
+            span,
+        }
+    }
+
+    fn warning(message: String, span: Option<SourceSpan>) -> Self {
+        Diagnostic {
+            code: None,
+            severity: Severity::Warning,
+            message,
+            span,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = match &self.code {
+            Some(code) => format!("{}[{}]", self.severity, code),
+            None => self.severity.to_string(),
+        };
+        let header = match self.severity {
+            Severity::Error => header.red().bold(),
+            Severity::Warning => header.yellow().bold(),
+        };
+        write!(f, "{}: {}", header, self.message)?;
+        if let Some(span) = &self.span {
+            write!(f, "\n  --> {}:{}:{}", span.file, span.line, span.column)?;
+        }
+        Ok(())
+    }
+}
+
+///An item in the config file that a diagnostic can be attributed to. Items are located by
+///their position in the config structure, so repeated names and values on different networks
+///point at the right entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigItem {
+    Network(u64),
+    ///A contract defined globally when there is no network id, or in the contracts of the network
+    Contract {
+        network_id: Option<u64>,
+        name: String,
+    },
+    ///Matches the `event:` of an evm event or the `name:` of a fuel event
+    Event {
+        network_id: Option<u64>,
+        contract: String,
+        event: String,
+    },
+    Call {
+        network_id: Option<u64>,
+        contract: String,
+        call: String,
+    },
+    Address {
+        network_id: u64,
+        contract: String,
+        address: String,
+    },
+    ///A block handler of a contract, or of the network itself when there is no contract
+    BlockHandler {
+        network_id: Option<u64>,
+        contract: Option<String>,
+        name: String,
+    },
+}
+
+impl ConfigItem {
+    fn find_network(root: &YamlNode, network_id: u64) -> Option<&YamlNode> {
+        root.find_item("networks", "id", |id| id.parse() == Ok(network_id))
+    }
+
+    fn find_contract<'a>(
+        root: &'a YamlNode,
+        network_id: Option<u64>,
+        name: &str,
+    ) -> Option<&'a YamlNode> {
+        let parent = match network_id {
+            Some(network_id) => Self::find_network(root, network_id)?,
+            None => root,
+        };
+        parent.find_item("contracts", "name", |value| value == name)
+    }
+
+    ///Finds the value of the item in a parsed config file
+    fn find_node<'a>(&self, root: &'a YamlNode) -> Option<&'a YamlNode> {
+        match self {
+            ConfigItem::Network(network_id) => Self::find_network(root, *network_id)?.get("id"),
+            ConfigItem::Contract { network_id, name } => {
+                Self::find_contract(root, *network_id, name)?.get("name")
+            }
+            ConfigItem::Event {
+                network_id,
+                contract,
+                event,
+            } => Self::find_contract(root, *network_id, contract)?
+                .get("events")?
+                .items()
+                .iter()
+                .filter_map(|item| item.get("event").or_else(|| item.get("name")))
+                .find(|value| value.value() == Some(event)),
+            ConfigItem::Call {
+                network_id,
+                contract,
+                call,
+            } => Self::find_contract(root, *network_id, contract)?
+                .find_item("calls", "call", |value| value == call)?
+                .get("call"),
+            ConfigItem::Address {
+                network_id,
+                contract,
+                address,
+            } => {
+                let addresses =
+                    Self::find_contract(root, Some(*network_id), contract)?.get("address")?;
+                std::iter::once(addresses)
+                    .chain(addresses.items())
+                    .find(|value| value.value() == Some(address))
+            }
+            ConfigItem::BlockHandler {
+                network_id,
+                contract,
+                name,
+            } => {
+                let parent = match contract {
+                    Some(contract) => Self::find_contract(root, *network_id, contract)?,
+                    None => Self::find_network(root, (*network_id)?)?,
+                };
+                parent
+                    .find_item("block_handlers", "name", |value| value == name)?
+                    .get("name")
+            }
+        }
+    }
+
+    fn find_span(&self, file: &Path, root: &YamlNode) -> Option<SourceSpan> {
+        match self.find_node(root)? {
+            YamlNode::Scalar { line, column, .. } => Some(SourceSpan::new(file, *line, *column)),
+            _ => None,
+        }
+    }
+}
+
+///A config file parsed with the position of every scalar, which serde_yaml doesn't expose
+#[derive(Debug, Clone, PartialEq)]
+enum YamlNode {
+    Scalar {
+        value: String,
+        line: usize,
+        column: usize,
+    },
+    Sequence(Vec<YamlNode>),
+    Mapping(Vec<(String, YamlNode)>),
+}
+
+struct YamlEventCollector(Vec<(YamlEvent, Marker)>);
+
+impl MarkedEventReceiver for YamlEventCollector {
+    fn on_event(&mut self, event: YamlEvent, mark: Marker) {
+        self.0.push((event, mark));
+    }
+}
+
+impl YamlNode {
+    ///Takes the positions from the file as it's written and the values from the interpolated
+    ///file, so the spans stay correct when an interpolated value has a different length.
+    ///The values as written are used when interpolation changed the structure of the file
+    fn parse(raw_source: &str, interpolated_source: &str) -> Option<Self> {
+        let raw_events = Self::read_events(raw_source)?;
+        let events = match Self::read_events(interpolated_source) {
+            Some(interpolated_events)
+                if interpolated_events.len() == raw_events.len()
+                    && interpolated_events.iter().zip(&raw_events).all(|(a, b)| {
+                        std::mem::discriminant(&a.0) == std::mem::discriminant(&b.0)
+                    }) =>
+            {
+                raw_events
+                    .into_iter()
+                    .zip(interpolated_events)
+                    .map(|((raw_event, mark), (interpolated_event, _))| {
+                        match (raw_event, interpolated_event) {
+                            (
+                                YamlEvent::Scalar(_, style, anchor, tag),
+                                YamlEvent::Scalar(value, ..),
+                            ) => (YamlEvent::Scalar(value, style, anchor, tag), mark),
+                            (raw_event, _) => (raw_event, mark),
+                        }
+                    })
+                    .collect()
+            }
+            _ => raw_events,
+        };
+        Self::from_events(&mut events.into_iter())
+    }
+
+    fn read_events(source: &str) -> Option<Vec<(YamlEvent, Marker)>> {
+        let mut collector = YamlEventCollector(vec![]);
+        YamlParser::new_from_str(source)
+            .load(&mut collector, false)
+            .ok()?;
+        Some(collector.0)
+    }
+
+    ///Returns None at the end of a sequence or mapping
+    fn from_events(events: &mut impl Iterator<Item = (YamlEvent, Marker)>) -> Option<Self> {
+        let (event, mark) = events.next()?;
+        match event {
+            YamlEvent::StreamStart | YamlEvent::DocumentStart => Self::from_events(events),
+            YamlEvent::Scalar(value, style, ..) => {
+                //Markers have a column starting at 0 and point at the opening quote
+                let quote_len = match style {
+                    TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted => 1,
+                    _ => 0,
+                };
+                Some(YamlNode::Scalar {
+                    value,
+                    line: mark.line(),
+                    column: mark.col() + 1 + quote_len,
+                })
+            }
+            YamlEvent::Alias(_) => Some(YamlNode::Scalar {
+                value: String::new(),
+                line: mark.line(),
+                column: mark.col() + 1,
+            }),
+            YamlEvent::SequenceStart(..) => {
+                let mut items = vec![];
+                while let Some(item) = Self::from_events(events) {
+                    items.push(item);
+                }
+                Some(YamlNode::Sequence(items))
+            }
+            YamlEvent::MappingStart(..) => {
+                let mut entries = vec![];
+                while let Some(key) = Self::from_events(events) {
+                    let value = Self::from_events(events)?;
+                    let key = key.value().unwrap_or_default().to_string();
+                    entries.push((key, value));
+                }
+                Some(YamlNode::Mapping(entries))
+            }
+            _ => None,
+        }
+    }
+
+    fn value(&self) -> Option<&str> {
+        match self {
+            YamlNode::Scalar { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&YamlNode> {
+        match self {
+            YamlNode::Mapping(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn items(&self) -> &[YamlNode] {
+        match self {
+            YamlNode::Sequence(items) => items,
+            _ => &[],
+        }
+    }
+
+    ///Finds the item of the list under the key, with a scalar that matches under the item key
+    fn find_item(
+        &self,
+        list_key: &str,
+        item_key: &str,
+        matches: impl Fn(&str) -> bool,
+    ) -> Option<&YamlNode> {
+        self.get(list_key)?.items().iter().find(|item| {
+            item.get(item_key)
+                .and_then(YamlNode::value)
+                .is_some_and(&matches)
+        })
+    }
+}
+
+struct DiagnosticsCollector {
+    ///The config file followed by the files it includes
+    config_files: Vec<(PathBuf, YamlNode)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticsCollector {
+    ///Reads the file as it's written for the positions of the config items. The interpolated
+    ///source is used for the values when it's given
+    fn parse_config_file(
+        path: PathBuf,
+        interpolated_source: Option<&str>,
+    ) -> Option<(PathBuf, YamlNode)> {
+        let raw_source = std::fs::read_to_string(&path).ok()?;
+        let root = YamlNode::parse(&raw_source, interpolated_source.unwrap_or(&raw_source))?;
+        Some((path, root))
+    }
+
+    ///Looks for the item in the config file first and then in the included files,
+    ///so the span points at the file where the item is defined
+    fn find_span(&self, item: &ConfigItem) -> Option<SourceSpan> {
+        self.config_files
+            .iter()
+            .find_map(|(path, root)| item.find_span(path, root))
+    }
+
+    fn push_config_error(&mut self, err: &anyhow::Error, item: Option<ConfigItem>) {
+        let span = item.and_then(|item| self.find_span(&item));
+        self.diagnostics.push(Diagnostic::from_error(err, span));
+    }
+
+    fn push_config_warning(&mut self, message: String, item: ConfigItem) {
+        let span = self.find_span(&item);
+        self.diagnostics.push(Diagnostic::warning(message, span));
+    }
+
+    fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+///Runs every config and schema check for the project without touching docker or the
+///generated directory. Unlike parsing the SystemConfig, this doesn't stop at the first
+///failure, so all diagnostics can be reported at once.
+pub fn validate_project(project_paths: &ParsedProjectPaths) -> Vec<Diagnostic> {
+    let config_path = &project_paths.config;
+
+    let mut secrets = Secrets::default();
+    let config_source = match SystemConfig::read_human_config_string(project_paths, &mut secrets) {
+        Ok(config_source) => config_source,
+        Err(err) => return vec![Diagnostic::from_error(&err, None)],
+    };
+
+    let human_config = match SystemConfig::deserialize_human_config(&config_source) {
+        Ok(human_config) => human_config,
+        Err(err) => {
+            let span = err
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<serde_yaml::Error>())
+                .and_then(|yaml_err| yaml_err.location())
+                .map(|location| SourceSpan::new(config_path, location.line(), location.column()));
+            return vec![Diagnostic::from_error(&err, span)];
+        }
+    };
+
+    let included_files =
+        match SystemConfig::read_included_files(&human_config, project_paths, &mut secrets) {
+            Ok(included_files) => included_files,
+            Err(err) => return vec![Diagnostic::from_error(&err, None)],
+        };
+
+    //The config is serialized again after the profile overrides are merged onto it,
+    //so look for the spans in the files as they're written
+    let config_files = match &project_paths.profile {
+        None => vec![(config_path.clone(), Some(config_source.as_str()))],
+        Some(profile) => vec![
+            (config_path.clone(), None),
+            (profiles::get_overlay_file_path(config_path, profile), None),
+        ],
+    };
+
+    let mut collector = DiagnosticsCollector {
+        config_files: config_files
+            .into_iter()
+            .chain(
+                included_files
+                    .iter()
+                    .map(|file| (file.path.clone(), Some(file.source.as_str()))),
+            )
+            .filter_map(|(path, interpolated_source)| {
+                DiagnosticsCollector::parse_config_file(path, interpolated_source)
+            })
+            .collect(),
+        diagnostics: vec![],
+    };
+
+    let human_config =
+        match SystemConfig::merge_included_files(human_config, &included_files, project_paths) {
+            Ok(human_config) => human_config,
+            Err(err) => {
+                //The error names the included file with the bad entry first
+                let message = err.to_string();
+                let span = included_files
+                    .iter()
+                    .filter_map(|file| {
+                        message
+                            .find(&format!("\"{}\"", file.name))
+                            .map(|position| (position, file))
+                    })
+                    .min_by_key(|(position, _)| *position)
+                    .map(|(_, file)| {
+                        let (line, column) = err
+                            .chain()
+                            .find_map(|cause| cause.downcast_ref::<serde_yaml::Error>())
+                            .and_then(|yaml_err| yaml_err.location())
+                            .map_or((1, 1), |location| (location.line(), location.column()));
+                        SourceSpan::new(&file.path, line, column)
+                    });
+                return vec![Diagnostic::from_error(&err, span)];
+            }
+        };
+    secrets.add_interpolated_endpoints(&human_config);
+
+    let schema = validate_schema(
+        project_paths,
+        human_config.get_schema_path(),
+        &mut collector.diagnostics,
+    );
+
+    match &human_config {
+        HumanConfig::Evm(evm_config) => {
+            validate_evm_config(evm_config, project_paths, &mut collector)
+        }
+        HumanConfig::Fuel(fuel_config) => {
+            validate_fuel_config(fuel_config, project_paths, &mut collector)
+        }
+    }
+
+    //The granular checks above don't cover everything that is checked while building the
+    //system config, so build it as well once they all pass
+    if let (Some(schema), false) = (schema, collector.has_errors()) {
+        if let Err(err) = SystemConfig::from_human_config(human_config, schema, project_paths) {
+            collector.push_config_error(&err, None);
+        }
+    }
+
+    collector
+        .diagnostics
+        .into_iter()
+        .map(|diagnostic| Diagnostic {
+            message: secrets.mask(&diagnostic.message),
+            ..diagnostic
+        })
+        .collect()
+}
+
+fn validate_schema(
+    project_paths: &ParsedProjectPaths,
+    maybe_custom_path: &Option<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<Schema> {
+    let relative_schema_path_from_config = maybe_custom_path
+        .clone()
+        .unwrap_or_else(|| DEFAULT_SCHEMA_PATH.to_string());
+
+    let schema_path = match path_utils::get_config_path_relative_to_root(
+        project_paths,
+        PathBuf::from(relative_schema_path_from_config),
+    ) {
+        Ok(schema_path) => schema_path,
+        Err(err) => {
+            diagnostics.push(Diagnostic::from_error(&err, None));
+            return None;
+        }
+    };
+
+    let schema_source = match std::fs::read_to_string(&schema_path) {
+        Ok(schema_source) => schema_source,
+        Err(err) => {
+            let err = anyhow!(err).context(format!(
+                "EE200: Failed to read schema file at {}. Please ensure that the schema file is \
+                 placed correctly in the directory.",
+                schema_path.to_string_lossy()
+            ));
+            diagnostics.push(Diagnostic::from_error(&err, None));
+            return None;
+        }
+    };
+
+    let schema_doc = match graphql_parser::parse_schema::<String>(&schema_source) {
+        Ok(schema_doc) => schema_doc,
+        Err(err) => {
+            //The parser only exposes the position of a syntax error through its message
+            let position_regex = Regex::new(r"(\d+):(\d+)").unwrap();
+            let span = position_regex.captures(&err.to_string()).map(|captures| {
+                SourceSpan::new(
+                    &schema_path,
+                    captures[1].parse().unwrap_or(1),
+                    captures[2].parse().unwrap_or(1),
+                )
+            });
+            let err = anyhow!(err).context("EE201: Failed to parse schema as document");
+            diagnostics.push(Diagnostic::from_error(&err, span));
+            return None;
+        }
+    };
+
+    let errors = Schema::collect_errors_from_document(&schema_doc);
+    if errors.is_empty() {
+        return match Schema::from_document(schema_doc) {
+            Ok(schema) => Some(schema),
+            Err(err) => {
+                diagnostics.push(Diagnostic::from_error(&err, None));
+                None
+            }
+        };
+    }
+
+    for (err, position) in errors {
+        let span = position.map(|pos| SourceSpan::new(&schema_path, pos.line, pos.column));
+        diagnostics.push(Diagnostic::from_error(&err, span));
+    }
+    None
+}
+
+fn validate_evm_config(
+    evm_config: &human_config::evm::HumanConfig,
+    project_paths: &ParsedProjectPaths,
+    collector: &mut DiagnosticsCollector,
+) {
+    for (err, item) in validation::collect_deserialized_config_yaml_errors(evm_config) {
+        collector.push_config_error(&err, item);
+    }
+
+    let has_rpc_sync_src = evm_config.networks.iter().any(|n| n.rpc_config.is_some());
+
+    let mut global_contract_names = vec![];
+    for g_contract in evm_config.contracts.iter().flatten() {
+        global_contract_names.push(g_contract.name.clone());
+        validate_evm_contract(
+            None,
+            &g_contract.name,
+            &g_contract.config,
+            project_paths,
+            has_rpc_sync_src,
+            collector,
+        );
+    }
+
+    let mut referenced_contract_names = HashSet::new();
+    for network in &evm_config.networks {
+        if let Err(err) = NetworkSource::from_evm_network_config(
+            network.clone(),
+            evm_config.event_decoder.clone(),
+        ) {
+            collector.push_config_error(&err, Some(ConfigItem::Network(network.id)));
+        }
+
+        if let Err(err) = BlockHandler::from_evm_network_config(network, evm_config) {
+            collector.push_config_error(&err, Some(ConfigItem::Network(network.id)));
+        }
+
+        if let Some(field_selection) = &network.field_selection {
+            if let Err(err) = FieldSelection::try_from_config_field_selection(
+                field_selection.clone(),
+                network.rpc_config.is_some(),
+            ) {
+                collector.push_config_error(&err, Some(ConfigItem::Network(network.id)));
+            }
+        }
+
+        for contract in &network.contracts {
+            match &contract.config {
+                Some(l_contract) => validate_evm_contract(
+                    Some(network.id),
+                    &contract.name,
+                    l_contract,
+                    project_paths,
+                    has_rpc_sync_src,
+                    collector,
+                ),
+                None => {
+                    referenced_contract_names.insert(contract.name.clone());
+                    if !global_contract_names.contains(&contract.name) {
+                        collector.push_config_error(
+                            &missing_global_contract_err(&contract.name, network.id),
+                            Some(ConfigItem::Contract {
+                                network_id: Some(network.id),
+                                name: contract.name.clone(),
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(field_selection) = &evm_config.field_selection {
+        if let Err(err) = FieldSelection::try_from_config_field_selection(
+            field_selection.clone(),
+            has_rpc_sync_src,
+        ) {
+            collector.push_config_error(&err, None);
+        }
+    }
+
+    push_unused_global_contract_warnings(
+        global_contract_names,
+        &referenced_contract_names,
+        collector,
+    );
+}
+
+fn validate_evm_contract(
+    network_id: Option<u64>,
+    name: &str,
+    contract_config: &human_config::evm::ContractConfig,
+    project_paths: &ParsedProjectPaths,
+    has_rpc_sync_src: bool,
+    collector: &mut DiagnosticsCollector,
+) {
+    if let Err(err) = EvmAbi::from_file(&contract_config.abi_file_path, project_paths) {
+        collector.push_config_error(
+            &err.context(format!("Failed parsing abi for contract {}", name)),
+            Some(ConfigItem::Contract {
+                network_id,
+                name: name.to_string(),
+            }),
+        );
+        return;
+    }
+
+    for event_config in &contract_config.events {
+        if let Err(err) = Event::from_evm_events_config(
+            vec![event_config.clone()],
+            &contract_config.abi_file_path,
+            project_paths,
+            has_rpc_sync_src,
+        ) {
+            collector.push_config_error(
+                &err.context(format!(
+                    "Failed parsing event {} in contract {}",
+                    event_config.event, name
+                )),
+                Some(ConfigItem::Event {
+                    network_id,
+                    contract: name.to_string(),
+                    event: event_config.event.clone(),
+                }),
+            );
+        }
+    }
+
+    for call_config in contract_config.calls.iter().flatten() {
+        if let Err(err) = Call::from_evm_calls_config(
+            vec![call_config.clone()],
+            &contract_config.abi_file_path,
+            project_paths,
+        ) {
+            collector.push_config_error(
+                &err.context(format!(
+                    "Failed parsing call {} in contract {}",
+                    call_config.call, name
+                )),
+                Some(ConfigItem::Call {
+                    network_id,
+                    contract: name.to_string(),
+                    call: call_config.call.clone(),
+                }),
+            );
+        }
+    }
+}
+
+fn validate_fuel_config(
+    fuel_config: &human_config::fuel::HumanConfig,
+    project_paths: &ParsedProjectPaths,
+    collector: &mut DiagnosticsCollector,
+) {
+    let mut global_contract_names = vec![];
+    for g_contract in fuel_config.contracts.iter().flatten() {
+        global_contract_names.push(g_contract.name.clone());
+        validate_fuel_contract(
+            None,
+            &g_contract.name,
+            &g_contract.config,
+            project_paths,
+            collector,
+        );
+    }
+
+    let mut referenced_contract_names = HashSet::new();
+    for network in &fuel_config.networks {
+        for contract in &network.contracts {
+            if let Err(err) = contract.validate_start_and_end_block(network.start_block) {
+                collector.push_config_error(
+                    &err,
+                    Some(ConfigItem::Contract {
+                        network_id: Some(network.id as u64),
+                        name: contract.name.clone(),
+                    }),
+                );
+            }
+
+            match &contract.config {
+                Some(l_contract) => validate_fuel_contract(
+                    Some(network.id as u64),
+                    &contract.name,
+                    l_contract,
+                    project_paths,
+                    collector,
+                ),
+                None => {
+                    referenced_contract_names.insert(contract.name.clone());
+                    if !global_contract_names.contains(&contract.name) {
+                        collector.push_config_error(
+                            &missing_global_contract_err(&contract.name, network.id as u64),
+                            Some(ConfigItem::Contract {
+                                network_id: Some(network.id as u64),
+                                name: contract.name.clone(),
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    push_unused_global_contract_warnings(
+        global_contract_names,
+        &referenced_contract_names,
+        collector,
+    );
+}
+
+fn validate_fuel_contract(
+    network_id: Option<u64>,
+    name: &str,
+    contract_config: &human_config::fuel::ContractConfig,
+    project_paths: &ParsedProjectPaths,
+    collector: &mut DiagnosticsCollector,
+) {
+    let abi = path_utils::get_config_path_relative_to_root(
+        project_paths,
+        PathBuf::from(&contract_config.abi_file_path),
+    )
+    .and_then(FuelAbi::parse);
+    if let Err(err) = abi {
+        collector.push_config_error(
+            &err.context(format!("Failed parsing abi for contract {}", name)),
+            Some(ConfigItem::Contract {
+                network_id,
+                name: name.to_string(),
+            }),
+        );
+        return;
+    }
+
+    for event_config in &contract_config.events {
+        if let Err(err) = Event::from_fuel_events_config(
+            std::slice::from_ref(event_config),
+            &contract_config.abi_file_path,
+            project_paths,
+        ) {
+            collector.push_config_error(
+                &err.context(format!(
+                    "Failed parsing event {} in contract {}",
+                    event_config.name, name
+                )),
+                Some(ConfigItem::Event {
+                    network_id,
+                    contract: name.to_string(),
+                    event: event_config.name.clone(),
+                }),
+            );
+        }
+    }
+}
+
+fn missing_global_contract_err(contract_name: &str, network_id: u64) -> anyhow::Error {
+    anyhow!(
+        "Failed to parse contract '{}' for the network '{}'. If you use a global contract \
+         definition, please verify that the name reference is correct.",
+        contract_name,
+        network_id
+    )
+}
+
+fn push_unused_global_contract_warnings(
+    global_contract_names: Vec<String>,
+    referenced_contract_names: &HashSet<String>,
+    collector: &mut DiagnosticsCollector,
+) {
+    for name in global_contract_names {
+        if !referenced_contract_names.contains(&name) {
+            collector.push_config_warning(
+                format!(
+                    "Contract '{}' is defined globally but isn't used on any network, so none of \
+                     its events will be indexed.",
+                    name
+                ),
+                ConfigItem::Contract {
+                    network_id: None,
+                    name,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigItem, Diagnostic, Severity, SourceSpan, YamlNode};
+    use crate::project_paths::ParsedProjectPaths;
+    use anyhow::anyhow;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn get_test_project_paths(config_file_name: &str) -> ParsedProjectPaths {
+        let project_root = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let config = format!("configs/{}", config_file_name);
+        ParsedProjectPaths::new(&project_root, "generated/", &config).expect("Parsed paths")
+    }
+
+    #[test]
+    fn diagnostic_takes_code_from_inner_error() {
+        let err = anyhow!("EE103: Unable to parse event signature").context("Failed parsing");
+        let diagnostic = Diagnostic::from_error(&err, None);
+
+        assert_eq!(diagnostic.code, Some("EE103".to_string()));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(
+            diagnostic.message,
+            "Failed parsing: EE103: Unable to parse event signature"
+        );
+    }
+
+    #[test]
+    fn finds_span_of_config_items() {
+        let file = PathBuf::from("config.yaml");
+        let source = r#"name: test
+networks:
+  - id: 137
+    contracts:
+      - name: "Greeter"
+        address: 0x2E645469f354BB4F5c8a05B3b30A929361cf77eC
+        events:
+          - event: NewGreeting
+"#;
+        let root = YamlNode::parse(source, source).expect("Parsed yaml");
+
+        let span = |item: ConfigItem| item.find_span(&file, &root).map(|s| (s.line, s.column));
+        let greeter = |network_id| ConfigItem::Contract {
+            network_id,
+            name: "Greeter".to_string(),
+        };
+
+        assert_eq!(span(ConfigItem::Network(137)), Some((3, 9)));
+        assert_eq!(span(greeter(Some(137))), Some((5, 16)));
+        assert_eq!(
+            span(ConfigItem::Event {
+                network_id: Some(137),
+                contract: "Greeter".to_string(),
+                event: "NewGreeting".to_string(),
+            }),
+            Some((8, 20))
+        );
+        assert_eq!(
+            span(ConfigItem::Address {
+                network_id: 137,
+                contract: "Greeter".to_string(),
+                address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string(),
+            }),
+            Some((6, 18))
+        );
+        assert_eq!(span(ConfigItem::Network(1)), None);
+        assert_eq!(span(greeter(None)), None);
+    }
+
+    #[test]
+    fn finds_span_of_repeated_config_items() {
+        let file = PathBuf::from("config.yaml");
+        let source = r#"name: test
+contracts:
+  - name: Greeter
+    handler: ./src/EventHandlers.ts
+    events:
+      - event: NewGreeting
+networks:
+  - id: 1
+    contracts:
+      - name: Greeter
+        address:
+          - 0x2E645469f354BB4F5c8a05B3b30A929361cf77eC
+  - id: 10
+    contracts:
+      - name: Greeter
+        address:
+          - 0x2E645469f354BB4F5c8a05B3b30A929361cf77eC
+"#;
+        let root = YamlNode::parse(source, source).expect("Parsed yaml");
+
+        let span = |item: ConfigItem| item.find_span(&file, &root).map(|s| (s.line, s.column));
+        let greeter = |network_id| ConfigItem::Contract {
+            network_id,
+            name: "Greeter".to_string(),
+        };
+
+        assert_eq!(span(ConfigItem::Network(10)), Some((13, 9)));
+        assert_eq!(span(greeter(None)), Some((3, 11)));
+        assert_eq!(span(greeter(Some(1))), Some((10, 15)));
+        assert_eq!(span(greeter(Some(10))), Some((15, 15)));
+        assert_eq!(
+            span(ConfigItem::Address {
+                network_id: 10,
+                contract: "Greeter".to_string(),
+                address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string(),
+            }),
+            Some((17, 13))
+        );
+    }
+
+    #[test]
+    fn finds_span_of_interpolated_config_items() {
+        let file = PathBuf::from("config.yaml");
+        let raw_source = r#"name: ${ENVIO_NAME}
+networks:
+  - id: ${ENVIO_CHAIN_ID}
+    contracts:
+      - name: ${ENVIO_CONTRACT_PREFIX}Greeter
+        address: ${ENVIO_ADDRESS}
+  - id: 1
+    contracts: []
+"#;
+        let interpolated_source = r#"name: a-much-longer-indexer-name
+networks:
+  - id: 137
+    contracts:
+      - name: PolygonGreeter
+        address: 0x2E645469f354BB4F5c8a05B3b30A929361cf77eC
+  - id: 1
+    contracts: []
+"#;
+        let root = YamlNode::parse(raw_source, interpolated_source).expect("Parsed yaml");
+
+        let span = |item: ConfigItem| item.find_span(&file, &root).map(|s| (s.line, s.column));
+
+        assert_eq!(span(ConfigItem::Network(137)), Some((3, 9)));
+        assert_eq!(span(ConfigItem::Network(1)), Some((7, 9)));
+        assert_eq!(
+            span(ConfigItem::Contract {
+                network_id: Some(137),
+                name: "PolygonGreeter".to_string(),
+            }),
+            Some((5, 15))
+        );
+        assert_eq!(
+            span(ConfigItem::Address {
+                network_id: 137,
+                contract: "PolygonGreeter".to_string(),
+                address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string(),
+            }),
+            Some((6, 18))
+        );
+    }
+
+    #[test]
+    fn valid_project_has_no_diagnostics() {
+        let diagnostics = super::validate_project(&get_test_project_paths("config1.yaml"));
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn collects_all_config_errors() {
+        let project_paths = get_test_project_paths("invalid-diagnostics-config.yaml");
+        let diagnostics = super::validate_project(&project_paths);
+
+        let config_file = project_paths.config.to_string_lossy().to_string();
+        let span = |line, column| {
+            Some(SourceSpan {
+                file: config_file.clone(),
+                line,
+                column,
+            })
+        };
+
+        let summary = diagnostics
+            .iter()
+            .map(|d| (d.code.clone(), d.severity, d.span.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                (Some("EE100".to_string()), Severity::Error, span(11, 19)),
+                (None, Severity::Error, span(14, 21)),
+                (None, Severity::Error, span(15, 15)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_sync_source_error_at_network() {
+        let project_paths = get_test_project_paths("invalid-multiple-sync-config.yaml");
+        let diagnostics = super::validate_project(&project_paths);
+
+        let first = diagnostics.first().expect("Expected a diagnostic");
+        assert_eq!(first.code, Some("EE118".to_string()));
+        assert_eq!(
+            first.span.as_ref().map(|s| (s.line, s.column)),
+            Some((5, 9))
+        );
+    }
+
+    #[test]
+    fn reports_error_at_included_file() {
+        let project_paths = get_test_project_paths("invalid-include-config.yaml");
+        let diagnostics = super::validate_project(&project_paths);
+
+        let first = diagnostics.first().expect("Expected a diagnostic");
+        assert_eq!(first.code, Some("EE118".to_string()));
+        assert_eq!(
+            first.span,
+            Some(SourceSpan {
+                file: format!(
+                    "{}/test/configs/include/invalid-sync-network.yaml",
+                    env!("CARGO_MANIFEST_DIR")
+                ),
+                line: 2,
+                column: 9,
+            })
+        );
+    }
+}
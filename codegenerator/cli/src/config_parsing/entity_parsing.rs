@@ -1,5 +1,5 @@
 use super::{
-    postgres_types::{Field as PGField, Primitive as PGPrimitive},
+    postgres_types::{Field as PGField, IndexType, Primitive as PGPrimitive},
     validation::{
         check_enums_for_internal_reserved_words, check_names_from_schema_for_reserved_words,
         is_valid_postgres_db_name,
@@ -14,9 +14,12 @@ use crate::{
 };
 use anyhow::{anyhow, Context};
 use ethers::abi::ethabi::ParamType as EthAbiParamType;
-use graphql_parser::schema::{
-    Definition, Directive, Document, EnumType, Field as ObjField, ObjectType, Type as ObjType,
-    TypeDefinition, Value,
+use graphql_parser::{
+    schema::{
+        Definition, Directive, Document, EnumType, Field as ObjField, InterfaceType, ObjectType,
+        Type as ObjType, TypeDefinition, Value,
+    },
+    Pos,
 };
 use itertools::Itertools;
 use serde::{Serialize, Serializer};
@@ -31,11 +34,14 @@ use subenum::subenum;
 pub struct Schema {
     pub entities: HashMap<String, Entity>,
     pub enums: HashMap<String, GraphQLEnum>,
+    pub interfaces: HashMap<String, GraphQLInterface>,
+    pub aggregations: HashMap<String, Aggregation>,
 }
 
 enum TypeDef<'a> {
     Entity(&'a Entity),
     Enum,
+    Interface(&'a GraphQLInterface),
 }
 
 impl Schema {
@@ -43,10 +49,16 @@ impl Schema {
         Schema {
             entities: HashMap::new(),
             enums: HashMap::new(),
+            interfaces: HashMap::new(),
+            aggregations: HashMap::new(),
         }
     }
 
-    pub fn new(entities: Vec<Entity>, enums: Vec<GraphQLEnum>) -> anyhow::Result<Self> {
+    pub fn new(
+        entities: Vec<Entity>,
+        enums: Vec<GraphQLEnum>,
+        interfaces: Vec<GraphQLInterface>,
+    ) -> anyhow::Result<Self> {
         let entities = unique_hashmap::from_vec_no_duplicates(
             entities.into_iter().map(|e| (e.name.clone(), e)).collect(),
         )
@@ -55,12 +67,43 @@ impl Schema {
             enums.into_iter().map(|e| (e.name.clone(), e)).collect(),
         )
         .context("Found enums with duplicate names")?;
+        let interfaces = unique_hashmap::from_vec_no_duplicates(
+            interfaces
+                .into_iter()
+                .map(|i| (i.name.clone(), i))
+                .collect(),
+        )
+        .context("Found interfaces with duplicate names")?;
 
-        Self { entities, enums }.validate()
+        Self {
+            entities,
+            enums,
+            interfaces,
+            aggregations: HashMap::new(),
+        }
+        .validate()
     }
 
-    fn from_document(document: Document<String>) -> anyhow::Result<Self> {
-        let entities = document
+    /// Adds the aggregations of the schema and validates them against their source entities.
+    /// The entities holding the buckets of their intervals should already be in the schema
+    fn with_aggregations(self, aggregations: Vec<Aggregation>) -> anyhow::Result<Self> {
+        let aggregations = unique_hashmap::from_vec_no_duplicates(
+            aggregations
+                .into_iter()
+                .map(|a| (a.name.clone(), a))
+                .collect(),
+        )
+        .context("Found aggregations with duplicate names")?;
+
+        Self {
+            aggregations,
+            ..self
+        }
+        .check_aggregations()
+    }
+
+    pub fn from_document(document: Document<String>) -> anyhow::Result<Self> {
+        let objects = document
             .definitions
             .iter()
             .filter_map(|d| match d {
@@ -71,10 +114,26 @@ impl Schema {
                 TypeDefinition::Object(obj) => Some(obj),
                 _ => None,
             })
+            .collect::<Vec<_>>();
+
+        let aggregations = objects
+            .iter()
+            .filter(|obj| Aggregation::is_aggregation(obj))
+            .map(|obj| Aggregation::from_object(obj))
+            .collect::<anyhow::Result<Vec<Aggregation>>>()
+            .context("Failed constructing aggregations in schema from document")?;
+
+        let mut entities = objects
+            .iter()
+            .filter(|obj| !Aggregation::is_aggregation(obj))
             .map(|obj| Entity::from_object(obj))
             .collect::<anyhow::Result<Vec<Entity>>>()
             .context("Failed constructing entities in schema from document")?;
 
+        for aggregation in &aggregations {
+            entities.extend(aggregation.get_entities()?);
+        }
+
         let enums = document
             .definitions
             .iter()
@@ -90,7 +149,22 @@ impl Schema {
             .collect::<anyhow::Result<Vec<GraphQLEnum>>>()
             .context("Failed constructing enums in schema from document")?;
 
-        Self::new(entities, enums)
+        let interfaces = document
+            .definitions
+            .iter()
+            .filter_map(|d| match d {
+                Definition::TypeDefinition(type_def) => Some(type_def),
+                _ => None,
+            })
+            .filter_map(|type_def| match type_def {
+                TypeDefinition::Interface(interface) => Some(interface),
+                _ => None,
+            })
+            .map(|interface| GraphQLInterface::from_interface(interface))
+            .collect::<anyhow::Result<Vec<GraphQLInterface>>>()
+            .context("Failed constructing interfaces in schema from document")?;
+
+        Self::new(entities, enums, interfaces)?.with_aggregations(aggregations)
     }
 
     pub fn parse_from_file(
@@ -120,12 +194,166 @@ impl Schema {
         Self::from_document(schema_doc).context("Failed converting schema doc to schema struct")
     }
 
+    /// Runs the same checks as constructing a schema from a document, but without stopping
+    /// at the first failure. Each error is returned with the position of the type definition
+    /// it was raised for, when there is one.
+    pub fn collect_errors_from_document(
+        document: &Document<String>,
+    ) -> Vec<(anyhow::Error, Option<Pos>)> {
+        let mut errors = vec![];
+        let mut positions = HashMap::new();
+        let mut entities = vec![];
+        let mut enums = vec![];
+        let mut interfaces = vec![];
+        let mut aggregations = vec![];
+
+        for type_def in document.definitions.iter().filter_map(|d| match d {
+            Definition::TypeDefinition(type_def) => Some(type_def),
+            _ => None,
+        }) {
+            match type_def {
+                TypeDefinition::Object(obj) if Aggregation::is_aggregation(obj) => {
+                    positions.insert(obj.name.clone(), obj.position);
+                    match Aggregation::from_object(obj).and_then(|aggregation| {
+                        let aggregation_entities = aggregation.get_entities()?;
+                        Ok((aggregation, aggregation_entities))
+                    }) {
+                        Ok((aggregation, aggregation_entities)) => {
+                            for entity in &aggregation_entities {
+                                positions.insert(entity.name.clone(), obj.position);
+                            }
+                            entities.extend(aggregation_entities);
+                            aggregations.push(aggregation);
+                        }
+                        Err(err) => errors.push((err, Some(obj.position))),
+                    }
+                }
+                TypeDefinition::Object(obj) => {
+                    positions.insert(obj.name.clone(), obj.position);
+                    match Entity::from_object(obj) {
+                        Ok(entity) => entities.push(entity),
+                        Err(err) => errors.push((err, Some(obj.position))),
+                    }
+                }
+                TypeDefinition::Enum(enm) => {
+                    positions.insert(enm.name.clone(), enm.position);
+                    match GraphQLEnum::from_enum(enm) {
+                        Ok(gql_enum) => enums.push(gql_enum),
+                        Err(err) => errors.push((err, Some(enm.position))),
+                    }
+                }
+                TypeDefinition::Interface(interface) => {
+                    positions.insert(interface.name.clone(), interface.position);
+                    match GraphQLInterface::from_interface(interface) {
+                        Ok(gql_interface) => interfaces.push(gql_interface),
+                        Err(err) => errors.push((err, Some(interface.position))),
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        //Checks across type definitions are only meaningful once every definition is valid
+        if !errors.is_empty() {
+            return errors;
+        }
+
+        let entities = match unique_hashmap::from_vec_no_duplicates(
+            entities.into_iter().map(|e| (e.name.clone(), e)).collect(),
+        ) {
+            Ok(entities) => entities,
+            Err(err) => return vec![(err.context("Found entities with duplicate names"), None)],
+        };
+        let enums = match unique_hashmap::from_vec_no_duplicates(
+            enums.into_iter().map(|e| (e.name.clone(), e)).collect(),
+        ) {
+            Ok(enums) => enums,
+            Err(err) => return vec![(err.context("Found enums with duplicate names"), None)],
+        };
+        let interfaces = match unique_hashmap::from_vec_no_duplicates(
+            interfaces
+                .into_iter()
+                .map(|i| (i.name.clone(), i))
+                .collect(),
+        ) {
+            Ok(interfaces) => interfaces,
+            Err(err) => return vec![(err.context("Found interfaces with duplicate names"), None)],
+        };
+        let aggregations = match unique_hashmap::from_vec_no_duplicates(
+            aggregations
+                .into_iter()
+                .map(|a| (a.name.clone(), a))
+                .collect(),
+        ) {
+            Ok(aggregations) => aggregations,
+            Err(err) => {
+                return vec![(err.context("Found aggregations with duplicate names"), None)]
+            }
+        };
+        let schema = Schema {
+            entities,
+            enums,
+            interfaces,
+            aggregations,
+        };
+
+        let schema_checks: [fn(Self) -> anyhow::Result<Self>; 4] = [
+            Self::check_enum_type_defs,
+            Self::check_schema_for_reserved_words,
+            Self::check_duplicate_naming_between_enums_and_entities,
+            Self::check_duplicate_naming_of_interfaces,
+        ];
+        for check in schema_checks {
+            if let Err(err) = check(schema.clone()) {
+                errors.push((err, None));
+            }
+        }
+
+        for entity in schema.entities.values().sorted_by_key(|e| e.name.clone()) {
+            let position = positions.get(&entity.name).copied();
+            if let Err(err) = entity.check_related_type_defs_exist(&schema) {
+                errors.push((err, position));
+            }
+            if let Err(err) = entity.validate_field_types(&schema) {
+                errors.push((err, position));
+            }
+            if let Err(err) = entity.check_implemented_interfaces_exist(&schema) {
+                errors.push((err, position));
+            }
+        }
+
+        for interface in schema.interfaces.values().sorted_by_key(|i| i.name.clone()) {
+            let position = positions.get(&interface.name).copied();
+            if let Err(err) = interface.check_implementers(&schema) {
+                errors.push((err, position));
+            }
+        }
+
+        for aggregation in schema
+            .aggregations
+            .values()
+            .sorted_by_key(|a| a.name.clone())
+        {
+            let position = positions.get(&aggregation.name).copied();
+            if let Err(err) = aggregation.check_name_is_unique(&schema) {
+                errors.push((err, position));
+            }
+            if let Err(err) = aggregation.check_source(&schema) {
+                errors.push((err, position));
+            }
+        }
+
+        errors
+    }
+
     fn validate(self) -> anyhow::Result<Self> {
         self.check_enum_type_defs()?
             .check_schema_for_reserved_words()?
             .check_duplicate_naming_between_enums_and_entities()?
+            .check_duplicate_naming_of_interfaces()?
             .check_related_type_defs_exist()?
-            .validate_entity_field_types()
+            .validate_entity_field_types()?
+            .check_interface_implementers()
     }
 
     fn get_all_enum_type_names(&self) -> Vec<String> {
@@ -144,6 +372,9 @@ impl Schema {
             .map(|v| v.name.clone())
             .collect()
     }
+    fn get_all_interface_type_names(&self) -> Vec<String> {
+        self.interfaces.keys().cloned().collect()
+    }
 
     fn check_enum_type_defs(self) -> anyhow::Result<Self> {
         match check_enums_for_internal_reserved_words(self.get_all_enum_type_names()) {
@@ -161,6 +392,7 @@ impl Schema {
             self.get_all_enum_values(),
             self.get_all_entity_type_names(),
             self.get_all_entity_field_names(),
+            self.get_all_interface_type_names(),
         ]
         .concat();
 
@@ -190,7 +422,28 @@ impl Schema {
         }
     }
 
-    fn try_get_type_def(&self, name: &String) -> anyhow::Result<TypeDef> {
+    fn check_duplicate_naming_of_interfaces(self) -> anyhow::Result<Self> {
+        let duplicate_names = self
+            .get_all_interface_type_names()
+            .into_iter()
+            .filter(|k| self.entities.get(k).is_some() || self.enums.get(k).is_some())
+            .sorted()
+            .collect::<Vec<_>>();
+        if !duplicate_names.is_empty() {
+            Err(anyhow!(
+                "EE214: Schema contains the following interfaces with the same name as an enum \
+                 or entity, all type definitions must be unique in the schema: {}",
+                duplicate_names.join(", ")
+            ))
+        } else {
+            Ok(self)
+        }
+    }
+
+    fn try_get_type_def(&self, name: &str) -> anyhow::Result<TypeDef> {
+        if let Some(interface) = self.interfaces.get(name) {
+            return Ok(TypeDef::Interface(interface));
+        }
         match (self.entities.get(name), self.enums.get(name)) {
             (None, None) => Err(anyhow!("No type definition '{}' exists in schema", name)),
             (Some(_), Some(_)) => Err(anyhow!(
@@ -204,44 +457,7 @@ impl Schema {
 
     fn check_related_type_defs_exist(self) -> anyhow::Result<Self> {
         for entity in self.entities.values() {
-            for rel in entity.get_relationships() {
-                match &rel {
-                    Relationship::TypeDef { name } => {
-                        let _ = self.try_get_type_def(name)?;
-                    }
-                    Relationship::DerivedFrom {
-                        name,
-                        derived_from_field,
-                    } => {
-                        let type_def = self.try_get_type_def(name)?;
-
-                        match type_def {
-                            TypeDef::Enum => Err(anyhow!(
-                                "Cannot derive field {derived_from_field} from enum {name}. \
-                                 derivedFrom is intended to be used with Entity type definitions"
-                            ))?,
-                            TypeDef::Entity(derived_entity) => {
-                                match derived_entity.fields.get(derived_from_field) {
-                                    None => Err(anyhow!(
-                                        "Derived field {derived_from_field} does not exist on \
-                                         entity {name}."
-                                    ))?,
-                                    Some(field) => match field.field_type.get_underlying_scalar() {
-                                        GqlScalar::Custom(name) if name == entity.name => (),
-                                        GqlScalar::ID | GqlScalar::String => (),
-                                        _ => Err(anyhow!(
-                                            "Derived field '{derived_from_field}' on entity \
-                                             '{name}' must either be an ID, String, or an Object \
-                                             relationship with Entity '{}'",
-                                            entity.name
-                                        ))?,
-                                    },
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            entity.check_related_type_defs_exist(&self)?;
         }
 
         Ok(self)
@@ -255,6 +471,29 @@ impl Schema {
         for e in self.entities.values() {
             e.validate_field_types(&self)?;
         }
+        for interface in self.interfaces.values() {
+            interface.validate_field_types(&self)?;
+        }
+        Ok(self)
+    }
+
+    /// Validates that every entity only implements defined interfaces and that each
+    /// interface is implemented with all of its fields.
+    fn check_interface_implementers(self) -> anyhow::Result<Self> {
+        for interface in self.interfaces.values() {
+            interface.check_implementers(&self)?;
+        }
+        for entity in self.entities.values() {
+            entity.check_implemented_interfaces_exist(&self)?;
+        }
+        Ok(self)
+    }
+
+    fn check_aggregations(self) -> anyhow::Result<Self> {
+        for aggregation in self.aggregations.values() {
+            aggregation.check_name_is_unique(&self)?;
+            aggregation.check_source(&self)?;
+        }
         Ok(self)
     }
 }
@@ -325,11 +564,729 @@ impl GraphQLEnum {
     }
 }
 
+/// An interface type definition, which entities implement to be referenced
+/// polymorphically by fields typed as the interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphQLInterface {
+    pub name: String,
+    pub fields: HashMap<String, Field>,
+}
+
+impl GraphQLInterface {
+    fn from_interface(interface: &InterfaceType<String>) -> anyhow::Result<Self> {
+        let name = &interface.name;
+
+        let has_id = interface.fields.iter().any(|field| field.name == "id");
+        if !has_id {
+            return Err(anyhow!(
+                "EE218: No 'id' field found on interface {}. Please add an 'id' field to your \
+                 interface.",
+                name
+            ));
+        }
+
+        let fields = interface
+            .fields
+            .iter()
+            .map(Field::from_obj_field)
+            .collect::<anyhow::Result<Vec<Field>>>()
+            .context(format!("Failed parsing fields on interface {name}"))?;
+
+        if let Some(field) = fields.iter().find(|f| f.field_type.is_derived_from()) {
+            return Err(anyhow!(
+                "EE218: The field '{}' on interface {} can't use @derivedFrom. Please define \
+                 derived fields on the entities implementing the interface",
+                field.name,
+                name
+            ));
+        }
+
+        //The interface view has a "type" column with the name of the implementing entity
+        if fields.iter().any(|f| f.name == "type") {
+            return Err(anyhow!(
+                "EE218: The field 'type' on interface {} is reserved for the name of the \
+                 entity implementing the interface. Please rename the field",
+                name
+            ));
+        }
+
+        let fields = unique_hashmap::from_vec_no_duplicates(
+            fields.into_iter().map(|f| (f.name.clone(), f)).collect(),
+        )
+        .context(format!(
+            "Found fields with duplicate names on interface {name}"
+        ))?;
+
+        Ok(Self {
+            name: name.clone(),
+            fields,
+        })
+    }
+
+    /// Returns the fields of this [`GraphQLInterface`] sorted by field name.
+    pub fn get_fields(&self) -> Vec<&Field> {
+        self.fields.values().sorted_by_key(|v| &v.name).collect()
+    }
+
+    /// Returns the entities implementing this [`GraphQLInterface`] sorted by name.
+    pub fn get_implementers<'a>(&self, schema: &'a Schema) -> Vec<&'a Entity> {
+        schema
+            .entities
+            .values()
+            .filter(|entity| entity.interfaces.contains(&self.name))
+            .sorted_by_key(|entity| &entity.name)
+            .collect()
+    }
+
+    fn validate_field_types(&self, schema: &Schema) -> anyhow::Result<()> {
+        for field in self.get_fields() {
+            field.validate_field_type(schema)?;
+        }
+        Ok(())
+    }
+
+    /// Validate that the interface is implemented by at least one entity and that every
+    /// implementer declares each field of the interface with the same type, so the union
+    /// of the implementer tables has the same columns.
+    fn check_implementers(&self, schema: &Schema) -> anyhow::Result<()> {
+        let implementers = self.get_implementers(schema);
+        if implementers.is_empty() {
+            return Err(anyhow!(
+                "EE218: Interface {} isn't implemented by any entity. Please add \
+                 'implements {}' to at least one entity",
+                self.name,
+                self.name
+            ));
+        }
+
+        for entity in implementers {
+            for interface_field in self.get_fields() {
+                match entity.fields.get(&interface_field.name) {
+                    None => Err(anyhow!(
+                        "EE218: Entity {} implements interface {} but doesn't have its field \
+                         '{}'",
+                        entity.name,
+                        self.name,
+                        interface_field.name
+                    ))?,
+                    Some(field)
+                        if field.field_type.to_user_defined_field_type()
+                            != interface_field.field_type.to_user_defined_field_type() =>
+                    {
+                        Err(anyhow!(
+                            "EE218: The field '{}' on entity {} should have the type {} of \
+                             interface {}, but has the type {}",
+                            field.name,
+                            entity.name,
+                            interface_field.field_type,
+                            self.name,
+                            field.field_type
+                        ))?
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum AggregationInterval {
+    Hour,
+    Day,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum AggregateFunction {
+    Sum,
+    Count,
+    Min,
+    Max,
+    First,
+    Last,
+}
+
+/// A field of an aggregation with the @aggregate directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateField {
+    pub field: Field,
+    pub function: AggregateFunction,
+    ///The field of the source entity which is aggregated. Count doesn't have one
+    pub arg: Option<String>,
+}
+
+/// A type with the @aggregation directive. The data points of its time-series source
+/// entity are rolled up into buckets for each of its intervals, which are stored in an
+/// entity generated for every interval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregation {
+    pub name: String,
+    pub source: String,
+    pub intervals: Vec<AggregationInterval>,
+    ///The fields without @aggregate, which the data points are grouped by in every bucket
+    pub dimensions: Vec<Field>,
+    pub aggregates: Vec<AggregateField>,
+}
+
+impl Aggregation {
+    fn is_aggregation(obj: &ObjectType<String>) -> bool {
+        obj.directives
+            .iter()
+            .any(|directive| directive.name == "aggregation")
+    }
+
+    fn from_object(obj: &ObjectType<String>) -> anyhow::Result<Self> {
+        let name = &obj.name;
+
+        let aggregation_directives = obj
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "aggregation")
+            .collect::<Vec<_>>();
+        if aggregation_directives.len() > 1 {
+            return Err(anyhow!(
+                "EE220: Cannot use more than one @aggregation directive on aggregation {}",
+                name
+            ));
+        }
+
+        let mut intervals = vec![];
+        let mut source = None;
+        for (arg_name, arg_value) in &aggregation_directives[0].arguments {
+            match (arg_name.as_str(), arg_value) {
+                ("source", Value::String(value)) => source = Some(value.clone()),
+                ("intervals", Value::List(values)) => {
+                    for value in values {
+                        let interval = match value {
+                            Value::String(interval) => interval.parse::<AggregationInterval>().ok(),
+                            _ => None,
+                        }
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "EE220: Invalid interval {} of aggregation {}. Only \"hour\" and \
+                                 \"day\" are supported",
+                                value,
+                                name
+                            )
+                        })?;
+                        if intervals.contains(&interval) {
+                            return Err(anyhow!(
+                                "EE220: The interval \"{}\" is listed more than once on \
+                                 aggregation {}",
+                                interval,
+                                name
+                            ));
+                        }
+                        intervals.push(interval);
+                    }
+                }
+                ("source" | "intervals", _) => Err(anyhow!(
+                    "EE220: Invalid {} argument of the @aggregation directive on aggregation {}. \
+                     Eg. @aggregation(intervals: [\"hour\", \"day\"], source: \"Swap\")",
+                    arg_name,
+                    name
+                ))?,
+                (unknown_arg, _) => Err(anyhow!(
+                    "EE220: Unknown argument '{}' of the @aggregation directive on aggregation \
+                     {}. Only 'intervals' and 'source' are supported",
+                    unknown_arg,
+                    name
+                ))?,
+            }
+        }
+
+        let source = source.ok_or_else(|| {
+            anyhow!(
+                "EE220: No source entity supplied to the @aggregation directive on aggregation {}",
+                name
+            )
+        })?;
+        if intervals.is_empty() {
+            return Err(anyhow!(
+                "EE220: No intervals supplied to the @aggregation directive on aggregation {}",
+                name
+            ));
+        }
+
+        let mut dimensions = vec![];
+        let mut aggregates = vec![];
+        let mut has_id = false;
+        let mut has_timestamp = false;
+        for obj_field in &obj.fields {
+            let field = Field::from_obj_field(obj_field)
+                .context(format!("Failed parsing fields on aggregation {name}"))?;
+            if field.field_type.is_derived_from() {
+                return Err(anyhow!(
+                    "EE221: The field '{}' on aggregation {} can't use @derivedFrom",
+                    field.name,
+                    name
+                ));
+            }
+
+            let aggregate = AggregateField::from_obj_field(obj_field, &field)
+                .context(format!("Failed parsing fields on aggregation {name}"))?;
+            let expected_type = match field.name.as_str() {
+                "id" => {
+                    has_id = true;
+                    Some(UserDefinedFieldType::NonNullType(Box::new(
+                        UserDefinedFieldType::Single(GqlScalar::ID),
+                    )))
+                }
+                "timestamp" => {
+                    has_timestamp = true;
+                    Some(UserDefinedFieldType::non_null_timestamp())
+                }
+                _ => None,
+            };
+
+            match (expected_type, aggregate) {
+                (Some(expected_type), aggregate) => {
+                    if aggregate.is_some()
+                        || field.field_type.to_user_defined_field_type() != expected_type
+                    {
+                        return Err(anyhow!(
+                            "EE221: The field '{}' on aggregation {} should be '{}: {}' without \
+                             @aggregate. It's set for every bucket",
+                            field.name,
+                            name,
+                            field.name,
+                            expected_type
+                        ));
+                    }
+                }
+                (None, Some(aggregate)) => aggregates.push(aggregate),
+                (None, None) => dimensions.push(field),
+            }
+        }
+
+        if !has_id || !has_timestamp {
+            return Err(anyhow!(
+                "EE221: Aggregation {} should have the fields 'id: ID!' and 'timestamp: \
+                 Timestamp!'",
+                name
+            ));
+        }
+        if aggregates.is_empty() {
+            return Err(anyhow!(
+                "EE221: Aggregation {} should have at least one field with the @aggregate \
+                 directive",
+                name
+            ));
+        }
+
+        dimensions.sort_by(|a, b| a.name.cmp(&b.name));
+        aggregates.sort_by(|a, b| a.field.name.cmp(&b.field.name));
+
+        Ok(Self {
+            name: name.clone(),
+            source,
+            intervals,
+            dimensions,
+            aggregates,
+        })
+    }
+
+    pub fn get_entity_name(&self, interval: &AggregationInterval) -> String {
+        format!("{}_{}", self.name, interval)
+    }
+
+    /// Returns the entities holding the buckets of every interval. A bucket is keyed by its
+    /// start timestamp and the values of the dimensions.
+    fn get_entities(&self) -> anyhow::Result<Vec<Entity>> {
+        let fields = [
+            Field {
+                name: "id".to_string(),
+                field_type: FieldType::RegularField {
+                    field_type: UserDefinedFieldType::NonNullType(Box::new(
+                        UserDefinedFieldType::Single(GqlScalar::ID),
+                    )),
+                    has_indexed_directive: false,
+                    index_type: None,
+                },
+            },
+            Field {
+                name: "timestamp".to_string(),
+                field_type: FieldType::RegularField {
+                    field_type: UserDefinedFieldType::non_null_timestamp(),
+                    has_indexed_directive: true,
+                    index_type: None,
+                },
+            },
+        ]
+        .into_iter()
+        .chain(self.dimensions.iter().cloned())
+        .chain(
+            self.aggregates
+                .iter()
+                .map(|aggregate| aggregate.field.clone()),
+        )
+        .collect::<Vec<_>>();
+
+        self.intervals
+            .iter()
+            .map(|interval| {
+                let name = self.get_entity_name(interval);
+                let entity = Entity::new(&name, fields.clone(), vec![])
+                    .context(format!("Failed constructing entity {name}"))?;
+                Ok(Entity {
+                    //Buckets are only written by the rollups, which don't save history, since
+                    //they're recomputed from the data points on rollbacks
+                    is_immutable: true,
+                    aggregation: Some(self.name.clone()),
+                    ..entity
+                })
+            })
+            .collect()
+    }
+
+    fn check_name_is_unique(&self, schema: &Schema) -> anyhow::Result<()> {
+        if schema.entities.contains_key(&self.name)
+            || schema.enums.contains_key(&self.name)
+            || schema.interfaces.contains_key(&self.name)
+        {
+            return Err(anyhow!(
+                "EE214: Schema contains an aggregation with the same name as an enum, entity or \
+                 interface, all type definitions must be unique in the schema: {}",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+
+    fn get_source<'a>(&self, schema: &'a Schema) -> anyhow::Result<&'a Entity> {
+        match schema.entities.get(&self.source) {
+            Some(source) if source.is_timeseries => Ok(source),
+            _ => Err(anyhow!(
+                "EE222: The source {} of aggregation {} should be an entity with \
+                 @entity(timeseries: true)",
+                self.source,
+                self.name
+            )),
+        }
+    }
+
+    /// Validates that the dimensions are fields of the source entity with the same type
+    /// and that every aggregate has a numeric argument matching its type.
+    fn check_source(&self, schema: &Schema) -> anyhow::Result<()> {
+        let source = self.get_source(schema)?;
+
+        for dimension in &self.dimensions {
+            let source_field = source.fields.get(&dimension.name);
+            let is_same_type = source_field.is_some_and(|source_field| {
+                source_field.field_type.to_user_defined_field_type()
+                    == dimension.field_type.to_user_defined_field_type()
+            });
+            if !is_same_type {
+                return Err(anyhow!(
+                    "EE222: The field '{}' on aggregation {} groups the buckets by a field of \
+                     {}, so it should have the same name and type as a field of {}",
+                    dimension.name,
+                    self.name,
+                    self.source,
+                    self.source
+                ));
+            }
+            if dimension.field_type.is_array() || dimension.get_interface(schema)?.is_some() {
+                return Err(anyhow!(
+                    "EE222: The field '{}' on aggregation {} can't group the buckets, since \
+                     arrays and interfaces are unsupported",
+                    dimension.name,
+                    self.name
+                ));
+            }
+        }
+
+        for aggregate in &self.aggregates {
+            aggregate
+                .check_type(source)
+                .context(format!("Invalid aggregate on aggregation {}", self.name))?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds the query rolling up the data points written or rolled back in a batch into the
+    /// buckets of an interval. Only the buckets of the data points are recomputed from all of
+    /// their data points and the ones left without data points are deleted, so running it
+    /// again gives the same result. The array of the timestamps of the data points is the $1
+    /// parameter.
+    pub fn get_rollup_query(
+        &self,
+        interval: &AggregationInterval,
+        schema: &Schema,
+        public_schema: &str,
+    ) -> anyhow::Result<String> {
+        let bucket = |timestamp: &str| {
+            format!("date_trunc('{interval}', {timestamp} AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'")
+        };
+        let source_table = format!("\"{public_schema}\".\"{}\"", self.source);
+        let bucket_table = format!("\"{public_schema}\".\"{}\"", self.get_entity_name(interval));
+
+        let mut dimension_names = vec![];
+        for dimension in &self.dimensions {
+            if dimension.field_type.is_entity_field(schema)? {
+                dimension_names.push(format!("\"{}_id\"", dimension.name));
+            } else {
+                dimension_names.push(format!("\"{}\"", dimension.name));
+            }
+        }
+
+        let mut aggregate_names = vec![];
+        let mut aggregate_expressions = vec![];
+        for aggregate in &self.aggregates {
+            let aggregate_name = format!("\"{}\"", aggregate.field.name);
+            let arg = aggregate
+                .arg
+                .as_ref()
+                .map(|arg| format!("\"{arg}\""))
+                .unwrap_or_default();
+            let expression = match aggregate.function {
+                AggregateFunction::Count => "count(*)".to_string(),
+                AggregateFunction::Sum => format!("sum({arg})"),
+                AggregateFunction::Min => format!("min({arg})"),
+                AggregateFunction::Max => format!("max({arg})"),
+                AggregateFunction::First => {
+                    format!("(array_agg({arg} ORDER BY \"timestamp\", \"id\"))[1]")
+                }
+                AggregateFunction::Last => {
+                    format!("(array_agg({arg} ORDER BY \"timestamp\" DESC, \"id\" DESC))[1]")
+                }
+            };
+            let sql_type = aggregate
+                .field
+                .field_type
+                .to_user_defined_field_type()
+                .to_underlying_postgres_primitive(schema)?
+                .get_sql_type();
+            aggregate_expressions.push(format!("{expression}::{sql_type} AS {aggregate_name}"));
+            aggregate_names.push(aggregate_name);
+        }
+
+        let bucket_id = ["extract(epoch FROM \"timestamp\")::BIGINT::TEXT".to_string()]
+            .into_iter()
+            .chain(
+                dimension_names
+                    .iter()
+                    .map(|name| format!("coalesce({name}::TEXT, '')")),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        let columns = ["\"timestamp\"".to_string()]
+            .into_iter()
+            .chain(dimension_names.iter().cloned())
+            .chain(aggregate_names.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let group_by = ["1".to_string()]
+            .into_iter()
+            .chain(dimension_names.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let bucket_columns = [format!("{} AS \"timestamp\"", bucket("\"timestamp\""))]
+            .into_iter()
+            .chain(dimension_names.iter().cloned())
+            .chain(aggregate_expressions)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let updates = aggregate_names
+            .iter()
+            .map(|name| format!("{name} = EXCLUDED.{name}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(format!(
+            "WITH \"bucket_starts\" AS (
+SELECT DISTINCT {bucket_start} AS \"start\"
+FROM unnest($1::TIMESTAMPTZ[]) AS \"data_point_timestamp\"
+), \"buckets\" AS (
+SELECT concat_ws('-', {bucket_id}) AS \"id\", {columns}
+FROM (
+SELECT {bucket_columns}
+FROM {source_table}
+JOIN \"bucket_starts\" ON \"timestamp\" >= \"bucket_starts\".\"start\"
+AND \"timestamp\" < {bucket_end}
+GROUP BY {group_by}
+) AS \"recomputed\"
+), \"upserted\" AS (
+INSERT INTO {bucket_table} (\"id\", {columns})
+SELECT \"id\", {columns} FROM \"buckets\"
+ON CONFLICT (\"id\") DO UPDATE SET {updates}
+)
+DELETE FROM {bucket_table}
+WHERE \"timestamp\" IN (SELECT \"start\" FROM \"bucket_starts\")
+AND \"id\" NOT IN (SELECT \"id\" FROM \"buckets\");",
+            bucket_start = bucket("\"data_point_timestamp\""),
+            bucket_end = format!(
+                "(\"bucket_starts\".\"start\" AT TIME ZONE 'UTC' + INTERVAL '1 {interval}') AT \
+                 TIME ZONE 'UTC'"
+            ),
+        ))
+    }
+}
+
+impl AggregateField {
+    fn from_obj_field(obj_field: &ObjField<String>, field: &Field) -> anyhow::Result<Option<Self>> {
+        let aggregate_directives = obj_field
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "aggregate")
+            .collect::<Vec<_>>();
+        let aggregate_directive = match aggregate_directives.as_slice() {
+            [] => return Ok(None),
+            [aggregate_directive] => aggregate_directive,
+            _ => {
+                return Err(anyhow!(
+                    "EE223: Cannot use more than one @aggregate directive on field {}",
+                    field.name
+                ))
+            }
+        };
+
+        let mut function = None;
+        let mut arg = None;
+        for (arg_name, arg_value) in &aggregate_directive.arguments {
+            match (arg_name.as_str(), arg_value) {
+                ("fn", Value::String(value)) => {
+                    function = Some(value.parse::<AggregateFunction>().map_err(|_| {
+                        anyhow!(
+                            "EE223: Unsupported aggregate function \"{}\" on field {}. Only \
+                             \"sum\", \"count\", \"min\", \"max\", \"first\" and \"last\" are \
+                             supported",
+                            value,
+                            field.name
+                        )
+                    })?)
+                }
+                ("arg", Value::String(value)) => arg = Some(value.clone()),
+                ("fn" | "arg", _) => Err(anyhow!(
+                    "EE223: The {} argument of the @aggregate directive on field {} should be a \
+                     string",
+                    arg_name,
+                    field.name
+                ))?,
+                (unknown_arg, _) => Err(anyhow!(
+                    "EE223: Unknown argument '{}' of the @aggregate directive on field {}. Only \
+                     'fn' and 'arg' are supported",
+                    unknown_arg,
+                    field.name
+                ))?,
+            }
+        }
+
+        let function = function.ok_or_else(|| {
+            anyhow!(
+                "EE223: No 'fn' argument supplied to the @aggregate directive on field {}",
+                field.name
+            )
+        })?;
+        match (&function, &arg) {
+            (AggregateFunction::Count, Some(_)) => Err(anyhow!(
+                "EE223: The count aggregate on field {} doesn't take an 'arg' argument",
+                field.name
+            ))?,
+            (AggregateFunction::Count, None) | (_, Some(_)) => (),
+            (_, None) => Err(anyhow!(
+                "EE223: No 'arg' argument supplied to the @aggregate directive on field {}",
+                field.name
+            ))?,
+        }
+
+        Ok(Some(Self {
+            field: field.clone(),
+            function,
+            arg,
+        }))
+    }
+
+    fn check_type(&self, source: &Entity) -> anyhow::Result<()> {
+        let field_type = self.field.field_type.to_user_defined_field_type();
+        let scalar = field_type.get_underlying_scalar();
+        if field_type.is_array() {
+            return Err(anyhow!(
+                "EE224: The aggregate field '{}' can't be an array",
+                self.field.name
+            ));
+        }
+
+        let Some(arg) = &self.arg else {
+            //Only count doesn't have an argument
+            return match scalar {
+                GqlScalar::Int | GqlScalar::Int8 | GqlScalar::BigInt(_) => Ok(()),
+                _ => Err(anyhow!(
+                    "EE224: The count aggregate field '{}' should be an Int, an Int8 or a BigInt",
+                    self.field.name
+                )),
+            };
+        };
+
+        let arg_type = match source.fields.get(arg) {
+            Some(Field {
+                field_type: FieldType::RegularField { field_type, .. },
+                ..
+            }) if !field_type.is_array() => field_type,
+            _ => Err(anyhow!(
+                "EE224: The argument '{}' of the aggregate field '{}' should be a field of {}",
+                arg,
+                self.field.name,
+                source.name
+            ))?,
+        };
+
+        let arg_scalar = arg_type.get_underlying_scalar();
+        let is_numeric = matches!(
+            arg_scalar,
+            GqlScalar::Int
+                | GqlScalar::Int8
+                | GqlScalar::BigInt(_)
+                | GqlScalar::Float
+                | GqlScalar::BigDecimal(_)
+        );
+        if !is_numeric || std::mem::discriminant(&arg_scalar) != std::mem::discriminant(&scalar) {
+            return Err(anyhow!(
+                "EE224: The aggregate field '{}' should have the numeric type of its argument \
+                 '{}', which is {}",
+                self.field.name,
+                arg,
+                arg_type
+            ));
+        }
+        if arg_type.is_optional() && !field_type.is_optional() {
+            return Err(anyhow!(
+                "EE224: The aggregate field '{}' should be nullable, since its argument '{}' is \
+                 nullable",
+                self.field.name,
+                arg
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Entity {
     pub name: String,
     pub fields: HashMap<String, Field>,
     pub multi_field_indexes: Vec<MultiFieldIndex>,
+    pub interfaces: Vec<String>,
+    ///Set with @entity(immutable: true). Immutable entities are only inserted once and
+    ///don't have a history table. Their rows keep the event which inserted them instead
+    pub is_immutable: bool,
+    ///Set with @entity(timeseries: true). The data points of a time-series entity are
+    ///rolled up into the buckets of the aggregations using it as their source
+    pub is_timeseries: bool,
+    ///The @aggregation type this entity holds the buckets of for one of its intervals
+    pub aggregation: Option<String>,
+}
+
+///The arguments of the @entity directive
+struct EntityDirective {
+    is_immutable: bool,
+    is_timeseries: bool,
 }
 
 impl Entity {
@@ -352,7 +1309,7 @@ impl Entity {
                     .validate_no_duplicates(&fields)?
                     .validate_field_name_exists_or_is_allowed(
                         &fields,
-                        &vec!["db_write_timestamp".to_string()],
+                        &["db_write_timestamp".to_string()],
                     )?
                     .validate_no_index_on_derived_field(&fields)?
                     .validate_no_index_on_id_field()
@@ -377,6 +1334,10 @@ impl Entity {
             name: name.to_string(),
             fields,
             multi_field_indexes,
+            interfaces: vec![],
+            is_immutable: false,
+            is_timeseries: false,
+            aggregation: None,
         })
     }
 
@@ -424,6 +1385,11 @@ impl Entity {
                 "Failed parsing multi field indexes on entity {name}"
             ))?;
 
+        let EntityDirective {
+            is_immutable,
+            is_timeseries,
+        } = Self::get_entity_directive(obj)?;
+
         // Map each field in the ObjectType to a Field, passing the indexed status
         let fields = obj
             .fields
@@ -437,8 +1403,110 @@ impl Entity {
         let entity = Self::new(name, fields, multi_field_indexes)
             .context(format!("Failed constructing entity {name}",))?;
 
+        let entity = if is_timeseries {
+            entity.index_timeseries_timestamp()?
+        } else {
+            entity
+        };
+
         // Here, store indexed information somewhere within your entity structure or handle them accordingly
-        Ok(entity)
+        Ok(Self {
+            interfaces: obj.implements_interfaces.clone(),
+            //Data points are never updated after they're rolled up
+            is_immutable: is_immutable || is_timeseries,
+            is_timeseries,
+            ..entity
+        })
+    }
+
+    /// Reads the arguments of the optional @entity directive. It's the directive used by
+    /// subgraph schemas, so they can be used as they are.
+    fn get_entity_directive(obj: &ObjectType<String>) -> anyhow::Result<EntityDirective> {
+        let entity_directives = obj
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "entity")
+            .collect::<Vec<_>>();
+
+        let entity_directive = match entity_directives.as_slice() {
+            [] => {
+                return Ok(EntityDirective {
+                    is_immutable: false,
+                    is_timeseries: false,
+                })
+            }
+            [entity_directive] => entity_directive,
+            _ => {
+                return Err(anyhow!(
+                    "EE219: Cannot use more than one @entity directive on entity {}",
+                    obj.name
+                ))
+            }
+        };
+
+        let mut is_immutable = None;
+        let mut is_timeseries = false;
+        for (arg_name, arg_value) in &entity_directive.arguments {
+            match (arg_name.as_str(), arg_value) {
+                ("immutable", Value::Boolean(value)) => is_immutable = Some(*value),
+                ("timeseries", Value::Boolean(value)) => is_timeseries = *value,
+                ("immutable" | "timeseries", _) => Err(anyhow!(
+                    "EE219: The {} argument of the @entity directive on entity {} should be a \
+                     boolean",
+                    arg_name,
+                    obj.name
+                ))?,
+                (unknown_arg, _) => Err(anyhow!(
+                    "EE219: Unknown argument '{}' of the @entity directive on entity {}. Only \
+                     'immutable' and 'timeseries' are supported",
+                    unknown_arg,
+                    obj.name
+                ))?,
+            }
+        }
+
+        if is_timeseries && is_immutable == Some(false) {
+            return Err(anyhow!(
+                "EE225: Time-series entity {} can't be mutable. Please remove the immutable \
+                 argument of the @entity directive",
+                obj.name
+            ));
+        }
+
+        Ok(EntityDirective {
+            is_immutable: is_immutable.unwrap_or(false),
+            is_timeseries,
+        })
+    }
+
+    /// Time-series entities are bucketed by their timestamp field. It gets indexed, since
+    /// the buckets are rolled up with a range query on it.
+    fn index_timeseries_timestamp(mut self) -> anyhow::Result<Self> {
+        let is_indexed = self
+            .fields
+            .get("timestamp")
+            .is_some_and(|field| field.is_indexed_field(&self));
+
+        match self.fields.get_mut("timestamp") {
+            Some(Field {
+                field_type:
+                    FieldType::RegularField {
+                        field_type,
+                        has_indexed_directive,
+                        ..
+                    },
+                ..
+            }) if *field_type == UserDefinedFieldType::non_null_timestamp() => {
+                if !is_indexed {
+                    *has_indexed_directive = true;
+                }
+                Ok(self)
+            }
+            _ => Err(anyhow!(
+                "EE225: Time-series entity {} should have a 'timestamp: Timestamp!' field",
+                self.name
+            )),
+        }
     }
 
     /// Returns the fields of this [`Entity`] sorted by field name.
@@ -484,7 +1552,7 @@ impl Entity {
                         |e| Some(Err(e)),
                         |type_def| match type_def {
                             TypeDef::Entity(entity) => Some(Ok((field, entity))),
-                            TypeDef::Enum => None,
+                            TypeDef::Enum | TypeDef::Interface(_) => None,
                         },
                     )
                 } else {
@@ -500,6 +1568,53 @@ impl Entity {
     ///
     /// This function will return an error if there is a defined related type where the type does
     /// not exist on the schema.
+    fn check_related_type_defs_exist(&self, schema: &Schema) -> anyhow::Result<()> {
+        for rel in self.get_relationships() {
+            match &rel {
+                Relationship::TypeDef { name } => {
+                    let _ = schema.try_get_type_def(name)?;
+                }
+                Relationship::DerivedFrom {
+                    name,
+                    derived_from_field,
+                } => {
+                    let type_def = schema.try_get_type_def(name)?;
+
+                    match type_def {
+                        TypeDef::Enum => Err(anyhow!(
+                            "Cannot derive field {derived_from_field} from enum {name}. \
+                             derivedFrom is intended to be used with Entity type definitions"
+                        ))?,
+                        TypeDef::Interface(_) => Err(anyhow!(
+                            "Cannot derive field {derived_from_field} from interface {name}. \
+                             derivedFrom is intended to be used with Entity type definitions"
+                        ))?,
+                        TypeDef::Entity(derived_entity) => {
+                            match derived_entity.fields.get(derived_from_field) {
+                                None => Err(anyhow!(
+                                    "Derived field {derived_from_field} does not exist on \
+                                     entity {name}."
+                                ))?,
+                                Some(field) => match field.field_type.get_underlying_scalar() {
+                                    GqlScalar::Custom(name) if name == self.name => (),
+                                    GqlScalar::ID | GqlScalar::String => (),
+                                    _ => Err(anyhow!(
+                                        "Derived field '{derived_from_field}' on entity \
+                                         '{name}' must either be an ID, String, or an Object \
+                                         relationship with Entity '{}'",
+                                        self.name
+                                    ))?,
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn validate_field_types(&self, schema: &Schema) -> anyhow::Result<()> {
         for field in self.get_fields() {
             field.validate_field_type(schema)?;
@@ -507,6 +1622,20 @@ impl Entity {
         Ok(())
     }
 
+    fn check_implemented_interfaces_exist(&self, schema: &Schema) -> anyhow::Result<()> {
+        for interface_name in &self.interfaces {
+            if !schema.interfaces.contains_key(interface_name) {
+                return Err(anyhow!(
+                    "EE218: Entity {} implements '{}', which isn't an interface defined in the \
+                     schema",
+                    self.name,
+                    interface_name
+                ));
+            }
+        }
+        Ok(())
+    }
+
     ///Returns defined multi field indices where definitions
     ///have > 1 fields.
     pub fn get_composite_indices(&self) -> Vec<Vec<String>> {
@@ -540,6 +1669,55 @@ fn get_positive_integer(arg_value: &Value<String>) -> anyhow::Result<u32> {
     }
 }
 
+///Gets the access method from the type argument of an @index directive on a field.
+///A GIN index is only supported on Json fields
+fn get_index_type(
+    directive: &Directive<'_, String>,
+    field_name: &str,
+    underlying_scalar: &GqlScalar,
+) -> anyhow::Result<Option<IndexType>> {
+    let mut index_type = None;
+    for (arg_name, arg_value) in &directive.arguments {
+        match (arg_name.as_str(), arg_value) {
+            ("type", Value::String(value)) => {
+                index_type = Some(value.parse::<IndexType>().map_err(|_| {
+                    anyhow!(
+                        "EE202: Unsupported index type '{}' on field {}. The only supported \
+                         type is \"gin\"",
+                        value,
+                        field_name
+                    )
+                })?);
+            }
+            ("type", _) => {
+                return Err(anyhow!(
+                    "EE202: The 'type' argument of the @index directive on field {} should be \
+                     a string",
+                    field_name
+                ))
+            }
+            (unknown_param, _) => {
+                return Err(anyhow!(
+                    "EE202: The @index directive on a field only takes a 'type' argument. \
+                     Unknown argument '{}' on field {}",
+                    unknown_param,
+                    field_name
+                ))
+            }
+        }
+    }
+
+    if index_type == Some(IndexType::Gin) && *underlying_scalar != GqlScalar::Json {
+        return Err(anyhow!(
+            "EE202: A gin index is only supported on Json fields. Field '{}' is of type {}",
+            field_name,
+            underlying_scalar
+        ));
+    }
+
+    Ok(index_type)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Field {
     pub name: String,
@@ -626,6 +1804,11 @@ impl Field {
         )
         .get_underlying_scalar();
 
+        let index_type = match indexed_directives.first() {
+            None => None,
+            Some(d) => get_index_type(d, &field.name, &underlying_scalar)?,
+        };
+
         let mut pg_type_modifications = PgTypeModifications::default();
 
         // Process @config
@@ -705,10 +1888,29 @@ impl Field {
                     pg_type_modifications.big_decimal_precision_scale =
                         Some((precision.unwrap(), scale.unwrap()));
                 }
+                GqlScalar::Bytes(_) => match config_directive.arguments.as_slice() {
+                    [(arg_name, Value::String(storage))] if arg_name == "storage" => {
+                        pg_type_modifications.bytes_storage = storage.parse().map_err(|_| {
+                            anyhow!(
+                                "EE216: Unsupported storage '{}' of the Bytes field '{}'. It \
+                                 should be either \"text\" or \"bytea\"",
+                                storage,
+                                field.name
+                            )
+                        })?;
+                    }
+                    _ => {
+                        return Err(anyhow!(
+                            "EE216: The config directive on a Bytes should only take a single \
+                             string argument called 'storage'. Field '{}'",
+                            field.name
+                        ));
+                    }
+                },
                 _ => {
                     return Err(anyhow!(
-                        "EE215: The config directive is only applicable to BigInt and BigDecimal \
-                         scalar types. Field '{}'",
+                        "EE215: The config directive is only applicable to BigInt, BigDecimal \
+                         and Bytes scalar types. Field '{}'",
                         field.name
                     ));
                 }
@@ -718,6 +1920,7 @@ impl Field {
         let params = FieldTypeParams {
             derived_from_field,
             has_indexed_directive: is_indexed,
+            index_type,
             pg_type_modifications,
         };
 
@@ -764,7 +1967,10 @@ impl Field {
                     //In the case where there is a recipracol lookup, the actual
                     //underlying field contains _id at the end
                     GqlScalar::Custom(name)
-                        if matches!(schema.try_get_type_def(&name)?, TypeDef::Entity(_)) =>
+                        if matches!(
+                            schema.try_get_type_def(&name)?,
+                            TypeDef::Entity(_) | TypeDef::Interface(_)
+                        ) =>
                     {
                         Ok(format!("{derived_from_field}_id"))
                     }
@@ -833,6 +2039,7 @@ impl Field {
                 field_type: gql_field_type.to_underlying_postgres_primitive(schema)?,
                 is_array: gql_field_type.is_array(),
                 is_index: self.is_indexed_field(entity),
+                index_type: self.field_type.get_index_type(),
                 linked_entity: gql_field_type.get_linked_entity(schema)?,
                 is_primary_key: self.is_primary_key(),
                 is_nullable: gql_field_type.is_optional(),
@@ -840,6 +2047,53 @@ impl Field {
         }
     }
 
+    ///Returns the interface when the field references an entity implementing it
+    pub fn get_interface<'a>(
+        &self,
+        schema: &'a Schema,
+    ) -> anyhow::Result<Option<&'a GraphQLInterface>> {
+        match &self.field_type {
+            FieldType::DerivedFromField { .. } => Ok(None),
+            FieldType::RegularField { field_type, .. } => {
+                match field_type.get_underlying_scalar() {
+                    GqlScalar::Custom(name) => match schema.try_get_type_def(&name)? {
+                        TypeDef::Interface(interface) => Ok(Some(interface)),
+                        TypeDef::Entity(_) | TypeDef::Enum => Ok(None),
+                    },
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+
+    ///Fields referencing an interface are stored as a tagged relational key, the id in
+    ///the <field>_id column and the name of the referenced entity in the <field>_type column.
+    ///Returns the postgres field of the <field>_type column, or None for any other field
+    pub fn get_type_tag_postgres_field(&self, schema: &Schema) -> anyhow::Result<Option<PGField>> {
+        let interface = self.get_interface(schema)?;
+        if let Some(interface) = interface {
+            //A single tag column can't describe the entities of every item in a list
+            if self.field_type.is_array() {
+                return Err(anyhow!(
+                    "EE211: Arrays of entities is unsupported. Please use one of the methods for \
+                     referencing entities outlined in the docs. The interface being referenced in \
+                     the array is '{}'.",
+                    interface.name
+                ));
+            }
+        }
+        Ok(interface.map(|_| PGField {
+            field_name: format!("{}_type", self.name),
+            field_type: PGPrimitive::Text,
+            is_array: false,
+            is_index: false,
+            index_type: None,
+            linked_entity: None,
+            is_primary_key: false,
+            is_nullable: self.field_type.is_optional(),
+        }))
+    }
+
     pub fn get_derived_from_field(&self) -> Option<DerivedFieldTemplate> {
         match &self.field_type {
             FieldType::DerivedFromField {
@@ -886,7 +2140,7 @@ impl MultiFieldIndex {
     fn validate_field_name_exists_or_is_allowed(
         self,
         fields: &HashMap<String, Field>,
-        allowed_names: &Vec<String>,
+        allowed_names: &[String],
     ) -> anyhow::Result<Self> {
         for field_name in &self.0 {
             if !fields.contains_key(field_name) && !allowed_names.contains(field_name) {
@@ -968,6 +2222,10 @@ pub enum UserDefinedFieldType {
 }
 
 impl UserDefinedFieldType {
+    fn non_null_timestamp() -> Self {
+        Self::NonNullType(Box::new(Self::Single(GqlScalar::Timestamp)))
+    }
+
     fn from_obj_field_type(
         obj_field_type: &ObjType<'_, String>,
         pg_type_modifications: &PgTypeModifications,
@@ -993,7 +2251,10 @@ impl UserDefinedFieldType {
                 Self::NonNullType(inner_field_type) => match inner_field_type.as_ref() {
                     //Don't allow non derived from enity relationships inside arrays
                     Self::Single(GqlScalar::Custom(name))
-                        if matches!(schema.try_get_type_def(name)?, TypeDef::Entity(_)) =>
+                        if matches!(
+                            schema.try_get_type_def(name)?,
+                            TypeDef::Entity(_) | TypeDef::Interface(_)
+                        ) =>
                     {
                         Err(anyhow!(
                             "EE211: Arrays of entities is unsupported. Please use one of the \
@@ -1011,6 +2272,14 @@ impl UserDefinedFieldType {
                     Self::Single(GqlScalar::Timestamp) => {
                         Err(anyhow!("Arrays of timestamps are not yet supported."))
                     }
+                    Self::Single(GqlScalar::Bytes(BytesStorage::Bytea)) => Err(anyhow!(
+                        "EE216: Arrays of Bytes can't be stored as bytea. Please remove the \
+                         storage config from the field."
+                    )),
+                    Self::Single(GqlScalar::Json) => Err(anyhow!(
+                        "Arrays of Json are not supported. Please use a single Json field \
+                         holding the array instead."
+                    )),
                     _ => field_type.validate_type(schema),
                 },
                 Self::Single(gql_scalar) => Err(anyhow!(
@@ -1183,11 +2452,23 @@ impl Serialize for UserDefinedFieldType {
 pub struct PgTypeModifications {
     pub big_int_precision: Option<u32>,
     pub big_decimal_precision_scale: Option<(u32, u32)>,
+    pub bytes_storage: BytesStorage,
+}
+
+///The column type of a Bytes field, set with @config(storage: ...). Bytes are hex strings
+///stored as text by default, or as half the size in a bytea column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum BytesStorage {
+    #[default]
+    Text,
+    Bytea,
 }
 
 pub struct FieldTypeParams {
     pub derived_from_field: Option<String>,
     pub has_indexed_directive: bool,
+    pub index_type: Option<IndexType>,
     pub pg_type_modifications: PgTypeModifications,
 }
 
@@ -1200,6 +2481,8 @@ pub enum FieldType {
     RegularField {
         field_type: UserDefinedFieldType,
         has_indexed_directive: bool,
+        ///Only set for an @index directive with a type argument
+        index_type: Option<IndexType>,
     },
 }
 
@@ -1229,6 +2512,7 @@ impl FieldType {
             None => Ok(Self::RegularField {
                 field_type,
                 has_indexed_directive: params.has_indexed_directive,
+                index_type: params.index_type,
             }),
             Some(derived_from_field) => match field_type.get_name_of_derived_from_entity() {
                 None => {
@@ -1277,6 +2561,13 @@ impl FieldType {
         }
     }
 
+    fn get_index_type(&self) -> Option<IndexType> {
+        match self {
+            Self::DerivedFromField { .. } => None,
+            Self::RegularField { index_type, .. } => index_type.clone(),
+        }
+    }
+
     pub fn is_array(&self) -> bool {
         match self {
             Self::DerivedFromField { .. } => true,
@@ -1310,6 +2601,7 @@ impl FieldType {
         Ok(Self::RegularField {
             field_type: UserDefinedFieldType::from_ethabi_type(abi_type)?,
             has_indexed_directive: false,
+            index_type: None,
         })
     }
 }
@@ -1348,9 +2640,13 @@ pub enum GqlScalar {
     #[subenum(AdditionalGqlScalar)]
     BigDecimal(Option<(u32, u32)>),
     #[subenum(AdditionalGqlScalar)]
-    Timestamp,
+    Timestamp,
+    #[subenum(AdditionalGqlScalar)]
+    Bytes(BytesStorage),
+    #[subenum(AdditionalGqlScalar)]
+    Int8,
     #[subenum(AdditionalGqlScalar)]
-    Bytes,
+    Json,
     Custom(String),
 }
 
@@ -1374,9 +2670,10 @@ pub enum Relationship {
 impl GqlScalar {
     fn is_entity(&self, schema: &Schema) -> anyhow::Result<bool> {
         match self {
-            GqlScalar::Custom(name) => {
-                Ok(matches!(schema.try_get_type_def(name)?, TypeDef::Entity(_)))
-            }
+            GqlScalar::Custom(name) => Ok(matches!(
+                schema.try_get_type_def(name)?,
+                TypeDef::Entity(_) | TypeDef::Interface(_)
+            )),
             _ => Ok(false),
         }
     }
@@ -1393,7 +2690,9 @@ impl GqlScalar {
                 GqlScalar::BigDecimal(pg_type_modifications.big_decimal_precision_scale)
             }
             "Timestamp" => GqlScalar::Timestamp,
-            "Bytes" => GqlScalar::Bytes,
+            "Bytes" => GqlScalar::Bytes(pg_type_modifications.bytes_storage),
+            "Int8" => GqlScalar::Int8,
+            "Json" => GqlScalar::Json,
             name => GqlScalar::Custom(name.to_string()),
         }
     }
@@ -1405,7 +2704,9 @@ impl GqlScalar {
             GqlScalar::Int => PGPrimitive::Integer,
             GqlScalar::Float => PGPrimitive::DoublePrecision, // Should we allow this type? Rounding issues will abound.
             GqlScalar::Boolean => PGPrimitive::Boolean,
-            GqlScalar::Bytes => PGPrimitive::Text,
+            GqlScalar::Int8 => PGPrimitive::Int8,
+            GqlScalar::Bytes(BytesStorage::Text) => PGPrimitive::Text,
+            GqlScalar::Bytes(BytesStorage::Bytea) => PGPrimitive::Bytea,
             GqlScalar::BigInt(None) => PGPrimitive::Numeric(None),
             GqlScalar::BigInt(Some(precision)) => PGPrimitive::Numeric(Some((*precision, 0))), //  We leave the scale as zero since it is not relevant for integers.
             GqlScalar::BigDecimal(None) => PGPrimitive::Numeric(None),
@@ -1413,8 +2714,9 @@ impl GqlScalar {
                 PGPrimitive::Numeric(Some((*precision, *scale)))
             }
             GqlScalar::Timestamp => PGPrimitive::Timestamp,
+            GqlScalar::Json => PGPrimitive::Json,
             GqlScalar::Custom(name) => match schema.try_get_type_def(name)? {
-                TypeDef::Entity(_) => PGPrimitive::Text,
+                TypeDef::Entity(_) | TypeDef::Interface(_) => PGPrimitive::Text,
                 TypeDef::Enum => PGPrimitive::Enum(name.clone()),
             },
         };
@@ -1429,11 +2731,14 @@ impl GqlScalar {
             GqlScalar::BigInt(_) => RescriptTypeIdent::BigInt,
             GqlScalar::BigDecimal(_) => RescriptTypeIdent::BigDecimal,
             GqlScalar::Float => RescriptTypeIdent::Float,
-            GqlScalar::Bytes => RescriptTypeIdent::String,
+            GqlScalar::Int8 => RescriptTypeIdent::BigInt,
+            GqlScalar::Bytes(BytesStorage::Text) => RescriptTypeIdent::String,
+            GqlScalar::Bytes(BytesStorage::Bytea) => RescriptTypeIdent::Bytea,
             GqlScalar::Boolean => RescriptTypeIdent::Bool,
             GqlScalar::Timestamp => RescriptTypeIdent::Timestamp,
+            GqlScalar::Json => RescriptTypeIdent::Json,
             GqlScalar::Custom(name) => match schema.try_get_type_def(name)? {
-                TypeDef::Entity(_) => RescriptTypeIdent::ID,
+                TypeDef::Entity(_) | TypeDef::Interface(_) => RescriptTypeIdent::ID,
                 TypeDef::Enum => RescriptTypeIdent::SchemaEnum(name.to_capitalized_options()),
             },
         };
@@ -1444,6 +2749,8 @@ impl GqlScalar {
         let opt_entity_name = match self {
             Self::Custom(name) => match schema.try_get_type_def(name)? {
                 TypeDef::Entity(entity) => Some(entity.name.clone()),
+                //Links to the union view of the interface implementers
+                TypeDef::Interface(interface) => Some(interface.name.clone()),
                 TypeDef::Enum => None,
             },
             _ => None,
@@ -1456,9 +2763,10 @@ impl GqlScalar {
 #[cfg(test)]
 mod tests {
     use super::{
-        anyhow, Entity, Field, FieldType, GqlScalar, GraphQLEnum, Schema, UserDefinedFieldType,
+        anyhow, AggregationInterval, Entity, Field, FieldType, GqlScalar, GraphQLEnum, Schema,
+        UserDefinedFieldType,
     };
-    use crate::config_parsing::postgres_types::Primitive as PGPrimitive;
+    use crate::config_parsing::postgres_types::{IndexType, Primitive as PGPrimitive};
     use graphql_parser::schema::{parse_schema, Definition, Document, ObjectType, TypeDefinition};
 
     fn setup_document(schema: &str) -> anyhow::Result<Document<String>> {
@@ -1721,7 +3029,7 @@ type TestEntity {
     fn gql_type_to_rescript_type_entity() {
         let test_entity_string = String::from("TestEntity");
         let test_entity = Entity::new(&test_entity_string, vec![], vec![]).unwrap();
-        let schema = Schema::new(vec![test_entity], vec![]).unwrap();
+        let schema = Schema::new(vec![test_entity], vec![], vec![]).unwrap();
         let rescript_type = UserDefinedFieldType::Single(GqlScalar::Custom(test_entity_string))
             .to_rescript_type(&schema)
             .expect("expected rescript type string");
@@ -1733,7 +3041,7 @@ type TestEntity {
     fn gql_type_to_rescript_type_enum() {
         let name = String::from("TestEnum");
         let test_enum = GraphQLEnum::new(name.clone(), vec![]).unwrap();
-        let schema = Schema::new(vec![], vec![test_enum]).unwrap();
+        let schema = Schema::new(vec![], vec![test_enum], vec![]).unwrap();
         let rescript_type = UserDefinedFieldType::Single(GqlScalar::Custom(name))
             .to_rescript_type(&schema)
             .expect("expected rescript type string");
@@ -1813,7 +3121,7 @@ type TestEntity {
         let test_enum = GraphQLEnum::new(name.clone(), vec!["TEST_VALUE".to_string()]).unwrap();
         let field_type =
             get_field_type_helper_with_additional("TestEnum!", vec![test_enum.clone()]);
-        let schema = Schema::new(vec![], vec![test_enum]).unwrap();
+        let schema = Schema::new(vec![], vec![test_enum], vec![]).unwrap();
         let pg_primitive = field_type
             .to_user_defined_field_type()
             .to_underlying_postgres_primitive(&schema)
@@ -1928,6 +3236,150 @@ type TestEntity {
         assert_eq!(pg_field.linked_entity, None);
     }
 
+    #[test]
+    fn test_get_postgres_field_json_with_gin_index() {
+        let schema_str = r#"
+type TestEntity {
+  id: ID!
+  metadata: Json! @index(type: "gin")
+  calldata: Json
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).unwrap();
+        let entity = schema.entities.get("TestEntity").unwrap();
+        let get_pg_field = |field_name: &str| {
+            entity
+                .fields
+                .get(field_name)
+                .unwrap()
+                .get_postgres_field(&schema, entity)
+                .expect("Failed to get postgres field")
+                .unwrap()
+        };
+
+        let metadata = get_pg_field("metadata");
+        assert_eq!(metadata.field_type, PGPrimitive::Json);
+        assert!(metadata.is_index);
+        assert_eq!(metadata.index_type, Some(IndexType::Gin));
+        assert!(!metadata.is_nullable);
+
+        let calldata = get_pg_field("calldata");
+        assert_eq!(calldata.field_type, PGPrimitive::Json);
+        assert!(!calldata.is_index);
+        assert_eq!(calldata.index_type, None);
+        assert!(calldata.is_nullable);
+        let calldata_type = entity
+            .fields
+            .get("calldata")
+            .unwrap()
+            .field_type
+            .to_rescript_type(&schema)
+            .unwrap();
+        assert_eq!(format!("{calldata_type}"), "option<Js.Json.t>");
+    }
+
+    #[test]
+    fn test_get_postgres_field_int8_and_bytea() {
+        let schema_str = r#"
+type TestEntity {
+  id: ID!
+  timestampMs: Int8!
+  hash: Bytes! @config(storage: "bytea")
+  data: Bytes @config(storage: "text")
+  calldata: Bytes
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).unwrap();
+        let entity = schema.entities.get("TestEntity").unwrap();
+        let get_types = |field_name: &str| {
+            let field = entity.fields.get(field_name).unwrap();
+            let pg_field = field
+                .get_postgres_field(&schema, entity)
+                .expect("Failed to get postgres field")
+                .unwrap();
+            let res_type = field.field_type.to_rescript_type(&schema).unwrap();
+            (pg_field.field_type, format!("{res_type}"))
+        };
+
+        assert_eq!(
+            get_types("timestampMs"),
+            (PGPrimitive::Int8, "bigint".to_string())
+        );
+        assert_eq!(
+            get_types("hash"),
+            (PGPrimitive::Bytea, "string".to_string())
+        );
+        assert_eq!(
+            get_types("data"),
+            (PGPrimitive::Text, "option<string>".to_string())
+        );
+        assert_eq!(
+            get_types("calldata"),
+            (PGPrimitive::Text, "option<string>".to_string())
+        );
+    }
+
+    #[test]
+    fn validates_bytes_storage() {
+        let get_err = |schema_str: &str| {
+            let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+            format!("{:#}", Schema::from_document(gql_doc).unwrap_err())
+        };
+
+        assert!(get_err(
+            "type TestEntity {\n  id: ID!\n  hash: Bytes! @config(storage: \"blob\")\n}"
+        )
+        .contains(
+            "EE216: Unsupported storage 'blob' of the Bytes field 'hash'. It should be either \
+             \"text\" or \"bytea\""
+        ));
+        assert!(
+            get_err("type TestEntity {\n  id: ID!\n  hash: Bytes! @config(precision: 32)\n}")
+                .contains("EE216: The config directive on a Bytes should only take a single")
+        );
+        assert!(get_err(
+            "type TestEntity {\n  id: ID!\n  hashes: [Bytes!]! @config(storage: \"bytea\")\n}"
+        )
+        .contains("EE216: Arrays of Bytes can't be stored as bytea"));
+        assert!(get_err(
+            "type TestEntity {\n  id: ID!\n  amount: Int8! @config(storage: \"bytea\")\n}"
+        )
+        .contains("EE215"));
+    }
+
+    #[test]
+    fn validates_json_fields() {
+        let get_err = |schema_str: &str| {
+            let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+            format!("{:#}", Schema::from_document(gql_doc).unwrap_err())
+        };
+
+        assert!(
+            get_err("type TestEntity {\n  id: ID!\n  name: String! @index(type: \"gin\")\n}")
+                .contains(
+                    "EE202: A gin index is only supported on Json fields. Field 'name' is of \
+                     type String"
+                )
+        );
+        assert!(get_err(
+            "type TestEntity {\n  id: ID!\n  metadata: Json! @index(type: \"hash\")\n}"
+        )
+        .contains(
+            "EE202: Unsupported index type 'hash' on field metadata. The only supported \
+                     type is \"gin\""
+        ));
+        assert!(get_err(
+            "type TestEntity {\n  id: ID!\n  metadata: Json! @index(fields: [\"a\"])\n}"
+        )
+        .contains("EE202: The @index directive on a field only takes a 'type' argument"));
+        assert!(
+            get_err("type TestEntity {\n  id: ID!\n  items: [Json!]!\n}")
+                .contains("Arrays of Json are not supported")
+        );
+    }
+
     #[test]
     fn test_get_postgres_field_with_linked_entity() {
         let schema_str = r#"
@@ -2354,4 +3806,432 @@ type TestEntity {
             vec!["b".to_string(), "a".to_string()]
         );
     }
+
+    #[test]
+    fn collects_errors_from_every_type_definition() {
+        let schema_str = r#"
+type MissingId {
+  name: String!
+}
+
+type AlsoMissingId {
+  name: String!
+}
+"#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+        let errors = Schema::collect_errors_from_document(&gql_doc);
+
+        let positions = errors
+            .iter()
+            .map(|(_, pos)| pos.map(|p| p.line))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![Some(2), Some(6)]);
+    }
+
+    #[test]
+    fn collects_errors_from_schema_wide_checks() {
+        let schema_str = r#"
+enum EVENT_TYPE {
+  A
+}
+
+type Entity {
+  id: ID!
+  related: NonExistent!
+}
+"#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+        let errors = Schema::collect_errors_from_document(&gql_doc)
+            .into_iter()
+            .map(|(err, pos)| (format!("{:#}", err), pos.map(|p| p.line)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].0.contains("EE212"));
+        assert_eq!(errors[0].1, None);
+        assert!(errors[1].0.contains("NonExistent"));
+        assert_eq!(errors[1].1, Some(6));
+    }
+
+    #[test]
+    fn parses_immutable_entities() {
+        let schema_str = r#"
+type Transfer @entity(immutable: true) {
+  id: ID!
+  value: BigInt!
+}
+
+type Account @entity {
+  id: ID!
+}
+
+type Token @entity(immutable: false) {
+  id: ID!
+}
+
+type Pool {
+  id: ID!
+}
+"#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+        let immutable_entities = schema
+            .entities
+            .values()
+            .filter(|entity| entity.is_immutable)
+            .map(|entity| entity.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(immutable_entities, vec!["Transfer"]);
+
+        let get_err = |schema_str: &str| {
+            let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+            format!("{:#}", Schema::from_document(gql_doc).unwrap_err())
+        };
+        assert!(get_err(
+            "type Transfer @entity(immutable: \"yes\") {\n  id: ID!\n}"
+        )
+        .contains(
+            "EE219: The immutable argument of the @entity directive on entity Transfer should be \
+             a boolean"
+        ));
+        assert!(
+            get_err("type Transfer @entity(mutable: true) {\n  id: ID!\n}").contains(
+                "EE219: Unknown argument 'mutable' of the @entity directive on entity Transfer"
+            )
+        );
+        assert!(
+            get_err("type Transfer @entity @entity(immutable: true) {\n  id: ID!\n}")
+                .contains("EE219: Cannot use more than one @entity directive")
+        );
+    }
+
+    #[test]
+    fn interface_fields_resolve_to_a_tagged_relational_key() {
+        let schema_str = r#"
+interface Position {
+  id: ID!
+  owner: Account!
+}
+
+type Account {
+  id: ID!
+  position: Position
+}
+
+type PerpPosition implements Position {
+  id: ID!
+  owner: Account!
+  leverage: Int!
+}
+
+type LpPosition implements Position {
+  id: ID!
+  owner: Account!
+}
+"#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+        let interface = schema
+            .interfaces
+            .get("Position")
+            .expect("Interface not found");
+        let implementer_names = interface
+            .get_implementers(&schema)
+            .iter()
+            .map(|entity| entity.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(implementer_names, vec!["LpPosition", "PerpPosition"]);
+
+        let account = schema.entities.get("Account").unwrap();
+        let field = account.fields.get("position").unwrap();
+        assert!(field.field_type.is_entity_field(&schema).unwrap());
+        let pg_field = field
+            .get_postgres_field(&schema, account)
+            .expect("Failed to get postgres field")
+            .unwrap();
+        assert_eq!(pg_field.field_type, PGPrimitive::Text);
+        assert_eq!(pg_field.linked_entity, Some("Position".to_string()));
+
+        let type_tag_field = field
+            .get_type_tag_postgres_field(&schema)
+            .expect("Failed to get type tag postgres field")
+            .unwrap();
+        assert_eq!(type_tag_field.field_name, "position_type");
+        assert_eq!(type_tag_field.field_type, PGPrimitive::Text);
+        assert!(type_tag_field.is_nullable);
+        assert_eq!(type_tag_field.linked_entity, None);
+
+        let owner = interface.fields.get("owner").unwrap();
+        assert_eq!(owner.get_type_tag_postgres_field(&schema).unwrap(), None);
+    }
+
+    #[test]
+    fn validates_interface_implementers() {
+        let get_err = |schema_str: &str| {
+            let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+            format!("{:#}", Schema::from_document(gql_doc).unwrap_err())
+        };
+
+        let missing_field = get_err(
+            r#"
+interface Position {
+  id: ID!
+  size: BigInt!
+}
+
+type LpPosition implements Position {
+  id: ID!
+}
+"#,
+        );
+        assert!(missing_field.contains(
+            "EE218: Entity LpPosition implements interface Position but doesn't have its field \
+             'size'"
+        ));
+
+        let different_type = get_err(
+            r#"
+interface Position {
+  id: ID!
+  size: BigInt!
+}
+
+type LpPosition implements Position {
+  id: ID!
+  size: BigInt
+}
+"#,
+        );
+        assert!(different_type.contains(
+            "EE218: The field 'size' on entity LpPosition should have the type BigInt! of \
+             interface Position, but has the type BigInt"
+        ));
+
+        let not_an_interface = get_err(
+            r#"
+type Account {
+  id: ID!
+}
+
+type LpPosition implements Account {
+  id: ID!
+}
+"#,
+        );
+        assert!(not_an_interface.contains(
+            "EE218: Entity LpPosition implements 'Account', which isn't an interface defined in \
+             the schema"
+        ));
+
+        let not_implemented = get_err(
+            r#"
+interface Position {
+  id: ID!
+}
+"#,
+        );
+        assert!(not_implemented.contains("EE218: Interface Position isn't implemented"));
+
+        let array_of_interfaces = get_err(
+            r#"
+interface Position {
+  id: ID!
+}
+
+type LpPosition implements Position {
+  id: ID!
+}
+
+type Account {
+  id: ID!
+  positions: [Position!]!
+}
+"#,
+        );
+        assert!(array_of_interfaces.contains("EE211"));
+
+        let interface_with_array_of_interfaces = get_err(
+            r#"
+interface Position {
+  id: ID!
+  children: [Position!]!
+}
+
+type LpPosition implements Position {
+  id: ID!
+}
+"#,
+        );
+        assert!(interface_with_array_of_interfaces.contains("EE211"));
+
+        let interface_with_type_field = get_err(
+            r#"
+interface Position {
+  id: ID!
+  type: String!
+}
+
+type LpPosition implements Position {
+  id: ID!
+}
+"#,
+        );
+        assert!(interface_with_type_field
+            .contains("EE218: The field 'type' on interface Position is reserved"));
+    }
+
+    const AGGREGATION_SCHEMA: &str = r#"
+type Token {
+  id: ID!
+}
+
+type Swap @entity(timeseries: true) {
+  id: ID!
+  timestamp: Timestamp!
+  token: Token!
+  amountUSD: BigDecimal!
+  price: Float!
+}
+
+type Stats @aggregation(intervals: ["hour", "day"], source: "Swap") {
+  id: ID!
+  timestamp: Timestamp!
+  token: Token!
+  volumeUSD: BigDecimal! @aggregate(fn: "sum", arg: "amountUSD")
+  swapCount: Int! @aggregate(fn: "count")
+  closePrice: Float! @aggregate(fn: "last", arg: "price")
+}
+"#;
+
+    #[test]
+    fn parses_aggregations_into_interval_entities() {
+        let gql_doc = setup_document(AGGREGATION_SCHEMA).expect("Failed to parse schema string");
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+
+        let swap = schema.entities.get("Swap").unwrap();
+        assert!(swap.is_timeseries && swap.is_immutable);
+        assert!(swap.fields.get("timestamp").unwrap().is_indexed_field(swap));
+
+        let stats = schema.aggregations.get("Stats").unwrap();
+        assert_eq!(
+            stats.intervals,
+            vec![AggregationInterval::Hour, AggregationInterval::Day]
+        );
+        assert!(!schema.entities.contains_key("Stats"));
+
+        for entity_name in ["Stats_hour", "Stats_day"] {
+            let entity = schema.entities.get(entity_name).unwrap();
+            assert_eq!(entity.aggregation, Some("Stats".to_string()));
+            assert!(entity.is_immutable);
+            assert_eq!(
+                entity
+                    .get_fields()
+                    .iter()
+                    .map(|field| field.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec![
+                    "closePrice",
+                    "id",
+                    "swapCount",
+                    "timestamp",
+                    "token",
+                    "volumeUSD"
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn builds_aggregation_rollup_query() {
+        let gql_doc = setup_document(AGGREGATION_SCHEMA).expect("Failed to parse schema string");
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+        let stats = schema.aggregations.get("Stats").unwrap();
+
+        let query = stats
+            .get_rollup_query(&AggregationInterval::Day, &schema, "public")
+            .unwrap();
+        let expected = r#"WITH "bucket_starts" AS (
+SELECT DISTINCT date_trunc('day', "data_point_timestamp" AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS "start"
+FROM unnest($1::TIMESTAMPTZ[]) AS "data_point_timestamp"
+), "buckets" AS (
+SELECT concat_ws('-', extract(epoch FROM "timestamp")::BIGINT::TEXT, coalesce("token_id"::TEXT, '')) AS "id", "timestamp", "token_id", "closePrice", "swapCount", "volumeUSD"
+FROM (
+SELECT date_trunc('day', "timestamp" AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS "timestamp", "token_id", (array_agg("price" ORDER BY "timestamp" DESC, "id" DESC))[1]::DOUBLE PRECISION AS "closePrice", count(*)::INTEGER AS "swapCount", sum("amountUSD")::NUMERIC AS "volumeUSD"
+FROM "public"."Swap"
+JOIN "bucket_starts" ON "timestamp" >= "bucket_starts"."start"
+AND "timestamp" < ("bucket_starts"."start" AT TIME ZONE 'UTC' + INTERVAL '1 day') AT TIME ZONE 'UTC'
+GROUP BY 1, "token_id"
+) AS "recomputed"
+), "upserted" AS (
+INSERT INTO "public"."Stats_day" ("id", "timestamp", "token_id", "closePrice", "swapCount", "volumeUSD")
+SELECT "id", "timestamp", "token_id", "closePrice", "swapCount", "volumeUSD" FROM "buckets"
+ON CONFLICT ("id") DO UPDATE SET "closePrice" = EXCLUDED."closePrice", "swapCount" = EXCLUDED."swapCount", "volumeUSD" = EXCLUDED."volumeUSD"
+)
+DELETE FROM "public"."Stats_day"
+WHERE "timestamp" IN (SELECT "start" FROM "bucket_starts")
+AND "id" NOT IN (SELECT "id" FROM "buckets");"#;
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn validates_aggregations() {
+        let get_err = |schema_str: &str| {
+            let gql_doc = setup_document(schema_str).expect("Failed to parse schema string");
+            format!("{:#}", Schema::from_document(gql_doc).unwrap_err())
+        };
+        let with_aggregation = |aggregation_str: &str| {
+            format!(
+                "type Swap @entity(timeseries: true) {{\n  id: ID!\n  timestamp: Timestamp!\n  \
+                 amount: BigInt!\n  memo: String\n}}\n{aggregation_str}"
+            )
+        };
+
+        assert!(
+            get_err("type Swap @entity(timeseries: true) {\n  id: ID!\n}").contains(
+                "EE225: Time-series entity Swap should have a 'timestamp: Timestamp!' field"
+            )
+        );
+        assert!(get_err(&with_aggregation(
+            "type Stats @aggregation(intervals: [\"week\"], source: \"Swap\") {\n  id: ID!\n  \
+             timestamp: Timestamp!\n  total: BigInt! @aggregate(fn: \"sum\", arg: \"amount\")\n}"
+        ))
+        .contains("EE220: Invalid interval \"week\" of aggregation Stats"));
+        assert!(get_err(&with_aggregation(
+            "type Stats @aggregation(intervals: [\"hour\"], source: \"Swap\") {\n  id: ID!\n  \
+             timestamp: Timestamp!\n}"
+        ))
+        .contains(
+            "EE221: Aggregation Stats should have at least one field with the @aggregate directive"
+        ));
+        assert!(get_err(&with_aggregation(
+            "type Stats @aggregation(intervals: [\"hour\"], source: \"Token\") {\n  id: ID!\n  \
+             timestamp: Timestamp!\n  total: BigInt! @aggregate(fn: \"sum\", arg: \"amount\")\n}"
+        ))
+        .contains("EE222: The source Token of aggregation Stats should be an entity"));
+        assert!(get_err(&with_aggregation(
+            "type Stats @aggregation(intervals: [\"hour\"], source: \"Swap\") {\n  id: ID!\n  \
+             timestamp: Timestamp!\n  total: Float! @aggregate(fn: \"sum\", arg: \"amount\")\n}"
+        ))
+        .contains(
+            "EE224: The aggregate field 'total' should have the numeric type of its argument"
+        ));
+        assert!(get_err(&with_aggregation(
+            "type Stats @aggregation(intervals: [\"hour\"], source: \"Swap\") {\n  id: ID!\n  \
+             timestamp: Timestamp!\n  memo: String\n  total: BigInt! @aggregate(fn: \"avg\", \
+             arg: \"amount\")\n}"
+        ))
+        .contains("EE223: Unsupported aggregate function \"avg\" on field total"));
+        assert!(get_err(&with_aggregation(
+            "type Stats @aggregation(intervals: [\"hour\"], source: \"Swap\") {\n  id: ID!\n  \
+             timestamp: Timestamp!\n  pool: String!\n  total: BigInt! @aggregate(fn: \"sum\", \
+             arg: \"amount\")\n}"
+        ))
+        .contains(
+            "EE222: The field 'pool' on aggregation Stats groups the buckets by a field of Swap"
+        ));
+    }
 }
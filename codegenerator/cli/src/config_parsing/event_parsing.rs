@@ -1,6 +1,13 @@
-use ethers::abi::{EventParam as EthAbiEventParam, ParamType as EthAbiParamType};
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::{EventParam as EthAbiEventParam, Param as EthAbiParam, ParamType as EthAbiParamType},
+    types::{Address, I256, U256},
+    utils::{hex, keccak256},
+};
 
-use crate::rescript_types::RescriptTypeIdent;
+use crate::{
+    config_parsing::human_config::evm::TopicFilterValue, rescript_types::RescriptTypeIdent,
+};
 
 pub struct EthereumEventParam<'a> {
     pub name: &'a str,
@@ -16,6 +23,15 @@ impl<'a> From<&'a EthAbiEventParam> for EthereumEventParam<'a> {
     }
 }
 
+impl<'a> From<&'a EthAbiParam> for EthereumEventParam<'a> {
+    fn from(abi_type: &'a EthAbiParam) -> EthereumEventParam<'a> {
+        EthereumEventParam {
+            name: &abi_type.name,
+            abi_type: &abi_type.kind,
+        }
+    }
+}
+
 impl EthereumEventParam<'_> {
     /// Returns the depth of the nested type
     /// A value type would return 0
@@ -98,6 +114,106 @@ impl EthereumEventParam<'_> {
             (encoder, IsValueEncoder(true)) => encoder,
         }
     }
+
+    /// Encodes a filter value from the config to the topic it's matched against.
+    /// Follows the same rules as the encoders from get_topic_encoder
+    /// but runs during codegen, so invalid values are reported early
+    pub fn encode_topic_filter(&self, value: &TopicFilterValue) -> Result<String> {
+        let invalid_value = || anyhow!("Invalid value {} for the {} param", value, self.abi_type);
+
+        let topic: [u8; 32] = match (self.abi_type, value) {
+            (EthAbiParamType::Address, TopicFilterValue::String(address)) => {
+                let address = address.parse::<Address>().map_err(|_| invalid_value())?;
+                left_pad(address.as_bytes())
+            }
+            (EthAbiParamType::Uint(size), value) => {
+                let number = match value {
+                    TopicFilterValue::UInt(number) => U256::from(*number),
+                    TopicFilterValue::Int(number) if *number >= 0 => U256::from(*number as u64),
+                    TopicFilterValue::String(number) => {
+                        parse_u256(number).map_err(|_| invalid_value())?
+                    }
+                    _ => return Err(invalid_value()),
+                };
+                if number.bits() > *size {
+                    return Err(anyhow!("{} as it overflows", invalid_value()));
+                }
+                let mut topic = [0u8; 32];
+                number.to_big_endian(&mut topic);
+                topic
+            }
+            (EthAbiParamType::Int(size), value) => {
+                let number = match value {
+                    TopicFilterValue::Int(number) => I256::from(*number),
+                    TopicFilterValue::UInt(number) => I256::from_raw(U256::from(*number)),
+                    TopicFilterValue::String(number) => {
+                        parse_i256(number).map_err(|_| invalid_value())?
+                    }
+                    _ => return Err(invalid_value()),
+                };
+                if *size < 256 {
+                    let max = I256::from_raw(U256::one() << (size - 1));
+                    if number >= max || number < -max {
+                        return Err(anyhow!("{} as it overflows", invalid_value()));
+                    }
+                }
+                let mut topic = [0u8; 32];
+                number.into_raw().to_big_endian(&mut topic);
+                topic
+            }
+            (EthAbiParamType::Bool, TopicFilterValue::Bool(value)) => left_pad(&[*value as u8]),
+            (EthAbiParamType::FixedBytes(size), TopicFilterValue::String(bytes)) => {
+                let bytes = hex::decode(bytes).map_err(|_| invalid_value())?;
+                if bytes.len() != *size {
+                    return Err(anyhow!(
+                        "{} as it should have {} bytes",
+                        invalid_value(),
+                        size
+                    ));
+                }
+                let mut topic = [0u8; 32];
+                topic[..bytes.len()].copy_from_slice(&bytes);
+                topic
+            }
+            //In the case of a string or bytes param the topic is a keccak256 hash of the value
+            (EthAbiParamType::Bytes, TopicFilterValue::String(bytes)) => {
+                keccak256(hex::decode(bytes).map_err(|_| invalid_value())?)
+            }
+            (EthAbiParamType::String, TopicFilterValue::String(value)) => keccak256(value),
+            (EthAbiParamType::Tuple(_), _)
+            | (EthAbiParamType::Array(_), _)
+            | (EthAbiParamType::FixedArray(_, _), _) => {
+                return Err(anyhow!(
+                    "Filtering by the {} param is not supported in the config. Use the \
+                     eventFilters option of the handler instead",
+                    self.abi_type
+                ))
+            }
+            _ => return Err(invalid_value()),
+        };
+
+        Ok(hex::encode_prefixed(topic))
+    }
+}
+
+fn left_pad(bytes: &[u8]) -> [u8; 32] {
+    let mut topic = [0u8; 32];
+    topic[32 - bytes.len()..].copy_from_slice(bytes);
+    topic
+}
+
+fn parse_u256(number: &str) -> Result<U256> {
+    match number.strip_prefix("0x") {
+        Some(hex_number) => Ok(U256::from_str_radix(hex_number, 16)?),
+        None => Ok(U256::from_dec_str(number)?),
+    }
+}
+
+fn parse_i256(number: &str) -> Result<I256> {
+    match number.strip_prefix("0x") {
+        Some(hex_number) => Ok(I256::from_hex_str(hex_number)?),
+        None => Ok(I256::from_dec_str(number)?),
+    }
 }
 
 pub fn abi_to_rescript_type(param: &EthereumEventParam) -> RescriptTypeIdent {
@@ -151,6 +267,7 @@ mod tests {
     use ethers::abi::{HumanReadableParser, ParamType};
 
     use super::{abi_to_rescript_type, EthereumEventParam};
+    use crate::config_parsing::human_config::evm::TopicFilterValue;
 
     #[test]
     fn test_record_type_array() {
@@ -242,4 +359,74 @@ mod tests {
             "[]".to_string()
         );
     }
+
+    #[test]
+    fn test_encode_topic_filter() {
+        let event = HumanReadableParser::parse_event(
+            "event MyEvent(address indexed to, uint8 indexed id, int256 indexed delta, bool \
+             indexed flag, bytes4 indexed selector, string indexed label, uint256[] indexed ids)",
+        )
+        .expect("parsing event");
+
+        let params: Vec<EthereumEventParam> = event.inputs.iter().map(|p| p.into()).collect();
+        let encode = |i: usize, value: TopicFilterValue| params[i].encode_topic_filter(&value);
+        let string = |value: &str| TopicFilterValue::String(value.to_string());
+
+        assert_eq!(
+            encode(0, string("0x2E645469f354BB4F5c8a05B3b30A929361cf77eC")).unwrap(),
+            "0x0000000000000000000000002e645469f354bb4f5c8a05b3b30a929361cf77ec"
+        );
+        assert_eq!(
+            encode(1, TopicFilterValue::UInt(255)).unwrap(),
+            "0x00000000000000000000000000000000000000000000000000000000000000ff"
+        );
+        assert_eq!(
+            encode(1, string("0x10")).unwrap(),
+            "0x0000000000000000000000000000000000000000000000000000000000000010"
+        );
+        assert_eq!(
+            encode(2, TopicFilterValue::Int(-1)).unwrap(),
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+        assert_eq!(
+            encode(3, TopicFilterValue::Bool(true)).unwrap(),
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(
+            encode(4, string("0xa9059cbb")).unwrap(),
+            "0xa9059cbb00000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            encode(5, string("hello")).unwrap(),
+            "0x1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"
+        );
+
+        assert_eq!(
+            encode(0, string("0x123")).unwrap_err().to_string(),
+            "Invalid value \"0x123\" for the address param"
+        );
+        assert_eq!(
+            encode(1, TopicFilterValue::UInt(256))
+                .unwrap_err()
+                .to_string(),
+            "Invalid value 256 for the uint8 param as it overflows"
+        );
+        assert_eq!(
+            encode(1, TopicFilterValue::Bool(true))
+                .unwrap_err()
+                .to_string(),
+            "Invalid value true for the uint8 param"
+        );
+        assert_eq!(
+            encode(4, string("0xa9059c")).unwrap_err().to_string(),
+            "Invalid value \"0xa9059c\" for the bytes4 param as it should have 4 bytes"
+        );
+        assert_eq!(
+            encode(6, TopicFilterValue::UInt(1))
+                .unwrap_err()
+                .to_string(),
+            "Filtering by the uint256[] param is not supported in the config. Use the \
+             eventFilters option of the handler instead"
+        );
+    }
 }
@@ -3,7 +3,9 @@ use crate::{
     config_parsing::{
         chain_helpers::{self, GraphNetwork},
         human_config::{
-            evm::{ContractConfig, EventConfig, HumanConfig, Network},
+            evm::{
+                BlockHandlerConfig, CallConfig, ContractConfig, EventConfig, HumanConfig, Network,
+            },
             NetworkContract,
         },
     },
@@ -13,7 +15,11 @@ use anyhow::{anyhow, Context};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_yaml;
-use std::{collections::HashMap, fs, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
 use tokio::{
     task::JoinSet,
     time::{timeout, Duration},
@@ -109,6 +115,52 @@ pub struct CallHandler {
 #[derive(Debug, Deserialize, Clone)]
 pub struct BlockHandler {
     pub handler: String,
+    pub filter: Option<BlockHandlerFilter>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BlockHandlerFilter {
+    pub kind: String,
+    pub every: Option<u64>,
+}
+
+impl CallHandler {
+    // Like for events, only the function name is kept, so the call is looked up
+    // in the downloaded abi with named inputs and outputs
+    fn to_call_config(&self) -> anyhow::Result<CallConfig> {
+        let start = self
+            .function
+            .find('(')
+            .ok_or_else(|| anyhow!("Unexepected function definition without a '(' char"))?;
+
+        Ok(CallConfig {
+            call: self.function[..start].to_string(),
+            name: None,
+        })
+    }
+}
+
+impl BlockHandler {
+    // Polling and once filters map to an interval and block range. Call filters
+    // run on blocks with calls to the contract which can't be expressed as an interval
+    fn to_block_handler_config(&self, start_block: &str) -> Option<BlockHandlerConfig> {
+        let (interval, once_block) = match &self.filter {
+            None => (None, None),
+            Some(BlockHandlerFilter { kind, every }) if kind == "polling" => (*every, None),
+            Some(BlockHandlerFilter { kind, .. }) if kind == "once" => {
+                (None, start_block.parse::<u64>().ok())
+            }
+            Some(_) => return None,
+        };
+
+        Some(BlockHandlerConfig {
+            name: self.handler.clone(),
+            interval,
+            start_block: once_block,
+            end_block: once_block,
+            handler: None,
+        })
+    }
 }
 
 // Logic to get the event handler directory based on the language
@@ -242,7 +294,7 @@ fn valid_ipfs_cid(cid: &str) -> bool {
 
 // Function to generate config, schema and abis from subgraph ID
 pub async fn generate_config_from_subgraph_id(
-    project_root_path: &PathBuf,
+    project_root_path: &Path,
     subgraph_id: &str,
     language: &Language,
 ) -> anyhow::Result<HumanConfig> {
@@ -275,6 +327,8 @@ pub async fn generate_config_from_subgraph_id(
         description: manifest.description,
         ecosystem: None,
         schema: None,
+        include: None,
+        profiles: None,
         contracts: None,
         networks: vec![],
         unordered_multichain_mode: None,
@@ -307,12 +361,17 @@ pub async fn generate_config_from_subgraph_id(
         let mut network = Network {
             id: chain_helpers::Network::from(*graph_network).get_network_id(),
             hypersync_config: None,
+            file_config: None,
+            source_stall_timeout: None,
             // TODO: update to the final rpc url
             rpc_config: None,
             start_block: 0,
             end_block: None,
             confirmed_block_threshold: None,
             contracts: vec![],
+            block_handlers: None,
+            field_selection: None,
+            raw_events: None,
         };
         // Iterate through contracts to get contract name, abi file path, address and event names
         for contract in contracts {
@@ -348,19 +407,56 @@ pub async fn generate_config_from_subgraph_id(
                                 event: event_name.to_string(),
                                 name: None,
                                 field_selection: None,
+                                filters: None,
                             };
 
                             Ok(event)
                         })
                         .collect::<anyhow::Result<Vec<_>>>()?;
 
+                    let calls = data_source
+                        .mapping
+                        .call_handlers
+                        .iter()
+                        .flatten()
+                        .map(|call_handler| call_handler.to_call_config())
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+
+                    let block_handlers = data_source
+                        .mapping
+                        .block_handlers
+                        .as_ref()
+                        .map(|block_handlers| {
+                            block_handlers
+                                .iter()
+                                .filter_map(|block_handler| {
+                                    let block_handler_config = block_handler
+                                        .to_block_handler_config(&data_source.source.start_block);
+                                    if block_handler_config.is_none() {
+                                        println!(
+                                            "Block handler {} uses an unsupported filter and was \
+                                             skipped",
+                                            block_handler.handler
+                                        );
+                                    }
+                                    block_handler_config
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .filter(|block_handlers| !block_handlers.is_empty());
+
                     let contract = NetworkContract {
                         name: data_source.name.to_string(),
                         address: vec![data_source.source.address.to_string()].into(),
+                        start_block: None,
+                        end_block: None,
                         config: Some(ContractConfig {
                             abi_file_path: Some(format!("abis/{}.json", data_source.name)),
                             handler: get_event_handler_directory(language),
                             events,
+                            calls: (!calls.is_empty()).then_some(calls),
+                            block_handlers,
+                            factory: None,
                         }),
                     };
 
@@ -444,10 +540,11 @@ mod test {
         cli_args::init_config::Language,
         config_parsing::{
             chain_helpers::{GraphNetwork, Network},
+            entity_parsing::Schema as EntitySchema,
             graph_migration::get_ipfs_id_from_file_path,
         },
     };
-    use std::{collections::HashMap, path::PathBuf};
+    use std::collections::HashMap;
     use tempdir::TempDir;
 
     // Integration test to see that a config file can be generated from a subgraph ID
@@ -458,8 +555,7 @@ mod test {
         // subgraph ID of USDC on Ethereum mainnet
         let cid: &str = "QmU5V3jy56KnFbxX2uZagvMwocYZASzy1inX828W2XWtTd";
         let language: Language = Language::ReScript;
-        let project_root = PathBuf::from(temp_dir.path());
-        super::generate_config_from_subgraph_id(&project_root, cid, &language)
+        super::generate_config_from_subgraph_id(temp_dir.path(), cid, &language)
             .await
             .unwrap();
     }
@@ -513,6 +609,22 @@ mod test {
         );
     }
 
+    // Unit test to see that immutable subgraph entities are kept after the migration
+    #[test]
+    fn test_migrated_schema_keeps_immutable_entities() {
+        let schema_str = "type Swap @entity(immutable: true) {
+                id: ID!
+                amountUSD: BigDecimal!
+            }"
+        .to_string();
+
+        let migrated_schema_str = super::update_schema_with_supported_field_types(schema_str);
+        let document =
+            graphql_parser::schema::parse_schema::<String>(&migrated_schema_str).unwrap();
+        let schema = EntitySchema::from_document(document).unwrap();
+        assert!(schema.entities.get("Swap").unwrap().is_immutable);
+    }
+
     // Unit test to see if the network name is deserialized correctly
     #[test]
     fn test_network_deserialization() {
@@ -26,7 +26,73 @@ impl<T: Clone + JsonSchema> JsonSchema for SingleOrList<T> {
     }
 }
 
-pub type Addresses = NormalizedList<String>;
+/// Addresses of a network contract, either listed in the config
+/// or loaded from a file when there are too many of them
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Addresses {
+    File(AddressesFile),
+    Json(AddressesJson),
+    List(NormalizedList<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AddressesFile {
+    #[schemars(
+        description = "Path to a file with the addresses. Either a CSV file with an address per \
+                       row (the \"address\" column is used when there is a header), a JSON array \
+                       of addresses or a Foundry broadcast file"
+    )]
+    pub file: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AddressesJson {
+    #[schemars(description = "Path to a JSON file with the addresses, eg. a deployments file")]
+    pub json: String,
+    #[schemars(
+        description = "JSONPath to the address or the list of addresses in the file, eg. \
+                       $.Vault.address or $.pools[*].address"
+    )]
+    pub path: String,
+}
+
+impl Addresses {
+    /// The path to the file the addresses are loaded from
+    pub fn get_file_path(&self) -> Option<&String> {
+        match self {
+            Addresses::File(AddressesFile { file }) => Some(file),
+            Addresses::Json(AddressesJson { json, .. }) => Some(json),
+            Addresses::List(_) => None,
+        }
+    }
+}
+
+impl Default for Addresses {
+    fn default() -> Self {
+        Addresses::List(vec![].into())
+    }
+}
+
+impl From<NormalizedList<String>> for Addresses {
+    fn from(addresses: NormalizedList<String>) -> Self {
+        Addresses::List(addresses)
+    }
+}
+
+impl From<Vec<String>> for Addresses {
+    fn from(addresses: Vec<String>) -> Self {
+        Addresses::List(addresses.into())
+    }
+}
+
+impl From<Option<SingleOrList<String>>> for Addresses {
+    fn from(addresses: Option<SingleOrList<String>>) -> Self {
+        Addresses::List(addresses.into())
+    }
+}
 
 impl JsonSchema for Addresses {
     fn schema_name() -> Cow<'static, str> {
@@ -46,7 +112,9 @@ impl JsonSchema for Addresses {
             {
               "type": "array",
               "items": t_schema
-            }
+            },
+            gen.subschema_for::<AddressesFile>(),
+            gen.subschema_for::<AddressesJson>(),
           ]
         })
     }
@@ -56,6 +124,28 @@ impl JsonSchema for Addresses {
     }
 }
 
+/// Overrides of a config profile. It's deep merged onto the config when the profile is
+/// selected, so its keys are validated together with the rest of the config
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct ProfileOverlay(pub serde_yaml::Mapping);
+
+impl JsonSchema for ProfileOverlay {
+    fn schema_name() -> Cow<'static, str> {
+        "ProfileOverlay".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+          "type": "object"
+        })
+    }
+
+    fn always_inline_schema() -> bool {
+        true
+    }
+}
+
 type NetworkId = u64;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -79,9 +169,24 @@ pub struct NetworkContract<T> {
     #[schemars(
         description = "A single address or a list of addresses to be indexed. This can be left as \
                        null in the case where this contracts addresses will be registered \
-                       dynamically."
+                       dynamically. Addresses can also be loaded from a file with { file: \
+                       ./addresses.csv } or { json: ./deployments.json, path: $.Vault.address }"
     )]
+    #[serde(default)]
     pub address: Addresses,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "The block at which the indexer should start ingesting data for this \
+                       contract. Can't be lower than the start_block of the network (default: the \
+                       start_block of the network)"
+    )]
+    pub start_block: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "The block after which the indexer should stop ingesting data for this \
+                       contract (default: the end_block of the network)"
+    )]
+    pub end_block: Option<u64>,
     #[serde(flatten)]
     //If this is "None" it should be expected that
     //there is a global config for the contract
@@ -99,6 +204,60 @@ pub enum HumanConfig {
     Fuel(fuel::HumanConfig),
 }
 
+impl HumanConfig {
+    pub fn get_schema_path(&self) -> &Option<String> {
+        match self {
+            HumanConfig::Evm(config) => &config.schema,
+            HumanConfig::Fuel(config) => &config.schema,
+        }
+    }
+
+    pub fn get_include(&self) -> &[String] {
+        let include = match self {
+            HumanConfig::Evm(config) => &config.include,
+            HumanConfig::Fuel(config) => &config.include,
+        };
+        include.as_deref().unwrap_or_default()
+    }
+
+    /// Serializes the config with the endpoint header values passed through `redact`.
+    /// Headers usually carry auth secrets, which must not end up in hashes or logs in plain text
+    pub fn to_redacted_string(&self, mut redact: impl FnMut(&str) -> String) -> String {
+        let mut value = match self {
+            HumanConfig::Evm(config) => serde_yaml::to_value(config),
+            HumanConfig::Fuel(config) => serde_yaml::to_value(config),
+        }
+        .expect("Failed to serialize config");
+
+        //The selected profile is already merged onto the config,
+        //and the other profiles might contain headers as well
+        if let Some(config) = value.as_mapping_mut() {
+            config.remove("profiles");
+        }
+
+        if let Some(networks) = value
+            .get_mut("networks")
+            .and_then(serde_yaml::Value::as_sequence_mut)
+        {
+            for network in networks {
+                for source in ["rpc_config", "hypersync_config"] {
+                    let headers = network
+                        .get_mut(source)
+                        .and_then(|source| source.get_mut("headers"))
+                        .and_then(serde_yaml::Value::as_mapping_mut);
+                    for (_, header_value) in headers.into_iter().flat_map(|h| h.iter_mut()) {
+                        if let Some(redacted) = header_value.as_str().map(&mut redact) {
+                            *header_value = serde_yaml::Value::String(redacted);
+                        }
+                    }
+                }
+            }
+        }
+
+        serde_yaml::to_string(&value).expect("Failed to serialize config")
+    }
+}
+
 impl Display for HumanConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -113,11 +272,11 @@ impl Display for HumanConfig {
 }
 
 pub mod evm {
-    use super::{GlobalContract, NetworkContract, NetworkId};
+    use super::{GlobalContract, NetworkContract, NetworkId, ProfileOverlay};
     use crate::utils::normalized_list::SingleOrList;
-    use schemars::JsonSchema;
+    use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
     use serde::{Deserialize, Serialize};
-    use std::fmt::Display;
+    use std::{borrow::Cow, collections::BTreeMap, fmt::Display};
     use strum::Display;
     use subenum::subenum;
 
@@ -140,6 +299,23 @@ pub mod evm {
         #[schemars(description = "Custom path to schema.yaml file")]
         pub schema: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Paths or glob patterns of YAML files with more networks and global \
+                           contracts, eg. ./networks/*.yaml. Use it to split a large config into \
+                           multiple files. Paths in the included files are relative to this \
+                           config file"
+        )]
+        pub include: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Overrides of the config by profile name, eg. dev, staging or prod. \
+                           The profile is selected with the --profile flag or the ENVIO_PROFILE \
+                           env var and its overrides are deep merged onto the config. Networks \
+                           are merged by id and contracts by name. The overrides can also be \
+                           defined in a config.<profile>.yaml file next to the config"
+        )]
+        pub profiles: Option<BTreeMap<String, ProfileOverlay>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "Global contract definitions that must contain all definitions except \
                            addresses. You can share a single handler/abi/event definitions for \
@@ -255,7 +431,8 @@ pub mod evm {
         Status,
         YParity,
         ChainId,
-        // AccessList, //TODO this should produce an array of AccessList records
+        #[subenum(RpcTransactionField)]
+        AccessList,
         MaxFeePerBlobGas,
         BlobVersionedHashes,
         Kind,
@@ -264,6 +441,8 @@ pub mod evm {
         L1GasUsed,
         L1FeeScalar,
         GasUsedForL1,
+        #[subenum(RpcTransactionField)]
+        Logs,
         //These values are available by default on the block
         //so no need to allow users to configure these values
         // BlockHash,
@@ -303,7 +482,7 @@ pub mod evm {
         ExcessBlobGas,
         ParentBeaconBlockRoot,
         WithdrawalsRoot,
-        // Withdrawals, //TODO: allow this field to be selectable (contains an array of rescript record type)
+        Withdrawals,
         L1BlockNumber,
         SendCount,
         SendRoot,
@@ -333,6 +512,36 @@ pub mod evm {
                            endpoint for the network)"
         )]
         pub url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "HTTP headers sent with every request to the HyperSync endpoint, for \
+                           example to authenticate with a bearer token, basic auth or an API \
+                           key. Use environment variable interpolation like ${ENV_VAR} to keep \
+                           secrets out of the config file"
+        )]
+        pub headers: Option<BTreeMap<String, String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "What the source is used for when the network has several sources: \
+                           historical blocks until reaching the head, realtime blocks at the \
+                           head, or a fallback when the other sources stall (default: both \
+                           historical and realtime)"
+        )]
+        pub role: Option<SourceRole>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Sources with a lower priority are preferred over sources with the same \
+                           role (default: the order the sources are defined in)"
+        )]
+        pub priority: Option<u32>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(rename_all = "lowercase", deny_unknown_fields)]
+    pub enum SourceRole {
+        Historical,
+        Realtime,
+        Fallback,
     }
 
     #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
@@ -376,12 +585,64 @@ pub mod evm {
         #[schemars(
             description = "URL of the RPC endpoint. Can be a single URL or an array of URLs. If \
                            multiple URLs are provided, the first one will be used as the primary \
-                           RPC endpoint and the rest will be used as fallbacks."
+                           RPC endpoint and the rest will be used as fallbacks. Both HTTP and \
+                           WebSocket (ws:// or wss://) URLs are supported."
         )]
         pub url: SingleOrList<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "WebSocket URL (ws:// or wss://) used to subscribe to new block heads \
+                           instead of polling the RPC for the latest block. Falls back to polling \
+                           while the socket is disconnected (default: The url, if it's a \
+                           WebSocket URL)"
+        )]
+        pub ws_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "HTTP headers sent with every HTTP request to the RPC endpoint, for \
+                           example to authenticate with a bearer token or an API key. Use \
+                           environment variable interpolation like ${ENV_VAR} to keep secrets \
+                           out of the config file"
+        )]
+        pub headers: Option<BTreeMap<String, String>>,
         #[serde(flatten, skip_serializing_if = "Option::is_none")]
         #[schemars(description = "Config options for RPC syncing")]
         pub sync_config: Option<RpcSyncConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "What the source is used for when the network has several sources: \
+                           historical blocks until reaching the head, realtime blocks at the \
+                           head, or a fallback when the other sources stall (default: fallback \
+                           when hypersync_config is also defined, otherwise both historical and \
+                           realtime)"
+        )]
+        pub role: Option<SourceRole>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Sources with a lower priority are preferred over sources with the same \
+                           role (default: the order the sources are defined in)"
+        )]
+        pub priority: Option<u32>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(rename_all = "lowercase", deny_unknown_fields)]
+    pub enum FileFormat {
+        Jsonl,
+        Parquet,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct FileConfig {
+        #[schemars(
+            description = "Path to the directory with the pre-recorded blocks, transactions and \
+                           logs files, relative to the config file"
+        )]
+        pub path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Format of the recorded files (default: jsonl)")]
+        pub format: Option<FileFormat>,
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -401,6 +662,20 @@ pub mod evm {
         #[schemars(description = "Optional HyperSync Config for additional fine-tuning")]
         pub hypersync_config: Option<HypersyncConfig>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Read pre-recorded blocks, transactions and logs from files on disk \
+                           instead of a live endpoint. Useful for deterministic offline indexing \
+                           and tests"
+        )]
+        pub file_config: Option<FileConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "When the network has several sources, the amount of time in ms to wait \
+                           for a response from the active source before switching to the next \
+                           one (default: 20000)"
+        )]
+        pub source_stall_timeout: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "The number of blocks from the head that the indexer should account for \
                            in case of reorgs."
@@ -413,6 +688,63 @@ pub mod evm {
         pub end_block: Option<u64>,
         #[schemars(description = "All the contracts that should be indexed on the given network")]
         pub contracts: Vec<NetworkContract<ContractConfig>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Handlers that should run every N blocks on the given network, \
+                           independent of any event"
+        )]
+        pub block_handlers: Option<Vec<BlockHandlerConfig>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Select the block and transaction fields to include in the events of \
+                           the network, in addition to the global field selection. The fields \
+                           are validated against the sources of this network only. Fields \
+                           which aren't selected globally are optional in the generated types"
+        )]
+        pub field_selection: Option<FieldSelection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "If true, the indexer will store the raw event data of the network in \
+                           the database (default: The global raw_events option)"
+        )]
+        pub raw_events: Option<bool>,
+    }
+
+    impl Network {
+        ///Gets the block handlers defined on the network and on any of its contracts,
+        ///including contracts that reference a global definition. Contract block handlers
+        ///come with the path of the contract handler.
+        pub fn get_block_handler_configs<'a>(
+            &'a self,
+            global_contracts: &'a Option<Vec<GlobalContract<ContractConfig>>>,
+        ) -> Vec<(&'a BlockHandlerConfig, Option<&'a String>)> {
+            let mut block_handler_configs = vec![];
+
+            for network_contract in &self.contracts {
+                let contract_config = match &network_contract.config {
+                    Some(contract_config) => Some(contract_config),
+                    None => global_contracts
+                        .iter()
+                        .flatten()
+                        .find_map(|global_contract| {
+                            (global_contract.name == network_contract.name)
+                                .then_some(&global_contract.config)
+                        }),
+                };
+
+                if let Some(contract_config) = contract_config {
+                    for block_handler in contract_config.block_handlers.iter().flatten() {
+                        block_handler_configs.push((block_handler, Some(&contract_config.handler)));
+                    }
+                }
+            }
+
+            for block_handler in self.block_handlers.iter().flatten() {
+                block_handler_configs.push((block_handler, None));
+            }
+
+            block_handler_configs
+        }
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -431,6 +763,89 @@ pub mod evm {
         pub handler: String,
         #[schemars(description = "A list of events that should be indexed on this contract")]
         pub events: Vec<EventConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "A list of function calls, including internal calls, that should be \
+                           indexed on this contract"
+        )]
+        pub calls: Option<Vec<CallConfig>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Handlers that should run every N blocks on each network the contract \
+                           is indexed on"
+        )]
+        pub block_handlers: Option<Vec<BlockHandlerConfig>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Register addresses of this contract from an event of a factory \
+                           contract, without writing a contractRegister handler"
+        )]
+        pub factory: Option<FactoryConfig>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct FactoryConfig {
+        #[schemars(description = "Name of the factory contract emitting the event")]
+        pub contract: String,
+        #[schemars(
+            description = "Name of the factory contract event that creates this contract. The \
+                           event should be configured on the factory contract"
+        )]
+        pub event: String,
+        #[schemars(
+            description = "Name of the event param with the address of the created contract"
+        )]
+        pub address_param: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct CallConfig {
+        #[schemars(description = "The human readable signature of a function 'eg. \
+                                  transfer(address to, uint256 amount) returns (bool)' OR a \
+                                  reference to the name of a function in a json ABI file defined \
+                                  in your contract config. A provided signature will take \
+                                  precedence over what is defined in the json ABI")]
+        pub call: String,
+        #[schemars(
+            description = "Name of the call in the HyperIndex generated code. When ommitted, the \
+                           function name will be used. Should be unique per contract"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct BlockHandlerConfig {
+        #[schemars(
+            description = "Name of the block handler in the HyperIndex generated code. Should be \
+                           unique per network"
+        )]
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Run the handler every N blocks (default: 1)")]
+        pub interval: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "The block at which the handler should start running (default: the \
+                           start_block of the network)"
+        )]
+        pub start_block: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "The block after which the handler should stop running (default: the \
+                           end_block of the network)"
+        )]
+        pub end_block: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "The relative path to a file where the block handler is registered. \
+                           Required for block handlers defined on a network, defaults to the \
+                           contract handler otherwise"
+        )]
+        pub handler: Option<String>,
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -454,13 +869,60 @@ pub mod evm {
                            event"
         )]
         pub field_selection: Option<FieldSelection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Only index the events matching the filters. A map of indexed event \
+                           params to a value or a list of values, eg. { to: \"0x123...\" }. \
+                           Can't be used together with the eventFilters option of the handler"
+        )]
+        pub filters: Option<BTreeMap<String, SingleOrList<TopicFilterValue>>>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+    #[serde(untagged)]
+    pub enum TopicFilterValue {
+        Bool(bool),
+        Int(i64),
+        UInt(u64),
+        String(String),
+    }
+
+    impl Display for TopicFilterValue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                TopicFilterValue::Bool(value) => write!(f, "{value}"),
+                TopicFilterValue::Int(value) => write!(f, "{value}"),
+                TopicFilterValue::UInt(value) => write!(f, "{value}"),
+                TopicFilterValue::String(value) => write!(f, "\"{value}\""),
+            }
+        }
+    }
+
+    impl JsonSchema for TopicFilterValue {
+        fn schema_name() -> Cow<'static, str> {
+            "TopicFilterValue".into()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            json_schema!({
+              "anyOf": [
+                String::json_schema(gen),
+                i64::json_schema(gen),
+                bool::json_schema(gen),
+              ]
+            })
+        }
+
+        fn always_inline_schema() -> bool {
+            true
+        }
     }
 }
 
 pub mod fuel {
-    use std::fmt::Display;
+    use std::{collections::BTreeMap, fmt::Display};
 
-    use super::{GlobalContract, NetworkContract, NetworkId};
+    use super::{GlobalContract, NetworkContract, NetworkId, ProfileOverlay};
     use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
     use strum::Display;
@@ -483,6 +945,23 @@ pub mod fuel {
         #[schemars(description = "Custom path to schema.yaml file")]
         pub schema: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Paths or glob patterns of YAML files with more networks and global \
+                           contracts, eg. ./networks/*.yaml. Use it to split a large config into \
+                           multiple files. Paths in the included files are relative to this \
+                           config file"
+        )]
+        pub include: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Overrides of the config by profile name, eg. dev, staging or prod. \
+                           The profile is selected with the --profile flag or the ENVIO_PROFILE \
+                           env var and its overrides are deep merged onto the config. Networks \
+                           are merged by id and contracts by name. The overrides can also be \
+                           defined in a config.<profile>.yaml file next to the config"
+        )]
+        pub profiles: Option<BTreeMap<String, ProfileOverlay>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "Global contract definitions that must contain all definitions except \
                            addresses. You can share a single handler/abi/event definitions for \
@@ -598,7 +1077,7 @@ pub mod fuel {
 mod tests {
     use super::{
         evm::{ContractConfig, EventDecoder, HumanConfig, Network},
-        NetworkContract,
+        Addresses, AddressesFile, AddressesJson, NetworkContract,
     };
     use crate::{config_parsing::human_config::fuel, utils::normalized_list::NormalizedList};
     use pretty_assertions::assert_eq;
@@ -650,11 +1129,17 @@ events: []
             name: "Contract1".to_string(),
             address: NormalizedList::from(vec![
                 "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()
-            ]),
+            ])
+            .into(),
+            start_block: None,
+            end_block: None,
             config: Some(ContractConfig {
                 abi_file_path: None,
                 handler: "./src/EventHandler.js".to_string(),
                 events: vec![],
+                calls: None,
+                block_handlers: None,
+                factory: None,
             }),
         };
 
@@ -673,10 +1158,15 @@ events: []
         let expected = NetworkContract {
             name: "Contract1".to_string(),
             address: vec![].into(),
+            start_block: None,
+            end_block: None,
             config: Some(ContractConfig {
                 abi_file_path: None,
                 handler: "./src/EventHandler.js".to_string(),
                 events: vec![],
+                calls: None,
+                block_handlers: None,
+                factory: None,
             }),
         };
 
@@ -696,10 +1186,15 @@ events: []
         let expected = NetworkContract {
             name: "Contract1".to_string(),
             address: vec!["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()].into(),
+            start_block: None,
+            end_block: None,
             config: Some(ContractConfig {
                 abi_file_path: None,
                 handler: "./src/EventHandler.js".to_string(),
                 events: vec![],
+                calls: None,
+                block_handlers: None,
+                factory: None,
             }),
         };
 
@@ -718,7 +1213,10 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
             name: "Contract1".to_string(),
             address: NormalizedList::from(vec![
                 "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()
-            ]),
+            ])
+            .into(),
+            start_block: None,
+            end_block: None,
             config: None,
         };
 
@@ -746,6 +1244,40 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
         );
     }
 
+    #[test]
+    fn deserialize_addresses_from_files() {
+        let deserialized: Addresses = serde_yaml::from_str("file: ./pools.csv").unwrap();
+        assert_eq!(
+            deserialized,
+            Addresses::File(AddressesFile {
+                file: "./pools.csv".to_string()
+            })
+        );
+        assert_eq!(
+            deserialized.get_file_path(),
+            Some(&"./pools.csv".to_string())
+        );
+
+        let deserialized: Addresses =
+            serde_yaml::from_str("{ json: ./mainnet.json, path: \"$.Vault.address\" }").unwrap();
+        assert_eq!(
+            deserialized,
+            Addresses::Json(AddressesJson {
+                json: "./mainnet.json".to_string(),
+                path: "$.Vault.address".to_string()
+            })
+        );
+
+        let deserialized: Addresses = serde_yaml::from_str("[\"0x123\", \"0x456\"]").unwrap();
+        assert_eq!(
+            deserialized,
+            vec!["0x123".to_string(), "0x456".to_string()].into()
+        );
+        assert_eq!(deserialized.get_file_path(), None);
+
+        assert!(serde_yaml::from_str::<Addresses>("{ json: ./mainnet.json }").is_err());
+    }
+
     #[test]
     fn deserializes_factory_contract_config() {
         let config_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -788,6 +1320,8 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
             name: "Fuel indexer".to_string(),
             description: None,
             schema: None,
+            include: None,
+            profiles: None,
             ecosystem: fuel::EcosystemTag::Fuel,
             contracts: None,
             raw_events: None,
@@ -801,6 +1335,8 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
                     address: "0x4a2ce054e3e94155f7092f7365b212f7f45105b74819c623744ebcc5d065c6ac"
                         .to_string()
                         .into(),
+                    start_block: None,
+                    end_block: None,
                     config: Some(fuel::ContractConfig {
                         abi_file_path: "./abis/spark-orderbook.json".to_string(),
                         handler: "./src/OrderBookHandlers.ts".to_string(),
@@ -836,6 +1372,8 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
             name: "Fuel indexer".to_string(),
             description: None,
             schema: None,
+            include: None,
+            profiles: None,
             raw_events: None,
             ecosystem: fuel::EcosystemTag::Fuel,
             contracts: None,
@@ -884,13 +1422,45 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
             Network {
                 id: 1,
                 hypersync_config: None,
+                file_config: None,
+                source_stall_timeout: None,
                 rpc_config: None,
                 start_block: 2_000,
                 confirmed_block_threshold: None,
                 end_block: Some(2_000_000),
-                contracts: vec![]
+                contracts: vec![],
+                block_handlers: None,
+                field_selection: None,
+                raw_events: None,
             },
             de
         );
     }
+
+    #[test]
+    fn redacts_endpoint_headers_when_serializing() {
+        let config_str = r#"
+name: config
+networks:
+  - id: 1
+    start_block: 0
+    hypersync_config:
+      url: https://hypersync.example.com
+      headers:
+        Authorization: Basic dXNlcjpzZWNyZXQ=
+    rpc_config:
+      url: https://rpc.example.com
+      headers:
+        x-api-key: my-secret-key
+    contracts: []
+"#;
+        let config = super::HumanConfig::Evm(serde_yaml::from_str(config_str).unwrap());
+
+        let redacted = config.to_redacted_string(|value| format!("redacted {}", value.len()));
+
+        assert!(!redacted.contains("dXNlcjpzZWNyZXQ="));
+        assert!(!redacted.contains("my-secret-key"));
+        assert!(redacted.contains("Authorization: redacted 22"));
+        assert!(redacted.contains("x-api-key: redacted 13"));
+    }
 }
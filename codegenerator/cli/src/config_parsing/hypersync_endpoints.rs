@@ -1,12 +1,22 @@
 use anyhow::Context;
 
-use super::chain_helpers::{HypersyncNetwork, Network};
+use super::{
+    chain_helpers::{HypersyncNetwork, Network},
+    chain_registry,
+};
 
 pub fn network_to_hypersync_url(network: &HypersyncNetwork) -> String {
     format!("https://{}.hypersync.xyz", *network as u64)
 }
 
 pub fn get_default_hypersync_endpoint(chain_id: u64) -> anyhow::Result<String> {
+    if let Some(hypersync_url) = chain_registry::get()
+        .get_by_id(chain_id)
+        .and_then(|chain| chain.hypersync_url.clone())
+    {
+        return Ok(hypersync_url);
+    }
+
     let network_name = Network::from_network_id(chain_id)
         .context(format!("Getting network name from id ({})", chain_id))?;
 
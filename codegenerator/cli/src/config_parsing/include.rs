@@ -0,0 +1,361 @@
+use super::human_config::{GlobalContract, HumanConfig};
+use crate::{
+    project_paths::{path_utils, ParsedProjectPaths},
+    utils::unique_hashmap,
+};
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+///A YAML file listed in the include option of the config
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludedFile {
+    pub path: PathBuf,
+    ///The path as it's shown in errors, relative to the config file
+    pub name: String,
+    ///The file content with the env variables already interpolated
+    pub source: String,
+}
+
+///The part of the config which can be defined in an included file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IncludedConfig<N, C> {
+    #[serde(default)]
+    networks: Vec<N>,
+    #[serde(default)]
+    contracts: Vec<GlobalContract<C>>,
+}
+
+fn has_wildcard(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+fn wildcard_to_regex(pattern: &str) -> Result<Regex> {
+    let regex = regex::escape(pattern)
+        .replace(r"\*", "[^/]*")
+        .replace(r"\?", "[^/]");
+    Regex::new(&format!("^{regex}$")).context("Failed to build a regex from the glob pattern")
+}
+
+///Expands the include patterns to the paths of the included files. Every pattern is relative
+///to the config file and supports the * and ? wildcards in any path component
+pub fn resolve_paths(
+    include: &[String],
+    project_paths: &ParsedProjectPaths,
+) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for pattern in include {
+        let path =
+            path_utils::get_config_path_relative_to_root(project_paths, PathBuf::from(pattern))
+                .context("Failed to get path to the included file relative to the config file")?;
+
+        if !has_wildcard(pattern) {
+            paths.push(path);
+            continue;
+        }
+
+        let mut matches = vec![PathBuf::new()];
+        for component in path.components() {
+            let component_str = component.as_os_str().to_string_lossy();
+            if !matches!(component, Component::Normal(_)) || !has_wildcard(&component_str) {
+                matches.iter_mut().for_each(|m| m.push(component));
+                continue;
+            }
+            let component_regex = wildcard_to_regex(&component_str)?;
+            matches = matches
+                .into_iter()
+                .flat_map(|dir| fs::read_dir(&dir).into_iter().flatten().flatten())
+                .map(|entry| entry.path())
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .is_some_and(|name| component_regex.is_match(&name.to_string_lossy()))
+                })
+                .collect();
+        }
+
+        let mut matches: Vec<_> = matches.into_iter().filter(|m| m.is_file()).collect();
+        if matches.is_empty() {
+            return Err(anyhow!(
+                "EE123: The include pattern \"{}\" doesn't match any file",
+                pattern
+            ));
+        }
+        matches.sort();
+        paths.extend(matches);
+    }
+
+    let mut unique_paths = vec![];
+    for path in paths {
+        if !unique_paths.contains(&path) {
+            unique_paths.push(path);
+        }
+    }
+    Ok(unique_paths)
+}
+
+///Gets the name of an included file relative to the config file
+pub fn get_file_name(path: &Path, project_paths: &ParsedProjectPaths) -> String {
+    project_paths
+        .config
+        .parent()
+        .and_then(|config_dir| path.strip_prefix(config_dir).ok())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn merge<N, C>(
+    networks: &mut Vec<N>,
+    contracts: &mut Option<Vec<GlobalContract<C>>>,
+    get_network_id: impl Fn(&N) -> u64,
+    config_name: &str,
+    included_files: &[IncludedFile],
+) -> Result<()>
+where
+    N: DeserializeOwned,
+    C: DeserializeOwned,
+{
+    //Keeps the file where every network and contract is defined, to point at both
+    //definitions when one is duplicated
+    let mut network_files: HashMap<u64, String> = HashMap::new();
+    let mut contract_files: HashMap<String, String> = HashMap::new();
+    for network in networks.iter() {
+        network_files.insert(get_network_id(network), config_name.to_string());
+    }
+    for contract in contracts.iter().flatten() {
+        contract_files.insert(contract.name.clone(), config_name.to_string());
+    }
+
+    for file in included_files {
+        let included: IncludedConfig<N, C> =
+            serde_yaml::from_str(&file.source).context(format!(
+            "EE123: Failed to deserialize the included file \"{}\". It can only define networks \
+             and global contracts",
+            file.name
+        ))?;
+
+        for network in included.networks {
+            let id = get_network_id(&network);
+            let defined_in = network_files.get(&id).cloned().unwrap_or_default();
+            unique_hashmap::try_insert(&mut network_files, id, file.name.clone()).context(
+                format!(
+                    "EE123: The network {} in \"{}\" is already defined in \"{}\"",
+                    id, file.name, defined_in
+                ),
+            )?;
+            networks.push(network);
+        }
+
+        for contract in included.contracts {
+            let defined_in = contract_files
+                .get(&contract.name)
+                .cloned()
+                .unwrap_or_default();
+            unique_hashmap::try_insert(
+                &mut contract_files,
+                contract.name.clone(),
+                file.name.clone(),
+            )
+            .context(format!(
+                "EE123: The contract {} in \"{}\" is already defined in \"{}\"",
+                contract.name, file.name, defined_in
+            ))?;
+            contracts.get_or_insert_with(Vec::new).push(contract);
+        }
+    }
+    Ok(())
+}
+
+///Merges the networks and global contracts of the included files into the config.
+///Networks and contracts can't be defined more than once across all the files
+pub fn merge_included_files(
+    human_config: HumanConfig,
+    config_name: &str,
+    included_files: &[IncludedFile],
+) -> Result<HumanConfig> {
+    match human_config {
+        HumanConfig::Evm(mut config) => {
+            merge(
+                &mut config.networks,
+                &mut config.contracts,
+                |network| network.id,
+                config_name,
+                included_files,
+            )?;
+            Ok(HumanConfig::Evm(config))
+        }
+        HumanConfig::Fuel(mut config) => {
+            merge(
+                &mut config.networks,
+                &mut config.contracts,
+                |network| network.id,
+                config_name,
+                included_files,
+            )?;
+            Ok(HumanConfig::Fuel(config))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{merge_included_files, resolve_paths, IncludedFile};
+    use crate::{
+        config_parsing::{human_config::HumanConfig, system_config::SystemConfig},
+        project_paths::ParsedProjectPaths,
+    };
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn get_human_config(source: &str) -> HumanConfig {
+        SystemConfig::deserialize_human_config(source).unwrap()
+    }
+
+    fn included_file(name: &str, source: &str) -> IncludedFile {
+        IncludedFile {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    const CONFIG: &str = r#"
+name: Test
+contracts:
+  - name: Greeter
+    handler: ./src/EventHandlers.js
+    events:
+      - event: NewGreeting(address user, string greeting)
+networks:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: Greeter
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+"#;
+
+    #[test]
+    fn merges_networks_and_contracts_of_included_files() {
+        let included = included_file(
+            "networks/optimism.yaml",
+            r#"
+contracts:
+  - name: Token
+    handler: ./src/EventHandlers.js
+    events:
+      - event: Transfer(address indexed from, address indexed to, uint256 value)
+networks:
+  - id: 10
+    start_block: 0
+    contracts:
+      - name: Greeter
+        address: "0x6B175474E89094C44Da98b954EedeAC495271d0F"
+      - name: Token
+        address: "0x6B175474E89094C44Da98b954EedeAC495271d0F"
+"#,
+        );
+
+        let merged = merge_included_files(get_human_config(CONFIG), "config.yaml", &[included]);
+        let HumanConfig::Evm(merged) = merged.unwrap() else {
+            panic!("Expected an evm config");
+        };
+
+        assert_eq!(
+            merged.networks.iter().map(|n| n.id).collect::<Vec<_>>(),
+            vec![1, 10]
+        );
+        assert_eq!(
+            merged
+                .contracts
+                .unwrap()
+                .iter()
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>(),
+            vec!["Greeter".to_string(), "Token".to_string()]
+        );
+    }
+
+    #[test]
+    fn rejects_duplicates_across_files() {
+        let duplicated_network = included_file(
+            "networks/mainnet.yaml",
+            "networks:\n  - id: 1\n    start_block: 0\n    contracts: []\n",
+        );
+        let err = merge_included_files(
+            get_human_config(CONFIG),
+            "config.yaml",
+            &[duplicated_network],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "EE123: The network 1 in \"networks/mainnet.yaml\" is already defined in \
+             \"config.yaml\""
+        );
+
+        let contract = "contracts:\n  - name: Token\n    handler: ./src/EventHandlers.js\n    \
+                        events: []\n";
+        let err = merge_included_files(
+            get_human_config(CONFIG),
+            "config.yaml",
+            &[
+                included_file("contracts/token.yaml", contract),
+                included_file("contracts/token-copy.yaml", contract),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "EE123: The contract Token in \"contracts/token-copy.yaml\" is already defined in \
+             \"contracts/token.yaml\""
+        );
+    }
+
+    #[test]
+    fn rejects_other_options_in_included_files() {
+        let included = included_file("networks/mainnet.yaml", "name: Other\n");
+        assert!(
+            merge_included_files(get_human_config(CONFIG), "config.yaml", &[included]).is_err()
+        );
+    }
+
+    #[test]
+    fn resolves_glob_patterns() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths =
+            ParsedProjectPaths::new(test_dir.as_str(), "generated/", "configs/config1.yaml")
+                .expect("Failed creating parsed_paths");
+
+        let paths = resolve_paths(
+            &[
+                "include/networks/*.yaml".to_string(),
+                "include/networks/optimism.yaml".to_string(),
+                "./include/contracts.yaml".to_string(),
+            ],
+            &project_paths,
+        )
+        .unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from(&test_dir).join("configs/include/networks/optimism.yaml"),
+                PathBuf::from(&test_dir).join("configs/include/networks/polygon.yaml"),
+                PathBuf::from(&test_dir).join("configs/include/contracts.yaml"),
+            ]
+        );
+
+        let err = resolve_paths(&["include/*.yml".to_string()], &project_paths).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "EE123: The include pattern \"include/*.yml\" doesn't match any file"
+        );
+    }
+}
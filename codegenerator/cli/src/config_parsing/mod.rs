@@ -1,10 +1,15 @@
+pub mod address_files;
 pub mod chain_helpers;
+pub mod chain_registry;
 pub mod contract_import;
+pub mod diagnostics;
 pub mod entity_parsing;
 pub mod event_parsing;
 pub mod graph_migration;
 pub mod human_config;
 pub mod hypersync_endpoints;
+pub mod include;
 pub mod postgres_types;
+pub mod profiles;
 pub mod system_config;
 pub mod validation;
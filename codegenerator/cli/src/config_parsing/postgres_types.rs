@@ -7,11 +7,13 @@ pub enum Primitive {
     Boolean,
     Text,
     Integer,
+    Int8,
     Numeric(Option<(u32, u32)>), // (precision, scale)
     DoublePrecision,
     Serial,
     Json,
     Timestamp,
+    Bytea,
     Enum(String),
 }
 
@@ -21,17 +23,40 @@ impl Primitive {
             Self::Boolean => "Boolean".to_string(),
             Self::Text => "Text".to_string(),
             Self::Integer => "Integer".to_string(),
+            Self::Int8 => "Int8".to_string(),
             Self::Numeric(None) => "Numeric".to_string(),
             Self::Numeric(Some((precision, scale))) => {
                 format!("Custom(\"NUMERIC({}, {})\")", precision, scale)
             }
             Self::Serial => "Serial".to_string(),
-            Self::Json => "Json".to_string(),
+            Self::Json => "JsonB".to_string(),
             Self::Timestamp => "Timestamp".to_string(),
+            Self::Bytea => "Bytea".to_string(),
             Self::DoublePrecision => "DoublePrecision".to_string(),
             Self::Enum(enum_name) => format!("Custom(Enums.{enum_name}.enum.name)"),
         }
     }
+
+    ///The postgres type used for the column, mirroring Table.res. Enums are
+    ///represented by the unquoted enum name
+    pub fn get_sql_type(&self) -> String {
+        match self {
+            Self::Boolean => "BOOLEAN".to_string(),
+            Self::Text => "TEXT".to_string(),
+            Self::Integer => "INTEGER".to_string(),
+            Self::Int8 => "BIGINT".to_string(),
+            Self::Numeric(None) => "NUMERIC".to_string(),
+            Self::Numeric(Some((precision, scale))) => {
+                format!("NUMERIC({}, {})", precision, scale)
+            }
+            Self::DoublePrecision => "DOUBLE PRECISION".to_string(),
+            Self::Serial => "SERIAL".to_string(),
+            Self::Json => "JSONB".to_string(),
+            Self::Timestamp => "TIMESTAMP WITH TIME ZONE".to_string(),
+            Self::Bytea => "BYTEA".to_string(),
+            Self::Enum(enum_name) => enum_name.clone(),
+        }
+    }
 }
 
 impl Serialize for Primitive {
@@ -49,11 +74,20 @@ impl Display for Primitive {
     }
 }
 
+///The access method of an index given with @index(type: ...). Indices without it use
+///the default btree. Serializes to the variant of Table.indexType
+#[derive(Serialize, Debug, PartialEq, Eq, Hash, Clone, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum IndexType {
+    Gin,
+}
+
 #[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct Field {
     pub field_name: String,
     pub linked_entity: Option<String>,
     pub is_index: bool,
+    pub index_type: Option<IndexType>,
     pub is_primary_key: bool,
     pub is_nullable: bool,
     pub is_array: bool,
@@ -0,0 +1,303 @@
+use super::human_config::{evm, fuel};
+use crate::project_paths::ParsedProjectPaths;
+use anyhow::{anyhow, Context, Result};
+use schemars::schema_for;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+pub const PROFILE_ENV_VAR: &str = "ENVIO_PROFILE";
+
+const PROFILES_KEY: &str = "profiles";
+///Keys which can't be overridden, since they change how the rest of the config is read
+const NON_OVERRIDABLE_KEYS: [&str; 2] = ["ecosystem", PROFILES_KEY];
+///Keys which identify an item in a list, so the overlay item is merged onto the matching one
+const LIST_ITEM_KEYS: [&str; 2] = ["id", "name"];
+
+///Gets the path of the overlay file of a profile next to the config, eg. config.prod.yaml
+pub fn get_overlay_file_path(config_path: &Path, profile: &str) -> PathBuf {
+    let stem = config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_name = match config_path.extension() {
+        Some(extension) => format!("{stem}.{profile}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{profile}"),
+    };
+    config_path.with_file_name(file_name)
+}
+
+///Gets the keys allowed at the top level of the config of the ecosystem
+fn get_config_keys(ecosystem: Option<&str>) -> Vec<String> {
+    let schema = match ecosystem {
+        Some("fuel") => schema_for!(fuel::HumanConfig),
+        _ => schema_for!(evm::HumanConfig),
+    };
+    serde_json::to_value(schema)
+        .ok()
+        .and_then(|schema| {
+            schema
+                .get("properties")
+                .and_then(|properties| properties.as_object())
+                .map(|properties| properties.keys().cloned().collect())
+        })
+        .unwrap_or_default()
+}
+
+fn validate_overlay(overlay: &Mapping, ecosystem: Option<&str>) -> Result<()> {
+    let config_keys = get_config_keys(ecosystem);
+    for key in overlay.keys() {
+        let key = key
+            .as_str()
+            .ok_or_else(|| anyhow!("Expected the overrides to have string keys"))?;
+        if NON_OVERRIDABLE_KEYS.contains(&key) {
+            return Err(anyhow!("The \"{}\" option can't be overridden", key));
+        }
+        if !config_keys.iter().any(|config_key| config_key == key) {
+            return Err(anyhow!(
+                "Unknown option \"{}\". The overrides can only have the options of the config",
+                key
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn get_list_item_key(item: &Value) -> Option<(&'static str, &Value)> {
+    LIST_ITEM_KEYS
+        .into_iter()
+        .find_map(|key| item.get(key).map(|value| (key, value)))
+}
+
+///Merges the overlay onto the base. Mappings are merged key by key, lists of networks
+///and contracts are merged by their id or name, and any other value is replaced
+fn deep_merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, overlay_value) in overlay {
+                match base.get_mut(&key) {
+                    Some(base_value) => deep_merge(base_value, overlay_value),
+                    None => {
+                        base.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        (Value::Sequence(base), Value::Sequence(overlay))
+            if !overlay.is_empty()
+                && overlay.iter().all(|item| get_list_item_key(item).is_some()) =>
+        {
+            for overlay_item in overlay {
+                let matching_item = get_list_item_key(&overlay_item).and_then(|(key, id)| {
+                    base.iter_mut()
+                        .find(|base_item| base_item.get(key) == Some(id))
+                });
+                match matching_item {
+                    Some(base_item) => deep_merge(base_item, overlay_item),
+                    None => base.push(overlay_item),
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+///Merges the overrides of the selected profile onto the config before it's interpolated.
+///The overrides come from the profiles section of the config and from the
+///config.<profile>.yaml file, which is applied last. The config is returned unchanged
+///when no profile is selected
+pub fn apply_profile(config_string: String, project_paths: &ParsedProjectPaths) -> Result<String> {
+    let Some(profile) = &project_paths.profile else {
+        return Ok(config_string);
+    };
+
+    let mut config: Value = serde_yaml::from_str(&config_string)
+        .context("EE105: Failed to deserialize config. The config.yaml file is not a valid yaml")?;
+    let ecosystem = config
+        .get("ecosystem")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    let section_overrides = config
+        .get(PROFILES_KEY)
+        .and_then(|profiles| profiles.get(profile.as_str()))
+        .cloned()
+        .map(|overrides| ("the profiles section".to_string(), overrides));
+
+    let overlay_file_path = get_overlay_file_path(&project_paths.config, profile);
+    let file_overrides = match std::fs::read_to_string(&overlay_file_path) {
+        Ok(overlay_string) => {
+            let overrides = serde_yaml::from_str(&overlay_string).context(format!(
+                "EE124: Failed to deserialize the profile file \"{}\"",
+                overlay_file_path.to_string_lossy()
+            ))?;
+            Some((
+                format!("\"{}\"", overlay_file_path.to_string_lossy()),
+                overrides,
+            ))
+        }
+        Err(_) => None,
+    };
+
+    if section_overrides.is_none() && file_overrides.is_none() {
+        return Err(anyhow!(
+            "EE124: The profile \"{}\" isn't defined. Add it to the profiles section of the \
+             config or create the \"{}\" file",
+            profile,
+            overlay_file_path.to_string_lossy()
+        ));
+    }
+
+    for (source, overrides) in section_overrides.into_iter().chain(file_overrides) {
+        let overrides = match overrides {
+            Value::Mapping(overrides) => overrides,
+            //An empty overlay file or profile
+            Value::Null => Mapping::new(),
+            _ => {
+                return Err(anyhow!(
+                    "EE124: The overrides of the profile \"{}\" in {} should be a mapping",
+                    profile,
+                    source
+                ))
+            }
+        };
+        validate_overlay(&overrides, ecosystem.as_deref()).context(format!(
+            "EE124: Invalid overrides of the profile \"{}\" in {}",
+            profile, source
+        ))?;
+        deep_merge(&mut config, Value::Mapping(overrides));
+    }
+
+    serde_yaml::to_string(&config).context("Failed to serialize the config with the profile")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apply_profile, deep_merge, get_overlay_file_path};
+    use crate::project_paths::ParsedProjectPaths;
+    use pretty_assertions::assert_eq;
+    use serde_yaml::Value;
+    use std::path::PathBuf;
+
+    fn yaml(source: &str) -> Value {
+        serde_yaml::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn gets_overlay_file_path() {
+        assert_eq!(
+            get_overlay_file_path(&PathBuf::from("indexer/config.yaml"), "prod"),
+            PathBuf::from("indexer/config.prod.yaml")
+        );
+    }
+
+    #[test]
+    fn deep_merges_networks_by_id() {
+        let mut base = yaml(
+            r#"
+name: indexer
+networks:
+  - id: 1
+    start_block: 0
+    rpc_config:
+      url: https://eth.dev
+    contracts:
+      - name: Greeter
+        address: "0x1"
+  - id: 10
+    start_block: 0
+    contracts: []
+"#,
+        );
+        let overlay = yaml(
+            r#"
+networks:
+  - id: 1
+    start_block: 100
+    end_block: 200
+    rpc_config:
+      url: https://eth.prod
+    contracts:
+      - name: Greeter
+        address: ["0x2", "0x3"]
+  - id: 137
+    start_block: 0
+    contracts: []
+"#,
+        );
+        deep_merge(&mut base, overlay);
+
+        assert_eq!(
+            base,
+            yaml(
+                r#"
+name: indexer
+networks:
+  - id: 1
+    start_block: 100
+    rpc_config:
+      url: https://eth.prod
+    contracts:
+      - name: Greeter
+        address: ["0x2", "0x3"]
+    end_block: 200
+  - id: 10
+    start_block: 0
+    contracts: []
+  - id: 137
+    start_block: 0
+    contracts: []
+"#
+            )
+        );
+    }
+
+    #[test]
+    fn applies_profile_from_section_and_file() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let mut project_paths =
+            ParsedProjectPaths::new(&test_dir, "generated/", "configs/profiles-config.yaml")
+                .expect("Failed creating parsed_paths");
+        let config_string = std::fs::read_to_string(&project_paths.config).unwrap();
+
+        assert_eq!(
+            apply_profile(config_string.clone(), &project_paths).unwrap(),
+            config_string
+        );
+
+        project_paths.profile = Some("prod".to_string());
+        let config = yaml(&apply_profile(config_string.clone(), &project_paths).unwrap());
+        let network = &config["networks"][0];
+        //The file is applied after the profiles section
+        assert_eq!(network["start_block"], yaml("16000000"));
+        assert_eq!(network["end_block"], yaml("null"));
+        assert_eq!(network["rpc_config"]["url"], yaml("${ENVIO_PROD_RPC_URL}"));
+
+        project_paths.profile = Some("staging".to_string());
+        assert!(apply_profile(config_string, &project_paths).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        let project_paths = ParsedProjectPaths {
+            profile: Some("prod".to_string()),
+            ..ParsedProjectPaths::default()
+        };
+        let config_string = |overrides: &str| {
+            format!(
+                "name: indexer\nnetworks: []\nprofiles:\n  prod:\n    {}\n",
+                overrides
+            )
+        };
+
+        assert_eq!(
+            format!(
+                "{:#}",
+                apply_profile(config_string("start_block: 100"), &project_paths).unwrap_err()
+            ),
+            "EE124: Invalid overrides of the profile \"prod\" in the profiles section: Unknown \
+             option \"start_block\". The overrides can only have the options of the config"
+        );
+        assert!(apply_profile(config_string("ecosystem: fuel"), &project_paths).is_err());
+        assert!(apply_profile(config_string("rollback_on_reorg: false"), &project_paths).is_ok());
+    }
+}
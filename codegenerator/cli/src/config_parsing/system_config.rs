@@ -1,16 +1,20 @@
 use super::{
+    address_files,
     chain_helpers::get_confirmed_block_threshold_from_id,
-    entity_parsing::{Entity, GraphQLEnum, Schema},
+    entity_parsing::{Aggregation, Entity, GraphQLEnum, GraphQLInterface, Schema},
+    event_parsing::EthereumEventParam,
     human_config::{
         self,
         evm::{
-            EventConfig as EvmEventConfig, EventDecoder, HumanConfig as EvmConfig,
-            Network as EvmNetwork,
+            CallConfig as EvmCallConfig, EventConfig as EvmEventConfig, EventDecoder, FileFormat,
+            HumanConfig as EvmConfig, Network as EvmNetwork, TopicFilterValue,
         },
         fuel::{EventConfig as FuelEventConfig, HumanConfig as FuelConfig},
         HumanConfig,
     },
     hypersync_endpoints,
+    include::{self, IncludedFile},
+    profiles,
     validation::{self, validate_names_valid_rescript},
 };
 use crate::{
@@ -19,17 +23,20 @@ use crate::{
     fuel::abi::{FuelAbi, BURN_EVENT_NAME, CALL_EVENT_NAME, MINT_EVENT_NAME, TRANSFER_EVENT_NAME},
     project_paths::{path_utils, ParsedProjectPaths},
     rescript_types::RescriptTypeIdent,
-    utils::unique_hashmap,
+    utils::{normalized_list::SingleOrList, unique_hashmap},
 };
 use anyhow::{anyhow, Context, Result};
 use dotenvy::{EnvLoader, EnvMap, EnvSequence};
-use ethers::abi::{ethabi::Event as EthAbiEvent, EventExt, EventParam, HumanReadableParser};
+use ethers::abi::{
+    ethabi::{Event as EthAbiEvent, Function as EthAbiFunction},
+    EventExt, EventParam, HumanReadableParser, Param, ParamType,
+};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 type ContractNameKey = String;
@@ -56,10 +63,10 @@ pub struct EnvState {
 }
 
 impl EnvState {
-    pub fn new(project_root: &PathBuf) -> Self {
+    pub fn new(project_root: &Path) -> Self {
         EnvState {
             maybe_dotenv: None,
-            project_root: project_root.clone(),
+            project_root: project_root.to_path_buf(),
         }
     }
 
@@ -101,9 +108,109 @@ impl EnvState {
     }
 }
 
+///Values read from secret files, and environment variables interpolated into the
+///endpoint urls and headers of the config. They're masked in the errors and diagnostics,
+///which might print parts of the config
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Secrets {
+    secrets: Vec<String>,
+    ///Every non-empty environment variable interpolated into the config.
+    ///Only the ones ending up in the endpoints are secrets
+    env_values: Vec<String>,
+}
+
+impl Secrets {
+    const MASK: &'static str = "<secret>";
+
+    fn add(&mut self, secret: &str) {
+        if !secret.is_empty() && !self.secrets.iter().any(|s| s == secret) {
+            self.secrets.push(secret.to_string());
+        }
+    }
+
+    ///Reads a secret file, which is relative to the config file, like the other paths of the
+    ///config. The trailing line break that secret mounts usually have is removed
+    fn read_file(&mut self, path: &str, project_paths: &ParsedProjectPaths) -> Result<String> {
+        let full_path =
+            path_utils::get_config_path_relative_to_root(project_paths, PathBuf::from(path))
+                .context("Failed to get path to the secret file relative to the config file")?;
+        let secret = fs::read_to_string(&full_path)
+            .context(format!("Failed to read the secret file at \"{}\"", path))?
+            .trim_end_matches(['\n', '\r'])
+            .to_string();
+        self.add(&secret);
+        Ok(secret)
+    }
+
+    fn add_env_values(&mut self, env_values: Vec<String>) {
+        for value in env_values {
+            if !value.is_empty() && !self.env_values.contains(&value) {
+                self.env_values.push(value);
+            }
+        }
+    }
+
+    ///Endpoint urls usually contain an API key and headers carry the auth,
+    ///so when they're interpolated from the environment, the whole value is a secret
+    pub fn add_interpolated_endpoints(&mut self, human_config: &HumanConfig) {
+        let endpoint_values: Vec<String> = match human_config {
+            HumanConfig::Evm(config) => config
+                .networks
+                .iter()
+                .flat_map(|network| {
+                    let rpc_values = network.rpc_config.iter().flat_map(|rpc_config| {
+                        let urls: Vec<String> = rpc_config.url.clone().into();
+                        urls.into_iter()
+                            .chain(rpc_config.ws_url.clone())
+                            .chain(rpc_config.headers.iter().flat_map(|h| h.values().cloned()))
+                    });
+                    let hypersync_values =
+                        network
+                            .hypersync_config
+                            .iter()
+                            .flat_map(|hypersync_config| {
+                                std::iter::once(hypersync_config.url.clone()).chain(
+                                    hypersync_config
+                                        .headers
+                                        .iter()
+                                        .flat_map(|h| h.values().cloned()),
+                                )
+                            });
+                    rpc_values.chain(hypersync_values).collect::<Vec<_>>()
+                })
+                .collect(),
+            HumanConfig::Fuel(config) => config
+                .networks
+                .iter()
+                .filter_map(|network| network.hyperfuel_config.as_ref())
+                .map(|hyperfuel_config| hyperfuel_config.url.clone())
+                .collect(),
+        };
+
+        for value in endpoint_values {
+            let is_interpolated = self
+                .env_values
+                .iter()
+                .any(|env_value| value.contains(env_value.as_str()));
+            if is_interpolated {
+                self.add(&value);
+            }
+        }
+    }
+
+    pub fn mask(&self, text: &str) -> String {
+        //Longer secrets first, in case one contains another
+        self.secrets
+            .iter()
+            .sorted_by_key(|secret| std::cmp::Reverse(secret.len()))
+            .fold(text.to_string(), |text, secret| {
+                text.replace(secret.as_str(), Self::MASK)
+            })
+    }
+}
+
 mod interpolation {
     use anyhow::{anyhow, Result};
-    use regex::{Captures, Regex};
 
     #[derive(PartialEq)]
     enum InterpolationResult {
@@ -111,104 +218,242 @@ mod interpolation {
         InvalidName,
         DefaultForMissing(String),
         DefaultForMissingAndEmpty(String),
+        ErrorForMissing(String),
+        ErrorForMissingAndEmpty(String),
+        AlternativeForPresent(String),
+        AlternativeForPresentAndNotEmpty(String),
+        SecretFile(String),
     }
 
+    const SECRET_FILE_PREFIX: &str = "file:";
+
     fn parse_capture(inner: &str) -> (String, InterpolationResult) {
-        let (name, result) = match (inner.find(":-"), inner.find('-')) {
-            (Some(pos1), Some(pos2)) if pos1 < pos2 => {
-                let name = &inner[..pos1];
-                let default_value = inner[pos1 + 2..].to_string();
-                (
-                    name,
-                    InterpolationResult::DefaultForMissingAndEmpty(default_value),
-                )
-            }
-            (_, Some(pos)) => {
-                let name = &inner[..pos];
-                let default_value = inner[pos + 1..].to_string();
-                (name, InterpolationResult::DefaultForMissing(default_value))
-            }
-            (Some(pos), _) => {
-                let name = &inner[..pos];
-                let default_value = inner[pos + 2..].to_string();
-                (
-                    name,
-                    InterpolationResult::DefaultForMissingAndEmpty(default_value),
-                )
-            }
-            (None, None) => (inner, InterpolationResult::DirectSubstitution),
-        };
+        let secret_file_path = inner
+            .strip_prefix(SECRET_FILE_PREFIX)
+            .filter(|path| !path.starts_with(['-', '?', '+']));
+        if let Some(path) = secret_file_path {
+            return (
+                path.to_string(),
+                InterpolationResult::SecretFile(path.to_string()),
+            );
+        }
 
-        if name.is_empty()
-            || name.chars().next().map_or(false, |c| c.is_ascii_digit())
-            || !name.chars().all(|c| match c {
-                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => true,
-                _ => false,
+        let name_end = inner
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(inner.len());
+        let (name, rest) = inner.split_at(name_end);
+
+        let operators: [(&str, fn(String) -> InterpolationResult); 6] = [
+            (":-", InterpolationResult::DefaultForMissingAndEmpty),
+            (":?", InterpolationResult::ErrorForMissingAndEmpty),
+            (":+", InterpolationResult::AlternativeForPresentAndNotEmpty),
+            ("-", InterpolationResult::DefaultForMissing),
+            ("?", InterpolationResult::ErrorForMissing),
+            ("+", InterpolationResult::AlternativeForPresent),
+        ];
+        let result = if rest.is_empty() {
+            Some(InterpolationResult::DirectSubstitution)
+        } else {
+            operators.into_iter().find_map(|(operator, to_result)| {
+                rest.strip_prefix(operator)
+                    .map(|word| to_result(word.to_string()))
             })
-        {
-            return (name.to_string(), InterpolationResult::InvalidName);
+        };
+
+        match result {
+            Some(result) if !name.is_empty() && !name.starts_with(|c: char| c.is_ascii_digit()) => {
+                (name.to_string(), result)
+            }
+            _ => (inner.to_string(), InterpolationResult::InvalidName),
+        }
+    }
+
+    ///Finds the end of the capture starting at the given offset, taking the nested
+    ///captures of the default and alternative values into account
+    fn find_capture_end(string: &str, start: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut chars = string[start..].char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '$' if chars.peek().map(|(_, next)| *next) == Some('{') => {
+                    chars.next();
+                    depth += 1;
+                }
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(start + i);
+                    }
+                }
+                _ => (),
+            }
         }
+        None
+    }
 
-        (name.to_string(), result)
+    fn get_location(string: &str, offset: usize) -> (usize, usize) {
+        let preceding = &string[..offset];
+        let line = preceding.matches('\n').count() + 1;
+        let line_start = preceding.rfind('\n').map_or(0, |i| i + 1);
+        let column = preceding[line_start..].chars().count() + 1;
+        (line, column)
     }
 
-    pub fn interpolate_config_variables(
-        config_string: String,
-        mut get_env: impl FnMut(&str) -> Option<String>,
-    ) -> Result<String> {
-        let mut missing_vars = Vec::new();
-        let mut invalid_vars = Vec::new();
-
-        // If we don't have `[^}]` and simpley use `.` in the regex, it will match the last `}` and the rest of the string until the last `}`
-        let re = Regex::new(r"\$\{([^}]*)\}").unwrap();
-        let config_string = re.replace_all(&config_string, |caps: &Captures| {
-            let name = &caps[1];
-            let (name, interpolation_result) = parse_capture(name);
+    struct Interpolator<'a, E, F> {
+        file_name: &'a str,
+        get_env: E,
+        read_file: F,
+        invalid_vars: Vec<String>,
+        missing_vars: Vec<String>,
+        failed_files: Vec<String>,
+    }
+
+    impl<'a, E, F> Interpolator<'a, E, F>
+    where
+        E: FnMut(&str) -> Option<String>,
+        F: FnMut(&str) -> Result<String>,
+    {
+        ///Replaces every capture in the string. The location is where the outermost capture
+        ///starts, so the vars of nested captures are reported at the capture using them
+        fn interpolate(&mut self, string: &str, location: Option<&str>) -> String {
+            let mut interpolated = String::new();
+            let mut rest_start = 0;
+            while let Some(start) = string[rest_start..].find("${").map(|i| rest_start + i) {
+                let Some(end) = find_capture_end(string, start) else {
+                    break;
+                };
+                interpolated.push_str(&string[rest_start..start]);
+                let capture_location = location.map(str::to_string).unwrap_or_else(|| {
+                    let (line, column) = get_location(string, start);
+                    format!("{}:{line}:{column}", self.file_name)
+                });
+                let value = self.interpolate_capture(&string[start + 2..end], &capture_location);
+                interpolated.push_str(&value);
+                rest_start = end + 1;
+            }
+            interpolated.push_str(&string[rest_start..]);
+            interpolated
+        }
+
+        fn interpolate_capture(&mut self, inner: &str, location: &str) -> String {
+            let (name, interpolation_result) = parse_capture(inner);
             if interpolation_result == InterpolationResult::InvalidName {
                 // Wrap invalid vars with quotes to make them more visible in the error message
                 // Don't need to do this for missing ones, because they won't have spaces in the name
-                invalid_vars.push(format!("\"{name}\""));
+                self.invalid_vars.push(format!("\"{name}\""));
                 return "".to_string();
             }
-            match (get_env(&name), interpolation_result) {
-                (Some(val), InterpolationResult::DefaultForMissingAndEmpty(default))
-                    if val == "" =>
+            if let InterpolationResult::SecretFile(path) = interpolation_result {
+                return match (self.read_file)(&path) {
+                    Ok(secret) => secret,
+                    Err(err) => {
+                        self.failed_files.push(format!("{err:#} ({location})"));
+                        "".to_string()
+                    }
+                };
+            }
+
+            let value = (self.get_env)(&name);
+            let is_empty = value.as_deref() == Some("");
+            match (value, interpolation_result) {
+                (Some(_), InterpolationResult::AlternativeForPresent(alternative)) => {
+                    self.interpolate(&alternative, Some(location))
+                }
+                (Some(_), InterpolationResult::AlternativeForPresentAndNotEmpty(alternative))
+                    if !is_empty =>
                 {
-                    default
+                    self.interpolate(&alternative, Some(location))
+                }
+                (_, InterpolationResult::AlternativeForPresent(_))
+                | (_, InterpolationResult::AlternativeForPresentAndNotEmpty(_)) => "".to_string(),
+                (Some(_), InterpolationResult::DefaultForMissingAndEmpty(default)) if is_empty => {
+                    self.interpolate(&default, Some(location))
+                }
+                (Some(_), InterpolationResult::ErrorForMissingAndEmpty(message)) if is_empty => {
+                    self.push_missing_var(&name, &message, location);
+                    "".to_string()
                 }
                 (Some(val), _) => val,
                 (None, InterpolationResult::DefaultForMissing(default))
-                | (None, InterpolationResult::DefaultForMissingAndEmpty(default)) => default,
+                | (None, InterpolationResult::DefaultForMissingAndEmpty(default)) => {
+                    self.interpolate(&default, Some(location))
+                }
+                (None, InterpolationResult::ErrorForMissing(message))
+                | (None, InterpolationResult::ErrorForMissingAndEmpty(message)) => {
+                    self.push_missing_var(&name, &message, location);
+                    "".to_string()
+                }
                 (None, _) => {
-                    missing_vars.push(name.to_string());
+                    self.missing_vars.push(format!("{name} ({location})"));
                     "".to_string()
                 }
             }
-        });
+        }
+
+        fn push_missing_var(&mut self, name: &str, message: &str, location: &str) {
+            let message = self.interpolate(message, Some(location));
+            if message.is_empty() {
+                self.missing_vars.push(format!("{name} ({location})"));
+            } else {
+                self.missing_vars
+                    .push(format!("{name} ({location}): {message}"));
+            }
+        }
+    }
+
+    ///Interpolates the environment variables and secret files into the config.
+    ///Every missing variable is reported with its location in the file
+    pub fn interpolate_config_variables(
+        config_string: String,
+        file_name: &str,
+        get_env: impl FnMut(&str) -> Option<String>,
+        read_file: impl FnMut(&str) -> Result<String>,
+    ) -> Result<String> {
+        let mut interpolator = Interpolator {
+            file_name,
+            get_env,
+            read_file,
+            invalid_vars: Vec::new(),
+            missing_vars: Vec::new(),
+            failed_files: Vec::new(),
+        };
+        let config_string = interpolator.interpolate(&config_string, None);
 
-        if !invalid_vars.is_empty() {
+        if !interpolator.invalid_vars.is_empty() {
             return Err(anyhow!(
                 "Failed to interpolate variables into your config file. Invalid environment \
                  variables are present: {}",
-                invalid_vars.join(", ")
+                interpolator.invalid_vars.join(", ")
             ));
         }
 
-        if !missing_vars.is_empty() {
+        if !interpolator.missing_vars.is_empty() {
             return Err(anyhow!(
                 "Failed to interpolate variables into your config file. Environment variables are \
                  not present: {}",
-                missing_vars.join(", ")
+                interpolator.missing_vars.join(", ")
+            ));
+        }
+
+        if !interpolator.failed_files.is_empty() {
+            return Err(anyhow!(
+                "Failed to interpolate secret files into your config file: {}",
+                interpolator.failed_files.join(", ")
             ));
         }
 
-        Ok(config_string.to_string())
+        Ok(config_string)
     }
 
     #[cfg(test)]
     mod test {
+        use anyhow::{anyhow, Result};
         use pretty_assertions::assert_eq;
 
+        fn read_no_files(path: &str) -> Result<String> {
+            Err(anyhow!("No file at {path}"))
+        }
+
         #[test]
         fn test_interpolate_config_variables_with_single_capture() {
             let config_string = r#"
@@ -216,12 +461,16 @@ networks:
   - id: ${ENVIO_NETWORK_ID}
     start_block: 0
 "#;
-            let interpolated_config_string =
-                super::interpolate_config_variables(config_string.to_string(), |name| match name {
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
                     "ENVIO_NETWORK_ID" => Some("0".to_string()),
                     _ => None,
-                })
-                .unwrap();
+                },
+                read_no_files,
+            )
+            .unwrap();
             assert_eq!(
                 interpolated_config_string,
                 r#"
@@ -240,14 +489,18 @@ networks:
     rpc_config:
       url: ${ENVIO_ETH_RPC_URL}?api_key=${ENVIO_ETH_RPC_KEY}
 "#;
-            let interpolated_config_string =
-                super::interpolate_config_variables(config_string.to_string(), |name| match name {
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
                     "ENVIO_NETWORK_ID" => Some("0".to_string()),
                     "ENVIO_ETH_RPC_URL" => Some("https://eth.com".to_string()),
                     "ENVIO_ETH_RPC_KEY" => Some("foo".to_string()),
                     _ => None,
-                })
-                .unwrap();
+                },
+                read_no_files,
+            )
+            .unwrap();
             assert_eq!(
                 interpolated_config_string,
                 r#"
@@ -266,12 +519,16 @@ networks:
   - id: 0
     start_block: 0
 "#;
-            let interpolated_config_string =
-                super::interpolate_config_variables(config_string.to_string(), |name| match name {
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
                     "ENVIO_NETWORK_ID" => Some("0".to_string()),
                     _ => None,
-                })
-                .unwrap();
+                },
+                read_no_files,
+            )
+            .unwrap();
             assert_eq!(
                 interpolated_config_string,
                 r#"
@@ -290,15 +547,19 @@ networks:
     rpc_config:
       url: https://eth.com?api_key=${ENVIO_ETH_API_KEY}
 "#;
-            let interpolated_config_string =
-                super::interpolate_config_variables(config_string.to_string(), |name| match name {
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
                     "ENVIO_NETWORK_ID" => Some("0".to_string()),
                     _ => None,
-                })
-                .unwrap_err();
+                },
+                read_no_files,
+            )
+            .unwrap_err();
             assert_eq!(
                 interpolated_config_string.to_string(),
-                r#"Failed to interpolate variables into your config file. Environment variables are not present: ENVIO_ETH_API_KEY"#
+                r#"Failed to interpolate variables into your config file. Environment variables are not present: ENVIO_ETH_API_KEY (config.yaml:5:36)"#
             );
         }
 
@@ -310,12 +571,16 @@ networks:
     rpc_config:
       url: ${My RPC URL}?api_key=${}
 "#;
-            let interpolated_config_string =
-                super::interpolate_config_variables(config_string.to_string(), |name| match name {
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
                     "ENVIO_NETWORK_ID" => Some("0".to_string()),
                     _ => None,
-                })
-                .unwrap_err();
+                },
+                read_no_files,
+            )
+            .unwrap_err();
             assert_eq!(
                 interpolated_config_string.to_string(),
                 r#"Failed to interpolate variables into your config file. Invalid environment variables are present: "My RPC URL", """#
@@ -340,13 +605,17 @@ DefaultForMissingAndEmpty with empty env: "${EMPTY_ENV:-default}"
 DefaultForMissingAndEmpty with empty env and many dashes: "${EMPTY_ENV:----:---}"
 DefaultForMissingAndEmpty with empty env and empty default: "${EMPTY_ENV:-}"
 "#;
-            let interpolated_config_string =
-                super::interpolate_config_variables(config_string.to_string(), |name| match name {
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
                     "EXISTING_ENV" => Some("val".to_string()),
                     "EMPTY_ENV" => Some("".to_string()),
                     _ => None,
-                })
-                .unwrap();
+                },
+                read_no_files,
+            )
+            .unwrap();
             assert_eq!(
                 interpolated_config_string,
                 r#"
@@ -367,6 +636,99 @@ DefaultForMissingAndEmpty with empty env and empty default: ""
 "#
             );
         }
+
+        #[test]
+        fn test_interpolate_config_variables_with_shell_like_substitutions() {
+            let config_string = r#"
+RequiredForMissing with existing env: "${EXISTING_ENV?Set it}"
+RequiredForMissing with empty env: "${EMPTY_ENV?Set it}"
+RequiredForMissingAndEmpty with existing env: "${EXISTING_ENV:?Set it}"
+AlternativeForPresent with existing env: "${EXISTING_ENV+alt}"
+AlternativeForPresent with empty env: "${EMPTY_ENV+alt}"
+AlternativeForPresent with missing env: "${MISSING_ENV+alt}"
+AlternativeForPresentAndNotEmpty with existing env: "${EXISTING_ENV:+alt}"
+AlternativeForPresentAndNotEmpty with empty env: "${EMPTY_ENV:+alt}"
+Nested default with existing env: "${MISSING_ENV:-${EXISTING_ENV}}"
+Nested default with missing env: "${MISSING_ENV:-${OTHER_MISSING_ENV:-default}/path}"
+Nested alternative: "${EXISTING_ENV:+https://${EXISTING_ENV}.com}"
+Secret file: "${file:./secrets/rpc_url}"
+"#;
+            let interpolated_config_string = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
+                    "EXISTING_ENV" => Some("val".to_string()),
+                    "EMPTY_ENV" => Some("".to_string()),
+                    _ => None,
+                },
+                |path| match path {
+                    "./secrets/rpc_url" => Ok("https://secret.com".to_string()),
+                    _ => read_no_files(path),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                interpolated_config_string,
+                r#"
+RequiredForMissing with existing env: "val"
+RequiredForMissing with empty env: ""
+RequiredForMissingAndEmpty with existing env: "val"
+AlternativeForPresent with existing env: "alt"
+AlternativeForPresent with empty env: "alt"
+AlternativeForPresent with missing env: ""
+AlternativeForPresentAndNotEmpty with existing env: "alt"
+AlternativeForPresentAndNotEmpty with empty env: ""
+Nested default with existing env: "val"
+Nested default with missing env: "default/path"
+Nested alternative: "https://val.com"
+Secret file: "https://secret.com"
+"#
+            );
+        }
+
+        #[test]
+        fn test_interpolate_config_variables_reports_every_missing_env() {
+            let config_string = r#"
+networks:
+  - id: ${ENVIO_NETWORK_ID:?Set the chain id}
+    rpc_config:
+      url: ${ENVIO_RPC_URL:-${ENVIO_FALLBACK_RPC_URL}}
+      headers:
+        Authorization: ${EMPTY_ENV:?}
+"#;
+            let err = super::interpolate_config_variables(
+                config_string.to_string(),
+                "config.yaml",
+                |name| match name {
+                    "EMPTY_ENV" => Some("".to_string()),
+                    _ => None,
+                },
+                read_no_files,
+            )
+            .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Failed to interpolate variables into your config file. Environment variables are \
+                 not present: ENVIO_NETWORK_ID (config.yaml:3:9): Set the chain id, \
+                 ENVIO_FALLBACK_RPC_URL (config.yaml:5:12), EMPTY_ENV (config.yaml:7:24)"
+            );
+        }
+
+        #[test]
+        fn test_interpolate_config_variables_with_missing_secret_file() {
+            let err = super::interpolate_config_variables(
+                "url: ${file:./secrets/rpc_url}".to_string(),
+                "config.yaml",
+                |_| None,
+                read_no_files,
+            )
+            .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Failed to interpolate secret files into your config file: No file at \
+                 ./secrets/rpc_url (config.yaml:1:6)"
+            );
+        }
     }
 }
 
@@ -447,6 +809,20 @@ impl SystemConfig {
         enums
     }
 
+    pub fn get_gql_interfaces(&self) -> Vec<&GraphQLInterface> {
+        let mut interfaces: Vec<&GraphQLInterface> = self.schema.interfaces.values().collect();
+        //For consistent templating in alphabetical order
+        interfaces.sort_by_key(|i| i.name.clone());
+        interfaces
+    }
+
+    pub fn get_aggregations(&self) -> Vec<&Aggregation> {
+        let mut aggregations: Vec<&Aggregation> = self.schema.aggregations.values().collect();
+        //For consistent templating in alphabetical order
+        aggregations.sort_by_key(|a| a.name.clone());
+        aggregations
+    }
+
     pub fn get_gql_enum_names_set(&self) -> HashSet<EntityKey> {
         self.schema.enums.keys().cloned().collect()
     }
@@ -457,6 +833,60 @@ impl SystemConfig {
         networks
     }
 
+    ///The events of every network share the generated block and transaction types,
+    ///so the fields selected per network are added to them as optional fields
+    pub fn with_network_fields(&self, field_selection: &FieldSelection) -> FieldSelection {
+        let network_field_selections: Vec<_> = self
+            .get_networks()
+            .into_iter()
+            .filter_map(|network| network.field_selection.clone())
+            .collect();
+        field_selection
+            .clone()
+            .with_network_fields(&network_field_selections)
+    }
+
+    ///The fields which are only in the generated types, because other networks select them.
+    ///The sources of the network don't query them. Fields of the global and event
+    ///selections are kept, since every network needs them
+    pub fn get_other_network_fields(&self, network: &Network) -> FieldSelection {
+        let other_fields = |get_fields: fn(&FieldSelection) -> &[SelectedField]| {
+            let selected_names: HashSet<&String> = std::iter::once(&self.field_selection)
+                .chain(
+                    self.contracts
+                        .values()
+                        .flat_map(|contract| contract.events.iter())
+                        .filter_map(|event| event.field_selection.as_ref()),
+                )
+                .chain(network.field_selection.as_ref())
+                .flat_map(|field_selection| get_fields(field_selection).iter())
+                .map(|field| &field.name)
+                .collect();
+            self.get_networks()
+                .into_iter()
+                .filter(|other_network| other_network.id != network.id)
+                .filter_map(|other_network| other_network.field_selection.as_ref())
+                .flat_map(|field_selection| get_fields(field_selection).iter())
+                .filter(|field| !selected_names.contains(&field.name))
+                .unique_by(|field| field.name.clone())
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        FieldSelection::new(
+            other_fields(|field_selection| field_selection.transaction_fields.as_slice()),
+            other_fields(|field_selection| field_selection.block_fields.as_slice()),
+        )
+    }
+
+    ///Block handlers of all networks, ordered by network id
+    pub fn get_block_handlers(&self) -> Vec<&BlockHandler> {
+        self.get_networks()
+            .into_iter()
+            .flat_map(|n| n.block_handlers.iter())
+            .collect()
+    }
+
     pub fn get_path_to_schema(&self) -> Result<PathBuf> {
         let schema_path = path_utils::get_config_path_relative_to_root(
             &self.parsed_project_paths,
@@ -468,10 +898,16 @@ impl SystemConfig {
     }
 
     pub fn get_all_paths_to_handlers(&self) -> Result<Vec<PathBuf>> {
+        let block_handler_paths = self
+            .get_block_handlers()
+            .into_iter()
+            .map(|b| b.get_path_to_handler(&self.parsed_project_paths));
+
         let mut all_paths_to_handlers = self
             .get_contracts()
             .into_iter()
             .map(|c| c.get_path_to_handler(&self.parsed_project_paths))
+            .chain(block_handler_paths)
             .collect::<Result<HashSet<_>>>()?
             .into_iter()
             .collect::<Vec<_>>();
@@ -494,6 +930,39 @@ impl SystemConfig {
         Ok(filtered_unique_abi_files)
     }
 
+    pub fn get_all_paths_to_address_files(&self) -> Result<Vec<PathBuf>> {
+        let address_files: Vec<&String> = match &self.human_config {
+            HumanConfig::Evm(config) => config
+                .networks
+                .iter()
+                .flat_map(|n| n.contracts.iter())
+                .filter_map(|c| c.address.get_file_path())
+                .collect(),
+            HumanConfig::Fuel(config) => config
+                .networks
+                .iter()
+                .flat_map(|n| n.contracts.iter())
+                .filter_map(|c| c.address.get_file_path())
+                .collect(),
+        };
+
+        let mut unique_address_files = address_files
+            .into_iter()
+            .map(|file| {
+                path_utils::get_config_path_relative_to_root(
+                    &self.parsed_project_paths,
+                    PathBuf::from(file),
+                )
+            })
+            .collect::<Result<HashSet<_>>>()
+            .context("Failed creating a relative path to address files")?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        unique_address_files.sort();
+        Ok(unique_address_files)
+    }
+
     pub fn from_human_config(
         human_config: HumanConfig,
         schema: Schema,
@@ -523,13 +992,27 @@ impl SystemConfig {
                             g_contract.name,
                         ))?;
 
+                        let calls = Call::from_evm_calls_config(
+                            g_contract.config.calls.clone().unwrap_or_default(),
+                            &g_contract.config.abi_file_path,
+                            &project_paths,
+                        )
+                        .context(format!(
+                            "Failed parsing abi types for calls in global contract {}",
+                            g_contract.name,
+                        ))?;
+
+                        let evm_abi = evm_abi.with_calls(&calls)?;
+
                         let contract = Contract::new(
                             g_contract.name.clone(),
                             g_contract.config.handler.clone(),
                             events,
+                            calls,
                             Abi::Evm(evm_abi),
                         )
-                        .context("Failed parsing globally defined contract")?;
+                        .context("Failed parsing globally defined contract")?
+                        .with_factory(g_contract.config.factory.clone());
 
                         //Check if contract exists
                         unique_hashmap::try_insert(&mut contracts, contract.name.clone(), contract)
@@ -554,17 +1037,32 @@ impl SystemConfig {
                                     contract.name, network.id,
                                 ))?;
 
+                                let calls = Call::from_evm_calls_config(
+                                    l_contract.calls.unwrap_or_default(),
+                                    &l_contract.abi_file_path,
+                                    &project_paths,
+                                )
+                                .context(format!(
+                                    "Failed parsing abi types for calls in contract {} on \
+                                     network {}",
+                                    contract.name, network.id,
+                                ))?;
+
+                                let evm_abi = evm_abi.with_calls(&calls)?;
+
                                 let contract = Contract::new(
                                     contract.name,
                                     l_contract.handler,
                                     events,
+                                    calls,
                                     Abi::Evm(evm_abi),
                                 )
                                 .context(format!(
                                     "Failed parsing locally defined network contract at network \
                                      id {}",
                                     network.id
-                                ))?;
+                                ))?
+                                .with_factory(l_contract.factory);
 
                                 //Check if contract exists
                                 unique_hashmap::try_insert(
@@ -594,20 +1092,76 @@ impl SystemConfig {
                         }
                     }
 
-                    let sync_source = SyncSource::from_evm_network_config(
+                    let sources = NetworkSource::from_evm_network_config(
                         network.clone(),
                         evm_config.event_decoder.clone(),
                     )?;
 
+                    //Calls are fetched from HyperSync traces, which the other sources don't
+                    //provide, so they would silently be skipped
+                    let has_non_hypersync_src = sources.iter().any(|source| {
+                        !matches!(source.sync_source, SyncSource::HypersyncConfig(_))
+                    });
+                    if has_non_hypersync_src {
+                        for network_contract in network.contracts.iter() {
+                            let has_calls = contracts
+                                .get(&network_contract.name)
+                                .is_some_and(|contract| !contract.calls.is_empty());
+                            if has_calls {
+                                return Err(anyhow!(
+                                    "EE126: The contract \"{}\" has calls, which are only \
+                                     fetched from HyperSync, but the network {} has other \
+                                     sources. Use only HyperSync for the network or remove the \
+                                     calls of the contract.",
+                                    network_contract.name,
+                                    network.id,
+                                ));
+                            }
+                        }
+                    }
+
                     let contracts: Vec<NetworkContract> = network
                         .contracts
                         .iter()
                         .cloned()
-                        .map(|c| NetworkContract {
-                            name: c.name,
-                            addresses: c.address.into(),
+                        .map(|c| {
+                            Ok(NetworkContract {
+                                addresses: address_files::load_addresses(
+                                    c.address,
+                                    &Ecosystem::Evm,
+                                    project_paths,
+                                )?,
+                                name: c.name,
+                                start_block: c.start_block,
+                                end_block: c.end_block,
+                            })
                         })
-                        .collect();
+                        .collect::<Result<_>>()?;
+
+                    let block_handlers =
+                        BlockHandler::from_evm_network_config(network, evm_config).context(
+                            format!("Failed parsing block handlers for network {}", network.id),
+                        )?;
+
+                    //Validated against the sources of the network only, so the network can
+                    //select fields which other networks can't get from their sources
+                    let network_has_rpc_sync_src = sources
+                        .iter()
+                        .any(|source| matches!(source.sync_source, SyncSource::RpcConfig(_)));
+                    let network_field_selection = network
+                        .field_selection
+                        .clone()
+                        .map(|field_selection| {
+                            FieldSelection::try_from_config_field_selection(
+                                field_selection,
+                                network_has_rpc_sync_src,
+                            )
+                        })
+                        .transpose()
+                        .context(format!(
+                            "Failed parsing the field selection of network {}",
+                            network.id
+                        ))?;
 
                     let network = Network {
                         id: network.id,
@@ -616,14 +1170,24 @@ impl SystemConfig {
                             .unwrap_or(get_confirmed_block_threshold_from_id(network.id)),
                         start_block: network.start_block,
                         end_block: network.end_block,
-                        sync_source,
+                        sources,
+                        source_stall_timeout: network
+                            .source_stall_timeout
+                            .unwrap_or(DEFAULT_SOURCE_STALL_TIMEOUT_MILLIS),
                         contracts,
+                        block_handlers,
+                        field_selection: network_field_selection,
+                        raw_events: network.raw_events,
                     };
 
                     unique_hashmap::try_insert(&mut networks, network.id.clone(), network)
                         .context("Failed inserting network at networks map")?;
                 }
 
+                for contract in contracts.values() {
+                    contract.validate_factory(&contracts)?;
+                }
+
                 let field_selection = FieldSelection::try_from_config_field_selection(
                     evm_config.field_selection.clone().unwrap_or(
                         human_config::evm::FieldSelection {
@@ -672,6 +1236,7 @@ impl SystemConfig {
                             g_contract.name.clone(),
                             g_contract.config.handler.clone(),
                             events,
+                            vec![],
                             Abi::Fuel(fuel_abi),
                         )?;
 
@@ -701,6 +1266,7 @@ impl SystemConfig {
                                     contract.name.clone(),
                                     l_contract.handler,
                                     events,
+                                    vec![],
                                     Abi::Fuel(fuel_abi),
                                 )?;
 
@@ -752,19 +1318,32 @@ impl SystemConfig {
                         .contracts
                         .iter()
                         .cloned()
-                        .map(|c| NetworkContract {
-                            name: c.name,
-                            addresses: c.address.into(),
+                        .map(|c| {
+                            c.validate_start_and_end_block(network.start_block)?;
+                            Ok(NetworkContract {
+                                addresses: address_files::load_addresses(
+                                    c.address,
+                                    &Ecosystem::Fuel,
+                                    project_paths,
+                                )?,
+                                name: c.name,
+                                start_block: c.start_block,
+                                end_block: c.end_block,
+                            })
                         })
-                        .collect();
+                        .collect::<Result<_>>()?;
 
                     let network = Network {
                         id: network.id as u64,
                         start_block: network.start_block,
                         end_block: network.end_block,
                         confirmed_block_threshold: 0,
-                        sync_source,
+                        sources: vec![NetworkSource::primary(sync_source)],
+                        source_stall_timeout: DEFAULT_SOURCE_STALL_TIMEOUT_MILLIS,
                         contracts,
+                        block_handlers: vec![],
+                        field_selection: None,
+                        raw_events: None,
                     };
 
                     unique_hashmap::try_insert(&mut networks, network.id.clone(), network)
@@ -792,7 +1371,12 @@ impl SystemConfig {
         }
     }
 
-    pub fn parse_from_project_files(project_paths: &ParsedProjectPaths) -> Result<Self> {
+    /// Reads the config file, applies the overrides of the selected profile
+    /// and interpolates any environment variables and secret files into it
+    pub fn read_human_config_string(
+        project_paths: &ParsedProjectPaths,
+        secrets: &mut Secrets,
+    ) -> Result<String> {
         let human_config_string =
             std::fs::read_to_string(&project_paths.config).context(format!(
                 "EE104: Failed to resolve config path {0}. Make sure you're in the correct \
@@ -800,14 +1384,28 @@ impl SystemConfig {
                 &project_paths.config.to_str().unwrap_or("{unknown}"),
             ))?;
 
+        let human_config_string = profiles::apply_profile(human_config_string, project_paths)?;
+
         let mut env_state = EnvState::new(&project_paths.project_root);
-        let human_config_string =
-            interpolation::interpolate_config_variables(human_config_string, |name| {
-                env_state.var(name)
-            })?;
+        let mut env_values = vec![];
+        let human_config_string = interpolation::interpolate_config_variables(
+            human_config_string,
+            &include::get_file_name(&project_paths.config, project_paths),
+            |name| {
+                let value = env_state.var(name);
+                env_values.extend(value.clone());
+                value
+            },
+            |path| secrets.read_file(path, project_paths),
+        );
+        secrets.add_env_values(env_values);
+        human_config_string
+    }
 
+    /// Deserializes an interpolated config string into the human config of its ecosystem
+    pub fn deserialize_human_config(human_config_string: &str) -> Result<HumanConfig> {
         let config_discriminant: human_config::ConfigDiscriminant =
-            serde_yaml::from_str(&human_config_string).context(
+            serde_yaml::from_str(human_config_string).context(
                 "EE105: Failed to deserialize config. The config.yaml file is either not a valid \
                  yaml or the \"ecosystem\" field is not a string.",
             )?;
@@ -824,46 +1422,170 @@ impl SystemConfig {
             None => Ecosystem::Evm,
         };
 
+        let deserialize_err_msg = format!(
+            "EE105: Failed to deserialize config. Visit the docs for more information {}",
+            links::DOC_CONFIGURATION_FILE
+        );
+
         match ecosystem {
             Ecosystem::Evm => {
                 let evm_config: EvmConfig =
-                    serde_yaml::from_str(&human_config_string).context(format!(
-                        "EE105: Failed to deserialize config. Visit the docs for more information \
-                         {}",
-                        links::DOC_CONFIGURATION_FILE
-                    ))?;
-                let schema = Schema::parse_from_file(&project_paths, &evm_config.schema)
-                    .context("Parsing schema file for config")?;
-                Self::from_human_config(HumanConfig::Evm(evm_config), schema, project_paths)
+                    serde_yaml::from_str(human_config_string).context(deserialize_err_msg)?;
+                Ok(HumanConfig::Evm(evm_config))
             }
             Ecosystem::Fuel => {
                 let fuel_config: FuelConfig =
-                    serde_yaml::from_str(&human_config_string).context(format!(
-                        "EE105: Failed to deserialize config. Visit the docs for more information \
-                         {}",
-                        links::DOC_CONFIGURATION_FILE
-                    ))?;
-                let schema = Schema::parse_from_file(&project_paths, &fuel_config.schema)
-                    .context("Parsing schema file for config")?;
-                Self::from_human_config(HumanConfig::Fuel(fuel_config), schema, project_paths)
+                    serde_yaml::from_str(human_config_string).context(deserialize_err_msg)?;
+                Ok(HumanConfig::Fuel(fuel_config))
             }
         }
     }
-}
 
-type ServerUrl = String;
+    /// Reads the files listed in the include option of the config and interpolates any
+    /// environment variables and secret files into them
+    pub fn read_included_files(
+        human_config: &HumanConfig,
+        project_paths: &ParsedProjectPaths,
+        secrets: &mut Secrets,
+    ) -> Result<Vec<IncludedFile>> {
+        let paths = include::resolve_paths(human_config.get_include(), project_paths)?;
 
-#[derive(Debug, Serialize, Clone, PartialEq)]
-pub struct HypersyncConfig {
-    pub endpoint_url: ServerUrl,
-    pub is_client_decoder: bool,
-}
+        let mut env_state = EnvState::new(&project_paths.project_root);
+        let mut env_values = vec![];
+        let included_files: Result<Vec<_>> = paths
+            .into_iter()
+            .map(|path| {
+                let name = include::get_file_name(&path, project_paths);
+                let source = std::fs::read_to_string(&path).context(format!(
+                    "EE123: Failed to read the included file \"{}\"",
+                    name
+                ))?;
+                let source = interpolation::interpolate_config_variables(
+                    source,
+                    &name,
+                    |var_name| {
+                        let value = env_state.var(var_name);
+                        env_values.extend(value.clone());
+                        value
+                    },
+                    |secret_path| secrets.read_file(secret_path, project_paths),
+                )
+                .context(format!(
+                    "EE123: Failed to interpolate the included file \"{}\"",
+                    name
+                ))?;
+                Ok(IncludedFile { path, name, source })
+            })
+            .collect();
+        secrets.add_env_values(env_values);
+        included_files
+    }
+
+    /// Merges the networks and global contracts of the included files into the human config
+    pub fn merge_included_files(
+        human_config: HumanConfig,
+        included_files: &[IncludedFile],
+        project_paths: &ParsedProjectPaths,
+    ) -> Result<HumanConfig> {
+        let config_name = include::get_file_name(&project_paths.config, project_paths);
+        include::merge_included_files(human_config, &config_name, included_files)
+    }
+
+    pub fn parse_from_project_files(project_paths: &ParsedProjectPaths) -> Result<Self> {
+        Self::parse_from_project_files_with_secrets(project_paths, &mut Secrets::default())
+    }
+
+    ///Collects the secrets of the config even when parsing fails, so the returned
+    ///error keeps its chain and can be masked by the caller before printing it
+    pub fn parse_from_project_files_with_secrets(
+        project_paths: &ParsedProjectPaths,
+        secrets: &mut Secrets,
+    ) -> Result<Self> {
+        let human_config_string = Self::read_human_config_string(project_paths, secrets)?;
+        let human_config = Self::deserialize_human_config(&human_config_string)?;
+        let included_files = Self::read_included_files(&human_config, project_paths, secrets)?;
+        let human_config =
+            Self::merge_included_files(human_config, &included_files, project_paths)?;
+        secrets.add_interpolated_endpoints(&human_config);
+
+        let schema = Schema::parse_from_file(&project_paths, human_config.get_schema_path())
+            .context("Parsing schema file for config")?;
+        Self::from_human_config(human_config, schema, project_paths)
+    }
+}
+
+type ServerUrl = String;
+
+///HTTP headers sent with every request to an endpoint.
+///They usually carry auth secrets, so Debug only prints the header names
+#[derive(Serialize, Clone, PartialEq, Default)]
+#[serde(transparent)]
+pub struct Headers(BTreeMap<String, String>);
+
+impl Headers {
+    fn parse(headers: Option<BTreeMap<String, String>>) -> Result<Self> {
+        let headers = headers.unwrap_or_default();
+        for (name, value) in headers.iter() {
+            let is_valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(c));
+            if !is_valid_name {
+                return Err(anyhow!(
+                    "EE119: The header name \"{}\" is invalid. Header names can't be empty or \
+                     contain whitespace or separator characters",
+                    name
+                ));
+            }
+            //Don't print the value, since it's most likely a secret
+            if value.chars().any(|c| c.is_control() && c != '\t') {
+                return Err(anyhow!(
+                    "EE119: The value of the header \"{}\" is invalid. Header values can't \
+                     contain line breaks or other control characters",
+                    name
+                ));
+            }
+        }
+        Ok(Self(headers))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter()
+    }
+}
+
+impl std::fmt::Debug for Headers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.0.keys().map(|name| (name, "<redacted>")))
+            .finish()
+    }
+}
+
+impl From<BTreeMap<String, String>> for Headers {
+    fn from(headers: BTreeMap<String, String>) -> Self {
+        Self(headers)
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct HypersyncConfig {
+    pub endpoint_url: ServerUrl,
+    pub headers: Headers,
+    pub is_client_decoder: bool,
+}
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct HyperfuelConfig {
     pub endpoint_url: ServerUrl,
 }
 
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FileConfig {
+    pub path: String,
+    pub format: FileFormat,
+}
+
 #[derive(Debug, Serialize, PartialEq, Clone)]
 pub struct SyncConfig {
     pub initial_block_interval: u32,
@@ -893,6 +1615,8 @@ impl Default for SyncConfig {
 #[derive(Debug, Serialize, PartialEq, Clone)]
 pub struct RpcConfig {
     pub urls: Vec<String>,
+    pub ws_url: Option<String>,
+    pub headers: Headers,
     pub sync_config: SyncConfig,
 }
 
@@ -901,12 +1625,16 @@ pub enum SyncSource {
     RpcConfig(RpcConfig),
     HypersyncConfig(HypersyncConfig),
     HyperfuelConfig(HyperfuelConfig),
+    FileConfig(FileConfig),
 }
 
+const HTTP_SCHEMES: [&str; 2] = ["http://", "https://"];
+const WS_SCHEMES: [&str; 2] = ["ws://", "wss://"];
+
 // Check if the given URL is valid in terms of formatting
-fn parse_url(url: &str) -> Option<String> {
+fn parse_url_with_schemes(url: &str, schemes: &[&str]) -> Option<String> {
     // Check URL format
-    if !url.starts_with("http://") && !url.starts_with("https://") {
+    if !schemes.iter().any(|scheme| url.starts_with(scheme)) {
         return None;
     }
     // Trim any trailing slashes from the URL
@@ -914,111 +1642,294 @@ fn parse_url(url: &str) -> Option<String> {
     Some(trimmed_url)
 }
 
-impl SyncSource {
-    fn from_evm_network_config(
+fn parse_url(url: &str) -> Option<String> {
+    parse_url_with_schemes(url, &HTTP_SCHEMES)
+}
+
+// RPC endpoints can also be reached over a WebSocket
+fn parse_rpc_url(url: &str) -> Option<String> {
+    parse_url_with_schemes(url, &[HTTP_SCHEMES, WS_SCHEMES].concat())
+}
+
+fn parse_ws_url(url: &str) -> Option<String> {
+    parse_url_with_schemes(url, &WS_SCHEMES)
+}
+
+impl SyncConfig {
+    fn from_rpc_sync_config(sync_config: Option<human_config::evm::RpcSyncConfig>) -> Self {
+        match sync_config {
+            None => Self::default(),
+            Some(c) => {
+                let query_timeout_millis = c
+                    .query_timeout_millis
+                    .unwrap_or_else(|| Self::default().query_timeout_millis);
+                Self {
+                    acceleration_additive: c
+                        .acceleration_additive
+                        .unwrap_or_else(|| Self::default().acceleration_additive),
+                    backoff_millis: c
+                        .backoff_millis
+                        .unwrap_or_else(|| Self::default().backoff_millis),
+                    backoff_multiplicative: c
+                        .backoff_multiplicative
+                        .unwrap_or_else(|| Self::default().backoff_multiplicative),
+                    initial_block_interval: c
+                        .initial_block_interval
+                        .unwrap_or_else(|| Self::default().initial_block_interval),
+                    interval_ceiling: c
+                        .interval_ceiling
+                        .unwrap_or_else(|| Self::default().interval_ceiling),
+                    query_timeout_millis,
+                    fallback_stall_timeout: c
+                        .fallback_stall_timeout
+                        .unwrap_or_else(|| query_timeout_millis / 2),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum SourceRole {
+    Primary,
+    Historical,
+    Realtime,
+    Fallback,
+}
+
+impl SourceRole {
+    pub fn is_historical(&self) -> bool {
+        matches!(self, SourceRole::Primary | SourceRole::Historical)
+    }
+
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, SourceRole::Primary | SourceRole::Realtime)
+    }
+}
+
+impl From<human_config::evm::SourceRole> for SourceRole {
+    fn from(role: human_config::evm::SourceRole) -> Self {
+        match role {
+            human_config::evm::SourceRole::Historical => SourceRole::Historical,
+            human_config::evm::SourceRole::Realtime => SourceRole::Realtime,
+            human_config::evm::SourceRole::Fallback => SourceRole::Fallback,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSource {
+    pub sync_source: SyncSource,
+    pub role: SourceRole,
+    pub priority: u32,
+}
+
+impl NetworkSource {
+    fn primary(sync_source: SyncSource) -> Self {
+        Self {
+            sync_source,
+            role: SourceRole::Primary,
+            priority: 0,
+        }
+    }
+
+    ///Resolves the sources of an evm network ordered by priority.
+    ///When both hypersync_config and rpc_config are defined, the RPC
+    ///is used as a fallback for HyperSync unless configured otherwise.
+    pub fn from_evm_network_config(
         network: EvmNetwork,
         event_decoder: Option<EventDecoder>,
-    ) -> Result<Self> {
+    ) -> Result<Vec<Self>> {
         let is_client_decoder = match event_decoder {
             Some(EventDecoder::HypersyncClient) | None => true,
             Some(EventDecoder::Viem) => false,
         };
-        match network {
-            human_config::evm::Network {
-                hypersync_config: Some(_),
-                rpc_config: Some(_),
-                ..
-            } => {
-                Err(anyhow!("EE106: Cannot define both rpc_config and hypersync_config for the same network, please choose only one of them, read more in our docs https://docs.envio.dev/docs/configuration-file"))
+
+        if let Some(human_config::evm::FileConfig { path, format }) = network.file_config {
+            if network.hypersync_config.is_some() || network.rpc_config.is_some() {
+                return Err(anyhow!("EE106: Cannot define file_config together with rpc_config or hypersync_config for the same network, please choose only one of them, read more in our docs https://docs.envio.dev/docs/configuration-file"));
+            }
+            return Ok(vec![Self::primary(SyncSource::FileConfig(FileConfig {
+                path,
+                format: format.unwrap_or(FileFormat::Jsonl),
+            }))]);
+        }
+
+        let has_multiple_sources =
+            network.hypersync_config.is_some() && network.rpc_config.is_some();
+        let mut sources = vec![];
+
+        match network.hypersync_config {
+            Some(human_config::evm::HypersyncConfig {
+                url,
+                headers,
+                role,
+                priority,
+            }) => {
+                let endpoint_url = parse_url(&url).ok_or_else(|| anyhow!("EE106: The HyperSync url \"{}\" is incorrect format. The HyperSync url needs to start with either http:// or https://", url))?;
+                sources.push(Self {
+                    sync_source: SyncSource::HypersyncConfig(HypersyncConfig {
+                        endpoint_url,
+                        headers: Headers::parse(headers)?,
+                        is_client_decoder,
+                    }),
+                    role: role.map(SourceRole::from).unwrap_or(SourceRole::Primary),
+                    priority: priority.unwrap_or(0),
+                });
             }
-            human_config::evm::Network {
-              hypersync_config: None,
-              rpc_config: None,
-                ..
-            } => {
+            None if network.rpc_config.is_none() => {
                 let defualt_hypersync_endpoint = hypersync_endpoints::get_default_hypersync_endpoint(network.id.clone())
                     .context("EE106: Undefined network config, please provide rpc_config, read more in our docs https://docs.envio.dev/docs/configuration-file")?;
-                Ok(Self::HypersyncConfig(HypersyncConfig {
-                    endpoint_url: defualt_hypersync_endpoint,
-                    is_client_decoder,
-                }))
+                sources.push(Self::primary(SyncSource::HypersyncConfig(
+                    HypersyncConfig {
+                        endpoint_url: defualt_hypersync_endpoint,
+                        headers: Headers::default(),
+                        is_client_decoder,
+                    },
+                )));
             }
-            human_config::evm::Network {
-              hypersync_config: None,
-              rpc_config: Some(human_config::evm::RpcConfig {
-                url,
-                sync_config
-              }),
-              ..
-            } => {
-              let config_urls: Vec<String> = url.into();
-              let mut urls = vec![];
-              for url in config_urls.iter() {
-                match parse_url(url) {
-                    None => return Err(anyhow!("EE109: The RPC url \"{}\" is incorrect format. The RPC url needs to start with either http:// or https://", url)),
+            None => (),
+        }
+
+        if let Some(human_config::evm::RpcConfig {
+            url,
+            ws_url,
+            headers,
+            sync_config,
+            role,
+            priority,
+        }) = network.rpc_config
+        {
+            let config_urls: Vec<String> = url.into();
+            let mut urls = vec![];
+            for url in config_urls.iter() {
+                match parse_rpc_url(url) {
+                    None => return Err(anyhow!("EE109: The RPC url \"{}\" is incorrect format. The RPC url needs to start with either http://, https://, ws:// or wss://", url)),
                     Some(endpoint_url) => urls.push(endpoint_url)
                 }
-              }
-              Ok(Self::RpcConfig(RpcConfig {
-                  urls,
-                  sync_config: match sync_config {
-                      None => SyncConfig::default(),
-                      Some(c) => {
-                        let query_timeout_millis = c
-                          .query_timeout_millis
-                          .unwrap_or_else(|| SyncConfig::default().query_timeout_millis);
-                        SyncConfig {
-                          acceleration_additive: c
-                              .acceleration_additive
-                              .unwrap_or_else(|| SyncConfig::default().acceleration_additive),
-                          backoff_millis: c
-                              .backoff_millis
-                              .unwrap_or_else(|| SyncConfig::default().backoff_millis),
-                          backoff_multiplicative: c
-                              .backoff_multiplicative
-                              .unwrap_or_else(|| SyncConfig::default().backoff_multiplicative),
-                          initial_block_interval: c
-                              .initial_block_interval
-                              .unwrap_or_else(|| SyncConfig::default().initial_block_interval),
-                          interval_ceiling: c
-                              .interval_ceiling
-                              .unwrap_or_else(|| SyncConfig::default().interval_ceiling),
-                          query_timeout_millis,
-                          fallback_stall_timeout: c
-                              .fallback_stall_timeout
-                              .unwrap_or_else(|| query_timeout_millis / 2),
-                      }},
-                  },
-              }))
-            },
-            human_config::evm::Network {
-              hypersync_config: Some(human_config::evm::HypersyncConfig { url }),
-              rpc_config: None,
-              ..
-            } => {
-              match parse_url(&url) {
-                  None => Err(anyhow!("EE106: The HyperSync url \"{}\" is incorrect format. The HyperSync url needs to start with either http:// or https://", url)),
-                  Some(endpoint_url) => Ok(Self::HypersyncConfig(HypersyncConfig { endpoint_url, is_client_decoder }))
-              }
             }
+            //Subscribe to new heads over the primary url if it's already a WebSocket
+            let ws_url = match ws_url {
+                Some(ws_url) => Some(parse_ws_url(&ws_url).ok_or_else(|| anyhow!("EE109: The RPC ws_url \"{}\" is incorrect format. The ws_url needs to start with either ws:// or wss://", ws_url))?),
+                None => urls.first().and_then(|url| parse_ws_url(url)),
+            };
+            let default_role = if has_multiple_sources {
+                SourceRole::Fallback
+            } else {
+                SourceRole::Primary
+            };
+            sources.push(Self {
+                sync_source: SyncSource::RpcConfig(RpcConfig {
+                    urls,
+                    ws_url,
+                    headers: Headers::parse(headers)?,
+                    sync_config: SyncConfig::from_rpc_sync_config(sync_config),
+                }),
+                role: role.map(SourceRole::from).unwrap_or(default_role),
+                priority: priority.unwrap_or(sources.len() as u32),
+            });
+        }
+
+        //Stable sort, so sources with the same priority keep the order they are defined in
+        sources.sort_by_key(|source| source.priority);
+
+        if !sources.iter().any(|source| source.role.is_historical()) {
+            return Err(anyhow!("EE118: None of the sources of network {} can sync historical blocks. Please remove the role or set it to \"historical\" for one of the sources, read more in our docs https://docs.envio.dev/docs/configuration-file", network.id));
+        }
+        if !sources.iter().any(|source| source.role.is_realtime()) {
+            return Err(anyhow!("EE118: None of the sources of network {} can sync realtime blocks. Please remove the role or set it to \"realtime\" for one of the sources, read more in our docs https://docs.envio.dev/docs/configuration-file", network.id));
         }
+
+        Ok(sources)
     }
 }
 
+const DEFAULT_SOURCE_STALL_TIMEOUT_MILLIS: u32 = 20_000;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Network {
     pub id: u64,
-    pub sync_source: SyncSource,
+    ///Sources ordered by priority, the first one being the preferred source
+    pub sources: Vec<NetworkSource>,
+    pub source_stall_timeout: u32,
     pub start_block: u64,
     pub end_block: Option<u64>,
     pub confirmed_block_threshold: i32,
     pub contracts: Vec<NetworkContract>,
+    pub block_handlers: Vec<BlockHandler>,
+    ///The fields selected for the events of this network only
+    pub field_selection: Option<FieldSelection>,
+    ///Overrides the global enable_raw_events for this network
+    pub raw_events: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockHandler {
+    pub name: String,
+    pub interval: u64,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
+    pub handler_path: String,
+}
+
+impl BlockHandler {
+    fn from_evm_config(
+        block_handler: &human_config::evm::BlockHandlerConfig,
+        contract_handler_path: Option<&String>,
+    ) -> Result<Self> {
+        let handler_path = block_handler
+            .handler
+            .as_ref()
+            .or(contract_handler_path)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "EE113: The block handler {} is defined on a network and needs a handler \
+                     path to register it in.",
+                    block_handler.name
+                )
+            })?;
+
+        Ok(Self {
+            name: block_handler.name.clone(),
+            interval: block_handler.interval.unwrap_or(1),
+            start_block: block_handler.start_block,
+            end_block: block_handler.end_block,
+            handler_path,
+        })
+    }
+
+    pub fn from_evm_network_config(
+        network: &human_config::evm::Network,
+        evm_config: &human_config::evm::HumanConfig,
+    ) -> Result<Vec<Self>> {
+        network
+            .get_block_handler_configs(&evm_config.contracts)
+            .into_iter()
+            .map(|(block_handler, contract_handler_path)| {
+                Self::from_evm_config(block_handler, contract_handler_path)
+            })
+            .collect()
+    }
+
+    pub fn get_path_to_handler(&self, project_paths: &ParsedProjectPaths) -> Result<PathBuf> {
+        path_utils::get_config_path_relative_to_root(
+            project_paths,
+            PathBuf::from(&self.handler_path),
+        )
+        .context(format!(
+            "Failed creating a relative path to handler in block handler {}",
+            self.name
+        ))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct NetworkContract {
     pub name: ContractNameKey,
     pub addresses: Vec<String>,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
 }
 
 impl NetworkContract {
@@ -1065,6 +1976,23 @@ impl EvmAbi {
         )
     }
 
+    ///Adds the functions of the configured calls, so they can be decoded with the contract abi
+    pub fn with_calls(mut self, calls: &[Call]) -> Result<Self> {
+        for call in calls {
+            let functions = self
+                .typed
+                .functions
+                .entry(call.function.name.clone())
+                .or_default();
+            if !functions.contains(&call.function) {
+                functions.push(call.function.clone());
+            }
+        }
+        self.raw = serde_json::to_string(&self.typed)
+            .context("Failed serializing ABI with functions from calls")?;
+        Ok(self)
+    }
+
     pub fn get_event_signatures(&self) -> Vec<String> {
         self.typed
             .events()
@@ -1136,24 +2064,127 @@ pub struct Contract {
     pub handler_path: String,
     pub abi: Abi,
     pub events: Vec<Event>,
+    pub calls: Vec<Call>,
+    pub factory: Option<Factory>,
+}
+
+///A factory contract event which registers the addresses of a contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factory {
+    pub contract_name: ContractNameKey,
+    pub event_name: String,
+    pub address_param: String,
 }
 
 impl Contract {
-    pub fn new(name: String, handler_path: String, events: Vec<Event>, abi: Abi) -> Result<Self> {
+    pub fn new(
+        name: String,
+        handler_path: String,
+        events: Vec<Event>,
+        calls: Vec<Call>,
+        abi: Abi,
+    ) -> Result<Self> {
         // TODO: Validatate that all event names are unique
         validate_names_valid_rescript(
             &events.iter().map(|e| e.name.clone()).collect(),
             "event".to_string(),
         )?;
 
+        let call_names = calls.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        validate_names_valid_rescript(&call_names, "call".to_string())?;
+
+        let duplicate_call_names: Vec<_> = call_names.iter().duplicates().collect();
+        if !duplicate_call_names.is_empty() {
+            return Err(anyhow!(
+                "EE116: The contract {} has duplicate call names: {}. Call names should be unique \
+                 per contract, use the name field to rename one of them.",
+                name,
+                duplicate_call_names.iter().join(", ")
+            ));
+        }
+
         Ok(Self {
             name,
             events,
+            calls,
             handler_path,
             abi,
+            factory: None,
         })
     }
 
+    pub fn with_factory(self, factory: Option<human_config::evm::FactoryConfig>) -> Self {
+        Self {
+            factory: factory.map(|factory| Factory {
+                contract_name: factory.contract,
+                event_name: factory.event,
+                address_param: factory.address_param,
+            }),
+            ..self
+        }
+    }
+
+    ///Checks that the factory event exists and has an address param for the contract
+    fn validate_factory(&self, contracts: &HashMap<ContractNameKey, Contract>) -> Result<()> {
+        let factory = match &self.factory {
+            Some(factory) => factory,
+            None => return Ok(()),
+        };
+
+        let factory_contract = contracts.get(&factory.contract_name).ok_or_else(|| {
+            anyhow!(
+                "EE120: The factory contract \"{}\" of the contract \"{}\" is not defined in \
+                 the config",
+                factory.contract_name,
+                self.name
+            )
+        })?;
+
+        let event = factory_contract
+            .events
+            .iter()
+            .find(|event| event.name == factory.event_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "EE120: The event \"{}\" is not configured on the factory contract \"{}\". \
+                     Please add it to the events of the contract, so it can register the \
+                     \"{}\" contract",
+                    factory.event_name,
+                    factory.contract_name,
+                    self.name
+                )
+            })?;
+
+        let param = match &event.kind {
+            EventKind::Params(params) => params
+                .iter()
+                .find(|param| param.name == factory.address_param),
+            EventKind::Fuel(_) => None,
+        }
+        .ok_or_else(|| {
+            anyhow!(
+                "EE120: The event \"{}\" of the factory contract \"{}\" doesn't have the \
+                 param \"{}\"",
+                factory.event_name,
+                factory.contract_name,
+                factory.address_param
+            )
+        })?;
+
+        if param.kind != ParamType::Address {
+            return Err(anyhow!(
+                "EE120: The param \"{}\" of the factory event \"{}\" should be an address to \
+                 register the \"{}\" contract, but it's {}",
+                factory.address_param,
+                factory.event_name,
+                self.name,
+                param.kind
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn get_path_to_handler(&self, project_paths: &ParsedProjectPaths) -> Result<PathBuf> {
         let handler_path = path_utils::get_config_path_relative_to_root(
             project_paths,
@@ -1180,6 +2211,114 @@ impl Contract {
             })
             .collect()
     }
+
+    ///Addresses of the contract on all the networks, without duplicates
+    pub fn get_addresses(&self, system_config: &SystemConfig) -> Vec<String> {
+        let mut addresses: Vec<String> = vec![];
+        for network in system_config.get_networks() {
+            for network_contract in network.contracts.iter().filter(|c| c.name == self.name) {
+                for address in &network_contract.addresses {
+                    if !addresses.contains(address) {
+                        addresses.push(address.clone());
+                    }
+                }
+            }
+        }
+        addresses
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub name: String,
+    pub sighash: String,
+    pub function: EthAbiFunction,
+}
+
+impl Call {
+    fn get_abi_function(call_string: &str, opt_abi: &Option<EvmAbi>) -> Result<EthAbiFunction> {
+        let parse_function_sig = |sig: &str| -> Result<EthAbiFunction> {
+            match HumanReadableParser::parse_function(sig) {
+                Ok(function) => Ok(function),
+                Err(err) => Err(anyhow!(
+                    "EE115: Unable to parse function signature {} due to the following error: \
+                     {}. Please refer to our docs on how to correctly define a human readable \
+                     ABI.",
+                    sig,
+                    err
+                )),
+            }
+        };
+
+        let call_string = call_string.trim();
+
+        if call_string.starts_with("function ") {
+            parse_function_sig(call_string)
+        } else if call_string.contains('(') {
+            let signature = format!("function {}", call_string);
+            parse_function_sig(&signature)
+        } else {
+            match opt_abi {
+                Some(abi) => {
+                    let function = abi.typed.function(call_string).context(format!(
+                        "Failed retrieving function {} from abi",
+                        call_string
+                    ))?;
+                    Ok(function.clone())
+                }
+                None => Err(anyhow!("No abi file provided for call {}", call_string)),
+            }
+        }
+    }
+
+    // Same as for events, unnamed params are named by their position,
+    // so the decoded inputs and outputs are records instead of arrays
+    fn normalize_unnamed_params(params: Vec<Param>) -> Vec<Param> {
+        params
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let name = if p.name.is_empty() {
+                    format!("_{}", i)
+                } else {
+                    p.name
+                };
+                Param { name, ..p }
+            })
+            .collect()
+    }
+
+    pub fn from_evm_calls_config(
+        calls_config: Vec<EvmCallConfig>,
+        abi_file_path: &Option<String>,
+        project_paths: &ParsedProjectPaths,
+    ) -> Result<Vec<Self>> {
+        if calls_config.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let abi_from_file = EvmAbi::from_file(abi_file_path, project_paths)?;
+
+        calls_config
+            .iter()
+            .map(|call_config| {
+                let mut function = Call::get_abi_function(&call_config.call, &abi_from_file)?;
+                let sighash = ethers::core::utils::hex::encode_prefixed(function.short_signature());
+
+                function.inputs = Self::normalize_unnamed_params(function.inputs);
+                function.outputs = Self::normalize_unnamed_params(function.outputs);
+
+                Ok(Call {
+                    name: call_config
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| function.name.clone()),
+                    sighash,
+                    function,
+                })
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -1203,10 +2342,13 @@ pub struct Event {
     pub name: String,
     pub sighash: String,
     pub field_selection: Option<FieldSelection>,
+    /// Encoded values of the config filters for every indexed param,
+    /// in the order of the topics. Empty when the param isn't filtered
+    pub topic_filters: Vec<Vec<String>>,
 }
 
 impl Event {
-    fn get_abi_event(event_string: &String, opt_abi: &Option<EvmAbi>) -> Result<EthAbiEvent> {
+    fn get_abi_event(event_string: &str, opt_abi: &Option<EvmAbi>) -> Result<EthAbiEvent> {
         let parse_event_sig = |sig: &str| -> Result<EthAbiEvent> {
             match HumanReadableParser::parse_event(sig) {
                 Ok(event) => Ok(event),
@@ -1240,6 +2382,62 @@ impl Event {
         }
     }
 
+    fn get_topic_filters(
+        filters: &Option<BTreeMap<String, SingleOrList<TopicFilterValue>>>,
+        params: &[EventParam],
+        event_name: &str,
+    ) -> Result<Vec<Vec<String>>> {
+        let filters = match filters {
+            Some(filters) => filters,
+            None => return Ok(vec![]),
+        };
+
+        for param_name in filters.keys() {
+            match params.iter().find(|param| &param.name == param_name) {
+                None => {
+                    return Err(anyhow!(
+                        "EE121: The filter \"{}\" of the event \"{}\" doesn't match any of the \
+                         event params.",
+                        param_name,
+                        event_name
+                    ))
+                }
+                Some(param) if !param.indexed => {
+                    return Err(anyhow!(
+                        "EE121: The filter \"{}\" of the event \"{}\" isn't an indexed param. \
+                         Only indexed params can be filtered.",
+                        param_name,
+                        event_name
+                    ))
+                }
+                Some(_) => (),
+            }
+        }
+
+        params
+            .iter()
+            .filter(|param| param.indexed)
+            .map(|param| match filters.get(&param.name) {
+                None => Ok(vec![]),
+                Some(values) => {
+                    let values: Vec<TopicFilterValue> = values.clone().into();
+                    values
+                        .iter()
+                        .map(|value| EthereumEventParam::from(param).encode_topic_filter(value))
+                        .collect::<Result<Vec<_>>>()
+                        .map_err(|err| {
+                            anyhow!(
+                                "EE121: Invalid filter \"{}\" of the event \"{}\". {}",
+                                param.name,
+                                event_name,
+                                err
+                            )
+                        })
+                }
+            })
+            .collect()
+    }
+
     pub fn from_evm_events_config(
         events_config: Vec<EvmEventConfig>,
         abi_file_path: &Option<String>,
@@ -1280,6 +2478,9 @@ impl Event {
             // instead of an object with named fields.
             event.inputs = normalized_unnamed_params.clone();
 
+            let topic_filters =
+                Self::get_topic_filters(&event_config.filters, &normalized_unnamed_params, &name)?;
+
             events_abi.events.entry(abi_name).or_default().push(event);
             events.push(Event {
                 name,
@@ -1294,6 +2495,7 @@ impl Event {
                     }
                     None => None,
                 },
+                topic_filters,
             })
         }
 
@@ -1314,15 +2516,15 @@ impl Event {
     }
 
     pub fn from_fuel_events_config(
-        events_config: &Vec<FuelEventConfig>,
-        abi_file_path: &String,
+        events_config: &[FuelEventConfig],
+        abi_file_path: &str,
         project_paths: &ParsedProjectPaths,
     ) -> Result<(Vec<Self>, FuelAbi)> {
         use human_config::fuel::EventType;
 
         let abi_path: PathBuf = path_utils::get_config_path_relative_to_root(
             project_paths,
-            PathBuf::from(&abi_file_path),
+            PathBuf::from(abi_file_path),
         )
         .context("Failed to get path to ABI relative to the root of the project")?;
         let fuel_abi = FuelAbi::parse(abi_path).context(format!("Failed to parse ABI",))?;
@@ -1373,6 +2575,7 @@ impl Event {
                         kind: EventKind::Fuel(FuelEventKind::LogData(log.data_type)),
                         sighash: log.id,
                         field_selection: None,
+                        topic_filters: vec![],
                     }
                 }
                 EventType::Mint => Event {
@@ -1380,24 +2583,28 @@ impl Event {
                     kind: EventKind::Fuel(FuelEventKind::Mint),
                     sighash: "mint".to_string(),
                     field_selection: None,
+                    topic_filters: vec![],
                 },
                 EventType::Burn => Event {
                     name: event_config.name.clone(),
                     kind: EventKind::Fuel(FuelEventKind::Burn),
                     sighash: "burn".to_string(),
                     field_selection: None,
+                    topic_filters: vec![],
                 },
                 EventType::Transfer => Event {
                     name: event_config.name.clone(),
                     kind: EventKind::Fuel(FuelEventKind::Transfer),
                     sighash: "transfer".to_string(),
                     field_selection: None,
+                    topic_filters: vec![],
                 },
                 EventType::Call => Event {
                     name: event_config.name.clone(),
                     kind: EventKind::Fuel(FuelEventKind::Call),
                     sighash: "call".to_string(),
                     field_selection: None,
+                    topic_filters: vec![],
                 },
             };
 
@@ -1433,6 +2640,38 @@ impl FieldSelection {
         Self::new(vec![], vec![])
     }
 
+    ///Adds the fields selected per network which aren't already selected. They're optional,
+    ///since the events of the other networks don't have them
+    pub fn with_network_fields(mut self, network_field_selections: &[FieldSelection]) -> Self {
+        fn add_optional_fields(fields: &mut Vec<SelectedField>, network_fields: &[SelectedField]) {
+            for network_field in network_fields {
+                if fields.iter().any(|field| field.name == network_field.name) {
+                    continue;
+                }
+                let data_type = match &network_field.data_type {
+                    RescriptTypeIdent::Option(_) => network_field.data_type.clone(),
+                    data_type => RescriptTypeIdent::option(data_type.clone()),
+                };
+                fields.push(SelectedField {
+                    name: network_field.name.clone(),
+                    data_type,
+                });
+            }
+        }
+
+        for network_field_selection in network_field_selections {
+            add_optional_fields(
+                &mut self.transaction_fields,
+                &network_field_selection.transaction_fields,
+            );
+            add_optional_fields(
+                &mut self.block_fields,
+                &network_field_selection.block_fields,
+            );
+        }
+        self
+    }
+
     pub fn fuel() -> Self {
         Self::new(
             vec![SelectedField {
@@ -1556,7 +2795,7 @@ impl FieldSelection {
                 Block::ExcessBlobGas => Res::option(Res::BigInt),
                 Block::ParentBeaconBlockRoot => Res::option(Res::String),
                 Block::WithdrawalsRoot => Res::option(Res::String),
-                // Block::Withdrawals => todo!(), //should be array of withdrawal record
+                Block::Withdrawals => Res::option(Res::array(Res::evm_withdrawal())),
                 Block::L1BlockNumber => Res::option(Res::Int),
                 Block::SendCount => Res::option(Res::String),
                 Block::SendRoot => Res::option(Res::String),
@@ -1595,7 +2834,7 @@ impl FieldSelection {
                 Tx::Status => Res::option(Res::Int),
                 Tx::YParity => Res::option(Res::String),
                 Tx::ChainId => Res::option(Res::Int),
-                // TxField::AccessList => todo!(),
+                Tx::AccessList => Res::option(Res::array(Res::evm_access_list_item())),
                 Tx::MaxFeePerBlobGas => Res::option(Res::BigInt),
                 Tx::BlobVersionedHashes => Res::option(Res::array(Res::String)),
                 Tx::Kind => Res::option(Res::Int),
@@ -1604,6 +2843,7 @@ impl FieldSelection {
                 Tx::L1GasUsed => Res::option(Res::BigInt),
                 Tx::L1FeeScalar => Res::option(Res::Float),
                 Tx::GasUsedForL1 => Res::option(Res::BigInt),
+                Tx::Logs => Res::array(Res::evm_log()),
             };
             selected_transaction_fields.push(SelectedField {
                 name: transaction_field.to_string(),
@@ -1626,10 +2866,14 @@ mod test {
     use crate::{
         config_parsing::{
             human_config::evm::HumanConfig as EvmConfig,
-            system_config::{Event, SyncConfig, SyncSource},
+            system_config::{
+                BlockHandler, Contract, Event, Factory, Headers, HypersyncConfig, NetworkSource,
+                RpcConfig, SourceRole, SyncConfig, SyncSource,
+            },
         },
         project_paths::ParsedProjectPaths,
     };
+    use anyhow::anyhow;
     use ethers::abi::{Event as EthAbiEvent, EventParam, ParamType};
     use handlebars::Handlebars;
     use pretty_assertions::assert_eq;
@@ -1806,6 +3050,114 @@ mod test {
         );
     }
 
+    #[test]
+    fn get_topic_filters_validates_and_encodes_config_filters() {
+        let event = Event::get_abi_event(
+            "Transfer(address indexed from, address indexed to, uint256 value)",
+            &None,
+        )
+        .unwrap();
+        let event_name = "Transfer".to_string();
+        let get_topic_filters = |filters: &str| {
+            Event::get_topic_filters(
+                &Some(serde_yaml::from_str(filters).unwrap()),
+                &event.inputs,
+                &event_name,
+            )
+        };
+
+        assert_eq!(
+            get_topic_filters(
+                r#"
+to:
+  - "0x0000000000000000000000000000000000000001"
+  - "0x0000000000000000000000000000000000000002"
+"#
+            )
+            .unwrap(),
+            vec![
+                vec![],
+                vec![
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                        .to_string(),
+                    "0x0000000000000000000000000000000000000000000000000000000000000002"
+                        .to_string(),
+                ]
+            ]
+        );
+        assert_eq!(
+            Event::get_topic_filters(&None, &event.inputs, &event_name).unwrap(),
+            Vec::<Vec<String>>::new()
+        );
+
+        assert_eq!(
+            get_topic_filters("sender: \"0x0000000000000000000000000000000000000001\"")
+                .unwrap_err()
+                .to_string(),
+            "EE121: The filter \"sender\" of the event \"Transfer\" doesn't match any of the \
+             event params."
+        );
+        assert_eq!(
+            get_topic_filters("value: 1").unwrap_err().to_string(),
+            "EE121: The filter \"value\" of the event \"Transfer\" isn't an indexed param. \
+             Only indexed params can be filtered."
+        );
+        assert_eq!(
+            get_topic_filters("from: 1").unwrap_err().to_string(),
+            "EE121: Invalid filter \"from\" of the event \"Transfer\". Invalid value 1 for \
+             the address param"
+        );
+    }
+
+    #[test]
+    fn parses_call_sig_and_names_unnamed_outputs() {
+        use crate::config_parsing::human_config::evm::CallConfig;
+        use ethers::abi::{Param, StateMutability};
+
+        let calls = super::Call::from_evm_calls_config(
+            vec![CallConfig {
+                call: "transfer(address to, uint256 amount) returns (bool)".to_string(),
+                name: None,
+            }],
+            &None,
+            &ParsedProjectPaths::default(),
+        )
+        .unwrap();
+
+        #[allow(deprecated)]
+        let expected_function = super::EthAbiFunction {
+            name: "transfer".to_string(),
+            inputs: vec![
+                Param {
+                    name: "to".to_string(),
+                    kind: ParamType::Address,
+                    internal_type: None,
+                },
+                Param {
+                    name: "amount".to_string(),
+                    kind: ParamType::Uint(256),
+                    internal_type: None,
+                },
+            ],
+            outputs: vec![Param {
+                name: "_0".to_string(),
+                kind: ParamType::Bool,
+                internal_type: None,
+            }],
+            constant: None,
+            state_mutability: StateMutability::NonPayable,
+        };
+
+        assert_eq!(
+            calls,
+            vec![super::Call {
+                name: "transfer".to_string(),
+                sighash: "0xa9059cbb".to_string(),
+                function: expected_function,
+            }]
+        );
+    }
+
     #[test]
     fn fails_to_parse_event_name_without_abi() {
         let event_string = ("MyEvent").to_string();
@@ -1831,6 +3183,17 @@ mod test {
         assert_eq!(super::parse_url(invalid_url_missing_slash), None);
         assert_eq!(super::parse_url(invalid_url_other_protocol), None);
 
+        // WebSocket urls are only valid for RPC endpoints
+        let ws_url = "wss://eth-mainnet.g.alchemy.com/v2/T7uPV59s7knYTOUardPPX0hq7n7_rQwv";
+        assert_eq!(super::parse_url(ws_url), None);
+        assert_eq!(super::parse_rpc_url(ws_url), Some(ws_url.to_string()));
+        assert_eq!(
+            super::parse_rpc_url(valid_url_1),
+            Some(valid_url_1.to_string())
+        );
+        assert_eq!(super::parse_ws_url(ws_url), Some(ws_url.to_string()));
+        assert_eq!(super::parse_ws_url(valid_url_1), None);
+
         // With trailing slashes
         assert_eq!(
             super::parse_url("https://somechain.hypersync.xyz/"),
@@ -1845,7 +3208,7 @@ mod test {
     #[test]
     fn deserializes_contract_config_with_multiple_sync_sources() {
         let config_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("test/configs/invalid-multiple-sync-config.yaml");
+            .join("test/configs/multiple-sync-config.yaml");
 
         let file_str = std::fs::read_to_string(config_path).unwrap();
 
@@ -1855,10 +3218,533 @@ mod test {
         assert!(cfg.networks[0].rpc_config.is_some());
         assert!(cfg.networks[0].hypersync_config.is_some());
 
-        let error = SyncSource::from_evm_network_config(cfg.networks[0].clone(), cfg.event_decoder)
+        let sources =
+            NetworkSource::from_evm_network_config(cfg.networks[0].clone(), cfg.event_decoder)
+                .unwrap();
+
+        assert_eq!(
+            sources,
+            vec![
+                NetworkSource {
+                    sync_source: SyncSource::HypersyncConfig(HypersyncConfig {
+                        endpoint_url: "https://myskar.com".to_string(),
+                        headers: Headers::default(),
+                        is_client_decoder: true,
+                    }),
+                    role: SourceRole::Primary,
+                    priority: 0,
+                },
+                NetworkSource {
+                    sync_source: SyncSource::RpcConfig(RpcConfig {
+                        urls: vec!["https://eth.com".to_string()],
+                        ws_url: None,
+                        headers: Headers::default(),
+                        sync_config: SyncConfig::default(),
+                    }),
+                    role: SourceRole::Fallback,
+                    priority: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn orders_sync_sources_by_priority_and_validates_roles() {
+        use crate::config_parsing::human_config::evm::{
+            HypersyncConfig as EvmHypersyncConfig, Network as EvmNetwork,
+            RpcConfig as EvmRpcConfig, SourceRole as EvmSourceRole,
+        };
+
+        let network = EvmNetwork {
+            id: 1,
+            hypersync_config: Some(EvmHypersyncConfig {
+                url: "https://somechain.hypersync.xyz".to_string(),
+                headers: None,
+                role: Some(EvmSourceRole::Historical),
+                priority: Some(2),
+            }),
+            rpc_config: Some(EvmRpcConfig {
+                url: "https://somechain.com".to_string().into(),
+                ws_url: None,
+                headers: None,
+                sync_config: None,
+                role: Some(EvmSourceRole::Realtime),
+                priority: None,
+            }),
+            file_config: None,
+            source_stall_timeout: None,
+            start_block: 0,
+            end_block: None,
+            confirmed_block_threshold: None,
+            contracts: vec![],
+            block_handlers: None,
+            field_selection: None,
+            raw_events: None,
+        };
+
+        let sources = NetworkSource::from_evm_network_config(network, None).unwrap();
+
+        assert_eq!(
+            sources
+                .iter()
+                .map(|source| (source.role.clone(), source.priority))
+                .collect::<Vec<_>>(),
+            vec![(SourceRole::Realtime, 1), (SourceRole::Historical, 2)]
+        );
+
+        let config_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test/configs/invalid-multiple-sync-config.yaml");
+        let cfg: EvmConfig =
+            serde_yaml::from_str(&std::fs::read_to_string(config_path).unwrap()).unwrap();
+
+        let error =
+            NetworkSource::from_evm_network_config(cfg.networks[0].clone(), cfg.event_decoder)
+                .unwrap_err();
+
+        assert_eq!(error.to_string(), "EE118: None of the sources of network 1 can sync realtime blocks. Please remove the role or set it to \"realtime\" for one of the sources, read more in our docs https://docs.envio.dev/docs/configuration-file");
+    }
+
+    #[test]
+    fn test_factory_contract_config() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/factory-contract-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let config =
+            SystemConfig::parse_from_project_files(&project_paths).expect("Failed parsing config");
+
+        let pool = config.get_contract(&"Pool".to_string()).unwrap();
+        assert_eq!(
+            pool.factory,
+            Some(Factory {
+                contract_name: "Factory".to_string(),
+                event_name: "PoolCreated".to_string(),
+                address_param: "pool".to_string(),
+            })
+        );
+
+        let with_factory = |event_name: &str, address_param: &str| Contract {
+            factory: Some(Factory {
+                contract_name: "Factory".to_string(),
+                event_name: event_name.to_string(),
+                address_param: address_param.to_string(),
+            }),
+            ..pool.clone()
+        };
+        let validate = |contract: Contract| {
+            contract
+                .validate_factory(&config.contracts)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            validate(with_factory("Swap", "pool")),
+            "EE120: The event \"Swap\" is not configured on the factory contract \"Factory\". \
+             Please add it to the events of the contract, so it can register the \"Pool\" \
+             contract"
+        );
+        assert_eq!(
+            validate(with_factory("PoolCreated", "pair")),
+            "EE120: The event \"PoolCreated\" of the factory contract \"Factory\" doesn't have \
+             the param \"pair\""
+        );
+        assert_eq!(
+            validate(with_factory("PoolCreated", "fee")),
+            "EE120: The param \"fee\" of the factory event \"PoolCreated\" should be an address \
+             to register the \"Pool\" contract, but it's uint24"
+        );
+    }
+
+    #[test]
+    fn test_block_handlers_from_contract_and_network() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/block-handlers-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let config =
+            SystemConfig::parse_from_project_files(&project_paths).expect("Failed parsing config");
+
+        let expected = vec![
+            BlockHandler {
+                name: "Snapshot".to_string(),
+                interval: 1,
+                start_block: Some(1000),
+                end_block: Some(2000),
+                handler_path: "./src/EventHandler.js".to_string(),
+            },
+            BlockHandler {
+                name: "EveryHundredBlocks".to_string(),
+                interval: 100,
+                start_block: None,
+                end_block: None,
+                handler_path: "./src/BlockHandlers.js".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            config.get_block_handlers(),
+            expected.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_calls_only_on_hypersync_networks() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths =
+            ParsedProjectPaths::new(test_dir.as_str(), "generated/", "configs/calls-config.yaml")
+                .expect("Failed creating parsed_paths");
+
+        let config =
+            SystemConfig::parse_from_project_files(&project_paths).expect("Failed parsing config");
+
+        let contract = config.get_contract(&"Contract1".to_string()).unwrap();
+        assert_eq!(
+            contract
+                .calls
+                .iter()
+                .map(|call| (call.name.as_str(), call.sighash.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("transfer", "0xa9059cbb"), ("Approval", "0x095ea7b3")]
+        );
+        //The functions are added to the abi, so the calls can be decoded with it
+        let Abi::Evm(abi) = &contract.abi else {
+            panic!("Expected an evm abi");
+        };
+        assert!(abi.typed.function("transfer").is_ok());
+        assert!(abi.typed.function("approve").is_ok());
+
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/invalid-calls-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let err = SystemConfig::parse_from_project_files(&project_paths).unwrap_err();
+        assert!(format!("{:#}", err).contains(
+            "EE126: The contract \"Contract1\" has calls, which are only fetched from HyperSync"
+        ));
+    }
+
+    #[test]
+    fn test_addresses_from_files() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/address-files-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let config =
+            SystemConfig::parse_from_project_files(&project_paths).expect("Failed parsing config");
+
+        let network = config.networks.get(&1).unwrap();
+        let addresses = network
+            .contracts
+            .iter()
+            .map(|c| (c.name.as_str(), c.addresses.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            addresses,
+            vec![
+                (
+                    "Factory",
+                    vec!["0x1F98431c8aD98523631AE4a59f267346ea31F984".to_string()]
+                ),
+                (
+                    "Pool",
+                    vec![
+                        "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".to_string(),
+                        "0xCBCdF9626bC03E24f779434178A73a0B4bad62eD".to_string(),
+                    ]
+                ),
+            ]
+        );
+
+        assert_eq!(
+            config.get_all_paths_to_address_files().unwrap(),
+            vec![
+                PathBuf::from(&test_dir).join("configs/addresses/deployments.json"),
+                PathBuf::from(&test_dir).join("configs/addresses/pools.csv"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_secrets_are_masked() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths =
+            ParsedProjectPaths::new(test_dir.as_str(), "generated/", "configs/config1.yaml")
+                .expect("Failed creating parsed_paths");
+
+        let mut secrets = super::Secrets::default();
+        let secret = secrets
+            .read_file("./secrets/rpc_url", &project_paths)
+            .unwrap();
+        assert_eq!(secret, "https://eth.rpc.com/3f1a9c");
+        assert!(secrets
+            .read_file("./secrets/missing", &project_paths)
+            .is_err());
+
+        assert_eq!(
+            secrets.mask(&format!("The url {} is invalid", secret)),
+            "The url <secret> is invalid"
+        );
+
+        //The error is kept as is and only masked when it's printed
+        let err =
+            anyhow!("EE106: The url {} is invalid", secret).context("Failed parsing network 1");
+        assert_eq!(err.chain().count(), 2);
+        assert_eq!(
+            secrets.mask(&format!("{:#}", err)),
+            "Failed parsing network 1: EE106: The url <secret> is invalid"
+        );
+    }
+
+    #[test]
+    fn test_secrets_are_collected_when_parsing_fails() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/invalid-secret-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let mut secrets = super::Secrets::default();
+        let err = SystemConfig::parse_from_project_files_with_secrets(&project_paths, &mut secrets)
             .unwrap_err();
+        assert!(format!("{:#}", err).contains("EE118"));
+        assert_eq!(
+            secrets.mask("Failed fetching https://eth.rpc.com/3f1a9c"),
+            "Failed fetching <secret>"
+        );
+    }
+
+    #[test]
+    fn test_interpolated_endpoints_are_secrets() {
+        let config_str = r#"
+name: config
+networks:
+  - id: 1
+    start_block: 0
+    hypersync_config:
+      url: https://1.hypersync.xyz
+    rpc_config:
+      url: https://eth.rpc.com/my-api-key
+      headers:
+        x-api-key: my-header-key
+    contracts: []
+"#;
+        let human_config = crate::config_parsing::human_config::HumanConfig::Evm(
+            serde_yaml::from_str(config_str).unwrap(),
+        );
+
+        let mut secrets = super::Secrets::default();
+        secrets.add_env_values(vec![
+            "my-api-key".to_string(),
+            "my-header-key".to_string(),
+            //Not used by an endpoint, so it isn't a secret
+            "config".to_string(),
+        ]);
+        secrets.add_interpolated_endpoints(&human_config);
+
+        assert_eq!(
+            secrets.mask(
+                "The url https://eth.rpc.com/my-api-key with the key my-header-key failed for \
+                 the config at https://1.hypersync.xyz"
+            ),
+            "The url <secret> with the key <secret> failed for the config at \
+             https://1.hypersync.xyz"
+        );
+    }
+
+    #[test]
+    fn test_included_files() {
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/include-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let config =
+            SystemConfig::parse_from_project_files(&project_paths).expect("Failed parsing config");
+
+        let networks = config
+            .get_networks()
+            .iter()
+            .map(|n| {
+                (
+                    n.id,
+                    n.contracts
+                        .iter()
+                        .map(|c| c.name.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            networks,
+            vec![
+                (1, vec!["Greeter"]),
+                (10, vec!["Greeter", "Token"]),
+                (137, vec!["Token"]),
+            ]
+        );
+        assert_eq!(
+            config
+                .get_contracts()
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Greeter", "Token"]
+        );
+    }
+
+    #[test]
+    fn test_network_field_selection() {
+        use crate::{
+            config_parsing::human_config::evm::TransactionField, rescript_types::RescriptTypeIdent,
+        };
+
+        let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths = ParsedProjectPaths::new(
+            test_dir.as_str(),
+            "generated/",
+            "configs/network-field-selection-config.yaml",
+        )
+        .expect("Failed creating parsed_paths");
+
+        let config =
+            SystemConfig::parse_from_project_files(&project_paths).expect("Failed parsing config");
+
+        let networks = config.get_networks();
+        assert_eq!(networks[0].field_selection, None);
+        assert_eq!(networks[0].raw_events, None);
+        assert_eq!(networks[1].raw_events, Some(true));
+        assert_eq!(
+            networks[1]
+                .field_selection
+                .as_ref()
+                .unwrap()
+                .transaction_fields
+                .len(),
+            3
+        );
+
+        //The fields of the network are optional in the generated types
+        let transaction_fields = config
+            .with_network_fields(&config.field_selection)
+            .transaction_fields
+            .iter()
+            .map(|field| (field.name.clone(), field.data_type.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transaction_fields,
+            vec![
+                (
+                    TransactionField::Hash.to_string(),
+                    RescriptTypeIdent::String
+                ),
+                (
+                    TransactionField::L1Fee.to_string(),
+                    RescriptTypeIdent::option(RescriptTypeIdent::BigInt)
+                ),
+                (
+                    TransactionField::GasUsedForL1.to_string(),
+                    RescriptTypeIdent::option(RescriptTypeIdent::BigInt)
+                ),
+            ]
+        );
+
+        //The sources of each network only query the fields selected for it
+        assert_eq!(
+            config.field_selection.transaction_fields.len(),
+            1,
+            "The global selection shouldn't have the fields of the networks"
+        );
+        let other_network_fields = config.get_other_network_fields(networks[0]);
+        assert_eq!(
+            other_network_fields
+                .transaction_fields
+                .iter()
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                TransactionField::L1Fee.to_string(),
+                TransactionField::GasUsedForL1.to_string(),
+            ]
+        );
+        assert_eq!(other_network_fields.block_fields, vec![]);
+        assert_eq!(
+            config.get_other_network_fields(networks[1]),
+            super::FieldSelection::empty()
+        );
 
-        assert_eq!(error.to_string(), "EE106: Cannot define both rpc_config and hypersync_config for the same network, please choose only one of them, read more in our docs https://docs.envio.dev/docs/configuration-file");
+        //The L1 fee fields can't be selected on the RPC network
+        let SystemConfig {
+            human_config,
+            schema,
+            ..
+        } = config;
+        let super::HumanConfig::Evm(mut evm_config) = human_config else {
+            panic!("Expected an evm config");
+        };
+        evm_config.networks[0].field_selection = evm_config.networks[1].field_selection.clone();
+        let err = SystemConfig::from_human_config(
+            super::HumanConfig::Evm(evm_config),
+            schema,
+            &project_paths,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed parsing the field selection of network 1"));
+    }
+
+    #[test]
+    fn test_transaction_logs_field_selection() {
+        use super::{FieldSelection, SelectedField};
+        use crate::{
+            config_parsing::human_config::evm::{
+                FieldSelection as HumanFieldSelection, TransactionField,
+            },
+            rescript_types::RescriptTypeIdent,
+        };
+
+        //The logs come from the transaction receipt, so they are available with RPC too
+        let field_selection = FieldSelection::try_from_config_field_selection(
+            HumanFieldSelection {
+                transaction_fields: Some(vec![TransactionField::Hash, TransactionField::Logs]),
+                block_fields: None,
+            },
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            field_selection.transaction_fields,
+            vec![
+                SelectedField {
+                    name: TransactionField::Hash.to_string(),
+                    data_type: RescriptTypeIdent::String,
+                },
+                SelectedField {
+                    name: TransactionField::Logs.to_string(),
+                    data_type: RescriptTypeIdent::array(RescriptTypeIdent::evm_log()),
+                },
+            ]
+        );
     }
 
     #[test]
@@ -1869,21 +3755,188 @@ mod test {
             id: 1,
             hypersync_config: Some(HypersyncConfig {
                 url: "https://somechain.hypersync.xyz//".to_string(),
+                headers: None,
+                role: None,
+                priority: None,
             }),
             rpc_config: None,
+            file_config: None,
+            source_stall_timeout: None,
             start_block: 0,
             end_block: None,
             confirmed_block_threshold: None,
             contracts: vec![],
+            block_handlers: None,
+            field_selection: None,
+            raw_events: None,
         };
 
-        let sync_source = SyncSource::from_evm_network_config(network, None).unwrap();
+        let sources = NetworkSource::from_evm_network_config(network, None).unwrap();
 
-        match sync_source {
+        match &sources[0].sync_source {
             SyncSource::HypersyncConfig(config) => {
                 assert_eq!(config.endpoint_url, "https://somechain.hypersync.xyz");
             }
             _ => panic!("Expected HypersyncConfig"),
         }
     }
+
+    #[test]
+    fn test_endpoint_headers() {
+        use crate::config_parsing::human_config::evm::{
+            HypersyncConfig as EvmHypersyncConfig, Network as EvmNetwork, RpcConfig as EvmRpcConfig,
+        };
+        use std::collections::BTreeMap;
+
+        let hypersync_headers =
+            BTreeMap::from([("Authorization".to_string(), "Bearer my-token".to_string())]);
+        let rpc_headers = BTreeMap::from([
+            ("Authorization".to_string(), "Bearer my-token".to_string()),
+            ("x-api-key".to_string(), "my-key".to_string()),
+        ]);
+        let network = EvmNetwork {
+            id: 1,
+            hypersync_config: Some(EvmHypersyncConfig {
+                url: "https://somechain.hypersync.xyz".to_string(),
+                headers: Some(hypersync_headers.clone()),
+                role: None,
+                priority: None,
+            }),
+            rpc_config: Some(EvmRpcConfig {
+                url: "https://somechain.com".to_string().into(),
+                ws_url: None,
+                headers: Some(rpc_headers.clone()),
+                sync_config: None,
+                role: None,
+                priority: None,
+            }),
+            file_config: None,
+            source_stall_timeout: None,
+            start_block: 0,
+            end_block: None,
+            confirmed_block_threshold: None,
+            contracts: vec![],
+            block_handlers: None,
+            field_selection: None,
+            raw_events: None,
+        };
+
+        let sources = NetworkSource::from_evm_network_config(network.clone(), None).unwrap();
+
+        match &sources[0].sync_source {
+            SyncSource::HypersyncConfig(config) => {
+                assert_eq!(config.headers, Headers::from(hypersync_headers));
+            }
+            _ => panic!("Expected HypersyncConfig"),
+        }
+        match &sources[1].sync_source {
+            SyncSource::RpcConfig(config) => {
+                assert_eq!(config.headers, Headers::from(rpc_headers.clone()));
+                assert_eq!(
+                    format!("{:?}", config.headers),
+                    r#"{"Authorization": "<redacted>", "x-api-key": "<redacted>"}"#
+                );
+            }
+            _ => panic!("Expected RpcConfig"),
+        }
+
+        let invalid_network = EvmNetwork {
+            hypersync_config: Some(EvmHypersyncConfig {
+                url: "https://somechain.hypersync.xyz".to_string(),
+                headers: Some(BTreeMap::from([(
+                    "x-api-key".to_string(),
+                    "my-key\r\nx-injected: 1".to_string(),
+                )])),
+                role: None,
+                priority: None,
+            }),
+            ..network.clone()
+        };
+        let error = NetworkSource::from_evm_network_config(invalid_network, None)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("EE119:"));
+        assert!(!error.contains("my-key"));
+
+        //Basic auth and other headers are sent by the indexer as well
+        let basic_auth_headers = BTreeMap::from([
+            (
+                "Authorization".to_string(),
+                "Basic dXNlcjpwYXNzd29yZA==".to_string(),
+            ),
+            ("x-api-key".to_string(), "my-key".to_string()),
+        ]);
+        let basic_auth_network = EvmNetwork {
+            hypersync_config: Some(EvmHypersyncConfig {
+                url: "https://somechain.hypersync.xyz".to_string(),
+                headers: Some(basic_auth_headers.clone()),
+                role: None,
+                priority: None,
+            }),
+            rpc_config: None,
+            ..network
+        };
+        let sources = NetworkSource::from_evm_network_config(basic_auth_network, None).unwrap();
+        match &sources[0].sync_source {
+            SyncSource::HypersyncConfig(config) => {
+                assert_eq!(config.headers, Headers::from(basic_auth_headers));
+            }
+            _ => panic!("Expected HypersyncConfig"),
+        }
+    }
+
+    #[test]
+    fn test_file_config_sync_source() {
+        use super::FileConfig;
+        use crate::config_parsing::human_config::evm::{
+            FileConfig as EvmFileConfig, FileFormat, HypersyncConfig, Network as EvmNetwork,
+        };
+
+        let network = EvmNetwork {
+            id: 1,
+            hypersync_config: None,
+            rpc_config: None,
+            file_config: Some(EvmFileConfig {
+                path: "./recordings/mainnet".to_string(),
+                format: None,
+            }),
+            source_stall_timeout: None,
+            start_block: 0,
+            end_block: None,
+            confirmed_block_threshold: None,
+            contracts: vec![],
+            block_handlers: None,
+            field_selection: None,
+            raw_events: None,
+        };
+
+        let sources = NetworkSource::from_evm_network_config(network.clone(), None).unwrap();
+
+        assert_eq!(
+            sources,
+            vec![NetworkSource {
+                sync_source: SyncSource::FileConfig(FileConfig {
+                    path: "./recordings/mainnet".to_string(),
+                    format: FileFormat::Jsonl,
+                }),
+                role: SourceRole::Primary,
+                priority: 0,
+            }]
+        );
+
+        let network_with_hypersync = EvmNetwork {
+            hypersync_config: Some(HypersyncConfig {
+                url: "https://somechain.hypersync.xyz".to_string(),
+                headers: None,
+                role: None,
+                priority: None,
+            }),
+            ..network
+        };
+
+        let error =
+            NetworkSource::from_evm_network_config(network_with_hypersync, None).unwrap_err();
+
+        assert_eq!(error.to_string(), "EE106: Cannot define file_config together with rpc_config or hypersync_config for the same network, please choose only one of them, read more in our docs https://docs.envio.dev/docs/configuration-file");
+    }
 }
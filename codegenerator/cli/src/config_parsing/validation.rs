@@ -1,5 +1,6 @@
 use super::{
     chain_helpers,
+    diagnostics::ConfigItem,
     human_config::{self, evm::HumanConfig},
 };
 use crate::constants::reserved_keywords::{
@@ -40,7 +41,7 @@ fn are_contract_names_unique(contract_names: &[String]) -> bool {
 
 // Check for reserved words in a string, to be applied for schema and config.
 // Words from config and schema are used in the codegen and eventually in eventHandlers for the user, thus cannot contain any reserved words.
-fn check_reserved_words(words: &Vec<String>) -> Vec<String> {
+fn check_reserved_words(words: &[String]) -> Vec<String> {
     let mut flagged_words = Vec::new();
     // Creating a deduplicated set of reserved words from javascript, typescript and rescript
     let mut set = HashSet::new();
@@ -61,7 +62,7 @@ fn check_reserved_words(words: &Vec<String>) -> Vec<String> {
     flagged_words
 }
 
-fn is_valid_identifier(s: &String) -> bool {
+fn is_valid_identifier(s: &str) -> bool {
     // Check if the string is empty
     if s.is_empty() {
         return false;
@@ -87,7 +88,7 @@ fn is_valid_identifier(s: &String) -> bool {
 
 // Check if all names in the config file are valid.
 pub fn validate_names_valid_rescript(
-    names_from_config: &Vec<String>,
+    names_from_config: &[String],
     part_of_config: String,
 ) -> anyhow::Result<()> {
     let detected_reserved_words = check_reserved_words(names_from_config);
@@ -107,7 +108,7 @@ pub fn validate_names_valid_rescript(
 
     let mut invalid_names = Vec::new();
     for name in names_from_config {
-        if !is_valid_identifier(&name) {
+        if !is_valid_identifier(name) {
             invalid_names.push(name.to_string());
         }
     }
@@ -146,15 +147,15 @@ impl human_config::evm::Network {
                     finite_end_block
                 ))
             };
-            match chain_helpers::Network::from_network_id(self.id) {
-                Ok(network) => match (self.end_block, network.get_finite_end_block()) {
-                    (Some(end_block), Some(finite_end_block)) if end_block > finite_end_block => {
-                        return make_err(finite_end_block)
-                    }
-                    (None, Some(finite_end_block)) => return make_err(finite_end_block),
-                    _ => (),
-                },
-                Err(_) => (),
+            match (
+                self.end_block,
+                chain_helpers::get_finite_end_block_from_id(self.id),
+            ) {
+                (Some(end_block), Some(finite_end_block)) if end_block > finite_end_block => {
+                    return make_err(finite_end_block)
+                }
+                (None, Some(finite_end_block)) => return make_err(finite_end_block),
+                _ => (),
             }
         }
         Ok(())
@@ -174,46 +175,200 @@ impl human_config::evm::Network {
     }
 }
 
+impl<T> human_config::NetworkContract<T> {
+    pub fn validate_start_and_end_block(&self, network_start_block: u64) -> anyhow::Result<()> {
+        if let Some(start_block) = self.start_block {
+            if start_block < network_start_block {
+                return Err(anyhow!(
+                    "EE117: The contract {} has a start_block of {} which is lower than the \
+                     start_block {} of its network. The contract start_block must be greater than \
+                     or equal to the network start_block.",
+                    self.name,
+                    start_block,
+                    network_start_block
+                ));
+            }
+        }
+        if let Some(end_block) = self.end_block {
+            let start_block = self.start_block.unwrap_or(network_start_block);
+            if end_block < start_block {
+                return Err(anyhow!(
+                    "EE117: The contract {} has an end_block that is less than its start_block. \
+                     The end_block must be greater than the start_block.",
+                    self.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl human_config::evm::BlockHandlerConfig {
+    pub fn validate_interval_and_block_range(&self) -> anyhow::Result<()> {
+        if self.interval == Some(0) {
+            return Err(anyhow!(
+                "EE112: The block handler {} has an interval of 0. The interval must be greater \
+                 than 0.",
+                self.name
+            ));
+        }
+        if let (Some(start_block), Some(end_block)) = (self.start_block, self.end_block) {
+            if end_block < start_block {
+                return Err(anyhow!(
+                    "EE112: The block handler {} has an end_block that is less than its \
+                     start_block.",
+                    self.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 pub fn validate_deserialized_config_yaml(evm_config: &HumanConfig) -> anyhow::Result<()> {
+    match collect_deserialized_config_yaml_errors(evm_config)
+        .into_iter()
+        .next()
+    {
+        Some((err, _)) => Err(err),
+        None => Ok(()),
+    }
+}
+
+// Runs the same checks as validate_deserialized_config_yaml without stopping at the first
+// failure. Errors are returned with the item in the config they relate to, when there is one.
+pub fn collect_deserialized_config_yaml_errors(
+    evm_config: &HumanConfig,
+) -> Vec<(anyhow::Error, Option<ConfigItem>)> {
+    let mut errors = Vec::new();
     let mut contract_names = Vec::new();
 
     if let Some(global_contracts) = &evm_config.contracts {
         for global_contract in global_contracts {
             contract_names.push(global_contract.name.clone());
+
+            for block_handler in global_contract.config.block_handlers.iter().flatten() {
+                if let Err(err) = block_handler.validate_interval_and_block_range() {
+                    let item = ConfigItem::BlockHandler {
+                        network_id: None,
+                        contract: Some(global_contract.name.clone()),
+                        name: block_handler.name.clone(),
+                    };
+                    errors.push((err, Some(item)));
+                }
+            }
         }
     }
 
     for network in &evm_config.networks {
         // validate endblock is a greater than the startblock
-        network.validate_endblock_lte_startblock()?;
-        network.validate_finite_endblock_networks(evm_config)?;
+        if let Err(err) = network.validate_endblock_lte_startblock() {
+            errors.push((err, Some(ConfigItem::Network(network.id))));
+        }
+        if let Err(err) = network.validate_finite_endblock_networks(evm_config) {
+            errors.push((err, Some(ConfigItem::Network(network.id))));
+        }
+
+        let block_handler_configs = network.get_block_handler_configs(&evm_config.contracts);
+        let block_handler_names: Vec<String> = block_handler_configs
+            .iter()
+            .map(|(block_handler, _)| block_handler.name.clone())
+            .collect();
 
         for contract in &network.contracts {
-            if let Some(_) = contract.config.as_ref() {
+            if let Err(err) = contract.validate_start_and_end_block(network.start_block) {
+                let item = ConfigItem::Contract {
+                    network_id: Some(network.id),
+                    name: contract.name.clone(),
+                };
+                errors.push((err, Some(item)));
+            }
+
+            if let Some(contract_config) = contract.config.as_ref() {
                 contract_names.push(contract.name.clone());
+
+                for block_handler in contract_config.block_handlers.iter().flatten() {
+                    if let Err(err) = block_handler.validate_interval_and_block_range() {
+                        let item = ConfigItem::BlockHandler {
+                            network_id: Some(network.id),
+                            contract: Some(contract.name.clone()),
+                            name: block_handler.name.clone(),
+                        };
+                        errors.push((err, Some(item)));
+                    }
+                }
             }
 
             // Checking if contract addresses are valid addresses
-            for contract_address in contract.address.clone().into_iter() {
+            // Addresses loaded from files are validated when they are loaded
+            let inline_addresses: Vec<String> = match &contract.address {
+                human_config::Addresses::List(addresses) => addresses.clone().into_iter().collect(),
+                _ => vec![],
+            };
+            for contract_address in inline_addresses {
                 if !is_valid_ethereum_address(&contract_address) {
-                    return Err(anyhow!(
-                        "EE100: One of the contract addresses in the config file isn't valid",
+                    errors.push((
+                        anyhow!(
+                            "EE100: One of the contract addresses in the config file isn't \
+                             valid: {}",
+                            contract_address
+                        ),
+                        Some(ConfigItem::Address {
+                            network_id: network.id,
+                            contract: contract.name.clone(),
+                            address: contract_address,
+                        }),
                     ));
                 }
             }
         }
+
+        for block_handler in network.block_handlers.iter().flatten() {
+            if let Err(err) = block_handler.validate_interval_and_block_range() {
+                let item = ConfigItem::BlockHandler {
+                    network_id: Some(network.id),
+                    contract: None,
+                    name: block_handler.name.clone(),
+                };
+                errors.push((err, Some(item)));
+            }
+        }
+
+        // Block handlers get registered by name, so they can't share one on a network
+        if !are_contract_names_unique(&block_handler_names) {
+            errors.push((
+                anyhow!(
+                    "EE114: The network {} has duplicate block handler names. Block handler \
+                     names need to be unique per network and are not case-sensitive.",
+                    network.id
+                ),
+                Some(ConfigItem::Network(network.id)),
+            ));
+        }
+
+        if let Err(err) =
+            validate_names_valid_rescript(&block_handler_names, "block handler".to_string())
+        {
+            errors.push((err, Some(ConfigItem::Network(network.id))));
+        }
     }
     // Checking that contract names are non-unique
     if !are_contract_names_unique(&contract_names) {
-        return Err(anyhow!(
-            "EE101: The config file cannot have duplicate contract names. All contract names need \
-             to be unique, regardless of network. Contract names are not case-sensitive.",
+        errors.push((
+            anyhow!(
+                "EE101: The config file cannot have duplicate contract names. All contract names \
+                 need to be unique, regardless of network. Contract names are not \
+                 case-sensitive.",
+            ),
+            None,
         ));
     }
 
-    validate_names_valid_rescript(&contract_names, "contract".to_string())?;
+    if let Err(err) = validate_names_valid_rescript(&contract_names, "contract".to_string()) {
+        errors.push((err, None));
+    }
 
-    Ok(())
+    errors
 }
 
 pub fn check_enums_for_internal_reserved_words(enum_name_words: Vec<String>) -> Vec<String> {
@@ -240,7 +395,7 @@ pub fn check_names_from_schema_for_reserved_words(schema_words: Vec<String>) ->
     detected_reserved_words_in_schema
 }
 
-pub fn check_schema_enums_are_valid_postgres(enum_names: &Vec<String>) -> Vec<String> {
+pub fn check_schema_enums_are_valid_postgres(enum_names: &[String]) -> Vec<String> {
     let mut detected_enum_not_valid = Vec::new();
     for name in enum_names {
         if !is_valid_postgres_db_name(&name.as_str()) {
@@ -252,6 +407,7 @@ pub fn check_schema_enums_are_valid_postgres(enum_names: &Vec<String>) -> Vec<St
 
 #[cfg(test)]
 mod tests {
+    use crate::config_parsing::human_config::NetworkContract;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -330,7 +486,7 @@ mod tests {
 
     #[test]
     fn test_check_reserved_words() {
-        let words = vec![
+        let words = [
             "This".to_string(),
             "is".to_string(),
             "a".to_string(),
@@ -359,7 +515,7 @@ mod tests {
 
     #[test]
     fn test_check_no_reserved_words() {
-        let words = vec![
+        let words = [
             "This".to_string(),
             "is".to_string(),
             "a".to_string(),
@@ -393,7 +549,7 @@ mod tests {
     #[test]
     fn test_contract_names_validation() {
         let valid_result = super::validate_names_valid_rescript(
-            &vec![
+            &[
                 "foo".to_string(),
                 "MyContract".to_string(),
                 "_Bar".to_string(),
@@ -403,7 +559,7 @@ mod tests {
         assert!(valid_result.is_ok());
 
         let reserved_names = super::validate_names_valid_rescript(
-            &vec![
+            &[
                 "foo".to_string(),
                 "MyContract".to_string(),
                 "_Bar".to_string(),
@@ -422,7 +578,7 @@ mod tests {
         );
 
         let invalid_names = super::validate_names_valid_rescript(
-            &vec![
+            &[
                 "foo".to_string(),
                 "MyContract".to_string(),
                 "_Bar".to_string(),
@@ -444,4 +600,40 @@ mod tests {
              alphanumeric characters and underscores."
         );
     }
+
+    #[test]
+    fn test_contract_start_block_lower_than_network_start_block() {
+        let contract = NetworkContract::<()> {
+            name: "Contract1".to_string(),
+            address: vec![].into(),
+            start_block: Some(100),
+            end_block: None,
+            config: None,
+        };
+
+        assert!(contract.validate_start_and_end_block(100).is_ok());
+        assert!(contract.validate_start_and_end_block(50).is_ok());
+        assert_eq!(
+            contract
+                .validate_start_and_end_block(200)
+                .unwrap_err()
+                .to_string(),
+            "EE117: The contract Contract1 has a start_block of 100 which is lower than the \
+             start_block 200 of its network. The contract start_block must be greater than or \
+             equal to the network start_block."
+        );
+
+        let contract = NetworkContract::<()> {
+            end_block: Some(99),
+            ..contract
+        };
+        assert_eq!(
+            contract
+                .validate_start_and_end_block(0)
+                .unwrap_err()
+                .to_string(),
+            "EE117: The contract Contract1 has an end_block that is less than its start_block. \
+             The end_block must be greater than the start_block."
+        );
+    }
 }
@@ -5,6 +5,7 @@ pub mod project_paths {
     pub const DEFAULT_GENERATED_PATH: &str = "generated";
     pub const DEFAULT_CONFIG_PATH: &str = "config.yaml";
     pub const DEFAULT_SCHEMA_PATH: &str = "schema.graphql";
+    pub const DEFAULT_FIXTURE_PATH: &str = "fixtures";
 }
 
 pub mod links {
@@ -0,0 +1,859 @@
+use crate::{
+    config_parsing::system_config::{
+        Ecosystem, Headers, HypersyncConfig, Network, RpcConfig, SelectedField, SyncSource,
+        SystemConfig,
+    },
+    rescript_types::RescriptTypeIdent,
+};
+use anyhow::{anyhow, Context, Result};
+use convert_case::{Case, Casing};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{Address, BlockNumber, Filter, Log, H256},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+};
+
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+///Converts the configured endpoint headers, which are validated when parsing the config
+fn to_header_map(headers: &Headers) -> Result<reqwest::header::HeaderMap> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header name \"{name}\""))?;
+            //The value isn't printed, since it's most likely a secret
+            let header_value = reqwest::header::HeaderValue::from_str(value)
+                .with_context(|| format!("Invalid value of the header \"{name}\""))?;
+            Ok((header_name, header_value))
+        })
+        .collect()
+}
+
+///A log row in the format replayed by the file_config sync source
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+struct RecordedLog {
+    block_number: u64,
+    log_index: u64,
+    transaction_index: u64,
+    transaction_hash: String,
+    address: String,
+    data: String,
+    topics: Vec<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+struct ManifestContract {
+    name: String,
+    addresses: Vec<String>,
+    event_sighashes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+struct FixtureManifest {
+    chain_id: u64,
+    from_block: u64,
+    to_block: u64,
+    format: String,
+    contracts: Vec<ManifestContract>,
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    logs: Vec<RecordedLog>,
+    blocks: BTreeMap<u64, Map<String, Value>>,
+    transactions: BTreeMap<String, Map<String, Value>>,
+}
+
+///Everything needed to query a network for the logs of the configured contracts
+struct RecordQuery {
+    from_block: u64,
+    to_block: u64,
+    contracts: Vec<ManifestContract>,
+    block_fields: Vec<SelectedField>,
+    transaction_fields: Vec<SelectedField>,
+}
+
+impl RecordQuery {
+    fn new(
+        config: &SystemConfig,
+        network: &Network,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Self> {
+        let mut block_fields = config.field_selection.block_fields.clone();
+        let mut transaction_fields = config.field_selection.transaction_fields.clone();
+        if let Some(field_selection) = &network.field_selection {
+            block_fields.extend(field_selection.block_fields.iter().cloned());
+            transaction_fields.extend(field_selection.transaction_fields.iter().cloned());
+        }
+        let mut contracts = vec![];
+
+        for network_contract in &network.contracts {
+            let contract = network_contract.get_contract(config)?;
+            for event in &contract.events {
+                if let Some(field_selection) = &event.field_selection {
+                    block_fields.extend(field_selection.block_fields.iter().cloned());
+                    transaction_fields.extend(field_selection.transaction_fields.iter().cloned());
+                }
+            }
+            contracts.push(ManifestContract {
+                name: contract.name.clone(),
+                addresses: network_contract.addresses.clone(),
+                event_sighashes: contract
+                    .events
+                    .iter()
+                    .map(|event| event.sighash.clone())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            });
+        }
+
+        //The transaction hash is always needed to join transactions with their logs
+        transaction_fields.push(SelectedField {
+            name: "hash".to_string(),
+            data_type: RescriptTypeIdent::String,
+        });
+
+        Ok(RecordQuery {
+            from_block,
+            to_block,
+            contracts,
+            block_fields: dedup_fields(block_fields),
+            transaction_fields: dedup_fields(transaction_fields),
+        })
+    }
+}
+
+fn dedup_fields(fields: Vec<SelectedField>) -> Vec<SelectedField> {
+    let mut seen = HashSet::new();
+    fields
+        .into_iter()
+        .filter(|field| seen.insert(field.name.to_case(Case::Camel)))
+        .collect()
+}
+
+fn is_int_field(data_type: &RescriptTypeIdent) -> bool {
+    match data_type {
+        RescriptTypeIdent::Int => true,
+        RescriptTypeIdent::Option(inner) => is_int_field(inner),
+        _ => false,
+    }
+}
+
+///Parses a quantity which is either a json number, a hex string or a decimal string
+fn parse_quantity(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => string.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+///Picks the selected fields out of a raw block or transaction object and converts
+///them into the shape expected by the generated block and transaction schemas.
+///Int fields become json numbers, other numeric values are kept as strings so they
+///can be parsed as BigInt.
+fn select_fields(
+    raw: &Map<String, Value>,
+    fields: &[SelectedField],
+    key_case: Case,
+) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|field| {
+            let value = raw
+                .get(&field.name.to_case(key_case))
+                .cloned()
+                .unwrap_or(Value::Null);
+            let value = match value {
+                Value::Null => Value::Null,
+                value if is_int_field(&field.data_type) => {
+                    parse_quantity(&value).map(Value::from).unwrap_or(value)
+                }
+                Value::Number(number) => Value::String(number.to_string()),
+                value => value,
+            };
+            (field.name.to_case(Case::Camel), value)
+        })
+        .collect()
+}
+
+mod hypersync {
+    use super::*;
+
+    #[derive(Serialize, Clone)]
+    struct LogSelection {
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        address: Vec<String>,
+        topics: Vec<Vec<String>>,
+    }
+
+    #[derive(Serialize, Clone)]
+    struct FieldSelection {
+        block: Vec<String>,
+        transaction: Vec<String>,
+        log: Vec<String>,
+    }
+
+    #[derive(Serialize, Clone)]
+    pub(super) struct Query {
+        pub(super) from_block: u64,
+        pub(super) to_block: u64,
+        logs: Vec<LogSelection>,
+        field_selection: FieldSelection,
+        pub(super) include_all_blocks: bool,
+    }
+
+    #[derive(Deserialize, Default)]
+    pub(super) struct ResponseData {
+        #[serde(default)]
+        pub(super) blocks: Vec<Map<String, Value>>,
+        #[serde(default)]
+        pub(super) transactions: Vec<Map<String, Value>>,
+        #[serde(default)]
+        pub(super) logs: Vec<Map<String, Value>>,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct Response {
+        pub(super) data: Vec<ResponseData>,
+        pub(super) archive_height: Option<u64>,
+        pub(super) next_block: u64,
+    }
+
+    const LOG_FIELDS: [&str; 10] = [
+        "block_number",
+        "log_index",
+        "transaction_index",
+        "transaction_hash",
+        "address",
+        "data",
+        "topic0",
+        "topic1",
+        "topic2",
+        "topic3",
+    ];
+
+    fn to_recorded_log(raw: &Map<String, Value>) -> Result<RecordedLog> {
+        let get_quantity = |key: &str| {
+            raw.get(key)
+                .and_then(parse_quantity)
+                .ok_or_else(|| anyhow!("HyperSync log is missing the {} field", key))
+        };
+        let get_string = |key: &str| {
+            raw.get(key)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("HyperSync log is missing the {} field", key))
+        };
+
+        Ok(RecordedLog {
+            block_number: get_quantity("block_number")?,
+            log_index: get_quantity("log_index")?,
+            transaction_index: get_quantity("transaction_index")?,
+            transaction_hash: get_string("transaction_hash")?,
+            address: get_string("address")?,
+            data: get_string("data")?,
+            topics: ["topic0", "topic1", "topic2", "topic3"]
+                .iter()
+                .filter_map(|key| raw.get(*key).and_then(Value::as_str).map(str::to_string))
+                .collect(),
+        })
+    }
+
+    async fn query(hypersync_config: &HypersyncConfig, query: Query) -> Result<Response> {
+        let client = reqwest::Client::new();
+        let mut request = client
+            .post(format!("{}/query", hypersync_config.endpoint_url))
+            .json(&query);
+        if let Ok(token) = std::env::var("ENVIO_API_TOKEN") {
+            request = request.bearer_auth(token);
+        }
+        //Configured headers replace the default auth header
+        let response = request
+            .headers(to_header_map(&hypersync_config.headers)?)
+            .send()
+            .await
+            .context("Failed sending HyperSync query")?
+            .error_for_status()
+            .context("HyperSync query returned an error status")?;
+        response
+            .json()
+            .await
+            .context("Failed parsing HyperSync query response")
+    }
+
+    pub(super) async fn record(
+        hypersync_config: &HypersyncConfig,
+        record_query: &RecordQuery,
+    ) -> Result<Recording> {
+        record_with(record_query, |q| query(hypersync_config, q)).await
+    }
+
+    ///Pages through the range with the given query function. All blocks are included,
+    ///since the replay source needs the header of every block it might stop at
+    pub(super) async fn record_with<F, Fut>(
+        record_query: &RecordQuery,
+        mut query: F,
+    ) -> Result<Recording>
+    where
+        F: FnMut(Query) -> Fut,
+        Fut: Future<Output = Result<Response>>,
+    {
+        let field_selection = || FieldSelection {
+            block: record_query
+                .block_fields
+                .iter()
+                .map(|field| field.name.to_case(Case::Snake))
+                .collect(),
+            transaction: record_query
+                .transaction_fields
+                .iter()
+                .map(|field| field.name.to_case(Case::Snake))
+                .collect(),
+            log: LOG_FIELDS.iter().map(|field| field.to_string()).collect(),
+        };
+
+        let logs = record_query
+            .contracts
+            .iter()
+            .map(|contract| LogSelection {
+                address: contract.addresses.clone(),
+                topics: vec![contract.event_sighashes.clone()],
+            })
+            .collect();
+
+        let mut recording = Recording::default();
+        let mut q = Query {
+            from_block: record_query.from_block,
+            to_block: record_query.to_block + 1,
+            logs,
+            field_selection: field_selection(),
+            include_all_blocks: true,
+        };
+
+        while q.from_block <= record_query.to_block {
+            let response = query(q.clone()).await?;
+            for data in response.data {
+                for block in data.blocks {
+                    let selected = select_fields(&block, &record_query.block_fields, Case::Snake);
+                    if let Some(number) = block.get("number").and_then(parse_quantity) {
+                        recording.blocks.insert(number, selected);
+                    }
+                }
+                for transaction in data.transactions {
+                    let selected =
+                        select_fields(&transaction, &record_query.transaction_fields, Case::Snake);
+                    if let Some(hash) = transaction.get("hash").and_then(Value::as_str) {
+                        recording.transactions.insert(hash.to_string(), selected);
+                    }
+                }
+                for log in data.logs {
+                    recording.logs.push(to_recorded_log(&log)?);
+                }
+            }
+
+            if response.next_block <= q.from_block {
+                let height = response.archive_height.unwrap_or_default();
+                return Err(anyhow!(
+                    "HyperSync hasn't reached the block {} yet (current height: {})",
+                    record_query.to_block,
+                    height
+                ));
+            }
+            q.from_block = response.next_block;
+        }
+
+        Ok(recording)
+    }
+}
+
+mod rpc {
+    use super::*;
+
+    fn to_recorded_log(log: Log) -> Result<RecordedLog> {
+        let missing = |field: &str| anyhow!("RPC log is missing the {} field", field);
+        Ok(RecordedLog {
+            block_number: log
+                .block_number
+                .ok_or_else(|| missing("blockNumber"))?
+                .as_u64(),
+            log_index: log.log_index.ok_or_else(|| missing("logIndex"))?.as_u64(),
+            transaction_index: log
+                .transaction_index
+                .ok_or_else(|| missing("transactionIndex"))?
+                .as_u64(),
+            transaction_hash: format!(
+                "{:?}",
+                log.transaction_hash
+                    .ok_or_else(|| missing("transactionHash"))?
+            ),
+            address: format!("{:?}", log.address),
+            data: log.data.to_string(),
+            topics: log
+                .topics
+                .iter()
+                .map(|topic| format!("{:?}", topic))
+                .collect(),
+        })
+    }
+
+    fn to_object<T: Serialize>(value: T) -> Result<Map<String, Value>> {
+        match serde_json::to_value(value).context("Failed serializing RPC response")? {
+            Value::Object(object) => Ok(object),
+            _ => Err(anyhow!("Unexpected RPC response, expected an object")),
+        }
+    }
+
+    pub(super) async fn record(
+        rpc_config: &RpcConfig,
+        record_query: &RecordQuery,
+    ) -> Result<Recording> {
+        let url = rpc_config
+            .urls
+            .first()
+            .ok_or_else(|| anyhow!("The network doesn't have an RPC url"))?;
+        let client = reqwest::Client::builder()
+            .default_headers(to_header_map(&rpc_config.headers)?)
+            .build()
+            .context("Failed creating RPC http client")?;
+        let url = reqwest::Url::parse(url).context("Failed parsing RPC url")?;
+        let provider = Provider::new(Http::new_with_client(url, client));
+
+        let mut recording = Recording::default();
+        let block_interval = u64::from(rpc_config.sync_config.initial_block_interval).max(1);
+
+        for contract in &record_query.contracts {
+            let addresses = contract
+                .addresses
+                .iter()
+                .map(|address| address.parse::<Address>())
+                .collect::<Result<Vec<_>, _>>()
+                .context("Failed parsing contract address")?;
+            let topics = contract
+                .event_sighashes
+                .iter()
+                .map(|sighash| sighash.parse::<H256>())
+                .collect::<Result<Vec<_>, _>>()
+                .context("Failed parsing event sighash")?;
+
+            let mut from_block = record_query.from_block;
+            while from_block <= record_query.to_block {
+                let to_block = (from_block + block_interval - 1).min(record_query.to_block);
+                let mut filter = Filter::new()
+                    .from_block(from_block)
+                    .to_block(to_block)
+                    .topic0(topics.clone());
+                if !addresses.is_empty() {
+                    filter = filter.address(addresses.clone());
+                }
+                let logs = provider
+                    .get_logs(&filter)
+                    .await
+                    .with_context(|| format!("Failed getting logs {from_block}-{to_block}"))?;
+                for log in logs {
+                    recording.logs.push(to_recorded_log(log)?);
+                }
+                from_block = to_block + 1;
+            }
+        }
+
+        //The replay source needs the header of every block it might stop at,
+        //not only of the blocks with logs
+        for block_number in record_query.from_block..=record_query.to_block {
+            let block = provider
+                .get_block(BlockNumber::Number(block_number.into()))
+                .await
+                .with_context(|| format!("Failed getting block {block_number}"))?
+                .ok_or_else(|| anyhow!("Block {} wasn't found", block_number))?;
+            let selected =
+                select_fields(&to_object(block)?, &record_query.block_fields, Case::Camel);
+            recording.blocks.insert(block_number, selected);
+        }
+
+        let needs_transactions = record_query
+            .transaction_fields
+            .iter()
+            .any(|field| field.name != "hash");
+        if needs_transactions {
+            let transaction_hashes: BTreeSet<String> = recording
+                .logs
+                .iter()
+                .map(|log| log.transaction_hash.clone())
+                .collect();
+            for transaction_hash in transaction_hashes {
+                let hash = transaction_hash
+                    .parse::<H256>()
+                    .context("Failed parsing transaction hash")?;
+                let transaction = provider
+                    .get_transaction(hash)
+                    .await
+                    .with_context(|| format!("Failed getting transaction {transaction_hash}"))?
+                    .ok_or_else(|| anyhow!("Transaction {} wasn't found", transaction_hash))?;
+                let selected = select_fields(
+                    &to_object(transaction)?,
+                    &record_query.transaction_fields,
+                    Case::Camel,
+                );
+                recording.transactions.insert(transaction_hash, selected);
+            }
+        }
+
+        Ok(recording)
+    }
+}
+
+fn write_jsonl<T: Serialize>(path: PathBuf, rows: impl Iterator<Item = T>) -> Result<()> {
+    let mut content = String::new();
+    for row in rows {
+        content.push_str(&serde_json::to_string(&row).context("Failed serializing row")?);
+        content.push('\n');
+    }
+    fs::write(&path, content).with_context(|| format!("Failed writing {}", path.display()))
+}
+
+fn write_fixture(
+    output_dir: &Path,
+    manifest: &FixtureManifest,
+    recording: &Recording,
+) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed creating {}", output_dir.display()))?;
+
+    fs::write(
+        output_dir.join(MANIFEST_FILE_NAME),
+        serde_json::to_string_pretty(manifest).context("Failed serializing manifest")?,
+    )
+    .context("Failed writing fixture manifest")?;
+
+    write_jsonl(output_dir.join("logs.jsonl"), recording.logs.iter())?;
+    write_jsonl(output_dir.join("blocks.jsonl"), recording.blocks.values())?;
+    write_jsonl(
+        output_dir.join("transactions.jsonl"),
+        recording.transactions.values(),
+    )
+}
+
+///Queries the network with the given chain id for the logs of the configured contracts
+///and writes them with their selected block and transaction fields into a fixture
+///directory which can be replayed with the file_config network option.
+pub async fn record(
+    config: &SystemConfig,
+    chain_id: Option<u64>,
+    from_block: Option<u64>,
+    to_block: u64,
+    output_dir: PathBuf,
+) -> Result<()> {
+    if config.get_ecosystem() != Ecosystem::Evm {
+        return Err(anyhow!(
+            "Recording fixtures is only supported for Evm indexers"
+        ));
+    }
+
+    let networks = config.get_networks();
+    let network = match (chain_id, networks.as_slice()) {
+        (Some(chain_id), networks) => networks
+            .iter()
+            .find(|network| network.id == chain_id)
+            .ok_or_else(|| anyhow!("Network {} is not defined in the config", chain_id))?,
+        (None, [network]) => network,
+        (None, _) => {
+            return Err(anyhow!(
+                "The config has multiple networks, please choose one with --chain-id"
+            ))
+        }
+    };
+
+    let from_block = from_block.unwrap_or(network.start_block);
+    if from_block > to_block {
+        return Err(anyhow!(
+            "The from block {} is greater than the to block {}",
+            from_block,
+            to_block
+        ));
+    }
+
+    let record_query = RecordQuery::new(config, network, from_block, to_block)?;
+
+    //Recording replays historical blocks, so use the preferred historical source
+    let sync_source = network
+        .sources
+        .iter()
+        .find(|source| source.role.is_historical())
+        .map(|source| &source.sync_source)
+        .ok_or_else(|| anyhow!("Network {} doesn't have a historical source", network.id))?;
+
+    let mut recording = match sync_source {
+        SyncSource::HypersyncConfig(hypersync_config) => {
+            hypersync::record(hypersync_config, &record_query).await?
+        }
+        SyncSource::RpcConfig(rpc_config) => rpc::record(rpc_config, &record_query).await?,
+        SyncSource::HyperfuelConfig(_) | SyncSource::FileConfig(_) => {
+            return Err(anyhow!(
+                "Network {} doesn't use HyperSync or RPC, so there is nothing to record",
+                network.id
+            ))
+        }
+    };
+    recording
+        .logs
+        .sort_by_key(|log| (log.block_number, log.log_index));
+
+    let manifest = FixtureManifest {
+        chain_id: network.id,
+        from_block,
+        to_block,
+        format: "jsonl".to_string(),
+        contracts: record_query.contracts,
+    };
+    write_fixture(&output_dir, &manifest, &recording)?;
+
+    println!(
+        "Recorded {} logs from blocks {}-{} of network {} into {}",
+        recording.logs.len(),
+        from_block,
+        to_block,
+        network.id,
+        output_dir.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        config_parsing::human_config::evm::TransactionField, project_paths::ParsedProjectPaths,
+    };
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    fn to_object(value: Value) -> Map<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    ///Reads both pretty printed json and jsonl files as a list of values
+    fn read_json_values(path: PathBuf) -> Vec<Value> {
+        let content = fs::read_to_string(&path).unwrap();
+        serde_json::Deserializer::from_str(&content)
+            .into_iter::<Value>()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn records_every_block_into_the_replayed_fixture() {
+        let sighash = "0x9ab3aefb2ba6dc12910ac1bce4692cf5c3c0d06cff16327c64a3ef78228b130b";
+        let address = "0x2b2f78c5bf6d9c12ee1225d5f374aa91204580c3";
+        let record_query = RecordQuery {
+            from_block: 10,
+            to_block: 15,
+            contracts: vec![ManifestContract {
+                name: "Gravatar".to_string(),
+                addresses: vec![address.to_string()],
+                event_sighashes: vec![sighash.to_string()],
+            }],
+            block_fields: vec![
+                SelectedField {
+                    name: "number".to_string(),
+                    data_type: RescriptTypeIdent::Int,
+                },
+                SelectedField {
+                    name: "timestamp".to_string(),
+                    data_type: RescriptTypeIdent::Int,
+                },
+                SelectedField {
+                    name: "hash".to_string(),
+                    data_type: RescriptTypeIdent::String,
+                },
+            ],
+            transaction_fields: vec![SelectedField {
+                name: "hash".to_string(),
+                data_type: RescriptTypeIdent::String,
+            }],
+        };
+
+        //Responds with pages of 4 blocks and a single log in block 12
+        let mut recording = hypersync::record_with(&record_query, |q| {
+            assert!(q.include_all_blocks);
+            let next_block = (q.from_block + 4).min(q.to_block);
+            let page = q.from_block..next_block;
+            let blocks = page
+                .clone()
+                .map(|number| {
+                    to_object(json!({
+                        "number": number,
+                        "timestamp": format!("0x{:x}", number * 15),
+                        "hash": format!("0x{:064x}", number),
+                    }))
+                })
+                .collect();
+            let (transactions, logs) = if page.contains(&12) {
+                (
+                    vec![to_object(json!({ "hash": "0xab12" }))],
+                    vec![to_object(json!({
+                        "block_number": 12,
+                        "log_index": 0,
+                        "transaction_index": 0,
+                        "transaction_hash": "0xab12",
+                        "address": address,
+                        "data": "0x",
+                        "topic0": sighash,
+                    }))],
+                )
+            } else {
+                (vec![], vec![])
+            };
+            std::future::ready(Ok(hypersync::Response {
+                data: vec![hypersync::ResponseData {
+                    blocks,
+                    transactions,
+                    logs,
+                }],
+                archive_height: Some(20),
+                next_block,
+            }))
+        })
+        .await
+        .unwrap();
+        recording
+            .logs
+            .sort_by_key(|log| (log.block_number, log.log_index));
+
+        assert_eq!(
+            recording.blocks.keys().cloned().collect::<Vec<_>>(),
+            (10..=15).collect::<Vec<_>>(),
+            "Should record the blocks without logs, so the replay can stop at any of them"
+        );
+
+        let manifest = FixtureManifest {
+            chain_id: 1337,
+            from_block: 10,
+            to_block: 15,
+            format: "jsonl".to_string(),
+            contracts: record_query.contracts,
+        };
+        let temp_dir = TempDir::new("recorder_fixture_test").unwrap();
+        write_fixture(temp_dir.path(), &manifest, &recording).unwrap();
+
+        //The same fixture is replayed by the file source in the scenario tests
+        let replayed_fixture_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../scenarios/test_codegen/test/fixtures/recording");
+        for file_name in [
+            MANIFEST_FILE_NAME,
+            "blocks.jsonl",
+            "logs.jsonl",
+            "transactions.jsonl",
+        ] {
+            assert_eq!(
+                read_json_values(temp_dir.path().join(file_name)),
+                read_json_values(replayed_fixture_dir.join(file_name)),
+                "The recorded {file_name} should match the replayed fixture"
+            );
+        }
+    }
+
+    #[test]
+    fn record_query_includes_the_network_field_selection() {
+        let project_paths = ParsedProjectPaths::new(
+            &format!("{}/test", env!("CARGO_MANIFEST_DIR")),
+            "generated/",
+            "configs/network-field-selection-config.yaml",
+        )
+        .unwrap();
+        let config = SystemConfig::parse_from_project_files(&project_paths).unwrap();
+        let network = config.networks.get(&10).unwrap();
+
+        let record_query = RecordQuery::new(&config, network, 0, 10).unwrap();
+
+        assert_eq!(
+            record_query
+                .transaction_fields
+                .iter()
+                .map(|field| (field.name.clone(), field.data_type.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    TransactionField::Hash.to_string(),
+                    RescriptTypeIdent::String
+                ),
+                (
+                    TransactionField::L1Fee.to_string(),
+                    RescriptTypeIdent::option(RescriptTypeIdent::BigInt)
+                ),
+                (
+                    TransactionField::GasUsedForL1.to_string(),
+                    RescriptTypeIdent::option(RescriptTypeIdent::BigInt)
+                ),
+            ],
+            "Should record the fields selected for the network"
+        );
+
+        let network = config.networks.get(&1).unwrap();
+        let record_query = RecordQuery::new(&config, network, 0, 10).unwrap();
+        assert_eq!(
+            record_query
+                .transaction_fields
+                .iter()
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>(),
+            vec![TransactionField::Hash.to_string()],
+            "Shouldn't record the fields selected only for the other network"
+        );
+    }
+
+    #[test]
+    fn selects_and_normalizes_fields() {
+        let fields = vec![
+            SelectedField {
+                name: "number".to_string(),
+                data_type: RescriptTypeIdent::Int,
+            },
+            SelectedField {
+                name: "timestamp".to_string(),
+                data_type: RescriptTypeIdent::Int,
+            },
+            SelectedField {
+                name: "GasUsed".to_string(),
+                data_type: RescriptTypeIdent::BigInt,
+            },
+            SelectedField {
+                name: "BaseFeePerGas".to_string(),
+                data_type: RescriptTypeIdent::option(RescriptTypeIdent::BigInt),
+            },
+        ];
+        let raw = json!({
+            "number": 10,
+            "timestamp": "0x10",
+            "gas_used": 21000,
+            "hash": "0xabc",
+        });
+
+        let selected = select_fields(raw.as_object().unwrap(), &fields, Case::Snake);
+
+        assert_eq!(
+            Value::Object(selected),
+            json!({
+                "number": 10,
+                "timestamp": 16,
+                "gasUsed": "21000",
+                "baseFeePerGas": null,
+            })
+        );
+    }
+}
@@ -1,12 +1,12 @@
 use crate::{
     commands,
-    config_parsing::system_config::SystemConfig,
+    config_parsing::system_config::{Secrets, SystemConfig},
     persisted_state::{PersistedStateExists, CURRENT_CRATE_VERSION},
     project_paths::ParsedProjectPaths,
 };
 use anyhow::{Context, Result};
 
-pub async fn run_codegen(project_paths: &ParsedProjectPaths) -> Result<()> {
+pub async fn run_codegen(project_paths: &ParsedProjectPaths, secrets: &mut Secrets) -> Result<()> {
     //Manage purging of gengerated folder
     match PersistedStateExists::get_persisted_state_file(&project_paths) {
         PersistedStateExists::Exists(ps) if &ps.envio_version != CURRENT_CRATE_VERSION => {
@@ -23,8 +23,8 @@ pub async fn run_codegen(project_paths: &ParsedProjectPaths) -> Result<()> {
         _ => (),
     };
 
-    let config =
-        SystemConfig::parse_from_project_files(project_paths).context("Failed parsing config")?;
+    let config = SystemConfig::parse_from_project_files_with_secrets(project_paths, secrets)
+        .context("Failed parsing config")?;
 
     commands::codegen::run_codegen(&config, project_paths).await?;
 
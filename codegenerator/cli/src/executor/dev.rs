@@ -1,15 +1,22 @@
 use crate::{
+    cli_args::clap_definitions::DevArgs,
     commands,
-    config_parsing::system_config::SystemConfig,
+    config_parsing::system_config::{Secrets, SystemConfig},
     persisted_state::{self, PersistedState, PersistedStateExists, CURRENT_CRATE_VERSION},
     project_paths::ParsedProjectPaths,
     service_health::{self, EndpointHealth},
 };
 use anyhow::{anyhow, Context, Result};
+use inquire::Confirm;
+use std::io::IsTerminal;
 
-pub async fn run_dev(project_paths: ParsedProjectPaths) -> Result<()> {
-    let config =
-        SystemConfig::parse_from_project_files(&project_paths).context("Failed parsing config")?;
+pub async fn run_dev(
+    dev_args: &DevArgs,
+    project_paths: ParsedProjectPaths,
+    secrets: &mut Secrets,
+) -> Result<()> {
+    let config = SystemConfig::parse_from_project_files_with_secrets(&project_paths, secrets)
+        .context("Failed parsing config")?;
 
     let current_state = PersistedState::get_current_state(&config)
         .context("Failed getting current indexer state")?;
@@ -111,11 +118,61 @@ pub async fn run_dev(project_paths: ParsedProjectPaths) -> Result<()> {
                     PersistedStateExists::Corrupted => println!("Invalid DB persisted state"),
                     PersistedStateExists::Exists(_) => print_changes_detected(changes_detected),
                 }
-                println!("Running db migrations");
-
-                commands::db_migrate::run_db_setup(&project_paths, &current_state)
-                    .await
-                    .context("Failed running db setup command")?;
+                let schema_migration_plan = match &persisted_state_db {
+                    PersistedStateExists::Exists(persisted_state) => {
+                        current_state.get_schema_migration_plan(persisted_state)
+                    }
+                    PersistedStateExists::NotExists | PersistedStateExists::Corrupted => None,
+                };
+
+                match schema_migration_plan {
+                    //Only the schema changed and it can be migrated without a reset
+                    Some(schema_migration_plan) if !schema_migration_plan.is_breaking() => {
+                        println!("Running schema migration");
+
+                        commands::db_migrate::run_schema_migration(
+                            &project_paths,
+                            &current_state,
+                            &schema_migration_plan,
+                        )
+                        .await
+                        .context("Failed running schema migration")?;
+                    }
+                    schema_migration_plan => {
+                        if let Some(schema_migration_plan) = schema_migration_plan {
+                            commands::db_migrate::print_breaking_changes(&schema_migration_plan);
+
+                            //The prompt can't be answered without a terminal, so the database
+                            //is only reset there when it's explicitly allowed
+                            let should_reset = if dev_args.yes {
+                                true
+                            } else if std::io::stdin().is_terminal() {
+                                Confirm::new(
+                                    "Do you want to reset the database and reindex from scratch?",
+                                )
+                                .with_default(true)
+                                .prompt()
+                                .context("Failed prompting for database reset")?
+                            } else {
+                                false
+                            };
+
+                            if !should_reset {
+                                return Err(anyhow!(
+                                    "Breaking schema changes can't be applied without resetting \
+                                     the database. Run 'envio dev --yes' to reset it and reindex \
+                                     from scratch"
+                                ));
+                            }
+                        }
+
+                        println!("Running db migrations");
+
+                        commands::db_migrate::run_db_setup(&project_paths, &current_state)
+                            .await
+                            .context("Failed running db setup command")?;
+                    }
+                }
             }
 
             println!("Starting indexer");
@@ -6,8 +6,10 @@ use crate::{
     },
     commands,
     config_parsing::{
-        entity_parsing::Schema, graph_migration::generate_config_from_subgraph_id,
-        human_config::HumanConfig, system_config::SystemConfig,
+        entity_parsing::Schema,
+        graph_migration::generate_config_from_subgraph_id,
+        human_config::HumanConfig,
+        system_config::{Secrets, SystemConfig},
     },
     hbs_templating::{
         contract_import_templates, hbs_dir_generator::HandleBarsDirGenerator,
@@ -30,7 +32,11 @@ fn is_valid_release_version_number(version: &str) -> bool {
     re_version_pattern.is_match(version) || version.contains("-main-")
 }
 
-pub async fn run_init_args(init_args: InitArgs, project_paths: &ProjectPaths) -> Result<()> {
+pub async fn run_init_args(
+    init_args: InitArgs,
+    project_paths: &ProjectPaths,
+    secrets: &mut Secrets,
+) -> Result<()> {
     let template_dirs = TemplateDirs::new();
     //get_init_args_interactive opens an interactive cli for required args to be selected
     //if they haven't already been
@@ -276,8 +282,9 @@ pub async fn run_init_args(init_args: InitArgs, project_paths: &ProjectPaths) ->
     println!("Project template ready");
     println!("Running codegen");
 
-    let config = SystemConfig::parse_from_project_files(&parsed_project_paths)
-        .context("Failed parsing config")?;
+    let config =
+        SystemConfig::parse_from_project_files_with_secrets(&parsed_project_paths, secrets)
+            .context("Failed parsing config")?;
 
     commands::codegen::run_codegen(&config, &parsed_project_paths).await?;
 
@@ -1,7 +1,7 @@
 use crate::{
     cli_args::clap_definitions::{DbMigrateSubcommands, LocalCommandTypes, LocalDockerSubcommands},
     commands,
-    config_parsing::system_config::SystemConfig,
+    config_parsing::system_config::{Secrets, SystemConfig},
     persisted_state::PersistedState,
     project_paths::ParsedProjectPaths,
 };
@@ -10,6 +10,7 @@ use anyhow::{Context, Result};
 pub async fn run_local(
     local_commands: &LocalCommandTypes,
     project_paths: &ParsedProjectPaths,
+    secrets: &mut Secrets,
 ) -> Result<()> {
     match local_commands {
         LocalCommandTypes::Docker(subcommand) => match subcommand {
@@ -22,9 +23,10 @@ pub async fn run_local(
         },
         LocalCommandTypes::DbMigrate(subcommand) => {
             //Use a closure just so running local dow doesn't need to construct persisted state
-            let get_persisted_state = || -> Result<PersistedState> {
-                let config = SystemConfig::parse_from_project_files(&project_paths)
-                    .context("Failed parsing config")?;
+            let mut get_persisted_state = || -> Result<PersistedState> {
+                let config =
+                    SystemConfig::parse_from_project_files_with_secrets(&project_paths, secrets)
+                        .context("Failed parsing config")?;
 
                 let persisted_state = PersistedState::get_current_state(&config)
                     .context("Failed constructing persisted state")?;
@@ -47,6 +49,11 @@ pub async fn run_local(
                     let persisted_state = get_persisted_state()?;
                     commands::db_migrate::run_db_setup(&project_paths, &persisted_state).await?;
                 }
+
+                DbMigrateSubcommands::Plan => {
+                    let persisted_state = get_persisted_state()?;
+                    commands::db_migrate::print_schema_migration_plan(&persisted_state).await?;
+                }
             }
         }
     }
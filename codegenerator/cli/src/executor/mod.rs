@@ -2,7 +2,10 @@ use crate::{
     clap_definitions::{JsonSchema, Script},
     cli_args::clap_definitions::{CommandLineArgs, CommandType},
     commands,
-    config_parsing::{human_config, system_config::SystemConfig},
+    config_parsing::{
+        chain_registry, human_config,
+        system_config::{Secrets, SystemConfig},
+    },
     persisted_state::{PersistedState, PersistedStateExists, CURRENT_CRATE_VERSION},
     project_paths::ParsedProjectPaths,
     scripts,
@@ -12,26 +15,40 @@ mod codegen;
 mod dev;
 pub mod init;
 mod local;
+mod record;
+mod validate;
 
 use anyhow::{Context, Result};
 use schemars::schema_for;
 
-pub async fn execute(command_line_args: CommandLineArgs) -> Result<()> {
+///The secrets of the parsed config are collected into `secrets`, even when it fails,
+///so the caller can mask them in the returned error
+pub async fn execute(command_line_args: CommandLineArgs, secrets: &mut Secrets) -> Result<()> {
     let global_project_paths = command_line_args.project_paths;
     let parsed_project_paths = ParsedProjectPaths::try_from(global_project_paths.clone())
         .context("Failed parsing project paths")?;
+    chain_registry::init(&parsed_project_paths.project_root)
+        .context("Failed loading the chains.yaml files")?;
 
     match command_line_args.command {
         CommandType::Init(init_args) => {
-            init::run_init_args(init_args, &global_project_paths).await?;
+            init::run_init_args(init_args, &global_project_paths, secrets).await?;
         }
 
         CommandType::Codegen => {
-            codegen::run_codegen(&parsed_project_paths).await?;
+            codegen::run_codegen(&parsed_project_paths, secrets).await?;
         }
 
-        CommandType::Dev => {
-            dev::run_dev(parsed_project_paths).await?;
+        CommandType::Validate(validate_args) => {
+            validate::run_validate(&validate_args, &parsed_project_paths)?;
+        }
+
+        CommandType::Record(record_args) => {
+            record::run_record(&record_args, &parsed_project_paths, secrets).await?;
+        }
+
+        CommandType::Dev(dev_args) => {
+            dev::run_dev(&dev_args, parsed_project_paths, secrets).await?;
         }
 
         CommandType::Stop => {
@@ -66,8 +83,11 @@ pub async fn execute(command_line_args: CommandLineArgs) -> Result<()> {
             }
 
             if start_args.restart {
-                let config = SystemConfig::parse_from_project_files(&parsed_project_paths)
-                    .context("Failed parsing config")?;
+                let config = SystemConfig::parse_from_project_files_with_secrets(
+                    &parsed_project_paths,
+                    secrets,
+                )
+                .context("Failed parsing config")?;
 
                 let persisted_state = PersistedState::get_current_state(&config)
                     .context("Failed constructing persisted state")?;
@@ -79,7 +99,7 @@ pub async fn execute(command_line_args: CommandLineArgs) -> Result<()> {
         }
 
         CommandType::Local(local_commands) => {
-            local::run_local(&local_commands, &parsed_project_paths).await?;
+            local::run_local(&local_commands, &parsed_project_paths, secrets).await?;
         }
 
         CommandType::BenchmarkSummary => {
@@ -0,0 +1,30 @@
+use crate::{
+    cli_args::clap_definitions::RecordArgs,
+    config_parsing::system_config::{Secrets, SystemConfig},
+    evm::recorder,
+    project_paths::ParsedProjectPaths,
+};
+use anyhow::{Context, Result};
+
+pub async fn run_record(
+    record_args: &RecordArgs,
+    project_paths: &ParsedProjectPaths,
+    secrets: &mut Secrets,
+) -> Result<()> {
+    let config = SystemConfig::parse_from_project_files_with_secrets(project_paths, secrets)
+        .context("Failed parsing config")?;
+
+    let output_dir = project_paths
+        .project_root
+        .join(&record_args.fixture_directory);
+
+    recorder::record(
+        &config,
+        record_args.chain_id,
+        record_args.from_block,
+        record_args.to_block,
+        output_dir,
+    )
+    .await
+    .context("Failed recording fixture")
+}
@@ -0,0 +1,46 @@
+use crate::{
+    cli_args::clap_definitions::{ValidateArgs, ValidateFormat},
+    config_parsing::diagnostics::{self, Severity},
+    project_paths::ParsedProjectPaths,
+};
+use anyhow::{anyhow, Context, Result};
+
+pub fn run_validate(
+    validate_args: &ValidateArgs,
+    project_paths: &ParsedProjectPaths,
+) -> Result<()> {
+    let diagnostics = diagnostics::validate_project(project_paths);
+
+    match validate_args.format {
+        ValidateFormat::Human => {
+            for diagnostic in &diagnostics {
+                println!("{}\n", diagnostic);
+            }
+        }
+        ValidateFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&diagnostics)
+                    .context("Failed serializing diagnostics")?
+            );
+        }
+    }
+
+    let error_count = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+
+    if error_count > 0 {
+        return Err(anyhow!(
+            "Validation failed with {} error(s) in the config and schema files",
+            error_count
+        ));
+    }
+
+    if let ValidateFormat::Human = validate_args.format {
+        println!("Config and schema files are valid");
+    }
+
+    Ok(())
+}
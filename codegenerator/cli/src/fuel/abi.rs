@@ -75,7 +75,7 @@ pub struct FuelAbi {
 }
 
 impl FuelAbi {
-    fn decode_program(raw: &String) -> Result<UnifiedProgramABI> {
+    fn decode_program(raw: &str) -> Result<UnifiedProgramABI> {
         Ok(UnifiedProgramABI::from_json_abi(raw)?)
     }
 
@@ -430,7 +430,7 @@ impl FuelAbi {
         })
     }
 
-    pub fn get_log(&self, log_id: &String) -> Result<FuelLog> {
+    pub fn get_log(&self, log_id: &str) -> Result<FuelLog> {
         match self.logs.get(log_id) {
             Some(log) => Ok(log.clone()),
             None => Err(anyhow!("ABI doesn't contain logged type with id {log_id}")),
@@ -1,14 +1,21 @@
-use std::{collections::HashMap, collections::HashSet, path::PathBuf, vec};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+    vec,
+};
 
 use super::hbs_dir_generator::HandleBarsDirGenerator;
 use crate::{
     config_parsing::{
-        entity_parsing::{Entity, Field, GraphQLEnum, MultiFieldIndex, Schema},
+        entity_parsing::{
+            Aggregation, Entity, Field, GraphQLEnum, GraphQLInterface, MultiFieldIndex, Schema,
+        },
         event_parsing::{abi_to_rescript_type, EthereumEventParam},
+        human_config::evm::FileFormat,
         postgres_types,
         system_config::{
-            self, Abi, Ecosystem, EventKind, FuelEventKind, HyperfuelConfig, HypersyncConfig,
-            RpcConfig, SelectedField, SystemConfig,
+            self, Abi, Ecosystem, EventKind, FuelEventKind, Headers, HyperfuelConfig,
+            HypersyncConfig, RpcConfig, SelectedField, SourceRole, SystemConfig,
         },
     },
     persisted_state::{PersistedState, PersistedStateJsonString},
@@ -23,7 +30,7 @@ use crate::{
     utils::text::{Capitalize, CapitalizedOptions, CaseOptions},
 };
 use anyhow::{anyhow, Context, Result};
-use ethers::abi::EventParam;
+use ethers::abi::{EventParam, Param};
 use pathdiff::diff_paths;
 use serde::Serialize;
 
@@ -67,6 +74,73 @@ impl GraphQlEnumTypeTemplate {
     }
 }
 
+///The query rolling up the data points of a time-series entity into the buckets
+///of one interval of an aggregation
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct AggregationRollupTemplate {
+    pub source_entity: CapitalizedOptions,
+    pub query: String,
+}
+
+impl AggregationRollupTemplate {
+    fn from_config_aggregation(aggregation: &Aggregation, schema: &Schema) -> Result<Vec<Self>> {
+        aggregation
+            .intervals
+            .iter()
+            .map(|interval| {
+                Ok(AggregationRollupTemplate {
+                    source_entity: aggregation.source.to_capitalized_options(),
+                    //Interpolated by the ReScript template string it's templated into
+                    query: aggregation.get_rollup_query(
+                        interval,
+                        schema,
+                        "${Env.Db.publicSchema}",
+                    )?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphQlInterfaceTemplate {
+    pub name: CapitalizedOptions,
+    pub implementers: Vec<CapitalizedOptions>,
+    ///The db field names shared by all the implementers, selected by the union view
+    pub view_field_names: Vec<String>,
+}
+
+impl GraphQlInterfaceTemplate {
+    fn from_config_gql_interface(
+        gql_interface: &GraphQLInterface,
+        schema: &Schema,
+    ) -> Result<Self> {
+        let implementers = gql_interface
+            .get_implementers(schema)
+            .iter()
+            .map(|entity| entity.name.to_capitalized_options())
+            .collect();
+
+        let mut view_field_names = vec![];
+        for field in gql_interface.get_fields() {
+            if field.field_type.is_entity_field(schema)? {
+                view_field_names.push(format!("{}_id", field.name));
+            } else {
+                view_field_names.push(field.name.clone());
+            }
+            if let Some(type_tag_field) = field.get_type_tag_postgres_field(schema)? {
+                view_field_names.push(type_tag_field.field_name);
+            }
+        }
+
+        Ok(GraphQlInterfaceTemplate {
+            name: gql_interface.name.to_capitalized_options(),
+            implementers,
+            view_field_names,
+        })
+    }
+}
+
 #[derive(Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum RelationshipTypeTemplate {
@@ -146,12 +220,20 @@ impl<T: HasIsDerivedFrom + Clone> FilteredTemplateLists<T> {
     }
 }
 
+///The <field>_type part of the tagged relational key of a field referencing an interface
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct EntityTypeTagTemplate {
+    pub res_type: RescriptTypeIdent,
+    pub res_schema_code: String,
+}
+
 #[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct EntityParamTypeTemplate {
     pub field_name: CapitalizedOptions,
     pub res_type: RescriptTypeIdent,
     pub res_schema_code: String,
     pub is_entity_field: bool,
+    pub type_tag: Option<EntityTypeTagTemplate>,
     ///Used in template to tell whether it is a field looked up from another table or a value in
     ///the table
     pub is_derived_from: bool,
@@ -186,12 +268,27 @@ impl EntityParamTypeTemplate {
         //Both of these cases have indexes on them and should exist
         let is_queryable_field = is_indexed_field || is_derived_lookup_field;
 
+        //The implementers of an interface are the variants of the enum generated for it
+        let type_tag = field.get_interface(schema)?.map(|interface| {
+            let tag_type = RescriptTypeIdent::SchemaEnum(interface.name.to_capitalized_options());
+            let res_type = if field.field_type.is_optional() {
+                RescriptTypeIdent::option(tag_type)
+            } else {
+                tag_type
+            };
+            EntityTypeTagTemplate {
+                res_schema_code: res_type.to_rescript_schema(&RescriptSchemaMode::ForDb),
+                res_type,
+            }
+        });
+
         Ok(EntityParamTypeTemplate {
             field_name: field.name.to_capitalized_options(),
             res_schema_code: res_type.to_rescript_schema(&RescriptSchemaMode::ForDb),
             res_type,
             is_derived_from,
             is_entity_field,
+            type_tag,
             is_indexed_field,
             is_queryable_field,
         })
@@ -219,6 +316,9 @@ pub struct DerivedFieldTemplate {
 #[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct EntityRecordTypeTemplate {
     pub name: CapitalizedOptions,
+    pub is_immutable: bool,
+    ///Aggregation entities are only written by the rollups of their source entity
+    pub is_aggregation: bool,
     pub postgres_fields: Vec<postgres_types::Field>,
     pub composite_indices: Vec<Vec<String>>,
     pub derived_fields: Vec<DerivedFieldTemplate>,
@@ -299,14 +399,15 @@ impl EntityRecordTypeTemplate {
             })
             .collect();
 
-        let postgres_fields = entity
-            .get_fields()
-            .iter()
-            .map(|gql_field| gql_field.get_postgres_field(&config.schema, entity))
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .filter_map(|opt| opt)
-            .collect();
+        let mut postgres_fields = vec![];
+        for gql_field in entity.get_fields() {
+            if let Some(pg_field) = gql_field.get_postgres_field(&config.schema, entity)? {
+                postgres_fields.push(pg_field);
+            }
+            if let Some(type_tag_field) = gql_field.get_type_tag_postgres_field(&config.schema)? {
+                postgres_fields.push(type_tag_field);
+            }
+        }
 
         let derived_fields = entity
             .get_fields()
@@ -318,6 +419,8 @@ impl EntityRecordTypeTemplate {
 
         Ok(EntityRecordTypeTemplate {
             name: entity.name.to_capitalized_options(),
+            is_immutable: entity.is_immutable,
+            is_aggregation: entity.aggregation.is_some(),
             postgres_fields,
             derived_fields,
             composite_indices,
@@ -339,6 +442,7 @@ pub struct EventMod {
     pub convert_hyper_sync_event_args_code: String,
     pub event_filter_type: String,
     pub get_topic_selection_code: String,
+    pub config_topic_selection_code: Option<String>,
     pub custom_field_selection: Option<system_config::FieldSelection>,
     pub fuel_event_kind: Option<FuelEventKind>,
 }
@@ -353,6 +457,10 @@ impl EventMod {
         let convert_hyper_sync_event_args_code = &self.convert_hyper_sync_event_args_code;
         let event_filter_type = &self.event_filter_type;
         let get_topic_selection_code = &self.get_topic_selection_code;
+        let config_topic_selection_arg = match &self.config_topic_selection_code {
+            Some(code) => format!("\n  ~configTopicSelection={code},"),
+            None => "".to_string(),
+        };
 
         let fuel_event_kind_code = match self.fuel_event_kind {
             None => None,
@@ -450,7 +558,7 @@ let convertHyperSyncEventArgs = {convert_hyper_sync_event_args_code}
 let handlerRegister: HandlerTypes.Register.t = HandlerTypes.Register.make(
   ~topic0=sighash->EvmTypes.Hex.fromStringUnsafe,
   ~contractName,
-  ~eventName=name,
+  ~eventName=name,{config_topic_selection_arg}
 )
 
 @genType
@@ -479,7 +587,7 @@ impl EventTemplate {
     const CONVERT_HYPER_SYNC_EVENT_ARGS_NEVER: &'static str =
         "_ => Js.Exn.raiseError(\"Not implemented\")";
 
-    pub fn generate_event_filter_type(params: &Vec<EventParam>) -> String {
+    pub fn generate_event_filter_type(params: &[EventParam]) -> String {
         let field_rows = params
             .iter()
             .filter(|param| param.indexed)
@@ -497,7 +605,7 @@ impl EventTemplate {
         format!("{{ {field_rows} }}")
     }
 
-    pub fn generate_get_topic_selection_code(params: &Vec<EventParam>) -> String {
+    pub fn generate_get_topic_selection_code(params: &[EventParam]) -> String {
         let indexed_params = params.iter().filter(|param| param.indexed);
 
         //Prefixed with underscore for cases where it is not used to avoid compiler warnings
@@ -531,7 +639,35 @@ impl EventTemplate {
         )
     }
 
-    pub fn generate_convert_hyper_sync_event_args_code(params: &Vec<EventParam>) -> String {
+    /// The topic selection of the filters defined in the config,
+    /// None when the event isn't filtered in the config
+    pub fn generate_config_topic_selection_code(topic_filters: &[Vec<String>]) -> Option<String> {
+        if topic_filters.iter().all(|topics| topics.is_empty()) {
+            return None;
+        }
+
+        let topic_args = topic_filters
+            .iter()
+            .enumerate()
+            .filter(|(_, topics)| !topics.is_empty())
+            .map(|(i, topics)| {
+                let topic_number = i + 1;
+                let topics = topics
+                    .iter()
+                    .map(|topic| format!("\"{topic}\"->EvmTypes.Hex.fromStringUnsafe"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("~topic{topic_number}=[{topics}], ")
+            })
+            .collect::<String>();
+
+        Some(format!(
+            "LogSelection.makeTopicSelection(~topic0=[sighash->EvmTypes.Hex.fromStringUnsafe], \
+             {topic_args})->Utils.unwrapResultExn"
+        ))
+    }
+
+    pub fn generate_convert_hyper_sync_event_args_code(params: &[EventParam]) -> String {
         if params.is_empty() {
             return Self::CONVERT_HYPER_SYNC_EVENT_ARGS_NOOP.to_string();
         }
@@ -589,6 +725,7 @@ impl EventTemplate {
                 .to_string(),
             event_filter_type: Self::EVENT_FILTER_TYPE_STUB.to_string(),
             get_topic_selection_code: Self::GET_TOPIC_SELECTION_CODE_STUB.to_string(),
+            config_topic_selection_code: None,
             custom_field_selection: config_event.field_selection.clone(),
             fuel_event_kind: Some(fuel_event_kind),
         };
@@ -614,6 +751,7 @@ impl EventTemplate {
                 .to_string(),
             event_filter_type: Self::EVENT_FILTER_TYPE_STUB.to_string(),
             get_topic_selection_code: Self::GET_TOPIC_SELECTION_CODE_STUB.to_string(),
+            config_topic_selection_code: None,
             custom_field_selection: config_event.field_selection.clone(),
             fuel_event_kind: Some(fuel_event_kind),
         };
@@ -624,6 +762,38 @@ impl EventTemplate {
         }
     }
 
+    ///Registers the addresses of the contracts created by the event, which have it
+    ///configured as their factory. Runs before the user defined contractRegister
+    pub fn with_factory_contract_register(
+        self,
+        created_contracts: Vec<(&String, &String)>,
+    ) -> Self {
+        if created_contracts.is_empty() {
+            return self;
+        }
+
+        let registrations = created_contracts
+            .into_iter()
+            .map(|(contract_name, address_param)| {
+                format!(
+                    "  context.add{}(event.params.{})\n",
+                    contract_name.capitalize(),
+                    RescriptRecordField::to_valid_res_name(address_param)
+                )
+            })
+            .collect::<String>();
+
+        EventTemplate {
+            module_code: format!(
+                "{}\nhandlerRegister->HandlerTypes.Register.setFactoryContractRegister(({{event, \
+                 context}}: Internal.genericContractRegisterArgs<event, contractRegistrations>) \
+                 => {{\n{registrations}}})\n",
+                self.module_code
+            ),
+            ..self
+        }
+    }
+
     pub fn from_config_event(config_event: &system_config::Event) -> Result<Self> {
         let event_name = config_event.name.capitalize();
         match &config_event.kind {
@@ -668,11 +838,14 @@ impl EventTemplate {
                     event_name: event_name.clone(),
                     data_type: data_type_expr.to_string(),
                     params_raw_event_schema: data_type_expr
-                        .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                        .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
                     convert_hyper_sync_event_args_code:
                         Self::generate_convert_hyper_sync_event_args_code(params),
                     event_filter_type: Self::generate_event_filter_type(params),
                     get_topic_selection_code: Self::generate_get_topic_selection_code(params),
+                    config_topic_selection_code: Self::generate_config_topic_selection_code(
+                        &config_event.topic_filters,
+                    ),
                     custom_field_selection: config_event.field_selection.clone(),
                     fuel_event_kind: None,
                 };
@@ -701,6 +874,7 @@ impl EventTemplate {
                             event_filter_type: Self::EVENT_FILTER_TYPE_STUB.to_string(),
                             get_topic_selection_code: Self::GET_TOPIC_SELECTION_CODE_STUB
                                 .to_string(),
+                            config_topic_selection_code: None,
                             custom_field_selection: config_event.field_selection.clone(),
                             fuel_event_kind: Some(fuel_event_kind),
                         };
@@ -723,11 +897,113 @@ impl EventTemplate {
     }
 }
 
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct CallTemplate {
+    pub name: String,
+    pub module_code: String,
+}
+
+impl CallTemplate {
+    fn params_type_expr(params: &[Param]) -> RescriptTypeExpr {
+        if params.is_empty() {
+            RescriptTypeExpr::Identifier(RescriptTypeIdent::Unit)
+        } else {
+            RescriptTypeExpr::Record(
+                params
+                    .iter()
+                    .map(|p| {
+                        RescriptRecordField::new(
+                            p.name.to_string(),
+                            abi_to_rescript_type(&p.into()),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    fn params_names_code(params: &[Param]) -> String {
+        format!(
+            "[{}]",
+            params
+                .iter()
+                .map(|p| format!("\"{}\"", p.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    pub fn from_config_call(config_call: &system_config::Call) -> Self {
+        let call_name = config_call.name.capitalize();
+        let sighash = &config_call.sighash;
+        let function_name = &config_call.function.name;
+        let inputs_expr = Self::params_type_expr(&config_call.function.inputs);
+        let outputs_expr = Self::params_type_expr(&config_call.function.outputs);
+
+        let inputs_type = inputs_expr.to_string();
+        let outputs_type = outputs_expr.to_string();
+        let inputs_schema = inputs_expr.to_rescript_schema("inputs", &RescriptSchemaMode::ForDb);
+        let outputs_schema = outputs_expr.to_rescript_schema("outputs", &RescriptSchemaMode::ForDb);
+        let input_names = Self::params_names_code(&config_call.function.inputs);
+        let output_names = Self::params_names_code(&config_call.function.outputs);
+
+        let module_code = format!(
+            r#"
+let sighash = "{sighash}"
+let name = "{call_name}"
+let contractName = contractName
+
+@genType
+type inputs = {inputs_type}
+@genType
+type outputs = {outputs_type}
+
+let inputsSchema = {inputs_schema}
+let outputsSchema = {outputs_schema}
+let paramsRawEventSchema = S.object(s =>
+  {{
+    "inputs": s.field("inputs", inputsSchema),
+    "outputs": s.field("outputs", outputsSchema),
+  }}
+)
+
+@genType
+type call = Internal.genericCall<inputs, outputs, Block.t, Transaction.t>
+@genType
+type handlerArgs = Internal.genericCallHandlerArgs<call, handlerContext>
+@genType
+type handler = Internal.genericHandler<handlerArgs>
+
+let handlerRef: ref<option<handler>> = ref(None)
+
+let register = (): Internal.callConfig => {{
+  name,
+  contractName,
+  sighash,
+  functionName: "{function_name}",
+  inputNames: {input_names},
+  outputNames: {output_names},
+  paramsRawEventSchema: paramsRawEventSchema->(
+    Utils.magic: S.t<{{"inputs": inputs, "outputs": outputs}}> => S.t<Internal.eventParams>
+  ),
+  handler: handlerRef.contents->(Utils.magic: option<handler> => option<Internal.callHandler>),
+}}"#
+        );
+
+        CallTemplate {
+            name: call_name,
+            module_code,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, PartialEq, Clone)]
 pub struct ContractTemplate {
     pub name: CapitalizedOptions,
     pub codegen_events: Vec<EventTemplate>,
+    pub codegen_calls: Vec<CallTemplate>,
     pub chain_ids: Vec<u64>,
+    pub addresses: Vec<EthAddress>,
     pub module_code: String,
     pub handler: HandlerPathsTemplate,
 }
@@ -744,8 +1020,37 @@ impl ContractTemplate {
         let codegen_events = contract
             .events
             .iter()
-            .map(|event| EventTemplate::from_config_event(event))
+            .map(|event| -> Result<EventTemplate> {
+                let created_contracts = config
+                    .get_contracts()
+                    .into_iter()
+                    .filter_map(|created_contract| match &created_contract.factory {
+                        Some(factory)
+                            if factory.contract_name == contract.name
+                                && factory.event_name == event.name =>
+                        {
+                            Some((&created_contract.name, &factory.address_param))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                //The generated types of the event have the fields selected by every network
+                let event = system_config::Event {
+                    field_selection: event
+                        .field_selection
+                        .as_ref()
+                        .map(|field_selection| config.with_network_fields(field_selection)),
+                    ..event.clone()
+                };
+                Ok(EventTemplate::from_config_event(&event)?
+                    .with_factory_contract_register(created_contracts))
+            })
             .collect::<Result<_>>()?;
+        let codegen_calls = contract
+            .calls
+            .iter()
+            .map(CallTemplate::from_config_call)
+            .collect();
 
         let module_code = match &contract.abi {
             Abi::Evm(abi) => {
@@ -781,12 +1086,15 @@ let eventSignatures = [{}]"#,
         };
 
         let chain_ids = contract.get_chain_ids(config);
+        let addresses = contract.get_addresses(config);
 
         Ok(ContractTemplate {
             name,
             handler,
             codegen_events,
+            codegen_calls,
             chain_ids,
+            addresses,
             module_code,
         })
     }
@@ -810,6 +1118,9 @@ pub struct PerNetworkContractTemplate {
     name: CapitalizedOptions,
     addresses: Vec<EthAddress>,
     events: Vec<PerNetworkContractEventTemplate>,
+    calls: Vec<PerNetworkContractEventTemplate>,
+    start_block: Option<u64>,
+    end_block: Option<u64>,
 }
 
 impl PerNetworkContractTemplate {
@@ -827,10 +1138,19 @@ impl PerNetworkContractTemplate {
             .map(|event| PerNetworkContractEventTemplate::new(event.name.clone()))
             .collect();
 
+        let calls = contract
+            .calls
+            .iter()
+            .map(|call| PerNetworkContractEventTemplate::new(call.name.clone()))
+            .collect();
+
         Ok(PerNetworkContractTemplate {
             name: network_contract.name.to_capitalized_options(),
             addresses: network_contract.addresses.clone(),
             events,
+            calls,
+            start_block: network_contract.start_block,
+            end_block: network_contract.end_block,
         })
     }
 }
@@ -838,39 +1158,179 @@ impl PerNetworkContractTemplate {
 type EthAddress = String;
 
 #[derive(Debug, Serialize, PartialEq, Clone)]
-struct NetworkTemplate {
-    pub id: u64,
+struct FileConfigTemplate {
+    path: HandlerPathsTemplate,
+    is_parquet: bool,
+}
+
+impl FileConfigTemplate {
+    fn from_config_file_config(
+        file_config: &system_config::FileConfig,
+        project_paths: &ParsedProjectPaths,
+    ) -> Result<Self> {
+        let path = HandlerPathsTemplate::from_handler_path(&file_config.path, project_paths)
+            .context("Failed getting file_config path")?;
+        Ok(FileConfigTemplate {
+            path,
+            is_parquet: file_config.format == FileFormat::Parquet,
+        })
+    }
+}
+
+///Header values can contain any printable character, so they're escaped
+///instead of being pasted into the string literal as is
+fn to_rescript_string_literal(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn headers_code(headers: &Headers) -> String {
+    let entries = headers
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "({}, {})",
+                to_rescript_string_literal(name),
+                to_rescript_string_literal(value)
+            )
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        "None".to_string()
+    } else {
+        format!("Some(Js.Dict.fromArray([{}]))", entries.join(", "))
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+struct NetworkSourceTemplate {
+    role: SourceRole,
     rpc_config: Option<RpcConfig>,
     hypersync_config: Option<HypersyncConfig>,
     hyperfuel_config: Option<HyperfuelConfig>,
-    confirmed_block_threshold: i32,
-    start_block: u64,
-    end_block: Option<u64>,
+    file_config: Option<FileConfigTemplate>,
+    //The endpoint headers as an optional dict, eg. Some(Js.Dict.fromArray([("key", "value")]))
+    headers_code: String,
 }
 
-impl NetworkTemplate {
-    fn from_config_network(network: &system_config::Network) -> Self {
-        NetworkTemplate {
-            id: network.id,
-            rpc_config: match &network.sync_source {
+impl NetworkSourceTemplate {
+    fn from_config_network_source(
+        source: &system_config::NetworkSource,
+        project_paths: &ParsedProjectPaths,
+    ) -> Result<Self> {
+        Ok(NetworkSourceTemplate {
+            role: source.role.clone(),
+            headers_code: match &source.sync_source {
+                system_config::SyncSource::RpcConfig(rpc_config) => {
+                    headers_code(&rpc_config.headers)
+                }
+                system_config::SyncSource::HypersyncConfig(hypersync_config) => {
+                    headers_code(&hypersync_config.headers)
+                }
+                _ => "None".to_string(),
+            },
+            rpc_config: match &source.sync_source {
                 system_config::SyncSource::RpcConfig(rpc_config) => Some(rpc_config.clone()),
                 _ => None,
             },
-            hypersync_config: match &network.sync_source {
+            hypersync_config: match &source.sync_source {
                 system_config::SyncSource::HypersyncConfig(hypersync_config) => {
                     Some(hypersync_config.clone())
                 }
                 _ => None,
             },
-            hyperfuel_config: match &network.sync_source {
+            hyperfuel_config: match &source.sync_source {
                 system_config::SyncSource::HyperfuelConfig(hyperfuel_config) => {
                     Some(hyperfuel_config.clone())
                 }
                 _ => None,
             },
+            file_config: match &source.sync_source {
+                system_config::SyncSource::FileConfig(file_config) => Some(
+                    FileConfigTemplate::from_config_file_config(file_config, project_paths)?,
+                ),
+                _ => None,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+struct NetworkTemplate {
+    pub id: u64,
+    sources: Vec<NetworkSourceTemplate>,
+    source_stall_timeout: u32,
+    confirmed_block_threshold: i32,
+    start_block: u64,
+    end_block: Option<u64>,
+    //Overrides the global enable_raw_events for the network, eg. Some(true)
+    enable_raw_events_code: String,
+    //Fields of the generated types which only the other networks select,
+    //so the sources of the network don't query them
+    excluded_block_fields: Vec<String>,
+    excluded_transaction_fields: Vec<String>,
+}
+
+impl NetworkTemplate {
+    fn from_config_network(
+        network: &system_config::Network,
+        config: &SystemConfig,
+    ) -> Result<Self> {
+        let project_paths = &config.parsed_project_paths;
+        let other_network_fields = config.get_other_network_fields(network);
+        let get_field_names = |fields: &[SelectedField]| {
+            fields
+                .iter()
+                .map(|field| CaseOptions::from(field.name.clone()).camel)
+                .collect::<Vec<_>>()
+        };
+        Ok(NetworkTemplate {
+            id: network.id,
+            sources: network
+                .sources
+                .iter()
+                .map(|source| {
+                    NetworkSourceTemplate::from_config_network_source(source, project_paths)
+                })
+                .collect::<Result<_>>()?,
+            source_stall_timeout: network.source_stall_timeout,
             confirmed_block_threshold: network.confirmed_block_threshold,
             start_block: network.start_block,
             end_block: network.end_block,
+            enable_raw_events_code: match network.raw_events {
+                Some(raw_events) => format!("Some({raw_events})"),
+                None => "None".to_string(),
+            },
+            excluded_block_fields: get_field_names(&other_network_fields.block_fields),
+            excluded_transaction_fields: get_field_names(&other_network_fields.transaction_fields),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+struct NetworkBlockHandlerTemplate {
+    name: CapitalizedOptions,
+    interval: u64,
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+}
+
+impl NetworkBlockHandlerTemplate {
+    fn from_config_block_handler(block_handler: &system_config::BlockHandler) -> Self {
+        NetworkBlockHandlerTemplate {
+            name: block_handler.name.to_capitalized_options(),
+            interval: block_handler.interval,
+            start_block: block_handler.start_block,
+            end_block: block_handler.end_block,
         }
     }
 }
@@ -879,6 +1339,7 @@ impl NetworkTemplate {
 pub struct NetworkConfigTemplate {
     network_config: NetworkTemplate,
     codegen_contracts: Vec<PerNetworkContractTemplate>,
+    block_handlers: Vec<NetworkBlockHandlerTemplate>,
 }
 
 impl NetworkConfigTemplate {
@@ -886,7 +1347,7 @@ impl NetworkConfigTemplate {
         network: &system_config::Network,
         config: &SystemConfig,
     ) -> Result<Self> {
-        let network_config = NetworkTemplate::from_config_network(network);
+        let network_config = NetworkTemplate::from_config_network(network, config)?;
         let codegen_contracts = network
             .contracts
             .iter()
@@ -895,10 +1356,16 @@ impl NetworkConfigTemplate {
             })
             .collect::<Result<_>>()
             .context("Failed mapping network contracts")?;
+        let block_handlers = network
+            .block_handlers
+            .iter()
+            .map(NetworkBlockHandlerTemplate::from_config_block_handler)
+            .collect();
 
         Ok(NetworkConfigTemplate {
             network_config,
             codegen_contracts,
+            block_handlers,
         })
     }
 }
@@ -981,18 +1448,22 @@ impl FieldSelection {
     }
 
     fn aggregated_selection(cfg: &system_config::SystemConfig) -> Self {
-        let mut transaction_fields: HashSet<_> = cfg
-            .field_selection
+        let global_field_selection = cfg.with_network_fields(&cfg.field_selection);
+        let mut transaction_fields: HashSet<_> = global_field_selection
             .transaction_fields
             .iter()
             .cloned()
             .collect();
-        let mut block_fields: HashSet<_> =
-            cfg.field_selection.block_fields.iter().cloned().collect();
+        let mut block_fields: HashSet<_> = global_field_selection
+            .block_fields
+            .iter()
+            .cloned()
+            .collect();
 
         cfg.contracts.iter().for_each(|(_name, contract)| {
             contract.events.iter().for_each(|event| {
                 if let Some(field_selection) = &event.field_selection {
+                    let field_selection = cfg.with_network_fields(field_selection);
                     field_selection.transaction_fields.iter().for_each(|field| {
                         transaction_fields.insert(field.clone());
                     });
@@ -1019,12 +1490,22 @@ struct SelectedFieldTemplate {
     default_value_rescript: String,
 }
 
+#[derive(Serialize)]
+struct BlockHandlerTemplate {
+    name: CapitalizedOptions,
+}
+
 #[derive(Serialize)]
 pub struct ProjectTemplate {
     project_name: String,
     codegen_contracts: Vec<ContractTemplate>,
+    block_handlers: Vec<BlockHandlerTemplate>,
+    //Handler files with block handlers which aren't already registered by a contract
+    block_handler_files: Vec<HandlerPathsTemplate>,
     entities: Vec<EntityRecordTypeTemplate>,
     gql_enums: Vec<GraphQlEnumTypeTemplate>,
+    gql_interfaces: Vec<GraphQlInterfaceTemplate>,
+    aggregation_rollups: Vec<AggregationRollupTemplate>,
     chain_configs: Vec<NetworkConfigTemplate>,
     codegen_out_path: String,
     persisted_state: PersistedStateJsonString,
@@ -1037,6 +1518,8 @@ pub struct ProjectTemplate {
     aggregated_field_selection: FieldSelection,
     is_evm_ecosystem: bool,
     is_fuel_ecosystem: bool,
+    //Adds the parquet reader dependency when a network reads parquet recordings
+    has_parquet_file_source: bool,
     //Used for the package.json reference to handlers in generated
     relative_path_to_root_from_generated: String,
 }
@@ -1072,6 +1555,33 @@ impl ProjectTemplate {
             .collect::<Result<_>>()
             .context("Failed generating contract template types")?;
 
+        //Block handlers with the same name on different networks share a single
+        //registration, so only unique names are templated
+        let block_handlers: Vec<BlockHandlerTemplate> = cfg
+            .get_block_handlers()
+            .iter()
+            .map(|block_handler| block_handler.name.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|name| BlockHandlerTemplate {
+                name: name.to_capitalized_options(),
+            })
+            .collect();
+
+        let mut block_handler_files: Vec<HandlerPathsTemplate> = vec![];
+        for block_handler in cfg.get_block_handlers() {
+            let handler =
+                HandlerPathsTemplate::from_handler_path(&block_handler.handler_path, project_paths)
+                    .context("Failed getting block handler paths")?;
+            let is_registered = codegen_contracts
+                .iter()
+                .any(|contract| contract.handler == handler)
+                || block_handler_files.contains(&handler);
+            if !is_registered {
+                block_handler_files.push(handler);
+            }
+        }
+
         let entities: Vec<EntityRecordTypeTemplate> = cfg
             .get_entities()
             .iter()
@@ -1086,6 +1596,26 @@ impl ProjectTemplate {
             .collect::<Result<_>>()
             .context("Failed generating enum template types")?;
 
+        let gql_interfaces: Vec<GraphQlInterfaceTemplate> = cfg
+            .get_gql_interfaces()
+            .iter()
+            .map(|gql_interface| {
+                GraphQlInterfaceTemplate::from_config_gql_interface(gql_interface, &cfg.schema)
+            })
+            .collect::<Result<_>>()
+            .context("Failed generating interface template types")?;
+
+        let mut aggregation_rollups: Vec<AggregationRollupTemplate> = vec![];
+        for aggregation in cfg.get_aggregations() {
+            aggregation_rollups.extend(
+                AggregationRollupTemplate::from_config_aggregation(aggregation, &cfg.schema)
+                    .context(format!(
+                        "Failed generating rollups of aggregation {}",
+                        aggregation.name
+                    ))?,
+            );
+        }
+
         let chain_configs: Vec<NetworkConfigTemplate> = cfg
             .get_networks()
             .iter()
@@ -1093,6 +1623,15 @@ impl ProjectTemplate {
             .collect::<Result<_>>()
             .context("Failed generating chain configs template")?;
 
+        let has_parquet_file_source = chain_configs.iter().any(|chain_config| {
+            chain_config.network_config.sources.iter().any(|source| {
+                source
+                    .file_config
+                    .as_ref()
+                    .is_some_and(|file_config| file_config.is_parquet)
+            })
+        });
+
         let persisted_state = PersistedState::get_current_state(cfg)
             .context("Failed creating default persisted state")?
             .into();
@@ -1121,15 +1660,20 @@ impl ProjectTemplate {
             diff_from_current(&project_paths.project_root, &project_paths.generated)
                 .context("Failed to diff generated to root path")?;
 
-        let global_field_selection = FieldSelection::global_selection(&cfg.field_selection);
+        let global_field_selection =
+            FieldSelection::global_selection(&cfg.with_network_fields(&cfg.field_selection));
         // TODO: Remove schemas for aggreaged, since they are not used in runtime
         let aggregated_field_selection = FieldSelection::aggregated_selection(&cfg);
 
         Ok(ProjectTemplate {
             project_name: cfg.name.clone(),
             codegen_contracts,
+            block_handlers,
+            block_handler_files,
             entities,
             gql_enums,
+            gql_interfaces,
+            aggregation_rollups,
             chain_configs,
             codegen_out_path: gitignore_path_str,
             persisted_state,
@@ -1142,6 +1686,7 @@ impl ProjectTemplate {
             aggregated_field_selection,
             is_evm_ecosystem: cfg.get_ecosystem() == Ecosystem::Evm,
             is_fuel_ecosystem: cfg.get_ecosystem() == Ecosystem::Fuel,
+            has_parquet_file_source,
             //Used for the package.json reference to handlers in generated
             relative_path_to_root_from_generated,
         })
@@ -1188,16 +1733,31 @@ mod test {
         project_template
     }
 
-    impl Default for NetworkTemplate {
+    impl Default for NetworkSourceTemplate {
         fn default() -> Self {
             Self {
-                id: 0,
+                role: SourceRole::Primary,
                 rpc_config: None,
                 hypersync_config: None,
                 hyperfuel_config: None,
+                file_config: None,
+                headers_code: "None".to_string(),
+            }
+        }
+    }
+
+    impl Default for NetworkTemplate {
+        fn default() -> Self {
+            Self {
+                id: 0,
+                sources: vec![],
+                source_stall_timeout: 20_000,
                 confirmed_block_threshold: 200,
                 start_block: 0,
                 end_block: None,
+                enable_raw_events_code: "None".to_string(),
+                excluded_block_fields: vec![],
+                excluded_transaction_fields: vec![],
             }
         }
     }
@@ -1208,6 +1768,8 @@ mod test {
 
         let rpc_config1 = RpcConfig {
             urls: vec!["https://eth.com".to_string()],
+            ws_url: None,
+            headers: Headers::default(),
             sync_config: system_config::SyncConfig {
                 acceleration_additive: 2_000,
                 ..system_config::SyncConfig::default()
@@ -1216,7 +1778,10 @@ mod test {
 
         let network1 = NetworkTemplate {
             id: 1,
-            rpc_config: Some(rpc_config1),
+            sources: vec![NetworkSourceTemplate {
+                rpc_config: Some(rpc_config1),
+                ..NetworkSourceTemplate::default()
+            }],
             ..NetworkTemplate::default()
         };
 
@@ -1225,11 +1790,15 @@ mod test {
             name: String::from("Contract1").to_capitalized_options(),
             addresses: vec![address1.clone()],
             events,
+            calls: vec![],
+            start_block: None,
+            end_block: None,
         };
 
         let chain_config_1 = super::NetworkConfigTemplate {
             network_config: network1,
             codegen_contracts: vec![contract1],
+            block_handlers: vec![],
         };
 
         let expected_chain_configs = vec![chain_config_1];
@@ -1255,6 +1824,8 @@ mod test {
 
         let rpc_config1 = RpcConfig {
             urls: vec!["https://eth.com".to_string()],
+            ws_url: None,
+            headers: Headers::default(),
             sync_config: system_config::SyncConfig {
                 acceleration_additive: 2_000,
                 ..system_config::SyncConfig::default()
@@ -1262,7 +1833,10 @@ mod test {
         };
         let network1 = NetworkTemplate {
             id: 1,
-            rpc_config: Some(rpc_config1.clone()),
+            sources: vec![NetworkSourceTemplate {
+                rpc_config: Some(rpc_config1.clone()),
+                ..NetworkSourceTemplate::default()
+            }],
             ..NetworkTemplate::default()
         };
 
@@ -1272,6 +1846,8 @@ mod test {
                 // Should support fallback urls
                 "https://eth.com/fallback".to_string(),
             ],
+            ws_url: None,
+            headers: Headers::default(),
             sync_config: system_config::SyncConfig {
                 acceleration_additive: 2_000,
                 ..system_config::SyncConfig::default()
@@ -1280,7 +1856,10 @@ mod test {
 
         let network2 = NetworkTemplate {
             id: 2,
-            rpc_config: Some(rpc_config2),
+            sources: vec![NetworkSourceTemplate {
+                rpc_config: Some(rpc_config2),
+                ..NetworkSourceTemplate::default()
+            }],
             ..NetworkTemplate::default()
         };
 
@@ -1289,6 +1868,9 @@ mod test {
             name: String::from("Contract1").to_capitalized_options(),
             addresses: vec![address1.clone()],
             events,
+            calls: vec![],
+            start_block: None,
+            end_block: None,
         };
 
         let events = get_per_contract_events_vec_helper(vec!["NewGravatar", "UpdatedGravatar"]);
@@ -1296,15 +1878,20 @@ mod test {
             name: String::from("Contract2").to_capitalized_options(),
             addresses: vec![address2.clone()],
             events,
+            calls: vec![],
+            start_block: None,
+            end_block: None,
         };
 
         let chain_config_1 = super::NetworkConfigTemplate {
             network_config: network1,
             codegen_contracts: vec![contract1],
+            block_handlers: vec![],
         };
         let chain_config_2 = super::NetworkConfigTemplate {
             network_config: network2,
             codegen_contracts: vec![contract2],
+            block_handlers: vec![],
         };
 
         let expected_chain_configs = vec![chain_config_1, chain_config_2];
@@ -1320,10 +1907,14 @@ mod test {
 
         let network1 = NetworkTemplate {
             id: 1,
-            hypersync_config: Some(HypersyncConfig {
-                endpoint_url: "https://1.hypersync.xyz".to_string(),
-                is_client_decoder: true,
-            }),
+            sources: vec![NetworkSourceTemplate {
+                hypersync_config: Some(HypersyncConfig {
+                    endpoint_url: "https://1.hypersync.xyz".to_string(),
+                    headers: Headers::default(),
+                    is_client_decoder: true,
+                }),
+                ..NetworkSourceTemplate::default()
+            }],
             ..NetworkTemplate::default()
         };
 
@@ -1333,11 +1924,15 @@ mod test {
             name: String::from("Contract1").to_capitalized_options(),
             addresses: vec![address1.clone()],
             events,
+            calls: vec![],
+            start_block: None,
+            end_block: None,
         };
 
         let chain_config_1 = super::NetworkConfigTemplate {
             network_config: network1,
             codegen_contracts: vec![contract1],
+            block_handlers: vec![],
         };
 
         let expected_chain_configs = vec![chain_config_1];
@@ -1351,30 +1946,40 @@ mod test {
     fn convert_to_chain_configs_case_4() {
         let network1 = NetworkTemplate {
             id: 1,
-            hypersync_config: Some(HypersyncConfig {
-                endpoint_url: "https://myskar.com".to_string(),
-                is_client_decoder: true,
-            }),
+            sources: vec![NetworkSourceTemplate {
+                hypersync_config: Some(HypersyncConfig {
+                    endpoint_url: "https://myskar.com".to_string(),
+                    headers: Headers::default(),
+                    is_client_decoder: true,
+                }),
+                ..NetworkSourceTemplate::default()
+            }],
             ..NetworkTemplate::default()
         };
 
         let network2 = NetworkTemplate {
             id: 137,
-            hypersync_config: Some(HypersyncConfig {
-                endpoint_url: "https://137.hypersync.xyz".to_string(),
-                is_client_decoder: true,
-            }),
+            sources: vec![NetworkSourceTemplate {
+                hypersync_config: Some(HypersyncConfig {
+                    endpoint_url: "https://137.hypersync.xyz".to_string(),
+                    headers: Headers::default(),
+                    is_client_decoder: true,
+                }),
+                ..NetworkSourceTemplate::default()
+            }],
             ..NetworkTemplate::default()
         };
 
         let chain_config_1 = super::NetworkConfigTemplate {
             network_config: network1,
             codegen_contracts: vec![],
+            block_handlers: vec![],
         };
 
         let chain_config_2 = super::NetworkConfigTemplate {
             network_config: network2,
             codegen_contracts: vec![],
+            block_handlers: vec![],
         };
 
         let expected_chain_configs = vec![chain_config_1, chain_config_2];
@@ -1390,6 +1995,30 @@ mod test {
         get_project_template_helper("config5.yaml");
     }
 
+    #[test]
+    fn chain_configs_exclude_the_fields_of_other_networks() {
+        let project_template = get_project_template_helper("network-field-selection-config.yaml");
+
+        let network_configs = project_template
+            .chain_configs
+            .iter()
+            .map(|chain_config| &chain_config.network_config)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            network_configs[0].excluded_transaction_fields,
+            vec!["l1Fee".to_string(), "gasUsedForL1".to_string()],
+            "The RPC network shouldn't query the L1 fee fields of the other network"
+        );
+        assert_eq!(
+            network_configs[0].excluded_block_fields,
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            network_configs[1].excluded_transaction_fields,
+            Vec::<String>::new()
+        );
+    }
+
     const RESCRIPT_BIG_INT_TYPE: RescriptTypeIdent = RescriptTypeIdent::BigInt;
     const RESCRIPT_ADDRESS_TYPE: RescriptTypeIdent = RescriptTypeIdent::Address;
     const RESCRIPT_STRING_TYPE: RescriptTypeIdent = RescriptTypeIdent::String;
@@ -1478,6 +2107,7 @@ let getTopicSelection = (eventFilters) => eventFilters->SingleOrMultiple.normali
             sighash: "0x50f7d27e90d1a5a38aeed4ceced2e8ec1ff185737aca96d15791b470d3f17363"
                 .to_string(),
             field_selection: None,
+            topic_filters: vec![],
         })
         .unwrap();
 
@@ -1545,6 +2175,7 @@ let getTopicSelection = (eventFilters) => eventFilters->SingleOrMultiple.normali
                     data_type: RescriptTypeIdent::option(RescriptTypeIdent::Address),
                 }],
             }),
+            topic_filters: vec![],
         })
         .unwrap();
 
@@ -1623,4 +2254,75 @@ let getTopicSelection = (eventFilters) => eventFilters->SingleOrMultiple.normali
 
         assert_eq!(&expected_event_template, new_gavatar_event_template);
     }
+
+    #[test]
+    fn event_template_with_factory_contract_register() {
+        let event_template = EventTemplate {
+            name: "PoolCreated".to_string(),
+            module_code: "let name = \"PoolCreated\"".to_string(),
+            params: vec![],
+        };
+
+        let pool = "Pool".to_string();
+        let pool_param = "pool".to_string();
+        let pair = "pair".to_string();
+        let type_param = "type".to_string();
+
+        assert_eq!(
+            event_template
+                .clone()
+                .with_factory_contract_register(vec![(&pool, &pool_param), (&pair, &type_param)])
+                .module_code,
+            r#"let name = "PoolCreated"
+handlerRegister->HandlerTypes.Register.setFactoryContractRegister(({event, context}: Internal.genericContractRegisterArgs<event, contractRegistrations>) => {
+  context.addPool(event.params.pool)
+  context.addPair(event.params.type_)
+})
+"#
+        );
+        assert_eq!(
+            event_template
+                .clone()
+                .with_factory_contract_register(vec![]),
+            event_template
+        );
+    }
+
+    #[test]
+    fn generates_config_topic_selection_code() {
+        assert_eq!(
+            EventTemplate::generate_config_topic_selection_code(&[]),
+            None
+        );
+        assert_eq!(
+            EventTemplate::generate_config_topic_selection_code(&[vec![], vec![]]),
+            None
+        );
+        assert_eq!(
+            EventTemplate::generate_config_topic_selection_code(&[
+                vec![],
+                vec!["0x01".to_string(), "0x02".to_string()]
+            ]),
+            Some(
+                "LogSelection.makeTopicSelection(~topic0=[sighash->EvmTypes.Hex.fromStringUnsafe], \
+                 ~topic2=[\"0x01\"->EvmTypes.Hex.fromStringUnsafe, \
+                 \"0x02\"->EvmTypes.Hex.fromStringUnsafe], )->Utils.unwrapResultExn"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn generates_escaped_headers_code() {
+        use std::collections::BTreeMap;
+
+        assert_eq!(super::headers_code(&Headers::default()), "None");
+        assert_eq!(
+            super::headers_code(&Headers::from(BTreeMap::from([
+                ("Authorization".to_string(), "Bearer my-token".to_string()),
+                ("x-api-key".to_string(), r#"key"with\quotes"#.to_string()),
+            ]))),
+            r#"Some(Js.Dict.fromArray([("Authorization", "Bearer my-token"), ("x-api-key", "key\"with\\quotes")]))"#
+        );
+    }
 }
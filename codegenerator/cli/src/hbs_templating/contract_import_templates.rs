@@ -202,7 +202,7 @@ use anyhow::{Context, Result};
 use ethers::abi::ParamType;
 use nested_params::{flatten_event_inputs, FlattenedEventParam, ParamIndex};
 use serde::Serialize;
-use std::{path::PathBuf, vec};
+use std::{path::Path, vec};
 
 ///The struct that houses all the details of each contract necessary for
 ///populating the contract import templates
@@ -395,7 +395,7 @@ impl AutoSchemaHandlerTemplate {
     pub fn generate_contract_import_templates(
         &self,
         lang: &Language,
-        project_root: &PathBuf,
+        project_root: &Path,
     ) -> Result<()> {
         let template_dirs = TemplateDirs::new();
 
@@ -407,8 +407,8 @@ impl AutoSchemaHandlerTemplate {
             .get_contract_import_lang_dir(lang)
             .context(format!("Failed getting {} contract import templates", lang))?;
 
-        let hbs = HandleBarsDirGenerator::new(&lang_dir, &self, &project_root);
-        let hbs_shared = HandleBarsDirGenerator::new(&shared_dir, &self, &project_root);
+        let hbs = HandleBarsDirGenerator::new(&lang_dir, &self, project_root);
+        let hbs_shared = HandleBarsDirGenerator::new(&shared_dir, &self, project_root);
         hbs.generate_hbs_templates().context(format!(
             "Failed generating {} contract import templates",
             lang
@@ -423,7 +423,7 @@ impl AutoSchemaHandlerTemplate {
     pub fn generate_subgraph_migration_templates(
         &self,
         lang: &Language,
-        project_root: &PathBuf,
+        project_root: &Path,
     ) -> Result<()> {
         let template_dirs = TemplateDirs::new();
 
@@ -434,7 +434,7 @@ impl AutoSchemaHandlerTemplate {
                 lang
             ))?;
 
-        let hbs = HandleBarsDirGenerator::new(&lang_dir, &self, &project_root);
+        let hbs = HandleBarsDirGenerator::new(&lang_dir, &self, project_root);
 
         hbs.generate_hbs_templates().context(format!(
             "Failed generating {} subgraph migration templates",
@@ -1,13 +1,17 @@
-use anyhow::{Context, Result};
+use anyhow::Context;
 use clap::Parser;
-use envio::{clap_definitions::CommandLineArgs, executor};
+use envio::{clap_definitions::CommandLineArgs, config_parsing::system_config::Secrets, executor};
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let command_line_args = CommandLineArgs::parse();
-    executor::execute(command_line_args)
+    let mut secrets = Secrets::default();
+    if let Err(err) = executor::execute(command_line_args, &mut secrets)
         .await
-        .context("Failed cli execution")?;
-
-    Ok(())
+        .context("Failed cli execution")
+    {
+        //Print it like returning the error from main would, but with the config secrets masked
+        eprintln!("Error: {}", secrets.mask(&format!("{err:?}")));
+        std::process::exit(1);
+    }
 }
@@ -1,4 +1,4 @@
-use super::{PersistedState, PersistedStateExists};
+use super::{PersistedState, PersistedStateExists, SchemaMigrationPlan};
 use crate::config_parsing::system_config::EnvState;
 use sqlx::postgres::{PgPool, PgPoolOptions, PgQueryResult};
 use std::env;
@@ -19,6 +19,13 @@ async fn get_pg_pool() -> Result<PgPool, sqlx::Error> {
     PgPoolOptions::new().connect(&connection_url).await
 }
 
+pub fn get_public_schema() -> String {
+    let mut env_state = EnvState::new(&std::env::current_dir().unwrap_or_default());
+    env_state
+        .var("ENVIO_PG_PUBLIC_SCHEMA")
+        .unwrap_or_else(|| "public".to_string())
+}
+
 impl PersistedState {
     pub async fn upsert_to_db(&self) -> Result<PgQueryResult, sqlx::Error> {
         let pool = get_pg_pool().await?;
@@ -26,10 +33,7 @@ impl PersistedState {
     }
 
     async fn upsert_to_db_with_pool(&self, pool: &PgPool) -> Result<PgQueryResult, sqlx::Error> {
-        let mut env_state = EnvState::new(&std::env::current_dir().unwrap_or_default());
-        let public_schema = env_state
-            .var("ENVIO_PG_PUBLIC_SCHEMA")
-            .unwrap_or_else(|| "public".to_string());
+        let public_schema = get_public_schema();
 
         sqlx::query(&format!(
             r#"
@@ -39,14 +43,18 @@ impl PersistedState {
                 config_hash,
                 schema_hash,
                 handler_files_hash,
-                abi_files_hash
+                abi_files_hash,
+                profile,
+                schema_snapshot
             ) VALUES (
                 $1, 
                 $2, 
                 $3, 
                 $4, 
                 $5, 
-                $6
+                $6,
+                $7,
+                $8
             )
             ON CONFLICT (id) DO UPDATE
             SET 
@@ -54,7 +62,9 @@ impl PersistedState {
                 config_hash = EXCLUDED.config_hash,
                 schema_hash = EXCLUDED.schema_hash,
                 handler_files_hash = EXCLUDED.handler_files_hash,
-                abi_files_hash = EXCLUDED.abi_files_hash
+                abi_files_hash = EXCLUDED.abi_files_hash,
+                profile = EXCLUDED.profile,
+                schema_snapshot = EXCLUDED.schema_snapshot
             "#,
             public_schema
         ))
@@ -64,11 +74,25 @@ impl PersistedState {
         .bind(&self.schema_hash)
         .bind(&self.handler_files_hash)
         .bind(&self.abi_files_hash)
+        .bind(&self.profile)
+        .bind(&self.schema_snapshot)
         .execute(pool)
         .await
     }
 }
 
+impl SchemaMigrationPlan {
+    ///Runs each statement separately since ALTER TYPE ... ADD VALUE
+    ///can't be used in a transaction block on older postgres versions
+    pub async fn apply_to_db(&self) -> Result<(), sqlx::Error> {
+        let pool = get_pg_pool().await?;
+        for statement in self.to_sql(&get_public_schema()) {
+            sqlx::query(&statement).execute(&pool).await?;
+        }
+        Ok(())
+    }
+}
+
 impl PersistedStateExists {
     pub async fn read_from_db() -> Result<PersistedStateExists, sqlx::Error> {
         let pool = get_pg_pool().await?;
@@ -77,18 +101,17 @@ impl PersistedStateExists {
     pub async fn read_from_db_with_pool(
         pool: &PgPool,
     ) -> Result<PersistedStateExists, sqlx::Error> {
-        let mut env_state = EnvState::new(&std::env::current_dir().unwrap_or_default());
-        let public_schema = env_state
-            .var("ENVIO_PG_PUBLIC_SCHEMA")
-            .unwrap_or_else(|| "public".to_string());
+        let public_schema = get_public_schema();
 
-            let val = sqlx::query_as::<_, PersistedState>(&format!(
+        let val = sqlx::query_as::<_, PersistedState>(&format!(
             "SELECT 
             envio_version,
             config_hash,
             schema_hash,
             handler_files_hash,
-            abi_files_hash
+            abi_files_hash,
+            profile,
+            schema_snapshot
             FROM \"{}\".persisted_state WHERE id = 1",
             public_schema
         ))
@@ -1,11 +1,15 @@
 mod db;
 mod hash_string;
+mod schema_snapshot;
 
-use crate::{config_parsing::system_config::SystemConfig, project_paths::ParsedProjectPaths};
+use crate::{
+    config_parsing::{chain_registry, system_config::SystemConfig},
+    project_paths::ParsedProjectPaths,
+};
 use anyhow::Context;
 use hash_string::HashString;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
 use std::{
     fmt::{self, Display},
     path::PathBuf,
@@ -13,6 +17,9 @@ use std::{
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
+pub use db::get_public_schema;
+pub use schema_snapshot::{SchemaMigrationPlan, SchemaSnapshot};
+
 #[derive(Serialize, Deserialize, Debug, FromRow)]
 pub struct PersistedState {
     pub envio_version: String,
@@ -20,6 +27,12 @@ pub struct PersistedState {
     pub schema_hash: HashString,
     pub handler_files_hash: HashString,
     pub abi_files_hash: HashString,
+    ///The config profile used, switching it resets the db like any other config change
+    #[serde(default)]
+    pub profile: Option<String>,
+    ///Used to diff schema changes against the db without resetting it
+    #[serde(default)]
+    pub schema_snapshot: Json<SchemaSnapshot>,
 }
 const PERSISTED_STATE_FILE_NAME: &str = "persisted_state.envio.json";
 pub static CURRENT_CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -32,6 +45,7 @@ pub enum StateField {
     Schema,
     AbiFiles,
     HandlerFiles,
+    Profile,
 }
 
 ///Gets the path to the persisted file in generated folder
@@ -49,6 +63,7 @@ impl PersistedState {
             StateField::Schema => self.schema_hash == other_state.schema_hash,
             StateField::AbiFiles => self.abi_files_hash == other_state.abi_files_hash,
             StateField::HandlerFiles => self.handler_files_hash == other_state.handler_files_hash,
+            StateField::Profile => self.profile == other_state.profile,
         }
     }
 
@@ -81,12 +96,44 @@ impl PersistedState {
             .get_all_paths_to_abi_files()
             .context("Failed getting abi file paths")?;
 
+        let all_address_file_paths = config
+            .get_all_paths_to_address_files()
+            .context("Failed getting address file paths")?;
+
         const HANDLER_FILES_MUST_EXIST: bool = false;
         const ABI_FILES_MUST_EXIST: bool = true;
+        const ADDRESS_FILES_MUST_EXIST: bool = true;
+
+        //Header values are left out of the config string and hashed on their own,
+        //so the secrets only ever end up in a single digest
+        //The included files are merged into the human config, so they're covered as well
+        let mut header_values = vec![];
+        let redacted_config = config.human_config.to_redacted_string(|value| {
+            header_values.push(value.to_string());
+            "<redacted>".to_string()
+        });
+        let headers_hash = HashString::from_string(
+            serde_json::to_string(&header_values).context("Failed serializing header values")?,
+        );
+        //Addresses loaded from files are part of the config, so changing a file reruns codegen
+        let address_files_hash =
+            HashString::from_file_paths(all_address_file_paths, ADDRESS_FILES_MUST_EXIST)
+                .context("Failed hashing address files")?;
+
+        //Chains from a chains.yaml file resolve the default endpoints and thresholds
+        let custom_chains: Vec<_> = chain_registry::get().iter().collect();
+        let custom_chains = if custom_chains.is_empty() {
+            String::new()
+        } else {
+            serde_yaml::to_string(&custom_chains)
+                .context("Failed serializing the chains of the chain registry")?
+        };
 
         Ok(PersistedState {
             envio_version: CURRENT_CRATE_VERSION.to_string(),
-            config_hash: HashString::from_string(config.human_config.to_string()),
+            config_hash: HashString::from_string(format!(
+                "{redacted_config}{headers_hash}{address_files_hash}{custom_chains}"
+            )),
             schema_hash: HashString::from_file_path(schema_path.clone())
                 .context("Failed hashing schema file")?,
             handler_files_hash: HashString::from_file_paths(
@@ -96,6 +143,11 @@ impl PersistedState {
             .context("Failed hashing handler files")?,
             abi_files_hash: HashString::from_file_paths(all_abi_file_paths, ABI_FILES_MUST_EXIST)
                 .context("Failed hashing abi files")?,
+            profile: config.parsed_project_paths.profile.clone(),
+            schema_snapshot: Json(
+                SchemaSnapshot::from_schema(&config.schema)
+                    .context("Failed creating schema snapshot")?,
+            ),
         })
     }
 
@@ -109,6 +161,8 @@ impl PersistedState {
             StateField::AbiFiles,
             //If schema has changed this will affect generated entity types
             StateField::Schema,
+            //The profile overrides the config, so switching it should regenerate the code
+            StateField::Profile,
             //If the version envio changes, this could infer differences that need to be re-code
             //generated
             StateField::EnvioVersion,
@@ -131,6 +185,27 @@ impl PersistedState {
 
         (!non_matching_fields.is_empty(), non_matching_fields)
     }
+
+    ///Plans the migration from the schema persisted on the db to the current schema.
+    ///Returns None if any state other than the schema changed since that still
+    ///requires resetting the db
+    pub fn get_schema_migration_plan(
+        &self,
+        persisted_state_db: &Self,
+    ) -> Option<SchemaMigrationPlan> {
+        let all_fields: Vec<_> = StateField::iter().collect();
+        let non_matching_fields = self.get_non_matching_fields(persisted_state_db, all_fields);
+
+        if non_matching_fields == vec![StateField::Schema] {
+            Some(
+                persisted_state_db
+                    .schema_snapshot
+                    .diff(&self.schema_snapshot),
+            )
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -297,4 +372,78 @@ mod test {
             "should run codegen should be false since nothing changed"
         );
     }
+
+    #[test]
+    fn gets_schema_migration_plan_only_when_schema_changed() {
+        let persisted_db: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+        }))
+        .unwrap();
+
+        let schema_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<CHANGED_HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+        }))
+        .unwrap();
+
+        let schema_and_handlers_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<CHANGED_HASH_STRING>",
+            "handler_files_hash": "<CHANGED_HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+        }))
+        .unwrap();
+
+        assert!(
+            schema_changed_state
+                .get_schema_migration_plan(&persisted_db)
+                .is_some(),
+            "should plan a migration since only the schema changed"
+        );
+        assert!(
+            schema_and_handlers_changed_state
+                .get_schema_migration_plan(&persisted_db)
+                .is_none(),
+            "should not plan a migration since handler files changed"
+        );
+    }
+
+    #[test]
+    fn switching_profile_runs_codegen_and_db_migrations() {
+        let persisted_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+        }))
+        .unwrap();
+
+        let current_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "profile": "prod",
+        }))
+        .unwrap();
+
+        assert!(
+            current_state.should_run_codegen(&persisted_state).0,
+            "should run codegen since the profile changed"
+        );
+        assert!(
+            current_state.should_run_db_migrations(&persisted_state).0,
+            "should run db migrations since the profile changed"
+        );
+    }
 }
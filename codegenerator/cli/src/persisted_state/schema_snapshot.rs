@@ -0,0 +1,735 @@
+use crate::config_parsing::{
+    entity_parsing::{FieldType, Schema},
+    postgres_types::{IndexType, Primitive as PGPrimitive},
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+};
+
+///A structured representation of the db tables and enums generated from a schema.
+///This gets persisted so that the next schema can be diffed against it without
+///needing to reset the database.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+pub struct SchemaSnapshot {
+    pub entities: BTreeMap<String, EntitySnapshot>,
+    pub enums: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+pub struct EntitySnapshot {
+    ///Keyed by the db field name (linked entity fields have an _id suffix)
+    pub fields: BTreeMap<String, FieldSnapshot>,
+    ///Db field names of all single field indices, including derivedFrom lookups
+    pub single_indices: BTreeSet<String>,
+    ///Db field names of all indices with more than one field
+    pub composite_indices: BTreeSet<Vec<String>>,
+    ///Db field names of the single field indices using a gin index
+    #[serde(default)]
+    pub gin_indices: BTreeSet<String>,
+    ///Immutable entities have no history table
+    #[serde(default)]
+    pub is_immutable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FieldSnapshot {
+    ///The postgres type without array brackets, or the enum name
+    pub pg_type: String,
+    pub is_enum: bool,
+    pub is_array: bool,
+    pub is_nullable: bool,
+    pub is_primary_key: bool,
+}
+
+impl FieldSnapshot {
+    fn to_sql_type(&self, public_schema: &str) -> String {
+        let pg_type = if self.is_enum {
+            format!("\"{}\".{}", public_schema, self.pg_type)
+        } else {
+            self.pg_type.clone()
+        };
+
+        if self.is_array {
+            format!("{pg_type}[]")
+        } else {
+            pg_type
+        }
+    }
+
+    fn has_same_type(&self, other: &Self) -> bool {
+        self.pg_type == other.pg_type
+            && self.is_enum == other.is_enum
+            && self.is_array == other.is_array
+            && self.is_primary_key == other.is_primary_key
+    }
+}
+
+///Mirrors the postgres types used for each field type in Table.res
+fn get_pg_type(primitive: &PGPrimitive) -> (String, bool) {
+    (
+        primitive.get_sql_type(),
+        matches!(primitive, PGPrimitive::Enum(_)),
+    )
+}
+
+impl SchemaSnapshot {
+    pub fn from_schema(schema: &Schema) -> anyhow::Result<Self> {
+        let mut entities: BTreeMap<String, EntitySnapshot> = BTreeMap::new();
+
+        for entity in schema.entities.values() {
+            let mut fields = BTreeMap::new();
+            let mut single_indices = BTreeSet::new();
+            let mut gin_indices = BTreeSet::new();
+            //Maps user defined field names to their db names for composite indices
+            let mut db_field_names = BTreeMap::new();
+
+            for field in entity.get_fields() {
+                let pg_field =
+                    match field.get_postgres_field(schema, entity).with_context(|| {
+                        format!(
+                            "Failed getting postgres field {}.{}",
+                            entity.name, field.name
+                        )
+                    })? {
+                        //Derived fields have no column
+                        None => continue,
+                        Some(pg_field) => pg_field,
+                    };
+
+                let db_field_name = if pg_field.linked_entity.is_some() {
+                    format!("{}_id", pg_field.field_name)
+                } else {
+                    pg_field.field_name.clone()
+                };
+
+                if pg_field.is_index {
+                    single_indices.insert(db_field_name.clone());
+                }
+                if pg_field.index_type == Some(IndexType::Gin) {
+                    gin_indices.insert(db_field_name.clone());
+                }
+
+                let (pg_type, is_enum) = get_pg_type(&pg_field.field_type);
+                db_field_names.insert(field.name.clone(), db_field_name.clone());
+                fields.insert(
+                    db_field_name,
+                    FieldSnapshot {
+                        pg_type,
+                        is_enum,
+                        is_array: pg_field.is_array,
+                        is_nullable: pg_field.is_nullable,
+                        is_primary_key: pg_field.is_primary_key,
+                    },
+                );
+
+                //Interface fields have an additional column with the type of the referenced entity
+                if let Some(type_tag_field) = field.get_type_tag_postgres_field(schema)? {
+                    let (pg_type, is_enum) = get_pg_type(&type_tag_field.field_type);
+                    fields.insert(
+                        type_tag_field.field_name,
+                        FieldSnapshot {
+                            pg_type,
+                            is_enum,
+                            is_array: type_tag_field.is_array,
+                            is_nullable: type_tag_field.is_nullable,
+                            is_primary_key: type_tag_field.is_primary_key,
+                        },
+                    );
+                }
+            }
+
+            let composite_indices = entity
+                .get_composite_indices()
+                .into_iter()
+                .map(|index_fields| {
+                    index_fields
+                        .into_iter()
+                        .map(|field_name| {
+                            db_field_names
+                                .get(&field_name)
+                                .cloned()
+                                .unwrap_or(field_name)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let entity_snapshot = entities.entry(entity.name.clone()).or_default();
+            entity_snapshot.fields = fields;
+            entity_snapshot.single_indices.append(&mut single_indices);
+            entity_snapshot.composite_indices = composite_indices;
+            entity_snapshot.gin_indices = gin_indices;
+            entity_snapshot.is_immutable = entity.is_immutable;
+        }
+
+        //derivedFrom fields are looked up by an index on the entity they are derived from
+        for entity in schema.entities.values() {
+            for field in entity.get_fields() {
+                if let FieldType::DerivedFromField { entity_name, .. } = &field.field_type {
+                    let db_field_name = field.get_relational_key(schema)?;
+                    entities
+                        .entry(entity_name.clone())
+                        .or_default()
+                        .single_indices
+                        .insert(db_field_name);
+                }
+            }
+        }
+
+        let enums = schema
+            .enums
+            .values()
+            .map(|gql_enum| (gql_enum.name.clone(), gql_enum.values.clone()))
+            .collect();
+
+        Ok(Self { entities, enums })
+    }
+
+    ///Diffs a previously persisted snapshot against the next one and
+    ///plans the changes needed to migrate the db without a reset
+    pub fn diff(&self, next: &Self) -> SchemaMigrationPlan {
+        let mut changes = vec![];
+
+        for (enum_name, values) in &next.enums {
+            match self.enums.get(enum_name) {
+                None => changes.push(SchemaChange::AddEnum {
+                    enum_name: enum_name.clone(),
+                }),
+                Some(prev_values) => {
+                    for value in prev_values {
+                        if !values.contains(value) {
+                            changes.push(SchemaChange::Breaking(format!(
+                                "Value '{value}' was removed from enum {enum_name}"
+                            )));
+                        }
+                    }
+                    for value in values {
+                        if !prev_values.contains(value) {
+                            changes.push(SchemaChange::AddEnumValue {
+                                enum_name: enum_name.clone(),
+                                value: value.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        for enum_name in self.enums.keys() {
+            if !next.enums.contains_key(enum_name) {
+                changes.push(SchemaChange::Breaking(format!(
+                    "Enum {enum_name} was removed"
+                )));
+            }
+        }
+
+        for entity_name in self.entities.keys() {
+            if !next.entities.contains_key(entity_name) {
+                changes.push(SchemaChange::Breaking(format!(
+                    "Entity {entity_name} was removed"
+                )));
+            }
+        }
+
+        for (entity_name, entity) in &next.entities {
+            let prev_entity = match self.entities.get(entity_name) {
+                None => {
+                    changes.push(SchemaChange::AddEntity {
+                        entity_name: entity_name.clone(),
+                    });
+                    continue;
+                }
+                Some(prev_entity) => prev_entity,
+            };
+
+            if prev_entity.is_immutable != entity.is_immutable {
+                changes.push(SchemaChange::Breaking(format!(
+                    "Entity {entity_name} was made {}",
+                    if entity.is_immutable {
+                        "immutable"
+                    } else {
+                        "mutable"
+                    }
+                )));
+            }
+
+            for field_name in prev_entity.fields.keys() {
+                if !entity.fields.contains_key(field_name) {
+                    changes.push(SchemaChange::Breaking(format!(
+                        "Field {entity_name}.{field_name} was removed"
+                    )));
+                }
+            }
+
+            for (field_name, field) in &entity.fields {
+                match prev_entity.fields.get(field_name) {
+                    //A non nullable column can't be added to a table that may already have rows
+                    None if !field.is_nullable => changes.push(SchemaChange::Breaking(format!(
+                        "Non nullable field {entity_name}.{field_name} was added"
+                    ))),
+                    None => changes.push(SchemaChange::AddColumn {
+                        entity_name: entity_name.clone(),
+                        field_name: field_name.clone(),
+                        field: field.clone(),
+                        has_history: !entity.is_immutable,
+                    }),
+                    Some(prev_field) if !prev_field.has_same_type(field) => {
+                        changes.push(SchemaChange::Breaking(format!(
+                            "Type of field {entity_name}.{field_name} was changed"
+                        )))
+                    }
+                    Some(prev_field) if prev_field.is_nullable && !field.is_nullable => changes
+                        .push(SchemaChange::Breaking(format!(
+                            "Field {entity_name}.{field_name} was made non nullable"
+                        ))),
+                    Some(prev_field) if !prev_field.is_nullable && field.is_nullable => changes
+                        .push(SchemaChange::DropNotNull {
+                            entity_name: entity_name.clone(),
+                            field_name: field_name.clone(),
+                        }),
+                    Some(_) => (),
+                }
+            }
+
+            let prev_indices = prev_entity.get_all_indices();
+            let indices = entity.get_all_indices();
+
+            //An index of which the type changed is dropped and created again
+            for (index_fields, is_gin) in &prev_indices {
+                if indices.get(index_fields) != Some(is_gin) {
+                    changes.push(SchemaChange::DropIndex {
+                        entity_name: entity_name.clone(),
+                        index_fields: index_fields.clone(),
+                    });
+                }
+            }
+
+            for (index_fields, is_gin) in &indices {
+                if prev_indices.get(index_fields) != Some(is_gin) {
+                    changes.push(SchemaChange::CreateIndex {
+                        entity_name: entity_name.clone(),
+                        index_fields: index_fields.clone(),
+                        is_gin: *is_gin,
+                    });
+                }
+            }
+        }
+
+        SchemaMigrationPlan { changes }
+    }
+}
+
+impl EntitySnapshot {
+    ///Maps the fields of every index to whether it's a gin index
+    fn get_all_indices(&self) -> BTreeMap<Vec<String>, bool> {
+        self.single_indices
+            .iter()
+            .map(|field_name| {
+                (
+                    vec![field_name.clone()],
+                    self.gin_indices.contains(field_name),
+                )
+            })
+            .chain(
+                self.composite_indices
+                    .iter()
+                    .map(|index_fields| (index_fields.clone(), false)),
+            )
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SchemaChange {
+    ///Tables for new entities get created by the up migrations
+    AddEntity {
+        entity_name: String,
+    },
+    ///New enums get created by the up migrations
+    AddEnum {
+        enum_name: String,
+    },
+    AddEnumValue {
+        enum_name: String,
+        value: String,
+    },
+    AddColumn {
+        entity_name: String,
+        field_name: String,
+        field: FieldSnapshot,
+        has_history: bool,
+    },
+    DropNotNull {
+        entity_name: String,
+        field_name: String,
+    },
+    CreateIndex {
+        entity_name: String,
+        index_fields: Vec<String>,
+        is_gin: bool,
+    },
+    DropIndex {
+        entity_name: String,
+        index_fields: Vec<String>,
+    },
+    ///A change that can't be applied without resetting the db and reindexing
+    Breaking(String),
+}
+
+///Matches the index naming in Migrations.res
+fn get_index_name(entity_name: &str, index_fields: &[String]) -> String {
+    format!("{}_{}", entity_name, index_fields.join("_"))
+}
+
+fn get_history_table_name(entity_name: &str) -> String {
+    format!("{entity_name}_history")
+}
+
+impl SchemaChange {
+    pub fn is_breaking(&self) -> bool {
+        matches!(self, Self::Breaking(_))
+    }
+
+    fn to_sql(&self, public_schema: &str) -> Vec<String> {
+        match self {
+            Self::AddEntity { .. } | Self::AddEnum { .. } | Self::Breaking(_) => vec![],
+            Self::AddEnumValue { enum_name, value } => vec![format!(
+                "ALTER TYPE \"{public_schema}\".{enum_name} ADD VALUE IF NOT EXISTS '{value}';"
+            )],
+            Self::AddColumn {
+                entity_name,
+                field_name,
+                field,
+                has_history,
+            } => {
+                let sql_type = field.to_sql_type(public_schema);
+                //History tables hold every entity field as nullable
+                let history_table_name = has_history.then(|| get_history_table_name(entity_name));
+                [entity_name.clone()]
+                    .into_iter()
+                    .chain(history_table_name)
+                    .map(|table_name| {
+                        format!(
+                            "ALTER TABLE \"{public_schema}\".\"{table_name}\" ADD COLUMN IF NOT \
+                             EXISTS \"{field_name}\" {sql_type};"
+                        )
+                    })
+                    .collect()
+            }
+            Self::DropNotNull {
+                entity_name,
+                field_name,
+            } => vec![format!(
+                "ALTER TABLE \"{public_schema}\".\"{entity_name}\" ALTER COLUMN \"{field_name}\" \
+                 DROP NOT NULL;"
+            )],
+            Self::CreateIndex {
+                entity_name,
+                index_fields,
+                is_gin,
+            } => {
+                let index_name = get_index_name(entity_name, index_fields);
+                let using = if *is_gin { " USING GIN" } else { "" };
+                let index = index_fields
+                    .iter()
+                    .map(|field_name| format!("\"{field_name}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                vec![format!(
+                    "CREATE INDEX IF NOT EXISTS \"{index_name}\" ON \
+                     \"{public_schema}\".\"{entity_name}\"{using}({index});"
+                )]
+            }
+            Self::DropIndex {
+                entity_name,
+                index_fields,
+            } => vec![format!(
+                "DROP INDEX IF EXISTS \"{public_schema}\".\"{}\";",
+                get_index_name(entity_name, index_fields)
+            )],
+        }
+    }
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AddEntity { entity_name } => write!(f, "Add entity {entity_name}"),
+            Self::AddEnum { enum_name } => write!(f, "Add enum {enum_name}"),
+            Self::AddEnumValue { enum_name, value } => {
+                write!(f, "Add value '{value}' to enum {enum_name}")
+            }
+            Self::AddColumn {
+                entity_name,
+                field_name,
+                ..
+            } => write!(f, "Add field {entity_name}.{field_name}"),
+            Self::DropNotNull {
+                entity_name,
+                field_name,
+            } => write!(f, "Make field {entity_name}.{field_name} nullable"),
+            Self::CreateIndex {
+                entity_name,
+                index_fields,
+                ..
+            } => write!(f, "Add index on {entity_name}({})", index_fields.join(", ")),
+            Self::DropIndex {
+                entity_name,
+                index_fields,
+            } => write!(
+                f,
+                "Remove index on {entity_name}({})",
+                index_fields.join(", ")
+            ),
+            Self::Breaking(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SchemaMigrationPlan {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaMigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(SchemaChange::is_breaking)
+    }
+
+    pub fn get_breaking_changes(&self) -> Vec<&SchemaChange> {
+        self.changes.iter().filter(|c| c.is_breaking()).collect()
+    }
+
+    ///Statements to run before the up migrations. New tables, enums, history insert
+    ///functions and derivedFrom indices are created by the up migrations themselves.
+    pub fn to_sql(&self, public_schema: &str) -> Vec<String> {
+        //Enum values need to exist before any column can reference them
+        let (enum_changes, other_changes): (Vec<_>, Vec<_>) = self
+            .changes
+            .iter()
+            .partition(|c| matches!(c, SchemaChange::AddEnumValue { .. }));
+
+        enum_changes
+            .into_iter()
+            .chain(other_changes)
+            .flat_map(|c| c.to_sql(public_schema))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SchemaChange, SchemaSnapshot};
+    use crate::config_parsing::entity_parsing::Schema;
+    use graphql_parser::schema::parse_schema;
+
+    fn snapshot_from_str(schema_str: &str) -> SchemaSnapshot {
+        let document = parse_schema::<String>(schema_str).unwrap();
+        let schema = Schema::from_document(document).unwrap();
+        SchemaSnapshot::from_schema(&schema).unwrap()
+    }
+
+    #[test]
+    fn unchanged_schema_has_empty_plan() {
+        let schema_str = r#"
+            type User {
+                id: ID!
+                name: String! @index
+                tokens: [Token!]! @derivedFrom(field: "owner")
+            }
+            type Token {
+                id: ID!
+                owner: User!
+            }
+        "#;
+        let plan = snapshot_from_str(schema_str).diff(&snapshot_from_str(schema_str));
+
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn snapshot_uses_db_field_names_and_derived_from_indices() {
+        let snapshot = snapshot_from_str(
+            r#"
+            type User {
+                id: ID!
+                tokens: [Token!]! @derivedFrom(field: "owner")
+            }
+            type Token @index(fields: ["owner", "amount"]) {
+                id: ID!
+                owner: User!
+                amount: BigInt!
+            }
+        "#,
+        );
+
+        let token = snapshot.entities.get("Token").unwrap();
+        assert!(token.fields.contains_key("owner_id"));
+        assert!(token.single_indices.contains("owner_id"));
+        assert!(token
+            .composite_indices
+            .contains(&vec!["owner_id".to_string(), "amount".to_string()]));
+
+        let user = snapshot.entities.get("User").unwrap();
+        assert_eq!(user.fields.keys().collect::<Vec<_>>(), vec!["id"]);
+    }
+
+    #[test]
+    fn plans_safe_changes() {
+        let prev = snapshot_from_str(
+            r#"
+            enum AccountType {
+                USER
+            }
+            type User {
+                id: ID!
+                accountType: AccountType!
+                name: String!
+            }
+        "#,
+        );
+        let next = snapshot_from_str(
+            r#"
+            enum AccountType {
+                USER
+                ADMIN
+            }
+            type User {
+                id: ID!
+                accountType: AccountType!
+                name: String
+                nickname: String @index
+            }
+        "#,
+        );
+
+        let plan = prev.diff(&next);
+
+        assert!(!plan.is_breaking());
+        assert_eq!(
+            plan.to_sql("public"),
+            vec![
+                r#"ALTER TYPE "public".AccountType ADD VALUE IF NOT EXISTS 'ADMIN';"#,
+                r#"ALTER TABLE "public"."User" ALTER COLUMN "name" DROP NOT NULL;"#,
+                r#"ALTER TABLE "public"."User" ADD COLUMN IF NOT EXISTS "nickname" TEXT;"#,
+                r#"ALTER TABLE "public"."User_history" ADD COLUMN IF NOT EXISTS "nickname" TEXT;"#,
+                r#"CREATE INDEX IF NOT EXISTS "User_nickname" ON "public"."User"("nickname");"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn plans_breaking_changes() {
+        let prev = snapshot_from_str(
+            r#"
+            type User {
+                id: ID!
+                balance: Int!
+                name: String
+            }
+        "#,
+        );
+        let next = snapshot_from_str(
+            r#"
+            type User {
+                id: ID!
+                balance: BigInt!
+                email: String!
+            }
+        "#,
+        );
+
+        let plan = prev.diff(&next);
+
+        assert!(plan.is_breaking());
+        assert_eq!(
+            plan.get_breaking_changes(),
+            vec![
+                &SchemaChange::Breaking("Field User.name was removed".to_string()),
+                &SchemaChange::Breaking("Type of field User.balance was changed".to_string()),
+                &SchemaChange::Breaking("Non nullable field User.email was added".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn plans_changes_of_immutable_entities() {
+        let prev = snapshot_from_str(
+            r#"
+            type Transfer @entity(immutable: true) {
+                id: ID!
+                value: BigInt!
+            }
+            type User {
+                id: ID!
+            }
+        "#,
+        );
+        let next = snapshot_from_str(
+            r#"
+            type Transfer @entity(immutable: true) {
+                id: ID!
+                value: BigInt!
+                memo: String
+            }
+            type User @entity(immutable: true) {
+                id: ID!
+            }
+        "#,
+        );
+
+        let plan = prev.diff(&next);
+
+        assert_eq!(
+            plan.get_breaking_changes(),
+            vec![&SchemaChange::Breaking(
+                "Entity User was made immutable".to_string()
+            )]
+        );
+        assert_eq!(
+            plan.to_sql("public"),
+            vec![r#"ALTER TABLE "public"."Transfer" ADD COLUMN IF NOT EXISTS "memo" TEXT;"#]
+        );
+    }
+    #[test]
+    fn plans_changes_of_gin_indices() {
+        let prev = snapshot_from_str(
+            r#"
+            type Token {
+                id: ID!
+                metadata: Json! @index
+            }
+        "#,
+        );
+        let next = snapshot_from_str(
+            r#"
+            type Token {
+                id: ID!
+                metadata: Json! @index(type: "gin")
+                calldata: Json @index(type: "gin")
+            }
+        "#,
+        );
+
+        let plan = prev.diff(&next);
+
+        assert!(!plan.is_breaking());
+        assert_eq!(
+            plan.to_sql("public"),
+            vec![
+                r#"ALTER TABLE "public"."Token" ADD COLUMN IF NOT EXISTS "calldata" JSONB;"#,
+                r#"ALTER TABLE "public"."Token_history" ADD COLUMN IF NOT EXISTS "calldata" JSONB;"#,
+                r#"DROP INDEX IF EXISTS "public"."Token_metadata";"#,
+                r#"CREATE INDEX IF NOT EXISTS "Token_calldata" ON "public"."Token" USING GIN("calldata");"#,
+                r#"CREATE INDEX IF NOT EXISTS "Token_metadata" ON "public"."Token" USING GIN("metadata");"#,
+            ]
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::path::{Component, PathBuf};
 
 use crate::{
     cli_args::{clap_definitions::ProjectPaths, init_config::InitConfig},
+    config_parsing::{profiles::PROFILE_ENV_VAR, system_config::EnvState},
     constants::project_paths::{
         DEFAULT_CONFIG_PATH, DEFAULT_GENERATED_PATH, DEFAULT_PROJECT_ROOT_PATH,
     },
@@ -16,6 +17,8 @@ pub struct ParsedProjectPaths {
     pub project_root: PathBuf,
     pub config: PathBuf,
     pub generated: PathBuf,
+    ///The config profile whose overrides are merged onto the config
+    pub profile: Option<String>,
 }
 
 impl ParsedProjectPaths {
@@ -44,6 +47,7 @@ impl ParsedProjectPaths {
             project_root,
             generated,
             config,
+            profile: None,
         })
     }
 
@@ -70,11 +74,17 @@ impl TryFrom<ProjectPaths> for ParsedProjectPaths {
             .directory
             .unwrap_or_else(|| DEFAULT_PROJECT_ROOT_PATH.to_string());
 
-        Self::new(
+        let mut parsed_paths = Self::new(
             &project_root,
             &project_paths.output_directory,
             &project_paths.config,
-        )
+        )?;
+        //The flag takes precedence over the env var
+        parsed_paths.profile = project_paths
+            .profile
+            .or_else(|| EnvState::new(&parsed_paths.project_root).var(PROFILE_ENV_VAR))
+            .filter(|profile| !profile.is_empty());
+        Ok(parsed_paths)
     }
 }
 
@@ -99,6 +109,7 @@ mod tests {
             project_root: PathBuf::from("."),
             config: PathBuf::from("config.yaml"),
             generated: PathBuf::from("generated"),
+            profile: None,
         };
         assert_eq!(expected_project_paths, project_paths,)
     }
@@ -114,6 +125,7 @@ mod tests {
             config: PathBuf::from("my_dir/my_project/custom_config.yaml"),
 
             generated: PathBuf::from("my_dir/my_project/custom_gen/my_project_generated"),
+            profile: None,
         };
         assert_eq!(expected_project_paths, project_paths,)
     }
@@ -128,6 +140,7 @@ mod tests {
             project_root: PathBuf::from("../my_dir/my_project/"),
             config: PathBuf::from("../my_dir/my_project/custom_config.yaml"),
             generated: PathBuf::from("../my_dir/my_project/custom_gen/my_project_generated"),
+            profile: None,
         };
         assert_eq!(expected_project_paths, project_paths)
     }
@@ -150,7 +150,7 @@ impl RescriptTypeDecl {
         )
     }
 
-    pub fn to_rescript_schema(&self, type_name: &String, mode: &RescriptSchemaMode) -> String {
+    pub fn to_rescript_schema(&self, type_name: &str, mode: &RescriptSchemaMode) -> String {
         if self.parameters.is_empty() {
             self.type_expr.to_rescript_schema(type_name, mode)
         } else {
@@ -220,7 +220,7 @@ impl RescriptTypeExpr {
         }
     }
 
-    pub fn to_rescript_schema(&self, type_name: &String, mode: &RescriptSchemaMode) -> String {
+    pub fn to_rescript_schema(&self, type_name: &str, mode: &RescriptSchemaMode) -> String {
         match self {
             Self::Identifier(type_ident) => type_ident.to_rescript_schema(mode),
             Self::Variant(items) => {
@@ -354,6 +354,9 @@ pub enum RescriptTypeIdent {
     Bool,
     Unknown,
     Timestamp,
+    Json,
+    //Hex strings stored in a bytea column
+    Bytea,
     //Enums defined in the user's schema
     SchemaEnum(CapitalizedOptions),
     Array(Box<RescriptTypeIdent>),
@@ -393,6 +396,8 @@ impl RescriptTypeIdent {
             Self::ID => "id".to_string(),
             Self::Bool => "bool".to_string(),
             Self::Timestamp => "Js.Date.t".to_string(),
+            Self::Json => "Js.Json.t".to_string(),
+            Self::Bytea => "string".to_string(),
             Self::Array(inner_type) => {
                 format!("array<{}>", inner_type.to_string())
             }
@@ -445,6 +450,11 @@ impl RescriptTypeIdent {
             Self::ID => "S.string".to_string(),
             Self::Bool => "S.bool".to_string(),
             Self::Timestamp => "Utils.Schema.dbDate".to_string(),
+            Self::Json => "S.json(~validate=false)".to_string(),
+            Self::Bytea => match mode {
+                RescriptSchemaMode::ForDb => "Utils.Schema.dbBytea".to_string(),
+                RescriptSchemaMode::ForFieldSelection => "S.string".to_string(),
+            },
             Self::Array(inner_type) => {
                 format!("S.array({})", inner_type.to_rescript_schema(mode))
             }
@@ -500,6 +510,8 @@ impl RescriptTypeIdent {
             | Self::ID
             | Self::Bool
             | Self::Timestamp
+            | Self::Json
+            | Self::Bytea
             | Self::SchemaEnum(_)
             | Self::GenericParam(_) => vec![],
             Self::TypeApplication {
@@ -532,6 +544,8 @@ impl RescriptTypeIdent {
             Self::ID => "\"my_id\"".to_string(),
             Self::Bool => "false".to_string(),
             Self::Timestamp => "Js.Date.fromFloat(0.)".to_string(),
+            Self::Json => "Js.Json.null".to_string(),
+            Self::Bytea => "\"0x\"".to_string(),
             Self::Array(_) => "[]".to_string(),
             Self::Option(_) => "None".to_string(),
             Self::SchemaEnum(enum_name) => {
@@ -598,6 +612,8 @@ impl RescriptTypeIdent {
             Self::ID => "\"my_id\"".to_string(),
             Self::Bool => "false".to_string(),
             Self::Timestamp => "new Date(0)".to_string(),
+            Self::Json => "null".to_string(),
+            Self::Bytea => "\"0x\"".to_string(),
             Self::Array(_) => "[]".to_string(),
             Self::Option(_) => "null".to_string(),
             Self::SchemaEnum(enum_name) => {
@@ -663,6 +679,30 @@ impl RescriptTypeIdent {
     pub fn array(inner_type: Self) -> Self {
         Self::Array(Box::new(inner_type))
     }
+
+    //Records nested in the block and transaction fields. They are declared once
+    //with their schemas in the EvmTypes module of the envio package, since ReScript
+    //doesn't support inline record types as type parameters
+    pub fn evm_access_list_item() -> Self {
+        Self::TypeApplication {
+            name: "EvmTypes.accessListItem".to_string(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn evm_withdrawal() -> Self {
+        Self::TypeApplication {
+            name: "EvmTypes.withdrawal".to_string(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn evm_log() -> Self {
+        Self::TypeApplication {
+            name: "EvmTypes.log".to_string(),
+            type_params: vec![],
+        }
+    }
 }
 
 impl Display for RescriptTypeIdent {
@@ -694,72 +734,77 @@ mod tests {
     fn test_to_rescript_schema() {
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::Bool)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.bool".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::Int)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.int".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::Float)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.float".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::Unit)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.literal(%raw(`null`))->S.to(_ => ())".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::BigInt)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "BigInt.schema".to_string()
         );
         assert_eq!(
-            RescriptTypeExpr::Identifier(RescriptTypeIdent::BigInt).to_rescript_schema(
-                &"eventArgs".to_string(),
-                &RescriptSchemaMode::ForFieldSelection
-            ),
+            RescriptTypeExpr::Identifier(RescriptTypeIdent::BigInt)
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForFieldSelection),
             "BigInt.nativeSchema".to_string()
         );
+        assert_eq!(
+            RescriptTypeExpr::Identifier(RescriptTypeIdent::Json)
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
+            "S.json(~validate=false)".to_string()
+        );
+        assert_eq!(
+            RescriptTypeExpr::Identifier(RescriptTypeIdent::Bytea)
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
+            "Utils.Schema.dbBytea".to_string()
+        );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::BigDecimal)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "BigDecimal.schema".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::Address)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "Address.schema".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::String)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.string".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::ID)
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.string".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::array(RescriptTypeIdent::Int))
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.array(S.int)".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::option(RescriptTypeIdent::BigInt))
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.null(BigInt.schema)".to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Identifier(RescriptTypeIdent::option(RescriptTypeIdent::BigInt))
-                .to_rescript_schema(
-                    &"eventArgs".to_string(),
-                    &RescriptSchemaMode::ForFieldSelection
-                ),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForFieldSelection),
             "S.option(BigInt.nativeSchema)".to_string()
         );
         assert_eq!(
@@ -767,7 +812,7 @@ mod tests {
                 RescriptTypeIdent::Int,
                 RescriptTypeIdent::Bool
             ]))
-            .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+            .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.tuple(s => (s.item(0, S.int), s.item(1, S.bool)))".to_string()
         );
         assert_eq!(
@@ -775,7 +820,7 @@ mod tests {
                 RescriptVariantConstr::new("ConstrA".to_string(), RescriptTypeIdent::Int),
                 RescriptVariantConstr::new("ConstrB".to_string(), RescriptTypeIdent::Bool),
             ])
-            .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+            .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             r#"S.union([S.object((s): eventArgs =>
 {
   s.tag("case", "ConstrA")
@@ -792,14 +837,14 @@ mod tests {
                 RescriptRecordField::new("fieldA".to_string(), RescriptTypeIdent::Int),
                 RescriptRecordField::new("fieldB".to_string(), RescriptTypeIdent::Bool),
             ])
-            .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+            .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.object((s): eventArgs => {fieldA: s.field(\"fieldA\", \
              S.int), fieldB: s.field(\"fieldB\", S.bool)})"
                 .to_string()
         );
         assert_eq!(
             RescriptTypeExpr::Record(vec![])
-                .to_rescript_schema(&"eventArgs".to_string(), &RescriptSchemaMode::ForDb),
+                .to_rescript_schema("eventArgs", &RescriptSchemaMode::ForDb),
             "S.object((_): eventArgs => {})".to_string()
         );
     }
@@ -859,6 +904,37 @@ mod tests {
         assert_eq!(type_decl.to_string(), expected);
     }
 
+    #[test]
+    fn field_selection_record_with_nested_records() {
+        let transaction_expr = RescriptTypeExpr::Record(vec![
+            RescriptRecordField::new(
+                "accessList".to_string(),
+                RescriptTypeIdent::option(RescriptTypeIdent::array(
+                    RescriptTypeIdent::evm_access_list_item(),
+                )),
+            ),
+            RescriptRecordField::new(
+                "withdrawals".to_string(),
+                RescriptTypeIdent::option(RescriptTypeIdent::array(
+                    RescriptTypeIdent::evm_withdrawal(),
+                )),
+            ),
+        ]);
+
+        assert_eq!(
+            transaction_expr.to_string(),
+            "{accessList: option<array<EvmTypes.accessListItem>>, withdrawals: \
+             option<array<EvmTypes.withdrawal>>}"
+        );
+        assert_eq!(
+            transaction_expr
+                .to_rescript_schema("transaction", &RescriptSchemaMode::ForFieldSelection),
+            "S.object((s): transaction => {accessList: s.field(\"accessList\", \
+             S.option(S.array(EvmTypes.accessListItemSchema))), withdrawals: \
+             s.field(\"withdrawals\", S.option(S.array(EvmTypes.withdrawalSchema)))})"
+        );
+    }
+
     #[test]
     fn type_decl_with_invalid_rescript_field_names_to_string_record() {
         let type_decl = RescriptTypeDecl::new(
@@ -326,7 +326,7 @@ impl<'a> TemplateDirs<'a> {
         &self,
         template: &T,
         lang: &Language,
-        project_root: &PathBuf,
+        project_root: &Path,
     ) -> Result<()> {
         let lang_files = self.get_template_lang_dir(template, lang).context(format!(
             "Failed getting static files for template {} with language {}",
@@ -355,7 +355,7 @@ impl<'a> TemplateDirs<'a> {
     pub fn get_and_extract_blank_template(
         &self,
         lang: &Language,
-        project_root: &PathBuf,
+        project_root: &Path,
     ) -> Result<()> {
         let lang_files = self.get_blank_lang_dir(lang).context(format!(
             "Failed getting static files for blank template with language {}",
@@ -438,14 +438,14 @@ mod test {
         for template in evm::Template::iter() {
             for lang in Language::iter() {
                 template_dirs
-                    .get_and_extract_template(&template, &lang, &(PathBuf::from(temp_dir.path())))
+                    .get_and_extract_template(&template, &lang, temp_dir.path())
                     .expect("static lang");
             }
         }
         for template in fuel::Template::iter() {
             for lang in Language::iter() {
                 template_dirs
-                    .get_and_extract_template(&template, &lang, &(PathBuf::from(temp_dir.path())))
+                    .get_and_extract_template(&template, &lang, temp_dir.path())
                     .expect("static lang");
             }
         }
@@ -5,6 +5,19 @@ type contract = {
   abi: Ethers.abi,
   addresses: array<Address.t>,
   events: array<module(Types.Event)>,
+  // Only fetched from HyperSync, which is validated by the codegen
+  calls?: array<Internal.callConfig>,
+  // Defaults to the startBlock and endBlock of the chain
+  startBlock?: int,
+  endBlock?: int,
+}
+
+type blockHandler = {
+  name: string,
+  interval: int,
+  startBlock: option<int>,
+  endBlock: option<int>,
+  handlerRef: ref<option<Types.BlockHandlers.handler>>,
 }
 
 type syncConfig = {
@@ -16,14 +29,23 @@ type syncConfig = {
   queryTimeoutMillis: int,
 }
 
-type syncSource = HyperSync | HyperFuel | Rpc
+type syncSource = HyperSync | HyperFuel | Rpc | File
 
 let usesHyperSync = syncSource =>
   switch syncSource {
   | HyperSync | HyperFuel => true
-  | Rpc => false
+  | Rpc | File => false
   }
 
+// Primary sources sync both historical and realtime blocks,
+// Fallback sources are only used when the other sources stall
+type sourceRole = Primary | Historical | Realtime | Fallback
+
+type sourceConfig = {
+  role: sourceRole,
+  source: Source.t,
+}
+
 type chainConfig = {
   syncSource: syncSource,
   startBlock: int,
@@ -31,7 +53,13 @@ type chainConfig = {
   confirmedBlockThreshold: int,
   chain: ChainMap.Chain.t,
   contracts: array<contract>,
-  source: Source.t,
+  blockHandlers: array<blockHandler>,
+  // Ordered by priority, the first source is the preferred one
+  sources: array<sourceConfig>,
+  // Time in ms to wait for the active source before switching to the next one
+  sourceStallTimeout: int,
+  // Overrides the global enableRawEvents for the chain
+  enableRawEvents?: bool,
 }
 
 let shouldPreRegisterDynamicContracts = (chainConfig: chainConfig) => {
@@ -139,6 +167,11 @@ let shouldPruneHistory = (config, ~isInReorgThreshold) =>
   | _ => false
   }
 
+let shouldSaveRawEvents = (config, ~chain) =>
+  (config.chainMap->ChainMap.get(chain)).enableRawEvents->Option.getWithDefault(
+    config.enableRawEvents,
+  )
+
 let getChain = (config, ~chainId) => {
   let chain = ChainMap.Chain.makeUnsafe(~chainId)
   config.chainMap->ChainMap.has(chain)
@@ -383,7 +383,7 @@ let runHandler = async (
   result->Result.map(() => {
     eventItem->updateEventSyncState(~inMemoryStore, ~isPreRegisteringDynamicContracts=false)
 
-    if config.enableRawEvents {
+    if config->Config.shouldSaveRawEvents(~chain=eventItem.chain) {
       eventItem->addEventToRawEvents(~inMemoryStore)
     }
 
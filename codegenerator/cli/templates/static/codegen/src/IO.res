@@ -141,6 +141,51 @@ let executeDbFunctionsEntity = (
   promises->Promise.all->Promise.thenResolve(_ => ())
 }
 
+let executeSetImmutableEntity = async (
+  type entity,
+  sql: Postgres.sql,
+  ~inMemoryStore: InMemoryStore.t,
+  ~entityMod: module(Entities.Entity with type t = entity),
+): unit => {
+  let module(Entity) = entityMod
+  let entities = []
+  let rows = []
+  inMemoryStore.entities
+  ->InMemoryStore.EntityTables.get(entityMod->Entities.entityModToInternal)
+  ->InMemoryTable.Entity.rows
+  ->Array.forEach(row =>
+    switch row {
+    | Updated({latest: {entityUpdateAction: Set(entity), eventIdentifier}}) =>
+      entities->Js.Array2.push(entity)->ignore
+      rows
+      ->Js.Array2.push(
+        entity->EntityHistory.Immutable.makeRow(
+          ~event={
+            chain_id: eventIdentifier.chainId,
+            block_timestamp: eventIdentifier.blockTimestamp,
+            block_number: eventIdentifier.blockNumber,
+            log_index: eventIdentifier.logIndex,
+          },
+        ),
+      )
+      ->ignore
+    | _ => ()
+    }
+  )
+
+  if rows->Array.length > 0 {
+    await sql->DbFunctionsEntities.batchSetImmutable(
+      ~entityMod=entityMod->Entities.entityModToInternal,
+    )(rows)
+    //The buckets are rolled up after the data points are written, since they're
+    //recomputed from the data points in the db
+    await sql->DbFunctions.AggregationRollup.rollUp(
+      ~sourceEntityName=Entity.name,
+      ~dataPoints=entities,
+    )
+  }
+}
+
 let executeBatch = async (sql, ~inMemoryStore: InMemoryStore.t, ~isInReorgThreshold, ~config) => {
   let entityDbExecutionComposer =
     config->Config.shouldSaveHistory(~isInReorgThreshold)
@@ -160,7 +205,10 @@ let executeBatch = async (sql, ~inMemoryStore: InMemoryStore.t, ~isInReorgThresh
   )
 
   let setEntities = Entities.allEntities->Belt.Array.map(entityMod => {
-    entityDbExecutionComposer(_, ~entityMod, ~inMemoryStore)
+    let module(Entity) = entityMod
+    Entity.isImmutable
+      ? executeSetImmutableEntity(_, ~entityMod, ~inMemoryStore)
+      : entityDbExecutionComposer(_, ~entityMod, ~inMemoryStore)
   })
 
   //In the event of a rollback, rollback all meta tables based on the given
@@ -213,7 +261,7 @@ module RollBack = {
     let inMemStore = InMemoryStore.make(~rollBackEventIdentifier)
 
     let _ =
-      await Entities.allEntities
+      await Entities.allEntitiesWithHistory
       ->Belt.Array.map(async entityMod => {
         let module(Entity) = entityMod
         let entityMod =
@@ -139,7 +139,7 @@ let makeAppState = (globalState: GlobalState.t): EnvioInkApp.appState => {
           poweredByHyperSync: switch cf.chainConfig.syncSource {
           | HyperSync
           | HyperFuel => true
-          | Rpc => false
+          | Rpc | File => false
           },
         }: EnvioInkApp.chainData
       )
@@ -0,0 +1,98 @@
+/**
+Decodes the logs of a transaction with the ABIs of the contracts in config.yaml.
+A log is matched to an event by its topic0 and number of topics.
+When the log is emitted by an address from config.yaml, only the events
+of the contract with the address are considered. Otherwise the log is
+decoded with any contract having the event, so it also works for
+dynamically registered and not indexed addresses.
+*/
+@genType
+type decodedLog = {
+  contractName: string,
+  eventName: string,
+  params: unknown,
+}
+
+type contract = {
+  name: string,
+  abi: EvmTypes.Abi.t,
+  addresses: array<Address.t>,
+  events: array<module(Types.Event)>,
+}
+
+type t = {
+  contractNameAbiMapping: dict<EvmTypes.Abi.t>,
+  eventsById: dict<array<module(Types.Event)>>,
+  contractNamesByAddress: dict<array<string>>,
+}
+
+let toAddressKey = address => address->Address.toString->Js.String2.toLowerCase
+
+let make = (contracts: array<contract>) => {
+  let contractNameAbiMapping = Js.Dict.empty()
+  let eventsById = Js.Dict.empty()
+  let contractNamesByAddress = Js.Dict.empty()
+  contracts->Js.Array2.forEach(contract => {
+    contractNameAbiMapping->Js.Dict.set(contract.name, contract.abi)
+    contract.addresses->Js.Array2.forEach(address => {
+      let key = address->toAddressKey
+      switch contractNamesByAddress->Utils.Dict.dangerouslyGetNonOption(key) {
+      | Some(names) => names->Js.Array2.push(contract.name)->ignore
+      | None => contractNamesByAddress->Js.Dict.set(key, [contract.name])
+      }
+    })
+    contract.events->Js.Array2.forEach(eventMod => {
+      let module(Event) = eventMod
+      switch eventsById->Utils.Dict.dangerouslyGetNonOption(Event.id) {
+      | Some(events) => events->Js.Array2.push(eventMod)->ignore
+      | None => eventsById->Js.Dict.set(Event.id, [eventMod])
+      }
+    })
+  })
+  {contractNameAbiMapping, eventsById, contractNamesByAddress}
+}
+
+let decode = (decoder: t, log: EvmTypes.log): option<decodedLog> => {
+  switch log.topics->Belt.Array.get(0) {
+  | None => None
+  | Some(topic0) =>
+    let events =
+      decoder.eventsById
+      ->Utils.Dict.dangerouslyGetNonOption(
+        EventRouter.getEvmEventId(~sighash=topic0, ~topicCount=log.topics->Js.Array2.length),
+      )
+      ->Belt.Option.getWithDefault([])
+    let events = switch decoder.contractNamesByAddress->Utils.Dict.dangerouslyGetNonOption(
+      log.address->toAddressKey,
+    ) {
+    | Some(contractNames) =>
+      events->Js.Array2.filter(eventMod => {
+        let module(Event) = eventMod
+        contractNames->Js.Array2.includes(Event.contractName)
+      })
+    | None => events
+    }
+    events->Belt.Array.reduce(None, (decoded, eventMod) => {
+      switch decoded {
+      | Some(_) => decoded
+      | None =>
+        let module(Event) = eventMod
+        // Different contracts might have an event with the same signature,
+        // but different indexed params, so try the next one when decoding fails
+        switch decoder.contractNameAbiMapping->Viem.parseLogOrThrow(
+          ~contractName=Event.contractName,
+          ~topics=log.topics->EvmTypes.Hex.fromStringsUnsafe,
+          ~data=log.data,
+        ) {
+        | exception _ => None
+        | decodedEvent =>
+          Some({
+            contractName: Event.contractName,
+            eventName: Event.name,
+            params: decodedEvent.args,
+          })
+        }
+      }
+    })
+  }
+}
@@ -24,7 +24,7 @@ let allEntityTables: array<Table.table> = Entities.allEntities->Belt.Array.map(e
 let allEntityHistoryTables: array<Table.table> = []
 let allEntityHistory: array<
   EntityHistory.t<EntityHistory.entityInternal>,
-> = Entities.allEntities->Belt.Array.map(entityMod => {
+> = Entities.allEntitiesWithHistory->Belt.Array.map(entityMod => {
   let module(Entity) = entityMod
   let entityHistory = Entity.entityHistory->EntityHistory.castInternal
   allEntityHistoryTables->Js.Array2.push(entityHistory.table)->ignore
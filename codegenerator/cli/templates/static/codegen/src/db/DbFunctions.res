@@ -15,10 +15,14 @@ let quote = (string: string): string => {
 }
 
 @module("./DbFunctionsImplementation.js")
-external makeBatchSetEntityValues: Table.table => (Postgres.sql, unknown) => promise<unit> =
-  "makeBatchSetEntityValues"
-
-let makeTableBatchSet = (table, schema: S.t<'entity>) => {
+external makeBatchSetEntityValues: (
+  Table.table,
+  ~isInsertOnly: bool,
+) => (Postgres.sql, unknown) => promise<unit> = "makeBatchSetEntityValues"
+
+//Insert only batch sets don't handle conflicts, so writing an immutable
+//entity with an existing id fails instead of updating it
+let makeTableBatchSet = (~isInsertOnly=false, table, schema: S.t<'entity>) => {
   let {dbSchema, quotedFieldNames, quotedNonPrimaryFieldNames, arrayFieldTypes, hasArrayField} =
     table->Table.toSqlParams(~schema)
   let isRawEvents = table.tableName === TablesStatic.RawEvents.table.tableName
@@ -44,7 +48,7 @@ SELECT * FROM unnest(${arrayFieldTypes
           `$${(idx + 1)->Js.Int.toString}::${arrayFieldType}`
         })
         ->Js.Array2.joinWith(",")})` ++
-      switch (isRawEvents, primaryKeyFieldNames) {
+      switch (isRawEvents || isInsertOnly, primaryKeyFieldNames) {
       | (true, _)
       | (_, []) => ``
       | (false, primaryKeyFieldNames) =>
@@ -66,14 +70,14 @@ SELECT * FROM unnest(${arrayFieldTypes
     }
   } else {
     let convertOrThrow = S.compile(
-      S.array(schema),
+      S.array(schema->Table.stringifyJsonFields(~table)),
       ~input=Value,
       ~output=Unknown,
       ~mode=Sync,
       ~typeValidation,
     )
 
-    let query = makeBatchSetEntityValues(table)
+    let query = makeBatchSetEntityValues(table, ~isInsertOnly)
 
     (sql, entityDataArray: array<'entity>): promise<unit> => {
       query(sql, convertOrThrow(entityDataArray))
@@ -228,6 +232,36 @@ module DynamicContractRegistry = {
   }
 }
 
+module AggregationRollup = {
+  let getTimestamp = (entity: 'entity) =>
+    (entity->(Utils.magic: 'entity => {"timestamp": Js.Date.t}))["timestamp"]
+
+  //Recomputes the buckets of the aggregations using the time-series entity as their source
+  //which have any of the given data points. It runs after the data points are written or
+  //deleted, since the buckets are computed from the data points in the db
+  let rollUp = (sql, ~sourceEntityName, ~dataPoints: array<'entity>) => {
+    let rollups =
+      Entities.allAggregationRollups->Js.Array2.filter(rollup =>
+        rollup.sourceEntityName == sourceEntityName
+      )
+    if rollups->Utils.Array.isEmpty || dataPoints->Utils.Array.isEmpty {
+      Promise.resolve()
+    } else {
+      let timestamps =
+        dataPoints->Belt.Array.map(dataPoint => dataPoint->getTimestamp->Js.Date.toISOString)
+      rollups
+      ->Belt.Array.map(rollup =>
+        sql->Postgres.preparedUnsafe(
+          rollup.query,
+          [timestamps]->(Utils.magic: array<array<string>> => unknown),
+        )
+      )
+      ->Promise.all
+      ->Promise.thenResolve(_ => ())
+    }
+  }
+}
+
 module EntityHistory = {
   type dynamicSqlQuery
   module UnorderedMultichain = {
@@ -251,6 +285,52 @@ module EntityHistory = {
     ) => dynamicSqlQuery = "getFirstChangeSerial_OrderedMultichain"
   }
 
+  module Immutable = {
+    @module("./DbFunctionsImplementation.js")
+    external getFirstChangeSerial_UnorderedMultichain: (
+      Postgres.sql,
+      ~reorgChainId: int,
+      ~safeBlockNumber: int,
+      ~tableName: string,
+    ) => dynamicSqlQuery = "getFirstImmutableChangeSerial_UnorderedMultichain"
+
+    @module("./DbFunctionsImplementation.js")
+    external getFirstChangeSerial_OrderedMultichain: (
+      Postgres.sql,
+      ~safeBlockTimestamp: int,
+      ~reorgChainId: int,
+      ~safeBlockNumber: int,
+      ~tableName: string,
+    ) => dynamicSqlQuery = "getFirstImmutableChangeSerial_OrderedMultichain"
+
+    @module("./DbFunctionsImplementation.js")
+    external getFirstChangePerChain: (
+      Postgres.sql,
+      ~tableName: string,
+      ~getFirstChangeSerial: Postgres.sql => dynamicSqlQuery,
+    ) => promise<Js.Json.t> = "getFirstChangeImmutableEntityPerChain"
+
+    //Returns the deleted rows
+    @module("./DbFunctionsImplementation.js")
+    external deleteRolledBack: (
+      Postgres.sql,
+      ~tableName: string,
+      ~getFirstChangeSerial: Postgres.sql => dynamicSqlQuery,
+    ) => promise<Js.Json.t> = "deleteRolledBackImmutableEntities"
+
+    let firstChangePerChainSchema = S.array(
+      S.object(s => (
+        s.field("chain_id", S.int),
+        (
+          {
+            blockNumber: s.field("block_number", S.int),
+            logIndex: s.field("log_index", S.int),
+          }: FetchState.blockNumberAndLogIndex
+        ),
+      )),
+    )
+  }
+
   @module("./DbFunctionsImplementation.js")
   external getFirstChangeEntityHistoryPerChain: (
     Postgres.sql,
@@ -353,6 +433,27 @@ module EntityHistory = {
           )
       }
 
+    let makeGetFirstImmutableChangeSerial = (self: t, ~tableName) =>
+      switch self {
+      | OrderedMultichain({safeBlockTimestamp, reorgChainId, safeBlockNumber}) =>
+        sql =>
+          Immutable.getFirstChangeSerial_OrderedMultichain(
+            sql,
+            ~safeBlockTimestamp,
+            ~reorgChainId,
+            ~safeBlockNumber,
+            ~tableName,
+          )
+      | UnorderedMultichain({reorgChainId, safeBlockNumber}) =>
+        sql =>
+          Immutable.getFirstChangeSerial_UnorderedMultichain(
+            sql,
+            ~reorgChainId,
+            ~safeBlockNumber,
+            ~tableName,
+          )
+      }
+
     let getLogger = (self: t, ~entityName) => {
       switch self {
       | OrderedMultichain({safeBlockTimestamp, reorgChainId, safeBlockNumber}) =>
@@ -399,16 +500,40 @@ module EntityHistory = {
       await allEntities
       ->Belt.Array.map(async entityMod => {
         let module(Entity) = entityMod
-        try await deleteRolledBackEntityHistory(
-          sql,
-          ~entityName=Entity.name,
-          ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
-        ) catch {
-        | exn =>
-          exn->ErrorHandling.mkLogAndRaise(
-            ~msg=`Failed to delete rolled back entity history`,
-            ~logger=args->Args.getLogger(~entityName=Entity.name),
-          )
+        if Entity.isImmutable {
+          //Immutable entities have no history, so their rows of the rolled back events are deleted
+          try {
+            let deletedRows =
+              (await Immutable.deleteRolledBack(
+                sql,
+                ~tableName=Entity.table.tableName,
+                ~getFirstChangeSerial=args->Args.makeGetFirstImmutableChangeSerial(
+                  ~tableName=Entity.table.tableName,
+                ),
+              ))->S.parseOrThrow(Entity.rowsSchema)
+            await sql->AggregationRollup.rollUp(
+              ~sourceEntityName=Entity.name,
+              ~dataPoints=deletedRows,
+            )
+          } catch {
+          | exn =>
+            exn->ErrorHandling.mkLogAndRaise(
+              ~msg=`Failed to delete rolled back immutable entities`,
+              ~logger=args->Args.getLogger(~entityName=Entity.name),
+            )
+          }
+        } else {
+          try await deleteRolledBackEntityHistory(
+            sql,
+            ~entityName=Entity.name,
+            ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
+          ) catch {
+          | exn =>
+            exn->ErrorHandling.mkLogAndRaise(
+              ~msg=`Failed to delete rolled back entity history`,
+              ~logger=args->Args.getLogger(~entityName=Entity.name),
+            )
+          }
         }
       })
       ->Promise.all
@@ -498,35 +623,63 @@ module EntityHistory = {
       await allEntities
       ->Belt.Array.map(async entityMod => {
         let module(Entity) = entityMod
-        let res = try await getFirstChangeEntityHistoryPerChain(
-          sql,
-          ~entityName=Entity.name,
-          ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
-        ) catch {
-        | exn =>
-          exn->ErrorHandling.mkLogAndRaise(
-            ~msg=`Failed to get first change entity history per chain for entity`,
-            ~logger=args->Args.getLogger(~entityName=Entity.name),
-          )
-        }
-
-        let chainHistoryRows = try res->S.parseOrThrow(Entity.entityHistory.schemaRows) catch {
-        | exn =>
-          exn->ErrorHandling.mkLogAndRaise(
-            ~msg=`Failed to parse entity history rows from db on getFirstChangeEntityHistoryPerChain`,
-            ~logger=args->Args.getLogger(~entityName=Entity.name),
-          )
+        if Entity.isImmutable {
+          let res = try await Immutable.getFirstChangePerChain(
+            sql,
+            ~tableName=Entity.table.tableName,
+            ~getFirstChangeSerial=args->Args.makeGetFirstImmutableChangeSerial(
+              ~tableName=Entity.table.tableName,
+            ),
+          ) catch {
+          | exn =>
+            exn->ErrorHandling.mkLogAndRaise(
+              ~msg=`Failed to get first change per chain for immutable entity`,
+              ~logger=args->Args.getLogger(~entityName=Entity.name),
+            )
+          }
+
+          let chainRows = try res->S.parseOrThrow(Immutable.firstChangePerChainSchema) catch {
+          | exn =>
+            exn->ErrorHandling.mkLogAndRaise(
+              ~msg=`Failed to parse immutable entity rows from db on getFirstChangePerChain`,
+              ~logger=args->Args.getLogger(~entityName=Entity.name),
+            )
+          }
+
+          chainRows->Belt.Array.forEach(((chainId, event)) => {
+            firstChangeEventPerChain->FirstChangeEventPerChain.setIfEarlier(~chainId, ~event)
+          })
+        } else {
+          let res = try await getFirstChangeEntityHistoryPerChain(
+            sql,
+            ~entityName=Entity.name,
+            ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
+          ) catch {
+          | exn =>
+            exn->ErrorHandling.mkLogAndRaise(
+              ~msg=`Failed to get first change entity history per chain for entity`,
+              ~logger=args->Args.getLogger(~entityName=Entity.name),
+            )
+          }
+
+          let chainHistoryRows = try res->S.parseOrThrow(Entity.entityHistory.schemaRows) catch {
+          | exn =>
+            exn->ErrorHandling.mkLogAndRaise(
+              ~msg=`Failed to parse entity history rows from db on getFirstChangeEntityHistoryPerChain`,
+              ~logger=args->Args.getLogger(~entityName=Entity.name),
+            )
+          }
+
+          chainHistoryRows->Belt.Array.forEach(chainHistoryRow => {
+            firstChangeEventPerChain->FirstChangeEventPerChain.setIfEarlier(
+              ~chainId=chainHistoryRow.current.chain_id,
+              ~event={
+                blockNumber: chainHistoryRow.current.block_number,
+                logIndex: chainHistoryRow.current.log_index,
+              },
+            )
+          })
         }
-
-        chainHistoryRows->Belt.Array.forEach(chainHistoryRow => {
-          firstChangeEventPerChain->FirstChangeEventPerChain.setIfEarlier(
-            ~chainId=chainHistoryRow.current.chain_id,
-            ~event={
-              blockNumber: chainHistoryRow.current.block_number,
-              logIndex: chainHistoryRow.current.log_index,
-            },
-          )
-        })
       })
       ->Promise.all
 
@@ -545,7 +698,7 @@ module EntityHistory = {
 
   let hasRows = async sql => {
     let all =
-      await Entities.allEntities
+      await Entities.allEntitiesWithHistory
       ->Belt.Array.map(async entityMod => {
         let module(Entity) = entityMod
         try await General.hasRows(sql, ~table=Entity.entityHistory.table) catch {
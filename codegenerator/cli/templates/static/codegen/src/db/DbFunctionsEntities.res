@@ -30,8 +30,8 @@ let makeReadEntities = (~table: Table.table, ~rowsSchema: S.t<array<'entityRow>>
   }
 }
 
-let makeBatchSet = (~table: Table.table, ~schema: S.t<'entity>) => {
-  let query = DbFunctions.makeTableBatchSet(table, schema)
+let makeBatchSet = (~table: Table.table, ~schema: S.t<'entity>, ~isInsertOnly) => {
+  let query = DbFunctions.makeTableBatchSet(~isInsertOnly, table, schema)
   async (sql: Postgres.sql, entities: array<'entity>, ~logger=?) => {
     switch await query(sql, entities) {
     | exception (S.Raised(_) as exn) =>
@@ -84,7 +84,7 @@ let batchSet = (type entity, ~entityMod: module(Entities.Entity with type t = en
   let {table, schema} = module(EntityMod)
   switch Utils.WeakMap.get(batchSetCache, entityMod->Entities.entityModToInternal) {
   | None =>
-    let query = makeBatchSet(~table, ~schema)
+    let query = makeBatchSet(~table, ~schema, ~isInsertOnly=false)
     Utils.WeakMap.set(
       batchSetCache,
       entityMod->Entities.entityModToInternal,
@@ -95,6 +95,41 @@ let batchSet = (type entity, ~entityMod: module(Entities.Entity with type t = en
   }
 }
 
+type batchSetImmutable<'entity> = (
+  Postgres.sql,
+  array<EntityHistory.Immutable.row<'entity>>,
+  ~logger: Pino.t=?,
+) => promise<unit>
+let batchSetImmutableCache: Utils.WeakMap.t<
+  module(Entities.InternalEntity),
+  batchSetImmutable<Internal.entity>,
+> = Utils.WeakMap.make()
+//Immutable entities are inserted with the event which inserted them, since
+//they have no history table for rolling back reorgs
+let batchSetImmutable = (
+  type entity,
+  ~entityMod: module(Entities.Entity with type t = entity),
+): batchSetImmutable<entity> => {
+  let module(EntityMod) = entityMod
+  let {table, schema} = module(EntityMod)
+  switch Utils.WeakMap.get(batchSetImmutableCache, entityMod->Entities.entityModToInternal) {
+  | None =>
+    let query = makeBatchSet(
+      ~table,
+      ~schema=schema->EntityHistory.Immutable.makeRowSchema,
+      ~isInsertOnly=true,
+    )
+    Utils.WeakMap.set(
+      batchSetImmutableCache,
+      entityMod->Entities.entityModToInternal,
+      query->(Utils.magic: batchSetImmutable<entity> => batchSetImmutable<Internal.entity>),
+    )->ignore
+    query
+  | Some(query) =>
+    query->(Utils.magic: batchSetImmutable<Internal.entity> => batchSetImmutable<entity>)
+  }
+}
+
 let batchDelete = (type entity, ~entityMod: module(Entities.Entity with type t = entity)) => {
   let module(EntityMod) = entityMod
   let {table} = module(EntityMod)
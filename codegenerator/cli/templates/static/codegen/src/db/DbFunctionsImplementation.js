@@ -140,10 +140,14 @@ module.exports.batchDeleteRawEvents = (sql, entityIdArray) => sql`
   WHERE (chain_id, event_id) IN ${sql(entityIdArray)};`;
 // end db operations for raw_events
 
-module.exports.makeBatchSetEntityValues = (table) => {
-  const fieldNames = TableModule.getFieldNames(table).filter(
-    (fieldName) => fieldName !== "db_write_timestamp"
-  );
+module.exports.makeBatchSetEntityValues = (table, isInsertOnly) => {
+  //Serial fields are set by the db
+  const fieldNames = TableModule.getFields(table)
+    .filter(
+      (field) =>
+        field.fieldName !== "db_write_timestamp" && field.fieldType !== "SERIAL"
+    )
+    .map(TableModule.getDbFieldName);
   const primaryKeyFieldNames = TableModule.getPrimaryKeyFieldNames(table);
   const fieldQueryConstructors = fieldNames.map(
     (fieldName) => (sql) => sql`${sql(fieldName)} = EXCLUDED.${sql(fieldName)}`
@@ -153,6 +157,11 @@ module.exports.makeBatchSetEntityValues = (table) => {
   );
 
   return chunkBatchQuery((sql, rowDataArray) => {
+    if (isInsertOnly) {
+      return sql`
+INSERT INTO ${sql(publicSchema)}.${sql(table.tableName)}
+${sql(rowDataArray, ...fieldNames)};`;
+    }
     return sql`
 INSERT INTO ${sql(publicSchema)}.${sql(table.tableName)}
 ${sql(rowDataArray, ...fieldNames)}
@@ -355,6 +364,97 @@ module.exports.deleteRolledBackEntityHistory = (
     );
   `;
 
+/**
+  Immutable entities have no history table, so the first change is found with the
+  event and the serial kept on their rows
+*/
+module.exports.getFirstImmutableChangeSerial_UnorderedMultichain = (
+  sql,
+  reorgChainId,
+  safeBlockNumber,
+  tableName
+) =>
+  sql`
+    SELECT
+      MIN(envio_serial) AS first_change_serial
+    FROM
+      ${sql(publicSchema)}.${sql(tableName)}
+    WHERE
+      envio_chain_id = ${reorgChainId}
+      AND envio_block_number > ${safeBlockNumber}
+  `;
+
+module.exports.getFirstImmutableChangeSerial_OrderedMultichain = (
+  sql,
+  safeBlockTimestamp,
+  reorgChainId,
+  safeBlockNumber,
+  tableName
+) =>
+  sql`
+    SELECT
+      MIN(envio_serial) AS first_change_serial
+    FROM
+      ${sql(publicSchema)}.${sql(tableName)}
+    WHERE
+      envio_block_timestamp > ${safeBlockTimestamp}
+      OR
+      (envio_block_timestamp = ${safeBlockTimestamp} AND envio_chain_id > ${reorgChainId})
+      OR
+      (envio_block_timestamp = ${safeBlockTimestamp} AND envio_chain_id = ${reorgChainId} AND envio_block_number > ${safeBlockNumber})
+  `;
+
+module.exports.getFirstChangeImmutableEntityPerChain = (
+  sql,
+  tableName,
+  getFirstChangeSerial
+) => sql`
+  WITH
+    first_change AS (
+      ${getFirstChangeSerial(sql)}
+    )
+  SELECT DISTINCT
+    ON (envio_chain_id)
+    envio_chain_id AS chain_id,
+    envio_block_number AS block_number,
+    envio_log_index AS log_index
+  FROM
+    ${sql(publicSchema)}.${sql(tableName)}
+  WHERE
+    envio_serial >= (
+      SELECT
+        first_change_serial
+      FROM
+        first_change
+    )
+  ORDER BY
+    envio_chain_id,
+    envio_serial
+    ASC; -- Select the row with the lowest serial per chain
+`;
+
+module.exports.deleteRolledBackImmutableEntities = (
+  sql,
+  tableName,
+  getFirstChangeSerial
+) => sql`
+  WITH
+    first_change AS (
+      ${getFirstChangeSerial(sql)}
+    )
+  -- Returns the deleted rows, since the buckets of the time-series ones are recomputed
+  DELETE FROM
+    ${sql(publicSchema)}.${sql(tableName)}
+  WHERE
+    envio_serial >= (
+      SELECT
+        first_change_serial
+      FROM
+        first_change
+    )
+  RETURNING *;
+  `;
+
 module.exports.pruneStaleEntityHistory = (
   sql,
   entityName,
@@ -36,10 +36,14 @@ let creatTableIfNotExists = (sql, table) => {
   sql->unsafe(query)
 }
 
-let makeCreateIndexQuery = (~tableName, ~indexFields) => {
+let makeCreateIndexQuery = (~tableName, ~indexFields, ~indexType: option<Table.indexType>=?) => {
   let indexName = tableName ++ "_" ++ indexFields->Js.Array2.joinWith("_")
   let index = indexFields->Belt.Array.map(idx => `"${idx}"`)->Js.Array2.joinWith(", ")
-  `CREATE INDEX IF NOT EXISTS "${indexName}" ON "${Env.Db.publicSchema}"."${tableName}"(${index}); `
+  let using = switch indexType {
+  | Some(indexType) => ` USING ${(indexType :> string)}`
+  | None => ""
+  }
+  `CREATE INDEX IF NOT EXISTS "${indexName}" ON "${Env.Db.publicSchema}"."${tableName}"${using}(${index}); `
 }
 
 let createTableIndices = (sql, table: Table.table) => {
@@ -50,12 +54,17 @@ let createTableIndices = (sql, table: Table.table) => {
     makeCreateIndexQuery(~tableName, ~indexFields)
   }
 
+  let createTypedIndex = ((indexField, indexType)) =>
+    makeCreateIndexQuery(~tableName, ~indexFields=[indexField], ~indexType)
+
   let singleIndices = table->Table.getSingleIndices
+  let typedSingleIndices = table->Table.getTypedSingleIndices
   let compositeIndices = table->Table.getCompositeIndices
 
   let query =
     singleIndices->Array.map(createIndex)->Js.Array2.joinWith("\n") ++
-      compositeIndices->Array.map(createCompositeIndex)->Js.Array2.joinWith("\n")
+    typedSingleIndices->Array.map(createTypedIndex)->Js.Array2.joinWith("\n") ++
+    compositeIndices->Array.map(createCompositeIndex)->Js.Array2.joinWith("\n")
 
   sql->unsafe(query)
 }
@@ -69,6 +78,42 @@ let createDerivedFromDbIndex = (~derivedFromField: Table.derivedFromField, ~sche
   sql->unsafe(query)
 }
 
+//The view is recreated, since the columns of the implementers can change between runs
+let createInterfaceView = (sql, view: Entities.interfaceView) => {
+  open Belt
+  let columns = view.fieldNames->Array.map(fieldName => `"${fieldName}"`)->Js.Array2.joinWith(", ")
+  let selects =
+    view.implementers
+    ->Array.map(implementer => {
+      let tableName = (implementer :> string)
+      `SELECT ${columns}, '${tableName}' AS "type" FROM "${Env.Db.publicSchema}"."${tableName}"`
+    })
+    ->Js.Array2.joinWith(" UNION ALL ")
+
+  let query = `
+    DROP VIEW IF EXISTS "${Env.Db.publicSchema}"."${view.viewName}";
+    CREATE VIEW "${Env.Db.publicSchema}"."${view.viewName}" AS ${selects};`
+
+  sql->unsafe(query)
+}
+
+//The functions of the computed fields serving the bytea columns as 0x prefixed hex strings
+let createByteaHexFunctions = (sql, table: Table.table) => {
+  open Belt
+  let {tableName} = table
+  let query =
+    table
+    ->Table.getByteaFieldNames
+    ->Array.map(fieldName => {
+      let functionName = Table.getByteaHexFunctionName(~tableName, ~fieldName)
+      `CREATE OR REPLACE FUNCTION "${Env.Db.publicSchema}"."${functionName}"(entity "${Env.Db.publicSchema}"."${tableName}")
+    RETURNS TEXT AS $$ SELECT '0x' || encode(entity."${fieldName}", 'hex') $$ LANGUAGE SQL STABLE;`
+    })
+    ->Js.Array2.joinWith("\n")
+
+  query === "" ? Promise.resolve() : sql->unsafe(query)
+}
+
 let createEnumIfNotExists = (sql, enum: Enum.enum<_>) => {
   open Belt
   let {variants, name} = enum
@@ -172,6 +217,19 @@ let runUpMigrations = async (
     ->handleFailure(~msg=`EE800: Error creating ${entityHistory.table.tableName} insert function`)
   })
 
+  //Create the union views of the interfaces (must be done after all tables are created)
+  await Entities.allInterfaceViews->awaitEach(view => {
+    createInterfaceView(Db.sql, view)->handleFailure(
+      ~msg=`EE800: Error creating ${view.viewName} interface view`,
+    )
+  })
+
+  await Db.allEntityTables->awaitEach(table => {
+    createByteaHexFunctions(Db.sql, table)->handleFailure(
+      ~msg=`EE800: Error creating ${table.tableName} bytea hex functions`,
+    )
+  })
+
   //Create all derivedFromField indices (must be done after all tables are created)
   await Db.allEntityTables->awaitEach(async table => {
     await table
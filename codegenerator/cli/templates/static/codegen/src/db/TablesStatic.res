@@ -89,6 +89,8 @@ module PersistedState = {
     schema_hash: string,
     handler_files_hash: string,
     abi_files_hash: string,
+    profile: option<string>,
+    schema_snapshot: Js.Json.t,
   }
 
   let table = mkTable(
@@ -101,6 +103,8 @@ module PersistedState = {
       mkField("schema_hash", Text),
       mkField("handler_files_hash", Text),
       mkField("abi_files_hash", Text),
+      mkField("profile", Text, ~isNullable),
+      mkField("schema_snapshot", JsonB),
     ],
   )
 }
@@ -180,6 +184,7 @@ module RawEvents = {
 
 module DynamicContractRegistry = {
   let name = Enums.EntityType.DynamicContractRegistry
+  let isImmutable = false
 
   @genType
   type t = {
@@ -131,7 +131,60 @@ let trackTables = async (~tableNames: array<string>) => {
   }
 }
 
-let createSelectPermissions = async (~tableName: string) => {
+let addComputedField = async (~tableName: string, ~name: string, ~functionName: string) => {
+  let body = {
+    "type": "pg_add_computed_field",
+    "args": {
+      "table": {
+        "schema": Env.Db.publicSchema,
+        "name": tableName,
+      },
+      "name": name,
+      "source": "default",
+      "definition": {
+        "function": {
+          "schema": Env.Db.publicSchema,
+          "name": functionName,
+        },
+      },
+    },
+  }
+
+  let response = await fetch(
+    Env.Hasura.graphqlEndpoint,
+    {
+      method: #POST,
+      body: body->Js.Json.stringifyAny->Belt.Option.getExn->Body.string,
+      headers: Headers.fromObject(headers),
+    },
+  )
+
+  let responseJson = await response->Response.json
+  let statusCode = response->Response.status
+
+  switch validateHasuraResponse(~statusCode, ~responseJson) {
+  | Error(_) =>
+    Logging.error({
+      "msg": `EE809: There was an issue adding the ${name} computed field to the ${tableName} table in hasura - indexing may still work - but you may have issues querying the data in hasura.`,
+      "tableName": tableName,
+      "requestStatusCode": statusCode,
+      "requestResponseJson": responseJson,
+    })
+  | Ok(case) =>
+    let msg = switch case {
+    | QuerySucceeded => "Hasura computed field added"
+    | AlreadyDone => "Hasura computed field already added"
+    }
+    Logging.trace({
+      "msg": msg,
+      "tableName": tableName,
+      "requestStatusCode": statusCode,
+      "requestResponseJson": responseJson,
+    })
+  }
+}
+
+let createSelectPermissions = async (~tableName: string, ~computedFieldNames=[]) => {
   let body = {
     "type": "pg_create_select_permission",
     "args": {
@@ -143,6 +196,7 @@ let createSelectPermissions = async (~tableName: string) => {
       "source": "default",
       "permission": {
         "columns": "*",
+        "computed_fields": computedFieldNames,
         "filter": Js.Obj.empty(),
         "limit": Env.Hasura.responseLimit,
       },
@@ -190,8 +244,14 @@ let createEntityRelationship = async (
   ~objectName: string,
   ~mappedEntity: string,
   ~isDerivedFrom: bool,
+  ~isInterface=false,
 ) => {
-  let derivedFromTo = isDerivedFrom ? `"id": "${relationalKey}"` : `"${relationalKey}_id" : "id"`
+  let derivedFromTo = switch (isDerivedFrom, isInterface) {
+  | (true, _) => `"id": "${relationalKey}"`
+  // The id is only unique for the entity type, so the tagged key maps to both view columns
+  | (false, true) => `"${relationalKey}_id" : "id", "${relationalKey}_type" : "type"`
+  | (false, false) => `"${relationalKey}_id" : "id"`
+  }
 
   let bodyString = `{"type": "pg_create_${relationshipType}_relationship","args": {"table": {"schema": "${Env.Db.publicSchema}", "name": "${tableName}"},"name": "${objectName}","source": "default","using": {"manual_configuration": {"remote_table": {"schema": "${Env.Db.publicSchema}", "name": "${mappedEntity}"},"column_mapping": {${derivedFromTo}}}}}}`
 
@@ -237,9 +297,34 @@ let trackAllTables = async () => {
     [Db.allStaticTables, Db.allEntityTables]
     ->Belt.Array.concatMany
     ->Js.Array2.map(({tableName}) => tableName)
+    // Views are tracked the same way as tables
+    ->Js.Array2.concat(Entities.allInterfaceViews->Js.Array2.map(({viewName}) => viewName))
   await trackTables(~tableNames)
+
+  //The bytea fields are also served as 0x prefixed hex strings
+  let computedFieldNamesPerTable = Js.Dict.empty()
+  await Db.allEntityTables->Utils.Array.awaitEach(async table => {
+    let {tableName} = table
+    let computedFieldNames = []
+    await table
+    ->Table.getByteaFieldNames
+    ->Utils.Array.awaitEach(async fieldName => {
+      let name = Table.getByteaHexComputedFieldName(fieldName)
+      await addComputedField(
+        ~tableName,
+        ~name,
+        ~functionName=Table.getByteaHexFunctionName(~tableName, ~fieldName),
+      )
+      computedFieldNames->Js.Array2.push(name)->ignore
+    })
+    computedFieldNamesPerTable->Js.Dict.set(tableName, computedFieldNames)
+  })
+
   await tableNames->Utils.Array.awaitEach(async tableName => {
-    await createSelectPermissions(~tableName)
+    await createSelectPermissions(
+      ~tableName,
+      ~computedFieldNames=?computedFieldNamesPerTable->Js.Dict.get(tableName),
+    )
   })
 
   await Db.allEntityTables->Utils.Array.awaitEach(async table => {
@@ -273,6 +358,9 @@ let trackAllTables = async () => {
         ~objectName=field.fieldName,
         ~relationalKey=field.fieldName,
         ~mappedEntity=linkedEntityName,
+        ~isInterface=Entities.allInterfaceViews->Js.Array2.some(({viewName}) =>
+          viewName == linkedEntityName
+        ),
       )
     })
   })
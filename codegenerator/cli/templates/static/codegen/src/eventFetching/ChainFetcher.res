@@ -7,6 +7,61 @@ type processingFilter = {
   isValid: (~fetchState: FetchState.t) => bool,
 }
 
+// Block handlers run after the events of their block, so they get the last log indexes,
+// which still fit into the packed event index
+let maxLogIndex = 65535
+
+let blockHandlerSrcAddress = "0x0000000000000000000000000000000000000000"->Address.unsafeFromString
+
+let makeBlockHandlerItem = (
+  ~chain,
+  ~name,
+  ~logIndex,
+  ~handler: Types.BlockHandlers.handler,
+  ~block: Internal.eventBlock,
+): Internal.eventItem => {
+  let chainId = chain->ChainMap.Chain.toChainId
+  let timestamp = block->Types.Block.getTimestamp
+  let blockNumber = block->Types.Block.getNumber
+  {
+    eventName: name,
+    contractName: "Block",
+    loader: None,
+    handler: Some(
+      ({context}: Internal.handlerArgs) =>
+        handler({
+          block: block->(Utils.magic: Internal.eventBlock => Types.BlockHandlers.block),
+          chainId,
+          context: context->(Utils.magic: Internal.handlerContext => Types.handlerContext),
+        }),
+    ),
+    contractRegister: None,
+    timestamp,
+    chain,
+    blockNumber,
+    logIndex,
+    event: {
+      params: %raw(`{}`),
+      chainId,
+      srcAddress: blockHandlerSrcAddress,
+      logIndex,
+      transaction: %raw(`{}`),
+      block,
+    },
+    paramsRawEventSchema: S.json(~validate=false)->(
+      Utils.magic: S.schema<Js.Json.t> => S.schema<Internal.eventParams>
+    ),
+  }
+}
+
+// Events of the contract are still fetched after its endBlock
+// when other contracts share the partition, so they are filtered out before processing
+let makeContractEndBlockFilter = (~contractName, ~contractEndBlock): processingFilter => {
+  filter: (item: Internal.eventItem) =>
+    item.contractName !== contractName || item.blockNumber <= contractEndBlock,
+  isValid: (~fetchState as _) => true,
+}
+
 type addressToDynContractLookup = dict<TablesStatic.DynamicContractRegistry.t>
 type t = {
   logger: Pino.t,
@@ -59,7 +114,9 @@ let make = (
 
   // Aggregate events we want to fetch
   let staticContracts = Js.Dict.empty()
+  let contractStartBlocks = Js.Dict.empty()
   let eventConfigs: array<FetchState.eventConfig> = []
+  let contractEndBlockFilters = []
 
   chainConfig.contracts->Array.forEach(contract => {
     let contractName = contract.name
@@ -109,13 +166,96 @@ let make = (
       }
     })
 
+    // Calls can't register dynamic contracts, so they aren't fetched on preRegistration phase
+    if !isPreRegisteringDynamicContracts {
+      contract.calls
+      ->Option.getWithDefault([])
+      ->Array.forEach(call => {
+        switch call.handler {
+        | Some(_) =>
+          eventConfigs->Array.push({
+            contractName,
+            // The sighash of a function doesn't collide with the event ids
+            eventId: call.sighash,
+            isWildcard: false,
+          })
+        | None =>
+          logger->Logging.childInfo(
+            `The call "${call.name}" for contract "${contractName}" is not going to be indexed, because it doesn't have a handler.`,
+          )
+        }
+      })
+    }
+
     staticContracts->Js.Dict.set(contractName, contract.addresses)
+
+    switch contract.startBlock {
+    | Some(contractStartBlock) => contractStartBlocks->Js.Dict.set(contractName, contractStartBlock)
+    | None => ()
+    }
+
+    switch contract.endBlock {
+    | Some(contractEndBlock) =>
+      contractEndBlockFilters->Array.push(
+        makeContractEndBlockFilter(~contractName, ~contractEndBlock),
+      )
+    | None => ()
+    }
   })
 
+  // Block handlers without a registered handler have nothing to run,
+  // and none of them run while dynamic contracts are pre-registered
+  let blockHandlers = if isPreRegisteringDynamicContracts {
+    []
+  } else {
+    let blockHandlersCount = chainConfig.blockHandlers->Array.length
+    chainConfig.blockHandlers
+    ->Array.mapWithIndex((index, blockHandler) => {
+      switch blockHandler.handlerRef.contents {
+      | Some(handler) =>
+        let logIndex = maxLogIndex - blockHandlersCount + 1 + index
+        Some(
+          (
+            {
+              name: blockHandler.name,
+              interval: blockHandler.interval,
+              // Intervals are counted from the start of the chain,
+              // so they stay the same after a restart
+              startBlock: blockHandler.startBlock->Option.getWithDefault(chainConfig.startBlock),
+              endBlock: blockHandler.endBlock,
+              makeItem: (~block) =>
+                makeBlockHandlerItem(
+                  ~chain=chainConfig.chain,
+                  ~name=blockHandler.name,
+                  ~logIndex,
+                  ~handler,
+                  ~block,
+                ),
+            }: FetchState.blockHandler
+          ),
+        )
+      | None =>
+        logger->Logging.childInfo(
+          `The block handler "${blockHandler.name}" is not going to run, because it doesn't have a registered onBlock handler.`,
+        )
+        None
+      }
+    })
+    ->Array.keepMap(blockHandler => blockHandler)
+  }
+
+  let processingFilters = switch (processingFilters, contractEndBlockFilters) {
+  | (_, []) => processingFilters
+  | (None, filters) => Some(filters)
+  | (Some(processingFilters), filters) => Some(processingFilters->Array.concat(filters))
+  }
+
   let fetchState = FetchState.make(
     ~maxAddrInPartition,
     ~staticContracts,
+    ~contractStartBlocks,
     ~dynamicContracts,
+    ~blockHandlers,
     ~startBlock,
     ~endBlock,
     ~eventConfigs,
@@ -126,6 +266,8 @@ let make = (
     chainConfig,
     startBlock,
     sourceManager: SourceManager.make(
+      ~sources=chainConfig.sources,
+      ~stallTimeoutMillis=chainConfig.sourceStallTimeout,
       ~maxPartitionConcurrency=Env.maxPartitionConcurrency,
       ~logger,
     ),
@@ -420,7 +562,7 @@ If not found, returns the higehest block below threshold
 let getLastKnownValidBlock = async (
   chainFetcher: t,
   //Parameter used for dependency injecting in tests
-  ~getBlockHashes=chainFetcher.chainConfig.source.getBlockHashes,
+  ~getBlockHashes=(chainFetcher.sourceManager->SourceManager.getActiveSource).getBlockHashes,
 ) => {
   let scannedBlockNumbers =
     chainFetcher.lastBlockScannedHashes->ReorgDetection.LastBlockScannedHashes.getThresholdBlockNumbers(
@@ -145,8 +145,11 @@ let determineNextEvent = (
 
 let makeFromConfig = (~config: Config.t, ~maxAddrInPartition=Env.maxAddrInPartition): t => {
   let chainFetchers =
-    config.chainMap->ChainMap.map(
-      ChainFetcher.makeFromConfig(_, ~maxAddrInPartition, ~enableRawEvents=config.enableRawEvents),
+    config.chainMap->ChainMap.map(chainConfig =>
+      chainConfig->ChainFetcher.makeFromConfig(
+        ~maxAddrInPartition,
+        ~enableRawEvents=config->Config.shouldSaveRawEvents(~chain=chainConfig.chain),
+      )
     )
   {
     chainFetchers,
@@ -165,7 +168,7 @@ let makeFromDbState = async (~config: Config.t, ~maxAddrInPartition=Env.maxAddrI
         chain,
         await chainConfig->ChainFetcher.makeFromDbState(
           ~maxAddrInPartition,
-          ~enableRawEvents=config.enableRawEvents,
+          ~enableRawEvents=config->Config.shouldSaveRawEvents(~chain),
         ),
       )
     })
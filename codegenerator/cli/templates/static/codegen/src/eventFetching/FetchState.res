@@ -19,9 +19,23 @@ type eventConfig = {
   isWildcard: bool,
 }
 
+/**
+A handler which runs every interval blocks between its start and end block,
+without needing an event
+*/
+type blockHandler = {
+  name: string,
+  interval: int,
+  startBlock: int,
+  endBlock: option<int>,
+  makeItem: (~block: Internal.eventBlock) => Internal.eventItem,
+}
+
 type selection = {
   eventConfigs: array<eventConfig>,
   isWildcard: bool,
+  // Set for the partition which fetches the blocks of the block handlers
+  blockHandlers?: array<blockHandler>,
 }
 
 type status = {mutable fetchingStateId: option<int>}
@@ -774,6 +788,61 @@ let getEarliestEvent = ({partitions}: t) => {
   item.contents
 }
 
+let isBlockHandlerBlock = (blockHandler: blockHandler, ~blockNumber) =>
+  blockNumber >= blockHandler.startBlock &&
+  switch blockHandler.endBlock {
+  | Some(endBlock) => blockNumber <= endBlock
+  | None => true
+  } &&
+  mod(blockNumber - blockHandler.startBlock, blockHandler.interval) === 0
+
+/**
+Returns the blocks between fromBlock and toBlock that any of the block handlers runs on,
+ordered from earliest to latest. When there are more than maxBlocks of them, the range
+is cut at the last returned block, which is returned as the new toBlock
+*/
+let getBlockHandlerBlockNumbers = (
+  blockHandlers: array<blockHandler>,
+  ~fromBlock,
+  ~toBlock,
+  ~maxBlocks,
+) => {
+  let blockNumbers = Utils.Set.make()
+  blockHandlers->Array.forEach(blockHandler => {
+    let firstBlock = if fromBlock <= blockHandler.startBlock {
+      blockHandler.startBlock
+    } else {
+      let intervalsToFromBlock =
+        (fromBlock - blockHandler.startBlock + blockHandler.interval - 1) / blockHandler.interval
+      blockHandler.startBlock + intervalsToFromBlock * blockHandler.interval
+    }
+    let lastBlock = switch blockHandler.endBlock {
+    | Some(endBlock) => Pervasives.min(endBlock, toBlock)
+    | None => toBlock
+    }
+    // Later blocks of a handler can't be within the returned range
+    let blockNumber = ref(firstBlock)
+    let count = ref(0)
+    while blockNumber.contents <= lastBlock && count.contents < maxBlocks {
+      blockNumbers->Utils.Set.add(blockNumber.contents)->ignore
+      blockNumber := blockNumber.contents + blockHandler.interval
+      count := count.contents + 1
+    }
+  })
+
+  let blockNumbers =
+    blockNumbers
+    ->Utils.Set.toArray
+    ->Js.Array2.sortInPlaceWith((a, b) => a - b)
+
+  if blockNumbers->Array.length > maxBlocks {
+    let blockNumbers = blockNumbers->Js.Array2.slice(~start=0, ~end_=maxBlocks)
+    (blockNumbers, blockNumbers->Js.Array2.unsafe_get(maxBlocks - 1))
+  } else {
+    (blockNumbers, toBlock)
+  }
+}
+
 /**
 Instantiates a fetch state with partitions for initial addresses
 */
@@ -782,7 +851,9 @@ let make = (
   ~endBlock,
   ~eventConfigs: array<eventConfig>,
   ~staticContracts: dict<array<Address.t>>,
+  ~contractStartBlocks: dict<int>=Js.Dict.empty(),
   ~dynamicContracts: array<TablesStatic.DynamicContractRegistry.t>,
+  ~blockHandlers: array<blockHandler>=[],
   ~maxAddrInPartition,
 ): t => {
   let latestFetchedBlock = {
@@ -866,13 +937,34 @@ let make = (
         }
       }
 
+      // Contracts starting after the chain startBlock get a partition per start block,
+      // so the blocks before it aren't queried for them
+      let lateContractsByStartBlock = Js.Dict.empty()
+
       staticContracts
       ->Js.Dict.entries
       ->Array.forEach(((contractName, addresses)) => {
         if contractNamesWithNormalEvents->Utils.Set.has(contractName) {
-          addresses->Array.forEach(a => {
-            registerAddress(contractName, a)
-          })
+          switch contractStartBlocks->Utils.Dict.dangerouslyGetNonOption(contractName) {
+          | Some(contractStartBlock) if contractStartBlock > startBlock => {
+              let key = contractStartBlock->Int.toString
+              let lateContracts = switch lateContractsByStartBlock->Utils.Dict.dangerouslyGetNonOption(
+                key,
+              ) {
+              | Some(lateContracts) => lateContracts
+              | None => {
+                  let lateContracts = []
+                  lateContractsByStartBlock->Js.Dict.set(key, lateContracts)
+                  lateContracts
+                }
+              }
+              lateContracts->Array.push((contractName, addresses))
+            }
+          | _ =>
+            addresses->Array.forEach(a => {
+              registerAddress(contractName, a)
+            })
+          }
         }
       })
 
@@ -888,9 +980,73 @@ let make = (
       ) {
         partitions->Array.push(pendingNormalPartition.contents)
       }
+
+      // Will be in the ASC order by Js spec
+      lateContractsByStartBlock
+      ->Js.Dict.entries
+      ->Array.forEach(((startBlockKey, lateContracts)) => {
+        let makeLatePartition = () => {
+          id: partitions->Array.length->Int.toString,
+          status: {
+            fetchingStateId: None,
+          },
+          latestFetchedBlock: {
+            blockTimestamp: 0,
+            blockNumber: Pervasives.max(startBlockKey->Int.fromString->Option.getExn - 1, 0),
+          },
+          selection: normalSelection,
+          contractAddressMapping: ContractAddressingMap.make(),
+          dynamicContracts: [],
+          fetchedEventQueue: [],
+        }
+
+        // Same as for registerAddress, split the addresses by maxAddrInPartition
+        let pendingLatePartition = ref(makeLatePartition())
+        lateContracts->Array.forEach(((contractName, addresses)) => {
+          addresses->Array.forEach(address => {
+            let pendingPartition = pendingLatePartition.contents
+            pendingPartition.contractAddressMapping->ContractAddressingMap.addAddress(
+              ~name=contractName,
+              ~address,
+            )
+            if (
+              pendingPartition.contractAddressMapping->ContractAddressingMap.addressCount ===
+                maxAddrInPartition
+            ) {
+              partitions->Array.push(pendingPartition)
+              pendingLatePartition := makeLatePartition()
+            }
+          })
+        })
+        if (
+          pendingLatePartition.contents.contractAddressMapping->ContractAddressingMap.addressCount > 0
+        ) {
+          partitions->Array.push(pendingLatePartition.contents)
+        }
+      })
     }
   }
 
+  // Block handler blocks aren't related to any address,
+  // so they are fetched by a partition which never merges, like a wildcard one
+  if blockHandlers->Array.length > 0 {
+    partitions->Array.push({
+      id: partitions->Array.length->Int.toString,
+      status: {
+        fetchingStateId: None,
+      },
+      latestFetchedBlock,
+      selection: {
+        isWildcard: true,
+        eventConfigs: [],
+        blockHandlers,
+      },
+      contractAddressMapping: ContractAddressingMap.make(),
+      dynamicContracts: [],
+      fetchedEventQueue: [],
+    })
+  }
+
   if partitions->Array.length === 0 {
     Js.Exn.raiseError(
       "Invalid configuration: Nothing to fetch. Make sure that you provided at least one contract address to index, or have events with Wildcard mode enabled.",
@@ -993,6 +1149,25 @@ Rolls back partitions to the given valid block
 */
 let rollbackPartition = (p: partition, ~firstChangeEvent: blockNumberAndLogIndex) => {
   switch p {
+  | {selection: {isWildcard: true}} if p.selection.blockHandlers->Option.isSome =>
+    // The blocks after the first change might be reorged,
+    // so they are fetched again with their new hashes
+    let shouldRollbackFetched = p.latestFetchedBlock.blockNumber >= firstChangeEvent.blockNumber
+    Some({
+      ...p,
+      status: {
+        fetchingStateId: None,
+      },
+      fetchedEventQueue: shouldRollbackFetched
+        ? p.fetchedEventQueue->pruneQueueFromFirstChangeEvent(~firstChangeEvent)
+        : p.fetchedEventQueue,
+      latestFetchedBlock: shouldRollbackFetched
+        ? {
+            blockNumber: Pervasives.max(firstChangeEvent.blockNumber - 1, 0),
+            blockTimestamp: 0,
+          }
+        : p.latestFetchedBlock,
+    })
   | {selection: {isWildcard: true}} =>
     Some({
       ...p,
@@ -31,7 +31,16 @@ type t = {
     ~blockNumbers: array<int>,
     ~logger: Pino.t,
   ) => promise<result<array<ReorgDetection.blockDataWithTimestamp>, exn>>,
+  /* Gets the blocks with the block fields of the global field selection,
+   which the block handlers run with */
+  getBlocks: (
+    ~blockNumbers: array<int>,
+    ~logger: Pino.t,
+  ) => promise<result<array<Internal.eventBlock>, exn>>,
   getHeightOrThrow: unit => promise<int>,
+  /* Waits for a height greater than the current one without polling.
+   Resolves with None when the source should fall back to polling. */
+  waitForNewHeight?: (~currentBlockHeight: int) => promise<option<int>>,
   fetchBlockRange: (
     ~fromBlock: int,
     ~toBlock: option<int>,
@@ -68,7 +77,7 @@ let getHeightWithRetry = async (~source, ~logger) => {
   height.contents
 }
 
-//Poll for a height greater or equal to the given blocknumber.
+//Wait for a height greater than the given blocknumber, polling when the source has no subscription.
 //Used for waiting until there is a new block to index
 let waitForNewBlock = async (source, ~currentBlockHeight, ~logger) => {
   let logger = Logging.createChildFrom(
@@ -80,14 +89,123 @@ let waitForNewBlock = async (source, ~currentBlockHeight, ~logger) => {
   )
   logger->Logging.childTrace("Waiting for new blocks")
 
-  let pollHeight = ref(await getHeightWithRetry(~source, ~logger))
+  let subscribedHeight = switch source.waitForNewHeight {
+  | Some(waitForNewHeight) => await waitForNewHeight(~currentBlockHeight)
+  | None => None
+  }
 
-  while pollHeight.contents <= currentBlockHeight {
-    await Time.resolvePromiseAfterDelay(~delayMilliseconds=source.pollingInterval)
-    pollHeight := (await getHeightWithRetry(~source, ~logger))
+  switch subscribedHeight {
+  | Some(height) => height
+  | None =>
+    let pollHeight = ref(await getHeightWithRetry(~source, ~logger))
+
+    while pollHeight.contents <= currentBlockHeight {
+      await Time.resolvePromiseAfterDelay(~delayMilliseconds=source.pollingInterval)
+      pollHeight := (await getHeightWithRetry(~source, ~logger))
+    }
+
+    pollHeight.contents
   }
+}
 
-  pollHeight.contents
+// Every block of the block handlers is fetched by a separate request on rpc,
+// so the range of a query is cut at this number of blocks
+let maxBlockHandlerBlocksPerQuery = 100
+
+/*
+Block handlers don't need any logs, so only the blocks they run on are fetched,
+together with the last block of the range and the parent of the first one for the reorg guard
+*/
+let fetchBlockHandlerRange = async (
+  source,
+  ~fromBlock,
+  ~toBlock,
+  ~currentBlockHeight,
+  ~blockHandlers: array<FetchState.blockHandler>,
+  ~logger,
+) => {
+  let totalTimeRef = Hrtime.makeTimer()
+  let toBlock = switch toBlock {
+  | Some(toBlock) => Pervasives.min(toBlock, currentBlockHeight)
+  | None => currentBlockHeight
+  }
+  let (blockNumbers, toBlock) =
+    blockHandlers->FetchState.getBlockHandlerBlockNumbers(
+      ~fromBlock,
+      ~toBlock,
+      ~maxBlocks=maxBlockHandlerBlocksPerQuery,
+    )
+  let parentBlockNumber = fromBlock - 1
+
+  let queriedBlockNumbers = Utils.Set.fromEntries(blockNumbers)
+  queriedBlockNumbers->Utils.Set.add(toBlock)->ignore
+  if parentBlockNumber >= 0 {
+    queriedBlockNumbers->Utils.Set.add(parentBlockNumber)->ignore
+  }
+
+  switch await source.getBlocks(~blockNumbers=queriedBlockNumbers->Utils.Set.toArray, ~logger) {
+  | Ok(blocks) =>
+    try {
+      let blocksByNumber = Js.Dict.empty()
+      blocks->Array.forEach(block =>
+        blocksByNumber->Js.Dict.set(block->Types.Block.getNumber->Int.toString, block)
+      )
+      let getBlock = blockNumber =>
+        blocksByNumber->Utils.Dict.dangerouslyGetNonOption(blockNumber->Int.toString)
+      let getBlockOrThrow = blockNumber =>
+        switch getBlock(blockNumber) {
+        | Some(block) => block
+        | None =>
+          Js.Exn.raiseError(
+            `The source didn't return the block ${blockNumber->Int.toString} for the block handlers`,
+          )
+        }
+
+      let parsedQueueItems = []
+      blockNumbers->Array.forEach(blockNumber => {
+        let block = getBlockOrThrow(blockNumber)
+        blockHandlers->Array.forEach(blockHandler => {
+          if blockHandler->FetchState.isBlockHandlerBlock(~blockNumber) {
+            parsedQueueItems->Array.push(blockHandler.makeItem(~block))
+          }
+        })
+      })
+
+      let toBlockData = block =>
+        (
+          {
+            blockNumber: block->Types.Block.getNumber,
+            blockTimestamp: block->Types.Block.getTimestamp,
+            blockHash: block->Types.Block.getId,
+          }: ReorgDetection.blockDataWithTimestamp
+        )
+      let lastBlockScannedData = getBlockOrThrow(toBlock)->toBlockData
+
+      let totalTimeElapsed = totalTimeRef->Hrtime.timeSince->Hrtime.toMillis->Hrtime.intFromMillis
+
+      Ok({
+        currentBlockHeight,
+        reorgGuard: {
+          lastBlockScannedData: lastBlockScannedData->ReorgDetection.generalizeBlockDataWithTimestamp,
+          firstBlockParentNumberAndHash: getBlock(parentBlockNumber)->Option.map(block =>
+            block->toBlockData->ReorgDetection.generalizeBlockDataWithTimestamp
+          ),
+        },
+        parsedQueueItems,
+        fromBlockQueried: fromBlock,
+        latestFetchedBlockNumber: lastBlockScannedData.blockNumber,
+        latestFetchedBlockTimestamp: lastBlockScannedData.blockTimestamp,
+        stats: {
+          totalTimeElapsed: totalTimeElapsed,
+        },
+      })
+    } catch {
+    | exn =>
+      exn->ErrorHandling.make(~logger, ~msg="Failed to fetch the blocks for block handlers")->Error
+    }
+  | Error(exn) =>
+    exn->ErrorHandling.make(~logger, ~msg="Failed to fetch the blocks for block handlers")->Error
+  }
 }
 
 let fetchBlockRange = async (
@@ -124,15 +242,26 @@ let fetchBlockRange = async (
   }
 
   (
-    await source.fetchBlockRange(
-      ~fromBlock,
-      ~toBlock,
-      ~contractAddressMapping,
-      ~partitionId,
-      ~logger,
-      ~selection,
-      ~currentBlockHeight,
-    )
+    switch selection.blockHandlers {
+    | Some(blockHandlers) =>
+      await source->fetchBlockHandlerRange(
+        ~fromBlock,
+        ~toBlock,
+        ~currentBlockHeight,
+        ~blockHandlers,
+        ~logger,
+      )
+    | None =>
+      await source.fetchBlockRange(
+        ~fromBlock,
+        ~toBlock,
+        ~contractAddressMapping,
+        ~partitionId,
+        ~logger,
+        ~selection,
+        ~currentBlockHeight,
+      )
+    }
   )->Utils.Result.forEach(response => {
     logger->Logging.childTrace({
       "msg": "Fetched block range from server",
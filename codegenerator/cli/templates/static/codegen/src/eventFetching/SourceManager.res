@@ -1,5 +1,12 @@
 open Belt
 
+type sourceState = {
+  source: Source.t,
+  role: Config.sourceRole,
+  // The source is skipped until this timestamp after it stalled or failed
+  mutable stalledUntil: float,
+}
+
 // Ideally the ChainFetcher name suits this better
 // But currently the ChainFetcher module is immutable
 // and handles both processing and fetching.
@@ -8,16 +15,168 @@ open Belt
 type t = {
   logger: Pino.t,
   maxPartitionConcurrency: int,
+  sources: array<sourceState>,
+  stallTimeoutMillis: int,
+  mutable activeSource: Source.t,
+  // Historical sources are replaced by realtime sources once the head is reached
+  mutable isAtHead: bool,
   mutable waitingForNewBlockStateId: option<int>,
   // Should take into consideration partitions fetching for previous states (before rollback)
   mutable fetchingPartitionsCount: int,
 }
 
-let make = (~maxPartitionConcurrency, ~logger) => {
-  logger,
-  maxPartitionConcurrency,
-  waitingForNewBlockStateId: None,
-  fetchingPartitionsCount: 0,
+// How long a stalled source is skipped before it's tried again
+let stalledSourceCooldownMillis = 60_000.
+
+// How far the fetched range can be behind the head before the realtime sources
+// are replaced by the historical sources again
+let maxRealtimeLagBlocks = 1_000
+
+let isSourceForPhase = (sourceState, ~isAtHead) =>
+  switch sourceState.role {
+  | Primary => true
+  | Historical => !isAtHead
+  | Realtime => isAtHead
+  | Fallback => false
+  }
+
+// Sources for the current phase ordered by priority, followed by the fallback sources
+let getCandidates = (sources, ~isAtHead) =>
+  Array.concat(
+    sources->Array.keep(sourceState => sourceState->isSourceForPhase(~isAtHead)),
+    sources->Array.keep(sourceState => sourceState.role === Config.Fallback),
+  )
+
+let isStalled = (sourceState, ~now) => sourceState.stalledUntil > now
+
+let make = (
+  ~sources: array<Config.sourceConfig>,
+  ~stallTimeoutMillis,
+  ~maxPartitionConcurrency,
+  ~logger,
+) => {
+  let sources = sources->Array.map(({source, role}) => {source, role, stalledUntil: 0.})
+  let activeSource = switch sources->getCandidates(~isAtHead=false)->Array.get(0) {
+  | Some({source}) => source
+  | None => Js.Exn.raiseError("Invalid configuration, no data-source for historical sync provided")
+  }
+  {
+    logger,
+    maxPartitionConcurrency,
+    sources,
+    stallTimeoutMillis,
+    activeSource,
+    isAtHead: false,
+    waitingForNewBlockStateId: None,
+    fetchingPartitionsCount: 0,
+  }
+}
+
+let getActiveSource = (sourceManager: t) => sourceManager.activeSource
+
+// Picks the preferred source which isn't stalled,
+// or keeps the preferred one when every source is stalled
+let selectSource = (sourceManager: t) => {
+  let now = Js.Date.now()
+  let candidates = sourceManager.sources->getCandidates(~isAtHead=sourceManager.isAtHead)
+  let source = switch candidates->Js.Array2.find(sourceState => !(sourceState->isStalled(~now))) {
+  | Some({source}) => source
+  | None => (candidates->Array.getExn(0)).source
+  }
+  if source !== sourceManager.activeSource {
+    sourceManager.logger->Logging.childInfo({
+      "msg": "Switching to another data source",
+      "from": sourceManager.activeSource.name,
+      "to": source.name,
+    })
+    sourceManager.activeSource = source
+  }
+  source
+}
+
+let hasAlternativeSource = (sourceManager: t, ~source) => {
+  let now = Js.Date.now()
+  sourceManager.sources
+  ->getCandidates(~isAtHead=sourceManager.isAtHead)
+  ->Array.some(sourceState => sourceState.source !== source && !(sourceState->isStalled(~now)))
+}
+
+let markStalled = (sourceManager: t, ~source) => {
+  sourceManager.sources->Array.forEach(sourceState => {
+    if sourceState.source === source {
+      sourceState.stalledUntil = Js.Date.now() +. stalledSourceCooldownMillis
+    }
+  })
+}
+
+let withStallTimeout = (promise, ~timeoutMillis) =>
+  [
+    promise->Promise.thenResolve(result => Some(result)),
+    Time.resolvePromiseAfterDelay(~delayMilliseconds=timeoutMillis)->Promise.thenResolve(() => None),
+  ]->Promise.race
+
+let rec executeQueryWithFailover = async (
+  sourceManager: t,
+  ~query: FetchState.query,
+  ~executeQuery: (
+    FetchState.query,
+    ~source: Source.t,
+  ) => promise<result<Source.blockRangeFetchResponse, ErrorHandling.t>>,
+) => {
+  let source = sourceManager->selectSource
+  let queryPromise = executeQuery(query, ~source)
+  if !(sourceManager->hasAlternativeSource(~source)) {
+    await queryPromise
+  } else {
+    switch await queryPromise->withStallTimeout(~timeoutMillis=sourceManager.stallTimeoutMillis) {
+    | Some(Ok(_) as ok) => ok
+    | Some(Error(error)) =>
+      sourceManager.logger->Logging.childWarn({
+        "msg": "Failed to fetch the block range. Retrying with another data source",
+        "source": source.name,
+        "err": error.exn->ErrorHandling.prettifyExn,
+      })
+      sourceManager->markStalled(~source)
+      await sourceManager->executeQueryWithFailover(~query, ~executeQuery)
+    | None =>
+      sourceManager.logger->Logging.childWarn({
+        "msg": "The data source stalled while fetching the block range. Retrying with another data source",
+        "source": source.name,
+        "stallTimeoutMillis": sourceManager.stallTimeoutMillis,
+      })
+      sourceManager->markStalled(~source)
+      await sourceManager->executeQueryWithFailover(~query, ~executeQuery)
+    }
+  }
+}
+
+let rec waitForNewBlockWithFailover = async (
+  sourceManager: t,
+  ~currentBlockHeight,
+  ~logger,
+  ~waitForNewBlock,
+) => {
+  let source = sourceManager->selectSource
+  let heightPromise = source->waitForNewBlock(~currentBlockHeight, ~logger)
+  if !(sourceManager->hasAlternativeSource(~source)) {
+    await heightPromise
+  } else {
+    switch await heightPromise->withStallTimeout(~timeoutMillis=sourceManager.stallTimeoutMillis) {
+    | Some(height) => height
+    | None =>
+      sourceManager.logger->Logging.childWarn({
+        "msg": "The data source didn't provide a new block in time. Switching to another data source",
+        "source": source.name,
+        "stallTimeoutMillis": sourceManager.stallTimeoutMillis,
+      })
+      sourceManager->markStalled(~source)
+      await sourceManager->waitForNewBlockWithFailover(
+        ~currentBlockHeight,
+        ~logger,
+        ~waitForNewBlock,
+      )
+    }
+  }
 }
 
 let fetchNext = async (
@@ -32,6 +191,20 @@ let fetchNext = async (
 ) => {
   let {logger, maxPartitionConcurrency} = sourceManager
 
+  // The head might get far ahead of the fetched range, eg. when the realtime source
+  // stalled for a while, so the historical sources are used again until it's caught up
+  if (
+    sourceManager.isAtHead &&
+    currentBlockHeight - fetchState.latestFullyFetchedBlock.blockNumber > maxRealtimeLagBlocks
+  ) {
+    sourceManager.isAtHead = false
+    logger->Logging.childInfo({
+      "msg": "Fell behind the head, switching back to the historical data sources",
+      "currentBlockHeight": currentBlockHeight,
+      "latestFetchedBlock": fetchState.latestFullyFetchedBlock.blockNumber,
+    })
+  }
+
   switch fetchState->FetchState.getNextQuery(
     ~concurrencyLimit={
       maxPartitionConcurrency - sourceManager.fetchingPartitionsCount
@@ -48,6 +221,7 @@ let fetchNext = async (
     | Some(_) // Case for the prev state before a rollback
     | None =>
       sourceManager.waitingForNewBlockStateId = Some(stateId)
+      sourceManager.isAtHead = true
       let currentBlockHeight = await waitForNewBlock(~currentBlockHeight, ~logger)
       switch sourceManager.waitingForNewBlockStateId {
         | Some(waitingStateId) if waitingStateId === stateId => {
@@ -0,0 +1,445 @@
+open Belt
+open Source
+
+/**
+Replays pre-recorded blocks, transactions and logs from files on disk.
+The directory is expected to contain blocks, transactions and logs files
+in the configured format (eg. blocks.jsonl, transactions.jsonl and logs.jsonl).
+Block and transaction rows use the same field names as the field selection,
+while log rows have the following shape:
+{"blockNumber", "logIndex", "transactionIndex", "transactionHash", "address", "data", "topics"}
+*/
+type format = Jsonl | Parquet
+
+type fileLog = {
+  blockNumber: int,
+  logIndex: int,
+  transactionIndex: int,
+  transactionHash: string,
+  address: Address.t,
+  data: string,
+  topics: array<EvmTypes.Hex.t>,
+}
+
+type fileBlock = {
+  number: int,
+  timestamp: int,
+  hash: string,
+}
+
+type recording = {
+  // Sorted by block number and log index
+  logs: array<fileLog>,
+  blocksByNumber: dict<Js.Json.t>,
+  transactionsByHash: dict<Js.Json.t>,
+  height: int,
+}
+
+exception MissingRecordedBlock({blockNumber: int})
+exception InvalidRecordedField({message: string})
+
+module Parquet = {
+  type asyncBuffer
+  type readOptions = {file: asyncBuffer}
+
+  @module("hyparquet")
+  external asyncBufferFromFile: string => promise<asyncBuffer> = "asyncBufferFromFile"
+  @module("hyparquet")
+  external readObjects: readOptions => promise<array<Js.Json.t>> = "parquetReadObjects"
+
+  // Parquet INT64 columns are read as bigint. Block numbers, timestamps and indexes
+  // should be plain numbers, while the rest of the bigint values are kept as strings
+  // the same way they are stored in JSONL recordings.
+  let normalizeRow: Js.Json.t => Js.Json.t = %raw(`row => {
+    const intFields = ["number", "timestamp", "blockNumber", "logIndex", "transactionIndex"]
+    const normalized = {}
+    for (const key in row) {
+      const value = row[key]
+      normalized[key] = typeof value === "bigint"
+        ? intFields.includes(key) ? Number(value) : value.toString()
+        : value
+    }
+    return normalized
+  }`)
+}
+
+let readRows = async (~dirPath, ~name, ~format) => {
+  switch format {
+  | Jsonl =>
+    let content = await NodeJsLocal.Fs.Promises.readFile(
+      ~filepath=dirPath->NodeJsLocal.Path.join(name ++ ".jsonl"),
+      ~encoding=Utf8,
+    )
+    content
+    ->Js.String2.split("\n")
+    ->Array.keepMap(line =>
+      switch line->Js.String2.trim {
+      | "" => None
+      | line => Some(line->Js.Json.parseExn)
+      }
+    )
+  | Parquet =>
+    let file = await Parquet.asyncBufferFromFile(
+      dirPath->NodeJsLocal.Path.join(name ++ ".parquet")->NodeJsLocal.Path.toString,
+    )
+    (await Parquet.readObjects({file: file}))->Array.map(Parquet.normalizeRow)
+  }
+}
+
+let isMissingFileExn = exn =>
+  switch exn {
+  | Js.Exn.Error(error) =>
+    switch (error->(Utils.magic: Js.Exn.t => {"code": option<string>}))["code"] {
+    | Some("ENOENT") => true
+    | _ => false
+    }
+  | _ => false
+  }
+
+let loadRecording = async (~dirPath, ~format) => {
+  let dirPath = dirPath->(Utils.magic: string => NodeJsLocal.Path.t)
+  let (blocks, logs) = await Promise.all2((
+    readRows(~dirPath, ~name="blocks", ~format),
+    readRows(~dirPath, ~name="logs", ~format),
+  ))
+  // The transactions file is only needed when transaction fields are selected,
+  // but when it exists, it should be readable
+  let transactions = try await readRows(~dirPath, ~name="transactions", ~format) catch {
+  | exn if exn->isMissingFileExn => []
+  }
+
+  let blocksByNumber = Js.Dict.empty()
+  let height = ref(0)
+  blocks->Array.forEach(row => {
+    let block = row->(Utils.magic: Js.Json.t => fileBlock)
+    blocksByNumber->Js.Dict.set(block.number->Int.toString, row)
+    height := Pervasives.max(height.contents, block.number)
+  })
+
+  let transactionsByHash = Js.Dict.empty()
+  transactions->Array.forEach(row => {
+    let transaction = row->(Utils.magic: Js.Json.t => {"hash": string})
+    transactionsByHash->Js.Dict.set(transaction["hash"], row)
+  })
+
+  let logs =
+    logs
+    ->(Utils.magic: array<Js.Json.t> => array<fileLog>)
+    // Recordings might store lowercased addresses, while the contract address mapping is checksummed
+    ->Array.map(log => {...log, address: log.address->Address.Evm.fromAddressOrThrow})
+    ->Js.Array2.sortInPlaceWith((a, b) =>
+      a.blockNumber === b.blockNumber ? a.logIndex - b.logIndex : a.blockNumber - b.blockNumber
+    )
+
+  {
+    logs,
+    blocksByNumber,
+    transactionsByHash,
+    height: height.contents,
+  }
+}
+
+let parseOrThrowReadableError = (data, ~schema, ~recordName) => {
+  try data->S.parseOrThrow(schema) catch {
+  | S.Raised(error) =>
+    raise(
+      InvalidRecordedField({
+        message: `Invalid ${recordName} field "${error.path
+          ->S.Path.toArray
+          ->Js.Array2.joinWith(".")}" found in the recorded files. Error: ${error->S.Error.reason}`,
+      }),
+    )
+  }
+}
+
+// The index of the first log at or after the block number,
+// or the length of the logs when every log is before it
+let getFirstLogIndexFrom = (logs: array<fileLog>, ~blockNumber) => {
+  let low = ref(0)
+  let high = ref(logs->Array.length)
+  while low.contents < high.contents {
+    let mid = (low.contents + high.contents) / 2
+    if (logs->Js.Array2.unsafe_get(mid)).blockNumber < blockNumber {
+      low := mid + 1
+    } else {
+      high := mid
+    }
+  }
+  low.contents
+}
+
+// Since the logs are sorted by block number,
+// the range is sliced without iterating over the rest of the recording
+let getLogsInRange = (logs, ~fromBlock, ~toBlock) =>
+  logs->Js.Array2.slice(
+    ~start=logs->getFirstLogIndexFrom(~blockNumber=fromBlock),
+    ~end_=logs->getFirstLogIndexFrom(~blockNumber=toBlock + 1),
+  )
+
+let isInTopicSelections = (log: fileLog, ~topicSelections: array<LogSelection.topicSelection>) => {
+  let matchesTopic = (topicFilter, index) =>
+    switch topicFilter {
+    | [] => true
+    | topicFilter =>
+      switch log.topics->Array.get(index) {
+      | Some(topic) => topicFilter->Js.Array2.includes(topic)
+      | None => false
+      }
+    }
+  topicSelections->Array.some(({topic0, topic1, topic2, topic3}) =>
+    matchesTopic(topic0, 0) &&
+    matchesTopic(topic1, 1) &&
+    matchesTopic(topic2, 2) &&
+    matchesTopic(topic3, 3)
+  )
+}
+
+type options = {
+  chain: ChainMap.Chain.t,
+  contracts: array<Config.contract>,
+  eventRouter: EventRouter.t<module(Types.InternalEvent)>,
+  dirPath: string,
+  format: format,
+}
+
+let make = ({chain, contracts, eventRouter, dirPath, format}: options): t => {
+  let name = "File"
+
+  let recordingRef = ref(None)
+  let getRecording = () =>
+    switch recordingRef.contents {
+    | Some(recording) => recording
+    | None =>
+      let recording = loadRecording(~dirPath, ~format)
+      recordingRef := Some(recording)
+      recording
+    }
+
+  let getBlockOrThrow = (recording, ~blockNumber) =>
+    switch recording.blocksByNumber->Utils.Dict.dangerouslyGetNonOption(blockNumber->Int.toString) {
+    | Some(row) => row->(Utils.magic: Js.Json.t => fileBlock)
+    | None => raise(MissingRecordedBlock({blockNumber: blockNumber}))
+    }
+
+  let contractNameAbiMapping = Js.Dict.empty()
+  contracts->Belt.Array.forEach(contract => {
+    contractNameAbiMapping->Js.Dict.set(contract.name, contract.abi)
+  })
+
+  let fetchBlockRange = async (
+    ~fromBlock,
+    ~toBlock,
+    ~contractAddressMapping,
+    ~currentBlockHeight,
+    ~partitionId as _,
+    ~selection: FetchState.selection,
+    ~logger,
+  ) => {
+    try {
+      let startFetchingBatchTimeRef = Hrtime.makeTimer()
+      let recording = await getRecording()
+
+      let toBlock = switch toBlock {
+      | Some(toBlock) => Pervasives.min(toBlock, currentBlockHeight)
+      | None => currentBlockHeight
+      }
+
+      let chainId = chain->ChainMap.Chain.toChainId
+
+      let logsInRange = recording.logs->getLogsInRange(~fromBlock, ~toBlock)
+      let parsedQueueItems = logsInRange->Array.keepMap(log => {
+        let isInAddressMapping =
+          selection.isWildcard ||
+          contractAddressMapping
+          ->ContractAddressingMap.getContractNameFromAddress(~contractAddress=log.address)
+          ->Option.isSome
+        switch log.topics->Array.get(0) {
+        | Some(topic0) if isInAddressMapping =>
+          switch eventRouter->EventRouter.get(
+            ~tag=EventRouter.getEvmEventId(
+              ~sighash=topic0->EvmTypes.Hex.toString,
+              ~topicCount=log.topics->Array.length,
+            ),
+            ~contractAddressMapping,
+            ~contractAddress=log.address,
+          ) {
+          | None => None //ignore events that aren't registered
+          | Some(eventMod: module(Types.InternalEvent)) =>
+            let module(Event) = eventMod
+            let {isWildcard, topicSelections} =
+              Event.handlerRegister->Types.HandlerTypes.Register.getEventOptions
+
+            if (
+              FetchState.checkIsInSelection(
+                ~selection,
+                ~contractName=Event.contractName,
+                ~eventId=Event.id,
+                ~isWildcard,
+              ) &&
+              log->isInTopicSelections(~topicSelections)
+            ) {
+              let logger = Logging.createChildFrom(
+                ~logger,
+                ~params={
+                  {
+                    "chainId": chainId,
+                    "blockNumber": log.blockNumber,
+                    "logIndex": log.logIndex,
+                  }
+                },
+              )
+
+              let block =
+                recording.blocksByNumber
+                ->Utils.Dict.dangerouslyGetNonOption(log.blockNumber->Int.toString)
+                ->Option.getWithDefault(%raw(`{}`))
+                ->parseOrThrowReadableError(~schema=Event.blockSchema, ~recordName="block")
+
+              let transaction =
+                recording.transactionsByHash
+                ->Utils.Dict.dangerouslyGetNonOption(log.transactionHash)
+                ->Option.getWithDefault(
+                  {
+                    "hash": log.transactionHash,
+                    "transactionIndex": log.transactionIndex,
+                  }->(Utils.magic: {"hash": string, "transactionIndex": int} => Js.Json.t),
+                )
+                ->parseOrThrowReadableError(
+                  ~schema=Event.transactionSchema,
+                  ~recordName="transaction",
+                )
+
+              let decodedEvent = try contractNameAbiMapping->Viem.parseLogOrThrow(
+                ~contractName=Event.contractName,
+                ~topics=log.topics,
+                ~data=log.data,
+              ) catch {
+              | exn =>
+                exn->ErrorHandling.mkLogAndRaise(
+                  ~msg="Failed to parse event with viem, please double-check your ABI.",
+                  ~logger,
+                )
+              }
+
+              Some(
+                (
+                  {
+                    eventName: Event.name,
+                    contractName: Event.contractName,
+                    loader: Event.handlerRegister->Types.HandlerTypes.Register.getLoader,
+                    handler: Event.handlerRegister->Types.HandlerTypes.Register.getHandler,
+                    contractRegister: Event.handlerRegister->Types.HandlerTypes.Register.getContractRegister,
+                    paramsRawEventSchema: Event.paramsRawEventSchema,
+                    timestamp: block->Types.Block.getTimestamp,
+                    chain,
+                    blockNumber: block->Types.Block.getNumber,
+                    logIndex: log.logIndex,
+                    event: {
+                      chainId,
+                      params: decodedEvent.args,
+                      transaction,
+                      block,
+                      srcAddress: log.address,
+                      logIndex: log.logIndex,
+                    }->Internal.fromGenericEvent,
+                  }: Internal.eventItem
+                ),
+              )
+            } else {
+              None
+            }
+          }
+        | _ => None
+        }
+      })
+
+      let latestFetchedBlock = recording->getBlockOrThrow(~blockNumber=toBlock)
+      let optFirstBlockParent =
+        fromBlock > 0
+          ? recording.blocksByNumber
+            ->Utils.Dict.dangerouslyGetNonOption((fromBlock - 1)->Int.toString)
+            ->Option.map(row => row->(Utils.magic: Js.Json.t => fileBlock))
+          : None
+
+      let totalTimeElapsed =
+        startFetchingBatchTimeRef->Hrtime.timeSince->Hrtime.toMillis->Hrtime.intFromMillis
+
+      let reorgGuard: ReorgDetection.reorgGuard = {
+        firstBlockParentNumberAndHash: optFirstBlockParent->Option.map(b => {
+          ReorgDetection.blockNumber: b.number,
+          blockHash: b.hash,
+        }),
+        lastBlockScannedData: {
+          blockNumber: latestFetchedBlock.number,
+          blockHash: latestFetchedBlock.hash,
+        },
+      }
+
+      {
+        latestFetchedBlockTimestamp: latestFetchedBlock.timestamp,
+        latestFetchedBlockNumber: latestFetchedBlock.number,
+        parsedQueueItems,
+        stats: {
+          totalTimeElapsed: totalTimeElapsed,
+        },
+        currentBlockHeight,
+        reorgGuard,
+        fromBlockQueried: fromBlock,
+      }->Ok
+    } catch {
+    | MissingRecordedBlock({blockNumber}) as exn =>
+      exn
+      ->ErrorHandling.make(
+        ~logger,
+        ~msg=`Block #${blockNumber->Int.toString} is missing in the recorded blocks file. The recording should include every block of the indexed range.`,
+      )
+      ->Error
+    | exn => exn->ErrorHandling.make(~logger, ~msg="Failed to fetch block Range")->Error
+    }
+  }
+
+  let getBlockHashes = async (~blockNumbers, ~logger as _currentlyUnusedLogger) => {
+    try {
+      let recording = await getRecording()
+      blockNumbers
+      ->Array.map((blockNumber): ReorgDetection.blockDataWithTimestamp => {
+        let block = recording->getBlockOrThrow(~blockNumber)
+        {
+          blockNumber: block.number,
+          blockHash: block.hash,
+          blockTimestamp: block.timestamp,
+        }
+      })
+      ->Ok
+    } catch {
+    | exn => exn->Error
+    }
+  }
+
+  let getBlocks = async (~blockNumbers, ~logger as _currentlyUnusedLogger) => {
+    try {
+      let recording = await getRecording()
+      blockNumbers
+      ->Array.map(blockNumber =>
+        recording
+        ->getBlockOrThrow(~blockNumber)
+        ->(Utils.magic: fileBlock => Js.Json.t)
+        ->parseOrThrowReadableError(~schema=Types.Block.schema, ~recordName="block")
+        ->(Utils.magic: Types.Block.t => Internal.eventBlock)
+      )
+      ->Ok
+    } catch {
+    | exn => exn->Error
+    }
+  }
+
+  {
+    name,
+    chain,
+    pollingInterval: 1000,
+    getBlockHashes,
+    getBlocks,
+    getHeightOrThrow: async () => (await getRecording()).height,
+    fetchBlockRange,
+  }
+}
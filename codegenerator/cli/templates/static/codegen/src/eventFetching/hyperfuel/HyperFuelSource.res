@@ -513,10 +513,14 @@ let make = ({chain, contracts, endpointUrl}: options): t => {
   let getBlockHashes = (~blockNumbers as _, ~logger as _) =>
     Js.Exn.raiseError("HyperFuel does not support getting block hashes")
 
+  let getBlocks = (~blockNumbers as _, ~logger as _) =>
+    Js.Exn.raiseError("HyperFuel does not support getting blocks")
+
   {
     name,
     chain,
     getBlockHashes,
+    getBlocks,
     pollingInterval: 100,
     getHeightOrThrow: () => HyperFuel.heightRoute->Rest.fetch(endpointUrl, ()),
     fetchBlockRange,
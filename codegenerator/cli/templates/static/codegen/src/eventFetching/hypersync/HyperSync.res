@@ -11,6 +11,30 @@ module Log = {
   let fieldNames = ["address", "data", "topics", "logIndex"]
 }
 
+module Trace = {
+  type t = {
+    from: Address.t,
+    @as("to") to_: Address.t,
+    input: string,
+    output: string,
+    blockNumber: int,
+    transactionHash: string,
+    transactionPosition: int,
+    traceAddress: array<int>,
+  }
+
+  let fieldNames = [
+    "from",
+    "to",
+    "input",
+    "output",
+    "blockNumber",
+    "transactionHash",
+    "transactionPosition",
+    "traceAddress",
+  ]
+}
+
 type hyperSyncPage<'item> = {
   items: array<'item>,
   nextBlock: int,
@@ -27,6 +51,12 @@ type logsQueryPageItem = {
 
 type logsQueryPage = hyperSyncPage<logsQueryPageItem>
 
+type callsQueryItem = {
+  trace: Trace.t,
+  block: Internal.eventBlock,
+  transaction: Internal.eventTransaction,
+}
+
 type missingParams = {
   queryName: string,
   missingParams: array<string>,
@@ -78,6 +108,19 @@ module LogsQuery = {
     fieldSelection,
   }
 
+  let makeAddressesWithTopics = (logSelections: array<LogSelection.t>) =>
+    logSelections->Array.flatMap(({addresses, topicSelections}) =>
+      topicSelections->Array.map(({topic0, topic1, topic2, topic3}) => {
+        let topics = HyperSyncClient.QueryTypes.makeTopicSelection(
+          ~topic0,
+          ~topic1,
+          ~topic2,
+          ~topic3,
+        )
+        HyperSyncClient.QueryTypes.makeLogSelection(~address=addresses, ~topics)
+      })
+    )
+
   let addMissingParams = (acc, fieldNames, returnedObj, ~prefix) => {
     fieldNames->Array.forEach(fieldName => {
       switch returnedObj
@@ -162,17 +205,7 @@ module LogsQuery = {
     ~nonOptionalTransactionFieldNames,
     ~logger,
   ): queryResponse<logsQueryPage> => {
-    let addressesWithTopics = logSelections->Array.flatMap(({addresses, topicSelections}) =>
-      topicSelections->Array.map(({topic0, topic1, topic2, topic3}) => {
-        let topics = HyperSyncClient.QueryTypes.makeTopicSelection(
-          ~topic0,
-          ~topic1,
-          ~topic2,
-          ~topic3,
-        )
-        HyperSyncClient.QueryTypes.makeLogSelection(~address=addresses, ~topics)
-      })
-    )
+    let addressesWithTopics = logSelections->makeAddressesWithTopics
 
     let query = makeRequestBody(
       ~fromBlock,
@@ -250,7 +283,13 @@ module BlockData = {
     ->Utils.Array.transposeResults
   }
 
-  let rec queryBlockData = async (~serverUrl, ~fromBlock, ~toBlock, ~logger): queryResponse<
+  let rec queryBlockData = async (
+    ~serverUrl,
+    ~fromBlock,
+    ~toBlock,
+    ~logger,
+    ~fetcher=?,
+  ): queryResponse<
     array<ReorgDetection.blockDataWithTimestamp>,
   > => {
     let body = makeRequestBody(~fromBlock, ~toBlock)
@@ -265,7 +304,7 @@ module BlockData = {
     )
 
     let maybeSuccessfulRes = switch await Time.retryAsyncWithExponentialBackOff(
-      () => HyperSyncJsonApi.queryRoute->Rest.fetch(serverUrl, body),
+      () => HyperSyncJsonApi.queryRoute->Rest.fetch(serverUrl, body, ~fetcher?),
       ~logger,
     ) {
     | exception _ => None
@@ -282,7 +321,7 @@ module BlockData = {
           `Block #${fromBlock->Int.toString} not found in HyperSync. HyperSync has multiple instances and it's possible that they drift independently slightly from the head. Indexing should continue correctly after retrying the query in ${delayMilliseconds->Int.toString}ms.`,
         )
         await Time.resolvePromiseAfterDelay(~delayMilliseconds)
-        await queryBlockData(~serverUrl, ~fromBlock, ~toBlock, ~logger)
+        await queryBlockData(~serverUrl, ~fromBlock, ~toBlock, ~logger, ~fetcher?)
       }
     | Some(res) =>
       switch res->convertResponse {
@@ -293,6 +332,7 @@ module BlockData = {
             ~fromBlock=res.nextBlock,
             ~toBlock,
             ~logger,
+            ~fetcher?,
           )
           restRes->Result.map(rest => datas->Array.concat(rest))
         }
@@ -301,7 +341,7 @@ module BlockData = {
     }
   }
 
-  let queryBlockDataMulti = async (~serverUrl, ~blockNumbers, ~logger) => {
+  let queryBlockDataMulti = async (~serverUrl, ~blockNumbers, ~logger, ~fetcher=?) => {
     switch blockNumbers->Array.get(0) {
     | None => Ok([])
     | Some(firstBlock) => {
@@ -328,6 +368,7 @@ module BlockData = {
           ~toBlock=toBlock.contents,
           ~serverUrl,
           ~logger,
+          ~fetcher?,
         )
         let filtered = res->Result.map(datas => {
           datas->Array.keep(data => set->Utils.Set.delete(data.blockNumber))
@@ -345,12 +386,344 @@ module BlockData = {
   }
 }
 
+module TransactionLogsQuery = {
+  let fieldSelection: HyperSyncClient.QueryTypes.fieldSelection = {
+    log: [TransactionHash, Address, Data, LogIndex, Topic0, Topic1, Topic2, Topic3],
+  }
+
+  let addLog = (
+    logsByTransactionHash: dict<array<EvmTypes.log>>,
+    log: HyperSyncClient.ResponseTypes.log,
+  ) =>
+    switch log.transactionHash {
+    | Some(transactionHash) =>
+      let transactionLog: EvmTypes.log = {
+        address: log.address->Option.getUnsafe,
+        data: log.data->Option.getUnsafe,
+        topics: log.topics
+        ->Option.getWithDefault([])
+        ->Array.keepMap(Js.Nullable.toOption)
+        ->EvmTypes.Hex.toStrings,
+        logIndex: log.index->Option.getUnsafe,
+      }
+      switch logsByTransactionHash->Utils.Dict.dangerouslyGetNonOption(transactionHash) {
+      | Some(logs) => logs->Js.Array2.push(transactionLog)->ignore
+      | None => logsByTransactionHash->Js.Dict.set(transactionHash, [transactionLog])
+      }
+    | None => ()
+    }
+
+  let rec queryRange = async (
+    ~client: HyperSyncClient.t,
+    ~fromBlock,
+    ~toBlockExclusive,
+    ~addressesWithTopics,
+    ~logger,
+    ~acc=[],
+  ) => {
+    let query: HyperSyncClient.QueryTypes.query = {
+      fromBlock,
+      toBlockExclusive,
+      logs: addressesWithTopics,
+      fieldSelection,
+      // Joins the transactions of the matched logs and then all the logs of these transactions
+      joinMode: JoinAll,
+    }
+
+    let executeQuery = async () => {
+      let res = await client.getEvents(~query)
+      if res.nextBlock <= fromBlock {
+        Js.Exn.raiseError(
+          "Received page response from another instance of HyperSync. Should work after a retry.",
+        )
+      }
+      res
+    }
+
+    let res = await executeQuery->Time.retryAsyncWithExponentialBackOff(~logger)
+    let acc = acc->Array.concat(res.data)
+    if res.nextBlock < toBlockExclusive {
+      await queryRange(
+        ~client,
+        ~fromBlock=res.nextBlock,
+        ~toBlockExclusive,
+        ~addressesWithTopics,
+        ~logger,
+        ~acc,
+      )
+    } else {
+      acc
+    }
+  }
+
+  //Queries all the logs of the transactions matching the log selections
+  //and groups them by the hash of their transaction
+  let queryTransactionLogs = async (
+    ~client,
+    ~fromBlock,
+    ~toBlockExclusive,
+    ~logSelections,
+    ~logger,
+  ) => {
+    let logsByTransactionHash: dict<array<EvmTypes.log>> = Js.Dict.empty()
+    let events = await queryRange(
+      ~client,
+      ~fromBlock,
+      ~toBlockExclusive,
+      ~addressesWithTopics=logSelections->LogsQuery.makeAddressesWithTopics,
+      ~logger,
+    )
+
+    events->Array.forEach(({log}: HyperSyncClient.ResponseTypes.event) =>
+      logsByTransactionHash->addLog(log)
+    )
+
+    logsByTransactionHash
+  }
+}
+
+module BlocksQuery = {
+  let queryBlock = async (
+    ~client: HyperSyncClient.t,
+    ~blockNumber,
+    ~fieldSelection,
+    ~nonOptionalBlockFieldNames,
+    ~logger,
+  ): Internal.eventBlock => {
+    let query: HyperSyncClient.QueryTypes.query = {
+      fromBlock: blockNumber,
+      toBlockExclusive: blockNumber + 1,
+      fieldSelection,
+      includeAllBlocks: true,
+    }
+
+    let executeQuery = async () => {
+      let res = await client.get(~query)
+      switch res.data.blocks->Array.get(0) {
+      | Some(block) => block
+      | None =>
+        // Might happen when /height response was from another instance of HyperSync
+        Js.Exn.raiseError(
+          `Block #${blockNumber->Int.toString} not found in HyperSync. Should work after a retry.`,
+        )
+      }
+    }
+
+    let block = await executeQuery->Time.retryAsyncWithExponentialBackOff(~logger)
+
+    let missingParams = []
+    missingParams->LogsQuery.addMissingParams(nonOptionalBlockFieldNames, block, ~prefix="block")
+    if missingParams->Array.length > 0 {
+      UnexpectedMissingParamsExn({
+        queryName: "queryBlocks HyperSync",
+        missingParams,
+      })->raise
+    }
+
+    block->Utils.magic
+  }
+
+  //Queries every block separately, since the blocks of the block handlers
+  //are far apart and a range query would return every block between them
+  let queryBlocks = (
+    ~client,
+    ~blockNumbers,
+    ~fieldSelection,
+    ~nonOptionalBlockFieldNames,
+    ~logger,
+  ): promise<queryResponse<array<Internal.eventBlock>>> =>
+    blockNumbers
+    ->Array.map(blockNumber =>
+      queryBlock(~client, ~blockNumber, ~fieldSelection, ~nonOptionalBlockFieldNames, ~logger)
+    )
+    ->Promise.all
+    ->Promise.thenResolve(blocks => Ok(blocks))
+    ->Promise.catch(exn =>
+      switch exn {
+      | UnexpectedMissingParamsExn(err) => Error(UnexpectedMissingParams(err))->Promise.resolve
+      | exn => raise(exn)
+      }
+    )
+}
+
+module CallsQuery = {
+  let traceFields: array<HyperSyncClient.QueryTypes.traceField> = [
+    From,
+    To,
+    Input,
+    Output,
+    Error,
+    BlockNumber,
+    TransactionHash,
+    TransactionPosition,
+    TraceAddress,
+  ]
+
+  let rec queryRange = async (
+    ~client: HyperSyncClient.t,
+    ~fromBlock,
+    ~toBlockExclusive,
+    ~traceSelections,
+    ~fieldSelection,
+    ~logger,
+    ~acc=[],
+  ) => {
+    let query: HyperSyncClient.QueryTypes.query = {
+      fromBlock,
+      toBlockExclusive,
+      traces: traceSelections,
+      fieldSelection,
+      // Joins the transactions and blocks of the matched traces
+      joinMode: JoinAll,
+    }
+
+    let executeQuery = async () => {
+      let res = await client.get(~query)
+      if res.nextBlock <= fromBlock {
+        Js.Exn.raiseError(
+          "Received page response from another instance of HyperSync. Should work after a retry.",
+        )
+      }
+      res
+    }
+
+    let res = await executeQuery->Time.retryAsyncWithExponentialBackOff(~logger)
+    let acc = acc->Array.concat([res.data])
+    if res.nextBlock < toBlockExclusive {
+      await queryRange(
+        ~client,
+        ~fromBlock=res.nextBlock,
+        ~toBlockExclusive,
+        ~traceSelections,
+        ~fieldSelection,
+        ~logger,
+        ~acc,
+      )
+    } else {
+      acc
+    }
+  }
+
+  //Queries the successful calls matching the trace selections together with
+  //their blocks and transactions. The traces of the joined transactions are
+  //returned as well, so the caller should match them by address and sighash
+  let queryCalls = async (
+    ~client,
+    ~fromBlock,
+    ~toBlockExclusive,
+    ~traceSelections,
+    ~fieldSelection: HyperSyncClient.QueryTypes.fieldSelection,
+    ~nonOptionalBlockFieldNames,
+    ~nonOptionalTransactionFieldNames,
+    ~logger,
+  ): queryResponse<array<callsQueryItem>> => {
+    let datas = await queryRange(
+      ~client,
+      ~fromBlock,
+      ~toBlockExclusive,
+      ~traceSelections,
+      ~fieldSelection={...fieldSelection, trace: traceFields},
+      ~logger,
+    )
+
+    try {
+      let items = []
+      datas->Array.forEach(data => {
+        let blocksByNumber = Js.Dict.empty()
+        data.blocks->Array.forEach(block =>
+          switch block.number {
+          | Some(number) => blocksByNumber->Js.Dict.set(number->Int.toString, block)
+          | None => ()
+          }
+        )
+        let transactionsByHash = Js.Dict.empty()
+        data.transactions->Array.forEach(transaction =>
+          switch transaction.hash {
+          | Some(hash) => transactionsByHash->Js.Dict.set(hash, transaction)
+          | None => ()
+          }
+        )
+        // With the log fields selected, the joined logs are all the logs of the transactions
+        if fieldSelection.log->Option.isSome {
+          let logsByTransactionHash = Js.Dict.empty()
+          data.logs->Array.forEach(log => logsByTransactionHash->TransactionLogsQuery.addLog(log))
+          transactionsByHash
+          ->Js.Dict.entries
+          ->Array.forEach(((hash, transaction)) =>
+            transaction
+            ->(Utils.magic: HyperSyncClient.ResponseTypes.transaction => dict<unknown>)
+            ->Js.Dict.set(
+              "logs",
+              logsByTransactionHash
+              ->Utils.Dict.dangerouslyGetNonOption(hash)
+              ->Option.getWithDefault([])
+              ->(Utils.magic: array<EvmTypes.log> => unknown),
+            )
+          )
+        }
+
+        data.traces->Array.forEach(trace =>
+          switch trace.error {
+          // Reverted calls didn't change any state, so there is nothing to index
+          | Some(_) => ()
+          | None =>
+            let missingParams = []
+            missingParams->LogsQuery.addMissingParams(Trace.fieldNames, trace, ~prefix="trace")
+            let block =
+              trace.blockNumber
+              ->Option.flatMap(blockNumber =>
+                blocksByNumber->Utils.Dict.dangerouslyGetNonOption(blockNumber->Int.toString)
+              )
+              ->Option.getWithDefault(%raw(`{}`))
+            let transaction =
+              trace.transactionHash
+              ->Option.flatMap(hash => transactionsByHash->Utils.Dict.dangerouslyGetNonOption(hash))
+              ->Option.getWithDefault(%raw(`{}`))
+            missingParams->LogsQuery.addMissingParams(
+              nonOptionalBlockFieldNames,
+              block,
+              ~prefix="block",
+            )
+            missingParams->LogsQuery.addMissingParams(
+              nonOptionalTransactionFieldNames,
+              transaction,
+              ~prefix="transaction",
+            )
+            if missingParams->Array.length > 0 {
+              UnexpectedMissingParamsExn({
+                queryName: "queryCalls HyperSync",
+                missingParams,
+              })->raise
+            }
+
+            items
+            ->Js.Array2.push({
+              trace: trace->(Utils.magic: HyperSyncClient.ResponseTypes.trace => Trace.t),
+              block: block->Utils.magic,
+              transaction: transaction->Utils.magic,
+            })
+            ->ignore
+          }
+        )
+      })
+
+      Ok(items)
+    } catch {
+    | UnexpectedMissingParamsExn(err) => Error(UnexpectedMissingParams(err))
+    }
+  }
+}
+
 let queryLogsPage = LogsQuery.queryLogsPage
-let queryBlockData = (~serverUrl, ~blockNumber, ~logger) =>
+let queryBlockData = (~serverUrl, ~blockNumber, ~logger, ~fetcher=?) =>
   BlockData.queryBlockData(
     ~serverUrl,
     ~fromBlock=blockNumber,
     ~toBlock=blockNumber,
     ~logger,
+    ~fetcher?,
   )->Promise.thenResolve(res => res->Result.map(res => res->Array.get(0)))
 let queryBlockDataMulti = BlockData.queryBlockDataMulti
+let queryTransactionLogs = TransactionLogsQuery.queryTransactionLogs
+let queryBlocks = BlocksQuery.queryBlocks
+let queryCalls = CallsQuery.queryCalls
@@ -43,12 +43,25 @@ type selectionConfig = {
   getLogSelectionOrThrow: (
     ~contractAddressMapping: ContractAddressingMap.mapping,
   ) => array<LogSelection.t>,
+  getTraceSelections: (
+    ~contractAddressMapping: ContractAddressingMap.mapping,
+  ) => array<HyperSyncClient.QueryTypes.traceSelection>,
+  // The calls of the selection by makeCallKey
+  callConfigs: dict<Internal.callConfig>,
   fieldSelection: HyperSyncClient.QueryTypes.fieldSelection,
   nonOptionalBlockFieldNames: array<string>,
   nonOptionalTransactionFieldNames: array<string>,
+  shouldQueryTransactionLogs: bool,
 }
 
-let getSelectionConfig = (selection: FetchState.selection, ~contracts: array<Config.contract>) => {
+let makeCallKey = (~contractName, ~sighash) => contractName ++ "_" ++ sighash
+
+let getSelectionConfig = (
+  selection: FetchState.selection,
+  ~contracts: array<Config.contract>,
+  ~excludedBlockFields=[],
+  ~excludedTransactionFields=[],
+) => {
   let nonOptionalBlockFieldNames = Utils.Set.make()
   let nonOptionalTransactionFieldNames = Utils.Set.make()
   let capitalizedBlockFields = Utils.Set.make()
@@ -56,9 +69,37 @@ let getSelectionConfig = (selection: FetchState.selection, ~contracts: array<Con
   let wildcardTopicSelections = []
 
   let contractTopicSelections = []
+  let contractCallSelections = []
+  let callConfigs = Js.Dict.empty()
 
   contracts->Array.forEach(contract => {
     let normalTopicSelections = []
+    let sighashes = []
+
+    contract.calls
+    ->Option.getWithDefault([])
+    ->Array.forEach(call => {
+      if (
+        FetchState.checkIsInSelection(
+          ~selection,
+          ~contractName=contract.name,
+          ~eventId=call.sighash,
+          ~isWildcard=false,
+        )
+      ) {
+        sighashes->Array.push(call.sighash)
+        callConfigs->Js.Dict.set(makeCallKey(~contractName=contract.name, ~sighash=call.sighash), call)
+      }
+    })
+
+    switch sighashes {
+    | [] => ()
+    | _ =>
+      contractCallSelections->Array.push({
+        "contractName": contract.name,
+        "sighashes": sighashes,
+      })
+    }
 
     contract.events->Array.forEach(event => {
       let module(Event) = event
@@ -103,6 +144,22 @@ let getSelectionConfig = (selection: FetchState.selection, ~contracts: array<Con
     }
   })
 
+  // The fields selected only by the other chains are in the shared types, but aren't queried
+  excludedBlockFields->Js.Array2.forEach(fieldName =>
+    capitalizedBlockFields->Utils.Set.delete(fieldName->Utils.String.capitalize)->ignore
+  )
+  excludedTransactionFields->Js.Array2.forEach(fieldName =>
+    capitalizedTransactionFields->Utils.Set.delete(fieldName->Utils.String.capitalize)->ignore
+  )
+
+  // The logs of the transaction aren't a HyperSync transaction field.
+  // They are queried separately and matched to the events by the transaction hash
+  let shouldQueryTransactionLogs = capitalizedTransactionFields->Utils.Set.delete("Logs")
+  if shouldQueryTransactionLogs {
+    capitalizedTransactionFields->Utils.Set.add("Hash")->ignore
+    nonOptionalTransactionFieldNames->Utils.Set.delete("logs")->ignore
+  }
+
   let fieldSelection: HyperSyncClient.QueryTypes.fieldSelection = {
     log: [Address, Data, LogIndex, Topic0, Topic1, Topic2, Topic3],
     block: capitalizedBlockFields
@@ -135,31 +192,230 @@ let getSelectionConfig = (selection: FetchState.selection, ~contracts: array<Con
   | false => getNormalLogSelectionOrThrow
   }
 
+  let getTraceSelections = (~contractAddressMapping): array<
+    HyperSyncClient.QueryTypes.traceSelection,
+  > =>
+    contractCallSelections->Belt.Array.keepMap(data => {
+      switch contractAddressMapping->ContractAddressingMap.getAddressesFromContractName(
+        ~contractName=data["contractName"],
+      ) {
+      | [] => None
+      | addresses =>
+        Some(
+          (
+            {to_: addresses, sighash: data["sighashes"]}: HyperSyncClient.QueryTypes.traceSelection
+          ),
+        )
+      }
+    })
+
   {
     getLogSelectionOrThrow,
+    getTraceSelections,
+    callConfigs,
     fieldSelection,
     nonOptionalBlockFieldNames: nonOptionalBlockFieldNames->Utils.Set.toArray,
     nonOptionalTransactionFieldNames: nonOptionalTransactionFieldNames->Utils.Set.toArray,
+    shouldQueryTransactionLogs,
   }
 }
 
-let memoGetSelectionConfig = (~contracts) => {
+let memoGetSelectionConfig = (~contracts, ~excludedBlockFields=?, ~excludedTransactionFields=?) => {
   let cache = Utils.WeakMap.make()
   selection =>
     switch cache->Utils.WeakMap.get(selection) {
     | Some(c) => c
     | None => {
-        let c = selection->getSelectionConfig(~contracts)
+        let c =
+          selection->getSelectionConfig(
+            ~contracts,
+            ~excludedBlockFields?,
+            ~excludedTransactionFields?,
+          )
         let _ = cache->Utils.WeakMap.set(selection, c)
         c
       }
     }
 }
 
+let makeFetcherWithHeaders = (headers: dict<string>): Rest.ApiFetcher.t => (
+  args: Rest.ApiFetcher.args,
+) => {
+  let requestHeaders = switch args.headers {
+  | Some(requestHeaders) => requestHeaders->Js.Dict.entries
+  | None => []
+  }
+  Rest.ApiFetcher.default({
+    ...args,
+    headers: Some(
+      requestHeaders
+      ->Js.Array2.concat(headers->(Utils.magic: dict<string> => dict<unknown>)->Js.Dict.entries)
+      ->Js.Dict.fromArray,
+    ),
+  })
+}
+
+let isBearerAuthHeader = ((key, value)) =>
+  key->Js.String2.toLowerCase === "authorization" && value->Js.String2.startsWith("Bearer ")
+
+let getBearerToken = (headers: dict<string>) =>
+  headers
+  ->Js.Dict.entries
+  ->Js.Array2.find(isBearerAuthHeader)
+  ->Belt.Option.map(((_, value)) => value->Js.String2.sliceToEnd(~from=7))
+
+// The headers which the HyperSync client can't send itself
+let getProxiedHeaders = (headers: dict<string>) =>
+  headers->Js.Dict.entries->Js.Array2.filter(header => !isBearerAuthHeader(header))
+
+/* The HyperSync client only supports bearer auth, so the other headers are added by a proxy
+   on localhost, which forwards the requests of the client to the endpoint. Resolves with the
+   url of the proxy. The headers override the ones of the client, eg. its default bearer token. */
+let startHeadersProxy: (string, array<(string, string)>) => promise<string> = %raw(`(endpointUrl, headers) => {
+  const http = require("http")
+  const https = require("https")
+  const target = new URL(endpointUrl)
+  const targetPath = target.pathname.replace(/\/$/, "")
+  const proxiedHeaders = {}
+  for (const [name, value] of headers) {
+    proxiedHeaders[name.toLowerCase()] = value
+  }
+  return new Promise((resolve, reject) => {
+    const server = http.createServer((req, res) => {
+      const upstream = (target.protocol === "https:" ? https : http).request(
+        {
+          protocol: target.protocol,
+          hostname: target.hostname,
+          port: target.port,
+          path: targetPath + req.url,
+          method: req.method,
+          headers: {...req.headers, host: target.host, ...proxiedHeaders},
+        },
+        upstreamRes => {
+          res.writeHead(upstreamRes.statusCode, upstreamRes.headers)
+          upstreamRes.pipe(res)
+        },
+      )
+      upstream.on("error", error => res.destroy(error))
+      req.pipe(upstream)
+    })
+    server.on("error", reject)
+    server.listen(0, "127.0.0.1", () => {
+      // Shouldn't keep the process alive
+      server.unref()
+      resolve("http://127.0.0.1:" + server.address().port)
+    })
+  })
+}`)
+
+// Calls don't have a log index, so they get one after the logs of their block,
+// in the order of their transaction and trace address
+let firstCallLogIndex = 32768
+
+// A parent call comes before its subcalls, which are ordered by their index
+let rec compareTraceAddress = (~index=0, a: array<int>, b: array<int>) =>
+  switch (a->Array.get(index), b->Array.get(index)) {
+  | (None, None) => 0
+  | (None, Some(_)) => -1
+  | (Some(_), None) => 1
+  | (Some(a_), Some(b_)) if a_ !== b_ => a_ - b_
+  | (Some(_), Some(_)) => compareTraceAddress(~index=index + 1, a, b)
+  }
+
+let compareTraceOrder = (a: HyperSync.Trace.t, b: HyperSync.Trace.t) =>
+  if a.blockNumber !== b.blockNumber {
+    a.blockNumber - b.blockNumber
+  } else if a.transactionPosition !== b.transactionPosition {
+    a.transactionPosition - b.transactionPosition
+  } else {
+    compareTraceAddress(a.traceAddress, b.traceAddress)
+  }
+
+let compareEventItemOrder = (a: Internal.eventItem, b: Internal.eventItem) =>
+  if a.blockNumber !== b.blockNumber {
+    a.blockNumber - b.blockNumber
+  } else {
+    a.logIndex - b.logIndex
+  }
+
+// The call fields are typed with the global field selection
+let callFieldSelection: HyperSyncClient.QueryTypes.fieldSelection = {
+  let transactionFields = Types.Transaction.schema->Utils.Schema.getCapitalizedFieldNames
+  let shouldQueryTransactionLogs = transactionFields->Js.Array2.includes("Logs")
+  let transactionLogFields: array<HyperSyncClient.QueryTypes.logField> = [
+    TransactionHash,
+    Address,
+    Data,
+    LogIndex,
+    Topic0,
+    Topic1,
+    Topic2,
+    Topic3,
+  ]
+  {
+    block: Types.Block.schema
+    ->Utils.Schema.getCapitalizedFieldNames
+    ->(Utils.magic: array<string> => array<HyperSyncClient.QueryTypes.blockField>),
+    // The hash is needed to match the transactions to the traces
+    transaction: transactionFields
+    ->Js.Array2.filter(field => field !== "Logs" && field !== "Hash")
+    ->Js.Array2.concat(["Hash"])
+    ->(Utils.magic: array<string> => array<HyperSyncClient.QueryTypes.transactionField>),
+    log: ?(shouldQueryTransactionLogs ? Some(transactionLogFields) : None),
+  }
+}
+let callNonOptionalBlockFieldNames = Types.Block.schema->Utils.Schema.getNonOptionalFieldNames
+let callNonOptionalTransactionFieldNames =
+  Types.Transaction.schema
+  ->Utils.Schema.getNonOptionalFieldNames
+  ->Js.Array2.filter(fieldName => fieldName !== "logs")
+
+// Viem decodes the values by position, so they are named by the abi params
+let makeCallParams = (values: array<unknown>, ~names) =>
+  switch names {
+  | [] => ()->(Utils.magic: unit => 'a)
+  | _ =>
+    let params = Js.Dict.empty()
+    names->Array.forEachWithIndex((index, name) =>
+      params->Js.Dict.set(name, values->Js.Array2.unsafe_get(index))
+    )
+    params->(Utils.magic: dict<unknown> => 'a)
+  }
+
+let decodeCallOrThrow = (
+  trace: HyperSync.Trace.t,
+  ~call: Internal.callConfig,
+  ~abi,
+): (Internal.callInputs, Internal.callOutputs) => {
+  let {args}: Viem.decodedFunctionData<unknown> = Viem.decodeFunctionDataOrThrow({
+    abi,
+    data: trace.input,
+  })
+  let result = Viem.decodeFunctionResultOrThrow({
+    abi,
+    functionName: call.functionName,
+    args,
+    data: trace.output,
+  })
+  let outputs = switch call.outputNames {
+  | [_] => [result]
+  | _ => result->(Utils.magic: unknown => array<unknown>)
+  }
+  (
+    args->Option.getWithDefault([])->makeCallParams(~names=call.inputNames),
+    outputs->makeCallParams(~names=call.outputNames),
+  )
+}
+
 type options = {
   contracts: array<Config.contract>,
   chain: ChainMap.Chain.t,
   endpointUrl: string,
+  // Sent with every request to the endpoint. Might contain secrets, so never log them
+  headers?: dict<string>,
+  // Fields of the shared block and transaction types which only the other chains select
+  excludedBlockFields?: array<string>,
+  excludedTransactionFields?: array<string>,
   allEventSignatures: array<string>,
   shouldUseHypersyncClientDecoder: bool,
   eventRouter: EventRouter.t<module(Types.InternalEvent)>,
@@ -170,6 +426,9 @@ let make = (
     contracts,
     chain,
     endpointUrl,
+    ?headers,
+    ?excludedBlockFields,
+    ?excludedTransactionFields,
     allEventSignatures,
     shouldUseHypersyncClientDecoder,
     eventRouter,
@@ -177,15 +436,40 @@ let make = (
 ): t => {
   let name = "HyperSync"
 
-  let getSelectionConfig = memoGetSelectionConfig(~contracts)
-
-  let client = HyperSyncClient.make(
-    ~url=endpointUrl,
-    ~bearerToken=Env.envioApiToken,
-    ~maxNumRetries=Env.hyperSyncClientMaxRetries,
-    ~httpReqTimeoutMillis=Env.hyperSyncClientTimeoutMillis,
+  let getSelectionConfig = memoGetSelectionConfig(
+    ~contracts,
+    ~excludedBlockFields?,
+    ~excludedTransactionFields?,
   )
 
+  let fetcher = headers->Belt.Option.map(makeFetcherWithHeaders)
+
+  let clientRef = ref(None)
+  let getClient = () =>
+    switch clientRef.contents {
+    | Some(client) => client
+    | None =>
+      let client = (
+        async () => {
+          let url = switch headers->Belt.Option.mapWithDefault([], getProxiedHeaders) {
+          | [] => endpointUrl
+          | proxiedHeaders => await startHeadersProxy(endpointUrl, proxiedHeaders)
+          }
+          HyperSyncClient.make(
+            ~url,
+            ~bearerToken=switch headers->Belt.Option.flatMap(getBearerToken) {
+            | Some(_) as bearerToken => bearerToken
+            | None => Env.envioApiToken
+            },
+            ~maxNumRetries=Env.hyperSyncClientMaxRetries,
+            ~httpReqTimeoutMillis=Env.hyperSyncClientTimeoutMillis,
+          )
+        }
+      )()
+      clientRef := Some(client)
+      client
+    }
+
   let hscDecoder: ref<option<HyperSyncClient.Decoder.t>> = ref(None)
   let getHscDecoder = () => {
     switch hscDecoder.contents {
@@ -236,6 +520,50 @@ let make = (
     }
   }
 
+  let makeCallQueueItem = (
+    item: HyperSync.callsQueryItem,
+    ~call: Internal.callConfig,
+    ~handler: Internal.callHandler,
+    ~inputs,
+    ~outputs,
+    ~logIndex,
+  ): Internal.eventItem => {
+    let {block, trace, transaction} = item
+    let chainId = chain->ChainMap.Chain.toChainId
+    let callArgs: Internal.call = {
+      inputs,
+      outputs,
+      from: trace.from,
+      to: trace.to_,
+      chainId,
+      block,
+      transaction,
+    }
+
+    {
+      eventName: call.name,
+      contractName: call.contractName,
+      loader: None,
+      handler: Some(({context}: Internal.handlerArgs) => handler({call: callArgs, context})),
+      contractRegister: None,
+      paramsRawEventSchema: call.paramsRawEventSchema,
+      timestamp: block->Types.Block.getTimestamp,
+      chain,
+      blockNumber: block->Types.Block.getNumber,
+      logIndex,
+      event: {
+        chainId,
+        params: {"inputs": inputs, "outputs": outputs}->(
+          Utils.magic: {"inputs": Internal.callInputs, "outputs": Internal.callOutputs} => Internal.eventParams
+        ),
+        transaction,
+        block,
+        srcAddress: trace.to_,
+        logIndex,
+      },
+    }
+  }
+
   let contractNameAbiMapping = Js.Dict.empty()
   contracts->Belt.Array.forEach(contract => {
     contractNameAbiMapping->Js.Dict.set(contract.name, contract.abi)
@@ -254,6 +582,7 @@ let make = (
     try {
       let totalTimeRef = Hrtime.makeTimer()
 
+      let client = await getClient()
       let selectionConfig = selection->getSelectionConfig
 
       let logSelections = try selectionConfig.getLogSelectionOrThrow(
@@ -285,6 +614,43 @@ let make = (
         )
       , logger)
 
+      if selectionConfig.shouldQueryTransactionLogs && pageUnsafe.items->Array.length > 0 {
+        let logsByTransactionHash = await HyperSync.queryTransactionLogs(
+          ~client,
+          ~fromBlock,
+          ~toBlockExclusive=pageUnsafe.nextBlock,
+          ~logSelections,
+          ~logger,
+        )
+        pageUnsafe.items->Array.forEach(({transaction}) => {
+          let transaction = transaction->(Utils.magic: Internal.eventTransaction => dict<unknown>)
+          let logs =
+            logsByTransactionHash
+            ->Utils.Dict.dangerouslyGetNonOption(
+              transaction->Js.Dict.unsafeGet("hash")->(Utils.magic: unknown => string),
+            )
+            ->Option.getWithDefault([])
+          transaction->Js.Dict.set("logs", logs->(Utils.magic: array<EvmTypes.log> => unknown))
+        })
+      }
+
+      let calls = switch selectionConfig.getTraceSelections(~contractAddressMapping) {
+      | [] => []
+      | traceSelections =>
+        (
+          await HyperSync.queryCalls(
+            ~client,
+            ~fromBlock,
+            ~toBlockExclusive=pageUnsafe.nextBlock,
+            ~traceSelections,
+            ~fieldSelection=callFieldSelection,
+            ~nonOptionalBlockFieldNames=callNonOptionalBlockFieldNames,
+            ~nonOptionalTransactionFieldNames=callNonOptionalTransactionFieldNames,
+            ~logger,
+          )
+        )->HyperSync.getExn
+      }
+
       let pageFetchTime =
         startFetchingBatchTimeRef->Hrtime.timeSince->Hrtime.toMillis->Hrtime.intFromMillis
 
@@ -333,6 +699,7 @@ let make = (
             ~serverUrl=endpointUrl,
             ~blockNumber=heighestBlockQueried,
             ~logger,
+            ~fetcher?,
           )->Promise.thenResolve(res =>
             switch res {
             | Ok(Some(blockData)) => blockData
@@ -476,6 +843,60 @@ let make = (
         })
       }
 
+      if calls->Array.length > 0 {
+        let callLogIndexRef = ref(firstCallLogIndex)
+        let callBlockNumberRef = ref(-1)
+        calls
+        ->Js.Array2.sortInPlaceWith((a, b) => compareTraceOrder(a.trace, b.trace))
+        ->Array.forEach(item => {
+          let {trace} = item
+          // The joined transactions might have traces of other contracts and functions
+          let maybeCall =
+            contractAddressMapping
+            ->ContractAddressingMap.getContractNameFromAddress(~contractAddress=trace.to_)
+            ->Option.flatMap(contractName =>
+              selectionConfig.callConfigs->Utils.Dict.dangerouslyGetNonOption(
+                makeCallKey(~contractName, ~sighash=trace.input->Js.String2.slice(~from=0, ~to_=10)),
+              )
+            )
+          switch maybeCall {
+          | Some({handler: Some(handler)} as call) =>
+            if trace.blockNumber !== callBlockNumberRef.contents {
+              callBlockNumberRef := trace.blockNumber
+              callLogIndexRef := firstCallLogIndex
+            }
+            let logIndex = callLogIndexRef.contents
+            callLogIndexRef := logIndex + 1
+
+            switch decodeCallOrThrow(
+              trace,
+              ~call,
+              ~abi=contractNameAbiMapping->Js.Dict.unsafeGet(call.contractName),
+            ) {
+            | exception exn =>
+              exn->ErrorHandling.mkLogAndRaise(
+                ~msg=`Failed to decode the call ${call.name} of contract ${call.contractName}, please double check your ABI.`,
+                ~logger=Logging.createChildFrom(
+                  ~logger,
+                  ~params={
+                    "blockNumber": trace.blockNumber,
+                    "transactionHash": trace.transactionHash,
+                  },
+                ),
+              )
+            | (inputs, outputs) =>
+              parsedQueueItems
+              ->Js.Array2.push(
+                makeCallQueueItem(item, ~call, ~handler, ~inputs, ~outputs, ~logIndex),
+              )
+              ->ignore
+            }
+          | Some({handler: None}) | None => ()
+          }
+        })
+        parsedQueueItems->Js.Array2.sortInPlaceWith(compareEventItemOrder)->ignore
+      }
+
       let parsingTimeElapsed =
         parsingTimeRef->Hrtime.timeSince->Hrtime.toMillis->Hrtime.intFromMillis
 
@@ -516,14 +937,35 @@ let make = (
       ~serverUrl=endpointUrl,
       ~blockNumbers,
       ~logger,
+      ~fetcher?,
     )->Promise.thenResolve(HyperSync.mapExn)
 
+  // The blocks of the block handlers have the fields of the global field selection
+  let blockHandlerFieldSelection: HyperSyncClient.QueryTypes.fieldSelection = {
+    block: Types.Block.schema
+    ->Utils.Schema.getCapitalizedFieldNames
+    ->(Utils.magic: array<string> => array<HyperSyncClient.QueryTypes.blockField>),
+  }
+  let blockHandlerNonOptionalFieldNames = Types.Block.schema->Utils.Schema.getNonOptionalFieldNames
+
+  let getBlocks = async (~blockNumbers, ~logger) =>
+    (
+      await HyperSync.queryBlocks(
+        ~client=await getClient(),
+        ~blockNumbers,
+        ~fieldSelection=blockHandlerFieldSelection,
+        ~nonOptionalBlockFieldNames=blockHandlerNonOptionalFieldNames,
+        ~logger,
+      )
+    )->HyperSync.mapExn
+
   {
     name,
     chain,
     getBlockHashes,
+    getBlocks,
     pollingInterval: 100,
-    getHeightOrThrow: () => HyperSyncJsonApi.heightRoute->Rest.fetch(endpointUrl, ()),
+    getHeightOrThrow: () => HyperSyncJsonApi.heightRoute->Rest.fetch(endpointUrl, (), ~fetcher?),
     fetchBlockRange,
   }
 }
@@ -0,0 +1,126 @@
+/**
+Keeps track of the chain head using a newHeads subscription over a WebSocket,
+so the RPC source doesn't need to poll for the latest block.
+When the socket drops, waiters are released and the source falls back
+to polling until the subscription is reconnected.
+*/
+type waiter = {
+  currentBlockHeight: int,
+  resolve: option<int> => unit,
+}
+
+type t = {
+  wsUrl: string,
+  network: Ethers.Network.t,
+  logger: Pino.t,
+  mutable provider: option<Ethers.WebSocketProvider.t>,
+  mutable latestHeight: option<int>,
+  mutable waiters: array<waiter>,
+}
+
+let reconnectDelayMillis = 5_000
+// Fall back to a polling request when the subscription is silent for too long
+let headTimeoutMillis = 60_000
+
+let resolveWaiters = (subscription: t, ~height) => {
+  let waiters = subscription.waiters
+  subscription.waiters = []
+  waiters->Js.Array2.forEach(waiter => {
+    switch height {
+    | Some(height) if height <= waiter.currentBlockHeight =>
+      subscription.waiters->Js.Array2.push(waiter)->ignore
+    | _ => waiter.resolve(height)
+    }
+  })
+}
+
+let rec connect = (subscription: t) => {
+  let provider = Ethers.WebSocketProvider.make(
+    ~wsUrl=subscription.wsUrl,
+    ~network=subscription.network,
+    ~options={staticNetwork: subscription.network},
+  )
+  subscription.provider = Some(provider)
+
+  let disconnect = (~reason) => {
+    switch subscription.provider {
+    | Some(currentProvider) if currentProvider === provider =>
+      subscription.provider = None
+      subscription.latestHeight = None
+      subscription->resolveWaiters(~height=None)
+      subscription.logger->Logging.childWarn({
+        "msg": `Lost the WebSocket subscription for new blocks. Falling back to polling and reconnecting in ${reconnectDelayMillis->Belt.Int.toString}ms`,
+        "err": reason,
+      })
+      provider->Ethers.WebSocketProvider.destroy->Promise.catch(_ => Promise.resolve())->ignore
+      let _ = Js.Global.setTimeout(() => subscription->connect, reconnectDelayMillis)
+    | _ => ()
+    }
+  }
+
+  provider
+  ->Ethers.WebSocketProvider.websocket
+  ->Ethers.WebSocketProvider.onClose(() =>
+    disconnect(~reason=Failure("WebSocket connection closed"))
+  )
+
+  provider
+  ->Ethers.WebSocketProvider.onError(exn => disconnect(~reason=exn->ErrorHandling.prettifyExn))
+  ->ignore
+
+  provider
+  ->Ethers.WebSocketProvider.onBlock(height => {
+    subscription.latestHeight = Some(height)
+    subscription->resolveWaiters(~height=Some(height))
+  })
+  ->Promise.catch(exn => {
+    disconnect(~reason=exn->ErrorHandling.prettifyExn)
+    Promise.resolve(provider)
+  })
+  ->ignore
+}
+
+let make = (~wsUrl, ~chain, ~logger) => {
+  let subscription = {
+    wsUrl,
+    network: Ethers.Network.fromChainId(~chainId=chain->ChainMap.Chain.toChainId),
+    logger: Logging.createChildFrom(
+      ~logger,
+      ~params={"logType": "RPC Head Subscription", "wsUrl": wsUrl},
+    ),
+    provider: None,
+    latestHeight: None,
+    waiters: [],
+  }
+  subscription->connect
+  subscription
+}
+
+/**
+Resolves with the first height greater than the current one,
+or None when the subscription isn't available and the caller should poll instead.
+*/
+let waitForNewHeight = (subscription: t, ~currentBlockHeight, ~timeoutMillis=headTimeoutMillis) => {
+  switch (subscription.provider, subscription.latestHeight) {
+  | (None, _) => Promise.resolve(None)
+  | (Some(_), Some(latestHeight)) if latestHeight > currentBlockHeight =>
+    Promise.resolve(Some(latestHeight))
+  | (Some(_), _) =>
+    Promise.make((resolve, _) => {
+      let timeoutRef = ref(None)
+      let waiter = {
+        currentBlockHeight,
+        resolve: height => {
+          timeoutRef.contents->Belt.Option.forEach(Js.Global.clearTimeout)
+          resolve(height)
+        },
+      }
+      subscription.waiters->Js.Array2.push(waiter)->ignore
+      // Remove the waiter on timeout, so silent subscriptions don't accumulate them
+      timeoutRef := Some(Js.Global.setTimeout(() => {
+            subscription.waiters = subscription.waiters->Js.Array2.filter(w => w !== waiter)
+            resolve(None)
+          }, timeoutMillis))
+    })
+  }
+}
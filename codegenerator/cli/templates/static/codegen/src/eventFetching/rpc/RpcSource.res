@@ -66,16 +66,20 @@ let memoGetSelectionConfig = (~contracts) => {
 
 exception InvalidTransactionField({message: string})
 
-let makeThrowingGetEventBlock = (~getBlock) => {
-  // The block fields type is a subset of Ethers.JsonRpcProvider.block so we can safely cast
-  let blockFieldsFromBlock: Ethers.JsonRpcProvider.block => Internal.eventBlock = Utils.magic
+// The block fields type is a subset of Ethers.JsonRpcProvider.block so we can safely cast
+let blockFieldsFromBlock: Ethers.JsonRpcProvider.block => Internal.eventBlock = Utils.magic
 
+let makeThrowingGetEventBlock = (~getBlock) => {
   async (log: Ethers.log): Internal.eventBlock => {
     (await getBlock(log.blockNumber))->blockFieldsFromBlock
   }
 }
 
-let makeThrowingGetEventTransaction = (~getTransactionFields) => {
+let makeThrowingGetEventTransaction = (
+  ~getTransactionFields,
+  ~getTransactionLogs=?,
+  ~excludedTransactionFields=[],
+) => {
   let fnsCache = Utils.WeakMap.make()
   (log, ~transactionSchema) => {
     (
@@ -86,7 +90,11 @@ let makeThrowingGetEventTransaction = (~getTransactionFields) => {
           let transactionSchema = transactionSchema->S.removeTypeValidation
 
           let transactionFieldItems = switch transactionSchema->S.classify {
-          | Object({items}) => items
+          | Object({items}) =>
+            // The fields selected only by the other chains are optional, so they aren't requested
+            items->Js.Array2.filter(item =>
+              !(excludedTransactionFields->Js.Array2.includes(item.location))
+            )
           | _ => Js.Exn.raiseError("Unexpected internal error: transactionSchema is not an object")
           }
 
@@ -119,6 +127,25 @@ let makeThrowingGetEventTransaction = (~getTransactionFields) => {
               }
               ->parseOrThrowReadableError
               ->Promise.resolve
+          // The logs are only a part of the transaction receipt, so they need another request
+          | _ if transactionFieldItems->Js.Array2.some(item => item.location === "logs") =>
+            switch getTransactionLogs {
+            | Some(getTransactionLogs) =>
+              log =>
+                Promise.all2((
+                  log->getTransactionFields,
+                  log->getTransactionLogs,
+                ))->Promise.thenResolve(((fields, logs)) => {
+                  fields
+                  ->(Utils.magic: unknown => dict<unknown>)
+                  ->Js.Dict.set("logs", logs->(Utils.magic: array<EvmTypes.log> => unknown))
+                  fields->parseOrThrowReadableError
+                })
+            | None =>
+              Js.Exn.raiseError(
+                "Unexpected internal error: the logs of the transaction are selected, but there's no way to get them",
+              )
+            }
           | _ =>
             log =>
               log
@@ -133,17 +160,52 @@ let makeThrowingGetEventTransaction = (~getTransactionFields) => {
   }
 }
 
+let makeTransactionReceiptLoader = (~provider) =>
+  LazyLoader.make(
+    ~loaderFn=transactionHash =>
+      provider->Ethers.JsonRpcProvider.getTransactionReceipt(~transactionHash),
+    ~onError=(am, ~exn) => {
+      Logging.error({
+        "err": exn,
+        "msg": `EE1100: Top level promise timeout reached. Please review other errors or warnings in the code. This function will retry in ${(am._retryDelayMillis / 1000)
+            ->Belt.Int.toString} seconds. It is highly likely that your indexer isn't syncing on one or more chains currently. Also take a look at the "suggestedFix" in the metadata of this command`,
+        "metadata": {
+          {
+            "asyncTaskName": "transactionReceiptLoader: fetching transaction logs - `getTransactionReceipt` rpc call",
+            "caller": "RPC Source",
+            "suggestedFix": "This likely means the RPC url you are using is not responding correctly. Please try another RPC endipoint.",
+          }
+        },
+      })
+    },
+  )
+
 type options = {
   syncConfig: Config.syncConfig,
   provider: Ethers.JsonRpcProvider.t,
+  // Subscribe to newHeads over the WebSocket instead of polling for the height
+  wsUrl?: string,
   chain: ChainMap.Chain.t,
   contracts: array<Config.contract>,
+  // Fields of the shared transaction type which only the other chains select
+  excludedTransactionFields?: array<string>,
   eventRouter: EventRouter.t<module(Types.InternalEvent)>,
 }
 
-let make = ({syncConfig, provider, chain, contracts, eventRouter}: options): t => {
+let make = (
+  {syncConfig, provider, ?wsUrl, chain, contracts, ?excludedTransactionFields, eventRouter}: options,
+): t => {
   let name = "RPC"
 
+  let headSubscription =
+    wsUrl->Belt.Option.map(wsUrl =>
+      RpcHeadSubscription.make(
+        ~wsUrl,
+        ~chain,
+        ~logger=Logging.createChild(~params={"chainId": chain->ChainMap.Chain.toChainId}),
+      )
+    )
+
   let getSelectionConfig = memoGetSelectionConfig(~contracts)
 
   let suggestedBlockIntervals = Js.Dict.empty()
@@ -166,6 +228,8 @@ let make = ({syncConfig, provider, chain, contracts, eventRouter}: options): t =
     },
   )
 
+  let transactionReceiptLoader = makeTransactionReceiptLoader(~provider)
+
   let blockLoader = LazyLoader.make(
     ~loaderFn=blockNumber =>
       EventFetching.getKnownBlockWithBackoff(~provider, ~backoffMsOnFailure=1000, ~blockNumber),
@@ -192,6 +256,10 @@ let make = ({syncConfig, provider, chain, contracts, eventRouter}: options): t =
     ~getTransactionFields=Ethers.JsonRpcProvider.makeGetTransactionFields(
       ~getTransactionByHash=LazyLoader.get(transactionLoader, _),
     ),
+    ~getTransactionLogs=Ethers.JsonRpcProvider.makeGetTransactionLogs(
+      ~getTransactionReceiptByHash=LazyLoader.get(transactionReceiptLoader, _),
+    ),
+    ~excludedTransactionFields?,
   )
 
   let contractNameAbiMapping = Js.Dict.empty()
@@ -377,12 +445,26 @@ let make = ({syncConfig, provider, chain, contracts, eventRouter}: options): t =
     ->Promise.catch(exn => exn->Error->Promise.resolve)
   }
 
+  let getBlocks = (~blockNumbers, ~logger as _currentlyUnusedLogger) => {
+    blockNumbers
+    ->Array.map(blockNum => blockLoader->LazyLoader.get(blockNum))
+    ->Promise.all
+    ->Promise.thenResolve(blocks => blocks->Array.map(blockFieldsFromBlock)->Ok)
+    ->Promise.catch(exn => exn->Error->Promise.resolve)
+  }
+
   {
     name,
     chain,
     pollingInterval: 1000,
     getBlockHashes,
+    getBlocks,
     getHeightOrThrow: () => provider->Ethers.JsonRpcProvider.getBlockNumber,
+    waitForNewHeight: ?(
+      headSubscription->Belt.Option.map(headSubscription => (~currentBlockHeight) =>
+        headSubscription->RpcHeadSubscription.waitForNewHeight(~currentBlockHeight)
+      )
+    ),
     fetchBlockRange,
   }
 }
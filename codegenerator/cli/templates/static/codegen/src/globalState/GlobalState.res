@@ -159,7 +159,7 @@ let updateChainMetadataTable = async (cm: ChainManager.t, ~throttler: Throttler.
         poweredByHyperSync: switch cf.chainConfig.syncSource {
         | HyperSync
         | HyperFuel => true
-        | Rpc => false
+        | Rpc | File => false
         },
         numBatchesFetched: cf.numBatchesFetched,
         latestFetchedBlockNumber: latestFetchedBlock.blockNumber,
@@ -709,7 +709,7 @@ let actionReducer = (state: t, action: action) => {
         ~processingFilters=None,
         ~maxAddrInPartition,
         ~dynamicContractPreRegistration=None,
-        ~enableRawEvents=config.enableRawEvents,
+        ~enableRawEvents=config->Config.shouldSaveRawEvents(~chain=chainConfig.chain),
       )
     })
 
@@ -774,17 +774,25 @@ let checkAndFetchForChain = (
 ) => async chain => {
   let chainFetcher = state.chainManager.chainFetchers->ChainMap.get(chain)
   if !isRollingBack(state) {
-    let {chainConfig: {source}, logger, currentBlockHeight, fetchState} = chainFetcher
+    let {sourceManager, logger, currentBlockHeight, fetchState} = chainFetcher
 
-    await chainFetcher.sourceManager->SourceManager.fetchNext(
+    await sourceManager->SourceManager.fetchNext(
       ~fetchState,
       ~waitForNewBlock=(~currentBlockHeight, ~logger) =>
-        source->waitForNewBlock(~currentBlockHeight, ~logger),
+        sourceManager->SourceManager.waitForNewBlockWithFailover(
+          ~currentBlockHeight,
+          ~logger,
+          ~waitForNewBlock,
+        ),
       ~onNewBlock=(~currentBlockHeight) =>
         dispatchAction(FinishWaitingForNewBlock({chain, currentBlockHeight})),
       ~currentBlockHeight,
       ~executeQuery=async query => {
-        switch await query->executeQuery(~logger, ~source, ~currentBlockHeight, ~chain) {
+        switch await sourceManager->SourceManager.executeQueryWithFailover(
+          ~query,
+          ~executeQuery=(query, ~source) =>
+            query->executeQuery(~logger, ~source, ~currentBlockHeight, ~chain),
+        ) {
         | Ok(response) => dispatchAction(PartitionQueryResponse({chain, response, query}))
         | Error(e) => dispatchAction(ErrorExit(e))
         }
@@ -864,7 +872,7 @@ let injectedTaskReducer = (
           false
         }
         let timeRef = Hrtime.makeTimer()
-        let _ = await Promise.all(Entities.allEntities->Belt.Array.map(entityMod => {
+        let _ = await Promise.all(Entities.allEntitiesWithHistory->Belt.Array.map(entityMod => {
           let module(Entity) = entityMod
           Db.sql->DbFunctions.EntityHistory.pruneStaleEntityHistory(
             ~entityName=Entity.name,
@@ -25,14 +25,17 @@ module InitApi = {
     ->ChainMap.values
     ->Array.forEach(({syncSource, chain}) => {
       switch syncSource {
-      | HyperSync => hyperSyncNetworks
+      | HyperSync => Some(hyperSyncNetworks)
       | HyperFuel =>
         ecosystem := Fuel
-        hyperSyncNetworks
-      | Rpc => rpcNetworks
+        Some(hyperSyncNetworks)
+      | Rpc => Some(rpcNetworks)
+      // Networks replaying recorded files don't use any remote data source
+      | File => None
       }
-      ->Js.Array2.push(chain->ChainMap.Chain.toChainId)
-      ->ignore
+      ->Option.forEach(networks =>
+        networks->Js.Array2.push(chain->ChainMap.Chain.toChainId)->ignore
+      )
     })
 
     {
@@ -119,7 +119,12 @@ module type S = {
         ~blockNumbers: array<int>,
         ~logger: Pino.t,
       ) => promise<result<array<ReorgDetection.blockDataWithTimestamp>, exn>>,
+      getBlocks: (
+        ~blockNumbers: array<int>,
+        ~logger: Pino.t,
+      ) => promise<result<array<Internal.eventBlock>, exn>>,
       getHeightOrThrow: unit => promise<int>,
+      waitForNewHeight?: (~currentBlockHeight: int) => promise<option<int>>,
       fetchBlockRange: (
         ~fromBlock: int,
         ~toBlock: option<int>,
@@ -138,9 +143,21 @@ module type S = {
       abi: Ethers.abi,
       addresses: array<Address.t>,
       events: array<module(Types.Event)>,
+      calls?: array<Internal.callConfig>,
+      startBlock?: int,
+      endBlock?: int,
     }
 
-    type syncSource = HyperSync | HyperFuel | Rpc
+    type syncSource = HyperSync | HyperFuel | Rpc | File
+
+    type blockHandler
+
+    type sourceRole = Primary | Historical | Realtime | Fallback
+
+    type sourceConfig = {
+      role: sourceRole,
+      source: Source.t,
+    }
 
     type chainConfig = {
       syncSource: syncSource,
@@ -149,7 +166,10 @@ module type S = {
       confirmedBlockThreshold: int,
       chain: ChainMap.Chain.t,
       contracts: array<contract>,
-      source: Source.t,
+      blockHandlers: array<blockHandler>,
+      sources: array<sourceConfig>,
+      sourceStallTimeout: int,
+      enableRawEvents?: bool,
     }
   }
 }
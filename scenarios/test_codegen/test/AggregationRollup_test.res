@@ -0,0 +1,149 @@
+open RescriptMocha
+
+describe("Aggregation rollups", () => {
+  let config = RegisterHandlers.registerAllHandlers()
+
+  Async.before(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  Async.after(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  let setTransferPoint = (inMemoryStore, ~id, ~timestamp, ~amount, ~blockNumber) => {
+    let transferPoint: Entities.TransferPoint.t = {
+      id,
+      timestamp: timestamp->Js.Date.fromString,
+      amount: BigInt.fromInt(amount),
+    }
+    inMemoryStore
+    ->InMemoryStore.getInMemTable(
+      ~entityMod=module(Entities.TransferPoint)->Entities.entityModToInternal,
+    )
+    ->InMemoryTable.Entity.set(
+      Set(transferPoint->(Utils.magic: Entities.TransferPoint.t => Entities.internalEntity))
+      ->Types.mkEntityUpdate(
+        ~eventIdentifier={
+          chainId: 1337,
+          blockTimestamp: blockNumber * 10,
+          blockNumber,
+          logIndex: 0,
+        },
+        ~entityId=id,
+      ),
+      ~shouldSaveHistory=true,
+    )
+  }
+
+  type bucket = {id: string, totalAmount: string, transferCount: int}
+
+  let getAllBuckets = async (): array<bucket> =>
+    await Db.sql->Postgres.unsafe(
+      `SELECT "id", "totalAmount"::TEXT AS "totalAmount", "transferCount" FROM public."TransferStats_hour" ORDER BY "id";`,
+    )
+
+  Async.it("Recomputes the buckets of the written and rolled back data points", async () => {
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransferPoint(
+      ~id="1",
+      ~timestamp="2024-01-01T00:10:00Z",
+      ~amount=1,
+      ~blockNumber=10,
+    )
+    inMemoryStore->setTransferPoint(
+      ~id="2",
+      ~timestamp="2024-01-01T00:50:00Z",
+      ~amount=2,
+      ~blockNumber=20,
+    )
+    inMemoryStore->setTransferPoint(
+      ~id="3",
+      ~timestamp="2024-01-01T01:10:00Z",
+      ~amount=4,
+      ~blockNumber=30,
+    )
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    Assert.deepEqual(
+      await getAllBuckets(),
+      [
+        {id: "1704067200", totalAmount: "3", transferCount: 2},
+        {id: "1704070800", totalAmount: "4", transferCount: 1},
+      ],
+    )
+
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransferPoint(
+      ~id="4",
+      ~timestamp="2024-01-01T00:30:00Z",
+      ~amount=8,
+      ~blockNumber=40,
+    )
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    Assert.deepEqual(
+      await getAllBuckets(),
+      [
+        {id: "1704067200", totalAmount: "11", transferCount: 3},
+        {id: "1704070800", totalAmount: "4", transferCount: 1},
+      ],
+      ~message="Should add the data points of a later batch to the existing buckets",
+    )
+
+    let rollbackInMemoryStore = await IO.RollBack.rollBack(
+      ~chainId=1337,
+      ~blockTimestamp=150,
+      ~blockNumber=15,
+      ~logIndex=0,
+      ~isUnorderedMultichainMode=true,
+    )
+    let _ =
+      await Db.sql->IO.executeBatch(
+        ~inMemoryStore=rollbackInMemoryStore,
+        ~isInReorgThreshold=true,
+        ~config,
+      )
+
+    Assert.deepEqual(
+      await getAllBuckets(),
+      [{id: "1704067200", totalAmount: "1", transferCount: 1}],
+      ~message="Should recompute the buckets without the rolled back data points and delete the emptied ones",
+    )
+  })
+
+  Async.it("Only recomputes the buckets of the written data points", async () => {
+    //A bucket without data points between the ones of the batch, which would be
+    //deleted if the buckets in the whole time range of the batch were recomputed
+    let _ =
+      await Db.sql->Postgres.unsafe(
+        `INSERT INTO public."TransferStats_hour" ("id", "timestamp", "totalAmount", "transferCount") VALUES ('1704157200', '2024-01-02T01:00:00Z', 100, 1);`,
+      )
+
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransferPoint(
+      ~id="5",
+      ~timestamp="2024-01-02T00:10:00Z",
+      ~amount=16,
+      ~blockNumber=50,
+    )
+    inMemoryStore->setTransferPoint(
+      ~id="6",
+      ~timestamp="2024-01-02T02:10:00Z",
+      ~amount=32,
+      ~blockNumber=60,
+    )
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    Assert.deepEqual(
+      await getAllBuckets(),
+      [
+        {id: "1704067200", totalAmount: "1", transferCount: 1},
+        {id: "1704153600", totalAmount: "16", transferCount: 1},
+        {id: "1704157200", totalAmount: "100", transferCount: 1},
+        {id: "1704160800", totalAmount: "32", transferCount: 1},
+      ],
+      ~message="Shouldn't touch the bucket between the ones of the data points",
+    )
+  })
+})
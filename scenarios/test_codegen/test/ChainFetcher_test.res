@@ -60,3 +60,27 @@ describe("Test Processing Filters", () => {
     )
   })
 })
+
+describe("Test contract end block filter", () => {
+  it("Removes items of the contract after its end block", () => {
+    let item = MockEvents.eventBatchItems->Js.Array2.unsafe_get(0)
+    let gravatarAtEndBlock = {...item, blockNumber: 10}
+    let gravatarAfterEndBlock = {...item, blockNumber: 11}
+    let otherContractAfterEndBlock = {...item, contractName: "NftFactory", blockNumber: 11}
+
+    let items =
+      [
+        gravatarAtEndBlock,
+        gravatarAfterEndBlock,
+        otherContractAfterEndBlock,
+      ]->ChainFetcher.applyProcessingFilters(
+        ~processingFilters=[
+          ChainFetcher.makeContractEndBlockFilter(~contractName="Gravatar", ~contractEndBlock=10),
+        ],
+      )
+
+    Assert.equal(items->Array.length, 2)
+    Assert.equal(items->Js.Array2.unsafe_get(0), gravatarAtEndBlock)
+    Assert.equal(items->Js.Array2.unsafe_get(1), otherContractAfterEndBlock)
+  })
+})
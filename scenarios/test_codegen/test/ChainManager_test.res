@@ -148,6 +148,8 @@ let populateChainQueuesWithRandomEvents = (~runTime=1000, ~maxBlockTime=15, ())
       fetchState: fetchState.contents,
       logger: Logging.logger,
       sourceManager: SourceManager.make(
+        ~sources=MockConfig.mockChainConfig.sources,
+        ~stallTimeoutMillis=20_000,
         ~maxPartitionConcurrency=Env.maxPartitionConcurrency,
         ~logger=Logging.logger,
       ),
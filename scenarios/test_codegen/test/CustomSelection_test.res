@@ -66,3 +66,54 @@ Async.it("Handles event with a custom field selection (in ReScript)", async () =
 
   Assert.notEqual(updatedMockDb.entities.customSelectionTestPass.get(hash), None)
 })
+
+describe("Nested records of the field selection", () => {
+  it("Decodes the access list and withdrawals with the generated schemas", () => {
+    let transaction = %raw(`{
+      "accessList": [
+        {
+          "address": "0x2B2f78c5BF6D9C12Ee1225D5F374aa91204580c3",
+          "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+        }
+      ]
+    }`)->S.parseOrThrow(Types.Gravatar.TestEventWithReservedKeyword.transactionSchema)
+    let block = %raw(`{
+      "number": 1,
+      "timestamp": 2,
+      "hash": "0x01",
+      "withdrawals": [
+        {
+          "index": "0x1",
+          "validatorIndex": "0x2",
+          "address": "0x2B2f78c5BF6D9C12Ee1225D5F374aa91204580c3",
+          "amount": "0x3"
+        }
+      ]
+    }`)->S.parseOrThrow(Types.Gravatar.TestEventWithReservedKeyword.blockSchema)
+
+    Assert.deepEqual(
+      transaction.accessList,
+      Some([
+        (
+          {
+            address: "0x2B2f78c5BF6D9C12Ee1225D5F374aa91204580c3"->Address.unsafeFromString,
+            storageKeys: ["0x0000000000000000000000000000000000000000000000000000000000000001"],
+          }: EvmTypes.accessListItem
+        ),
+      ]),
+    )
+    Assert.deepEqual(
+      block.withdrawals,
+      Some([
+        (
+          {
+            index: "0x1",
+            validatorIndex: "0x2",
+            address: "0x2B2f78c5BF6D9C12Ee1225D5F374aa91204580c3"->Address.unsafeFromString,
+            amount: "0x3",
+          }: EvmTypes.withdrawal
+        ),
+      ]),
+    )
+  })
+})
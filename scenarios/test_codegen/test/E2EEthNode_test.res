@@ -33,22 +33,29 @@ describe("E2E Integration Test", () => {
         endBlock: None,
         chain,
         contracts,
-        source: RpcSource.make({
-          chain,
-          contracts,
-          syncConfig: {
-            initialBlockInterval: 10000,
-            backoffMultiplicative: 10000.0,
-            accelerationAdditive: 10000,
-            intervalCeiling: 10000,
-            backoffMillis: 10000,
-            queryTimeoutMillis: 10000,
+        blockHandlers: [],
+        sources: [
+          {
+            role: Primary,
+            source: RpcSource.make({
+              chain,
+              contracts,
+              syncConfig: {
+                initialBlockInterval: 10000,
+                backoffMultiplicative: 10000.0,
+                accelerationAdditive: 10000,
+                intervalCeiling: 10000,
+                backoffMillis: 10000,
+                queryTimeoutMillis: 10000,
+              },
+              provider,
+              eventRouter: contracts
+              ->Belt.Array.flatMap(contract => contract.events)
+              ->EventRouter.fromEvmEventModsOrThrow(~chain),
+            }),
           },
-          provider,
-          eventRouter: contracts
-          ->Belt.Array.flatMap(contract => contract.events)
-          ->EventRouter.fromEvmEventModsOrThrow(~chain),
-        }),
+        ],
+        sourceStallTimeout: 20_000,
       }
     }
 
@@ -0,0 +1,246 @@
+open Belt
+open RescriptMocha
+
+@module("fs") external mkdtempSync: string => string = "mkdtempSync"
+@module("fs") external writeFileSync: (string, string) => unit = "writeFileSync"
+@module("os") external tmpdir: unit => string = "tmpdir"
+@module("path") external join: (string, string) => string = "join"
+@val external __dirname: string = "__dirname"
+
+let makeRecordingDir = (~files) => {
+  let dirPath = mkdtempSync(tmpdir()->join("envio-file-source-"))
+  files->Array.forEach(((name, content)) => dirPath->join(name)->writeFileSync(content))
+  dirPath
+}
+
+let blocksJsonl = `{"number":1,"timestamp":15,"hash":"0x1"}
+{"number":2,"timestamp":30,"hash":"0x2"}
+`
+
+let logsJsonl = `{"blockNumber":2,"logIndex":1,"transactionIndex":0,"transactionHash":"0xabc","address":"0x2b2f78c5bf6d9c12ee1225d5f374aa91204580c3","data":"0x","topics":[]}
+{"blockNumber":2,"logIndex":0,"transactionIndex":0,"transactionHash":"0xabc","address":"0x2b2f78c5bf6d9c12ee1225d5f374aa91204580c3","data":"0x","topics":[]}
+`
+
+describe("FileSource.loadRecording", () => {
+  Async.it("Loads a recording without a transactions file", async () => {
+    let dirPath = makeRecordingDir(
+      ~files=[("blocks.jsonl", blocksJsonl), ("logs.jsonl", logsJsonl)],
+    )
+
+    let recording = await FileSource.loadRecording(~dirPath, ~format=FileSource.Jsonl)
+
+    Assert.equal(recording.height, 2)
+    Assert.deepEqual(recording.transactionsByHash, Js.Dict.empty())
+    Assert.deepEqual(
+      recording.logs->Array.map(log => (log.blockNumber, log.logIndex)),
+      [(2, 0), (2, 1)],
+      ~message="Logs should be sorted by block number and log index",
+    )
+    Assert.equal(
+      (recording.logs->Js.Array2.unsafe_get(0)).address,
+      "0x2b2f78c5bf6d9c12ee1225d5f374aa91204580c3"->Address.Evm.fromStringOrThrow,
+      ~message="Addresses should be checksummed",
+    )
+  })
+
+  Async.it("Fails on a transactions file which can't be parsed", async () => {
+    let dirPath = makeRecordingDir(
+      ~files=[
+        ("blocks.jsonl", blocksJsonl),
+        ("logs.jsonl", logsJsonl),
+        ("transactions.jsonl", `{"hash":"0xabc"`),
+      ],
+    )
+
+    switch await FileSource.loadRecording(~dirPath, ~format=FileSource.Jsonl) {
+    | _ => Assert.fail("Should fail instead of ignoring the transactions file")
+    | exception exn =>
+      Assert.equal(
+        exn->FileSource.isMissingFileExn,
+        false,
+        ~message="Should surface the parsing error",
+      )
+    }
+  })
+
+  Async.it("Fails without a blocks file", async () => {
+    let dirPath = makeRecordingDir(~files=[("logs.jsonl", logsJsonl)])
+
+    switch await FileSource.loadRecording(~dirPath, ~format=FileSource.Jsonl) {
+    | _ => Assert.fail("Should fail without a blocks file")
+    | exception exn =>
+      Assert.equal(exn->FileSource.isMissingFileExn, true, ~message="Should be a missing file error")
+    }
+  })
+})
+
+describe("FileSource replay of a recorded fixture", () => {
+  // The fixture is written by the recorder test of the envio cli,
+  // which records every block of the range even without logs
+  let source = FileSource.make({
+    chain: ChainMap.Chain.makeUnsafe(~chainId=1337),
+    contracts: [],
+    eventRouter: EventRouter.empty(),
+    dirPath: __dirname->join("fixtures/recording"),
+    format: FileSource.Jsonl,
+  })
+
+  Async.it("Stops the range at a block without logs", async () => {
+    switch await source.fetchBlockRange(
+      ~fromBlock=10,
+      ~toBlock=Some(14),
+      ~contractAddressMapping=ContractAddressingMap.make(),
+      ~currentBlockHeight=15,
+      ~partitionId="0",
+      ~selection={isWildcard: false, eventConfigs: []},
+      ~logger=Logging.logger,
+    ) {
+    | Ok(response) =>
+      Assert.equal(response.latestFetchedBlockNumber, 14)
+      Assert.equal(response.latestFetchedBlockTimestamp, 210)
+      Assert.deepEqual(
+        response.reorgGuard,
+        {
+          firstBlockParentNumberAndHash: None,
+          lastBlockScannedData: {
+            blockNumber: 14,
+            blockHash: "0x000000000000000000000000000000000000000000000000000000000000000e",
+          },
+        },
+      )
+    | Error(_) => Assert.fail("Should replay the range without missing blocks")
+    }
+  })
+
+  Async.it("Gets the hashes of blocks without logs", async () => {
+    Assert.equal(await source.getHeightOrThrow(), 15)
+    Assert.deepEqual(
+      await source.getBlockHashes(~blockNumbers=[11, 13], ~logger=Logging.logger),
+      Ok([
+        {
+          blockNumber: 11,
+          blockHash: "0x000000000000000000000000000000000000000000000000000000000000000b",
+          blockTimestamp: 165,
+        },
+        {
+          blockNumber: 13,
+          blockHash: "0x000000000000000000000000000000000000000000000000000000000000000d",
+          blockTimestamp: 195,
+        },
+      ]),
+    )
+  })
+})
+
+describe("FileSource.getLogsInRange", () => {
+  let makeLog = (~blockNumber, ~logIndex): FileSource.fileLog => {
+    blockNumber,
+    logIndex,
+    transactionIndex: 0,
+    transactionHash: "0xabc",
+    address: "0x2b2f78c5bf6d9c12ee1225d5f374aa91204580c3"->Address.Evm.fromStringOrThrow,
+    data: "0x",
+    topics: [],
+  }
+  let logs = [
+    makeLog(~blockNumber=1, ~logIndex=0),
+    makeLog(~blockNumber=3, ~logIndex=0),
+    makeLog(~blockNumber=3, ~logIndex=1),
+    makeLog(~blockNumber=5, ~logIndex=0),
+  ]
+  let getRange = (~fromBlock, ~toBlock) =>
+    logs
+    ->FileSource.getLogsInRange(~fromBlock, ~toBlock)
+    ->Array.map(log => (log.blockNumber, log.logIndex))
+
+  it("Slices the logs of the inclusive block range", () => {
+    Assert.deepEqual(getRange(~fromBlock=3, ~toBlock=3), [(3, 0), (3, 1)])
+    Assert.deepEqual(getRange(~fromBlock=2, ~toBlock=4), [(3, 0), (3, 1)])
+    Assert.deepEqual(getRange(~fromBlock=0, ~toBlock=5), [(1, 0), (3, 0), (3, 1), (5, 0)])
+  })
+
+  it("Returns no logs for a range without logs", () => {
+    Assert.deepEqual(getRange(~fromBlock=6, ~toBlock=10), [])
+    Assert.deepEqual(getRange(~fromBlock=4, ~toBlock=4), [])
+    Assert.deepEqual(getRange(~fromBlock=0, ~toBlock=0), [])
+  })
+})
+
+describe("FileSource replay of recorded events", () => {
+  let gravatarAddress = "0x2B2f78c5BF6D9C12Ee1225D5F374aa91204580c3"->Address.Evm.fromStringOrThrow
+  // NewGravatar(id, owner, "a", "b") with the id in the first word of the data
+  let makeNewGravatarLog = (~blockNumber, ~id) =>
+    `{"blockNumber":${blockNumber->Int.toString},"logIndex":0,"transactionIndex":0,"transactionHash":"0xab${blockNumber->Int.toString}","address":"0x2b2f78c5bf6d9c12ee1225d5f374aa91204580c3","data":"0x000000000000000000000000000000000000000000000000000000000000000${id->Int.toString}0000000000000000000000002b2f78c5bf6d9c12ee1225d5f374aa91204580c3000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000001610000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000016200000000000000000000000000000000000000000000000000000000000000","topics":["0x9ab3aefb2ba6dc12910ac1bce4692cf5c3c0d06cff16327c64a3ef78228b130b"]}`
+
+  let source = FileSource.make({
+    chain: MockConfig.chain1337,
+    contracts: MockConfig.contracts,
+    eventRouter: MockConfig.contracts
+    ->Array.flatMap(contract => contract.events)
+    ->EventRouter.fromEvmEventModsOrThrow(~chain=MockConfig.chain1337),
+    dirPath: makeRecordingDir(
+      ~files=[
+        (
+          "blocks.jsonl",
+          `{"number":1,"timestamp":15,"hash":"0x1"}
+{"number":2,"timestamp":30,"hash":"0x2"}
+{"number":3,"timestamp":45,"hash":"0x3"}
+`,
+        ),
+        (
+          "logs.jsonl",
+          [
+            makeNewGravatarLog(~blockNumber=1, ~id=1),
+            makeNewGravatarLog(~blockNumber=2, ~id=2),
+            makeNewGravatarLog(~blockNumber=3, ~id=3),
+          ]->Js.Array2.joinWith("\n"),
+        ),
+      ],
+    ),
+    format: FileSource.Jsonl,
+  })
+
+  Async.it("Decodes the events of the range with the contract abi", async () => {
+    switch await source.fetchBlockRange(
+      ~fromBlock=2,
+      ~toBlock=Some(2),
+      ~contractAddressMapping=ContractAddressingMap.fromArray([(gravatarAddress, "Gravatar")]),
+      ~currentBlockHeight=3,
+      ~partitionId="0",
+      ~selection={
+        isWildcard: false,
+        eventConfigs: [
+          {
+            contractName: "Gravatar",
+            eventId: Types.Gravatar.NewGravatar.id,
+            isWildcard: false,
+          },
+        ],
+      },
+      ~logger=Logging.logger,
+    ) {
+    | Ok(response) =>
+      Assert.deepEqual(
+        response.parsedQueueItems->Array.map(item => {
+          let event = item.event->(Utils.magic: Internal.event => Types.Gravatar.NewGravatar.event)
+          (item.eventName, item.blockNumber, event.srcAddress, event.params)
+        }),
+        [
+          (
+            "NewGravatar",
+            2,
+            gravatarAddress,
+            {
+              id: 2n,
+              owner: gravatarAddress,
+              displayName: "a",
+              imageUrl: "b",
+            },
+          ),
+        ],
+        ~message="Should only decode the event of the fetched block",
+      )
+    | Error(_) => Assert.fail("Should replay the recorded events")
+    }
+  })
+})
@@ -0,0 +1,165 @@
+open RescriptMocha
+
+describe("Immutable entities", () => {
+  let config = RegisterHandlers.registerAllHandlers()
+
+  Async.before(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  Async.after(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  let makeTransfer = (~id, ~value): Entities.ImmutableTransfer.t => {
+    id,
+    value: BigInt.fromInt(value),
+  }
+
+  let setTransfer = (
+    inMemoryStore,
+    ~transfer: Entities.ImmutableTransfer.t,
+    ~blockNumber,
+    ~chainId=1337,
+  ) => {
+    inMemoryStore
+    ->InMemoryStore.getInMemTable(
+      ~entityMod=module(Entities.ImmutableTransfer)->Entities.entityModToInternal,
+    )
+    ->InMemoryTable.Entity.set(
+      Set(transfer->(Utils.magic: Entities.ImmutableTransfer.t => Entities.internalEntity))
+      ->Types.mkEntityUpdate(
+        ~eventIdentifier={
+          chainId,
+          blockTimestamp: blockNumber * 10,
+          blockNumber,
+          logIndex: 0,
+        },
+        ~entityId=transfer.id,
+      ),
+      ~shouldSaveHistory=true,
+    )
+  }
+
+  let getAllTransfers = async () =>
+    (await Db.sql->Postgres.unsafe(`SELECT * FROM public."ImmutableTransfer" ORDER BY id;`))
+    ->Js.Array2.map(S.parseJsonOrThrow(_, Entities.ImmutableTransfer.schema))
+
+  Async.it("Deletes the rows inserted after the block of a reorg", async () => {
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransfer(~transfer=makeTransfer(~id="1", ~value=1), ~blockNumber=10)
+    inMemoryStore->setTransfer(~transfer=makeTransfer(~id="2", ~value=2), ~blockNumber=20)
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransfer(
+      ~transfer=makeTransfer(~id="3", ~value=3),
+      ~blockNumber=5,
+      ~chainId=1,
+    )
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    let firstChangeEventPerChain =
+      await Db.sql->DbFunctions.EntityHistory.getFirstChangeEventPerChain(
+        UnorderedMultichain({reorgChainId: 1337, safeBlockNumber: 15}),
+      )
+    Assert.deepEqual(
+      firstChangeEventPerChain->DbFunctions.EntityHistory.FirstChangeEventPerChain.get(
+        ~chainId=1337,
+      ),
+      Some({blockNumber: 20, logIndex: 0}),
+      ~message="Should find the first rolled back row of the reorg chain",
+    )
+    Assert.deepEqual(
+      firstChangeEventPerChain->DbFunctions.EntityHistory.FirstChangeEventPerChain.get(~chainId=1),
+      Some({blockNumber: 5, logIndex: 0}),
+      ~message="Should find the rows of other chains inserted after the first rolled back one",
+    )
+
+    let rollbackInMemoryStore = await IO.RollBack.rollBack(
+      ~chainId=1337,
+      ~blockTimestamp=150,
+      ~blockNumber=15,
+      ~logIndex=0,
+      ~isUnorderedMultichainMode=true,
+    )
+    let _ =
+      await Db.sql->IO.executeBatch(
+        ~inMemoryStore=rollbackInMemoryStore,
+        ~isInReorgThreshold=true,
+        ~config,
+      )
+
+    Assert.deepEqual(
+      await getAllTransfers(),
+      [makeTransfer(~id="1", ~value=1)],
+      ~message="Only the transfer from before the reorg should be kept",
+    )
+    Assert.deepEqual(
+      await Db.sql->Postgres.unsafe(
+        `SELECT envio_chain_id, envio_block_number, envio_log_index FROM public."ImmutableTransfer";`,
+      ),
+      [{"envio_chain_id": 1337, "envio_block_number": 10, "envio_log_index": 0}],
+      ~message="Should keep the event which inserted the row",
+    )
+
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransfer(~transfer=makeTransfer(~id="2", ~value=3), ~blockNumber=16)
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    Assert.deepEqual(
+      await getAllTransfers(),
+      [makeTransfer(~id="1", ~value=1), makeTransfer(~id="2", ~value=3)],
+      ~message="Should insert the transfer of the reorged chain with the id of a rolled back one",
+    )
+  })
+
+  it("Doesn't have a history table", () => {
+    Assert.ok(
+      !(
+        Db.allEntityHistoryTables->Js.Array2.some(table =>
+          table.tableName == "ImmutableTransfer_history"
+        )
+      ),
+    )
+  })
+
+  Async.it("Fails the batch when an id was inserted in a previous batch", async () => {
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setTransfer(~transfer=makeTransfer(~id="1", ~value=4), ~blockNumber=30)
+
+    switch await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config) {
+    | exception _ => ()
+    | _ => Assert.fail("Should fail instead of ignoring the duplicate")
+    }
+
+    Assert.deepEqual(
+      (await getAllTransfers())->Js.Array2.map(transfer => transfer.value),
+      [BigInt.fromInt(1), BigInt.fromInt(3)],
+      ~message="Shouldn't update the existing transfer",
+    )
+  })
+
+  it("Raises when a handler sets the same id twice", () => {
+    let contextEnv = ContextEnv.make(
+      ~eventItem=MockEvents.newGravatarLog1->MockEvents.newGravatarEventToBatchItem,
+      ~logger=Logging.logger,
+    )
+    let handlerContext =
+      contextEnv
+      ->ContextEnv.getHandlerContext(
+        ~inMemoryStore=InMemoryStore.make(),
+        ~loadLayer=LoadLayer.makeWithDbConnection(),
+        ~shouldSaveHistory=false,
+      )
+      ->(Utils.magic: Internal.handlerContext => Types.handlerContext)
+
+    handlerContext.immutableTransfer.set(makeTransfer(~id="1", ~value=1))
+    Assert.throws(
+      () => handlerContext.immutableTransfer.set(makeTransfer(~id="1", ~value=2)),
+      ~error={
+        "message": "Entity ImmutableTransfer with id 1 is immutable and was already set",
+      },
+    )
+  })
+})
@@ -0,0 +1,68 @@
+open RescriptMocha
+
+describe("LogDecoder - decode", () => {
+  let gravatarAddress = "0x2B2f78c5BF6D9C12Ee1225D5F374aa91204580c3"->Address.Evm.fromStringOrThrow
+  let unknownAddress = "0x1234567890abcdef1234567890abcdef12345678"->Address.Evm.fromStringOrThrow
+
+  let mockTransferLog = (~address): EvmTypes.log => {
+    address,
+    data: "0x",
+    topics: [
+      // Transfer(address indexed from, address indexed to, uint256 indexed tokenId)
+      "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+      "0x0000000000000000000000000000000000000000000000000000000000000000",
+      "0x000000000000000000000000a2f6e6029638ccb484a2ccb6414499ad3e825cac",
+      "0x0000000000000000000000000000000000000000000000000000000000000001",
+    ],
+    logIndex: 0,
+  }
+
+  let toNames = (decoded: option<LogDecoder.decodedLog>) =>
+    decoded->Belt.Option.map(({contractName, eventName}) => (contractName, eventName))
+
+  it(
+    "Decodes a log of an address which isn't in config.yaml with any contract having the event",
+    () => {
+      Assert.deepEqual(
+        Handlers.decodeTransactionLog(mockTransferLog(~address=unknownAddress))->toNames,
+        Some(("SimpleNft", "Transfer")),
+      )
+    },
+  )
+
+  it("Only uses the events of the contract the address belongs to", () => {
+    Assert.deepEqual(
+      Handlers.decodeTransactionLog(mockTransferLog(~address=gravatarAddress))->toNames,
+      None,
+      ~message="Gravatar doesn't have the Transfer event, so it shouldn't be decoded as a SimpleNft one",
+    )
+  })
+
+  it("Matches the address regardless of its case", () => {
+    let decoder = LogDecoder.make([
+      {
+        name: Types.SimpleNft.contractName,
+        abi: Types.SimpleNft.abi,
+        addresses: [unknownAddress],
+        events: [module(Types.SimpleNft.Transfer)],
+      },
+      {
+        name: Types.Gravatar.contractName,
+        abi: Types.Gravatar.abi,
+        addresses: [gravatarAddress],
+        events: [module(Types.Gravatar.NewGravatar)],
+      },
+    ])
+
+    Assert.deepEqual(
+      decoder
+      ->LogDecoder.decode(
+        mockTransferLog(
+          ~address=unknownAddress->Address.toString->Js.String2.toLowerCase->Address.unsafeFromString,
+        ),
+      )
+      ->toNames,
+      Some(("SimpleNft", "Transfer")),
+    )
+  })
+})
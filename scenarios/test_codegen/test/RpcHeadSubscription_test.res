@@ -0,0 +1,52 @@
+open RescriptMocha
+
+describe("RpcHeadSubscription.waitForNewHeight", () => {
+  let makeSubscription = (): RpcHeadSubscription.t => {
+    wsUrl: "ws://localhost:8545",
+    network: Ethers.Network.fromChainId(~chainId=1),
+    logger: Logging.logger,
+    // Pretend to be connected without opening a socket
+    provider: Some(Utils.magic("provider")),
+    latestHeight: None,
+    waiters: [],
+  }
+
+  Async.it("Resolves with the first height greater than the current one", async () => {
+    let subscription = makeSubscription()
+
+    let heightPromise = subscription->RpcHeadSubscription.waitForNewHeight(~currentBlockHeight=10)
+    subscription->RpcHeadSubscription.resolveWaiters(~height=Some(10))
+    Assert.equal(
+      subscription.waiters->Js.Array2.length,
+      1,
+      ~message="Should keep waiting for a height greater than the current one",
+    )
+    subscription->RpcHeadSubscription.resolveWaiters(~height=Some(11))
+
+    Assert.deepEqual(await heightPromise, Some(11))
+    Assert.deepEqual(subscription.waiters, [])
+  })
+
+  Async.it("Removes the waiter when the subscription is silent for too long", async () => {
+    let subscription = makeSubscription()
+
+    let height =
+      await subscription->RpcHeadSubscription.waitForNewHeight(
+        ~currentBlockHeight=10,
+        ~timeoutMillis=0,
+      )
+
+    Assert.deepEqual(height, None, ~message="Should fall back to polling")
+    Assert.deepEqual(subscription.waiters, [], ~message="Shouldn't leak the timed out waiter")
+  })
+
+  Async.it("Resolves with None when the subscription isn't connected", async () => {
+    let subscription = {...makeSubscription(), provider: None}
+
+    Assert.deepEqual(
+      await subscription->RpcHeadSubscription.waitForNewHeight(~currentBlockHeight=10),
+      None,
+    )
+    Assert.deepEqual(subscription.waiters, [])
+  })
+})
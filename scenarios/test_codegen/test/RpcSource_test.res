@@ -130,6 +130,33 @@ describe("RpcSource - getEventTransactionOrThrow", () => {
     )
   })
 
+  Async.it(
+    "Doesn't request the fields selected only by other chains. Doesn't make a transaction request",
+    async () => {
+      let getEventTransactionOrThrow = RpcSource.makeThrowingGetEventTransaction(
+        ~getTransactionFields=neverGetTransactionFields,
+        ~excludedTransactionFields=["l1Fee", "gasUsedForL1"],
+      )
+      Assert.deepEqual(
+        await mockEthersLog()->getEventTransactionOrThrow(
+          ~transactionSchema=S.schema(
+            s =>
+              {
+                "hash": s.matches(S.string),
+                "l1Fee": s.matches(S.option(BigInt.nativeSchema)),
+                "gasUsedForL1": s.matches(S.option(BigInt.nativeSchema)),
+              },
+          ),
+        ),
+        {
+          "hash": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdef",
+          "l1Fee": None,
+          "gasUsedForL1": None,
+        },
+      )
+    },
+  )
+
   Async.it("Queries transaction with a non-log field (with real Ethers.provider)", async () => {
     let testTransactionHash = "0x3dce529e9661cfb65defa88ae5cd46866ddf39c9751d89774d89728703c2049f"
 
@@ -236,6 +263,72 @@ describe("RpcSource - getEventTransactionOrThrow", () => {
   })
 })
 
+describe("RpcSource - transaction logs", () => {
+  Async.it("Adds the logs from the transaction receipt to the transaction", async () => {
+    let requestedHashes = []
+    let provider = {
+      "getTransactionReceipt": transactionHash => {
+        requestedHashes->Js.Array2.push(transactionHash)->ignore
+        Promise.resolve(
+          (
+            {
+              logs: [
+                {
+                  address: "0x1234567890abcdef1234567890abcdef12345678"->Address.unsafeFromString,
+                  data: "0x",
+                  topics: ["0xd78ad95fa46c994b6551d0da85fc275fe613dbe680204dd5837f03aa2f863b9b"],
+                  index: 2,
+                },
+              ],
+            }: Ethers.JsonRpcProvider.transactionReceipt
+          ),
+        )
+      },
+    }->Utils.magic
+    let transactionReceiptLoader = RpcSource.makeTransactionReceiptLoader(~provider)
+    let getEventTransactionOrThrow = RpcSource.makeThrowingGetEventTransaction(
+      ~getTransactionFields=(log: Ethers.log) =>
+        {"hash": log.transactionHash}->Utils.magic->Promise.resolve,
+      ~getTransactionLogs=Ethers.JsonRpcProvider.makeGetTransactionLogs(
+        ~getTransactionReceiptByHash=LazyLoader.get(transactionReceiptLoader, _),
+      ),
+    )
+    let transactionSchema = S.schema(s =>
+      {
+        "hash": s.matches(S.string),
+        "logs": s.matches(S.array(EvmTypes.logSchema)),
+      }
+    )
+
+    let expectedTransaction = {
+      "hash": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdef",
+      "logs": [
+        (
+          {
+            address: "0x1234567890abcdef1234567890abcdef12345678"->Address.unsafeFromString,
+            data: "0x",
+            topics: ["0xd78ad95fa46c994b6551d0da85fc275fe613dbe680204dd5837f03aa2f863b9b"],
+            logIndex: 2,
+          }: EvmTypes.log
+        ),
+      ],
+    }
+    Assert.deepEqual(
+      await mockEthersLog()->getEventTransactionOrThrow(~transactionSchema),
+      expectedTransaction,
+    )
+    Assert.deepEqual(
+      await mockEthersLog()->getEventTransactionOrThrow(~transactionSchema),
+      expectedTransaction,
+    )
+    Assert.deepEqual(
+      requestedHashes,
+      ["0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdef"],
+      ~message="Should request the receipt once per transaction",
+    )
+  })
+})
+
 module MockEvent = HyperSyncSource_test.MockEvent
 describe("RpcSource - getSelectionConfig", () => {
   let withConfig = HyperSyncSource_test.withConfig
@@ -0,0 +1,110 @@
+open Belt
+open RescriptMocha
+
+describe("Source.fetchBlockRange", () => {
+  Async.it("Fetches the blocks of the block handlers without querying logs", async () => {
+    let chain = ChainMap.Chain.makeUnsafe(~chainId=1)
+    let getBlocksCalls = []
+    let source: Source.t = {
+      name: "MockSource",
+      chain,
+      pollingInterval: 0,
+      getBlockHashes: (~blockNumbers as _, ~logger as _) =>
+        Js.Exn.raiseError("Block handlers should get the blocks with the selected fields"),
+      getBlocks: (~blockNumbers, ~logger as _) => {
+        getBlocksCalls->Js.Array2.push(blockNumbers)->ignore
+        blockNumbers
+        ->Array.map(blockNumber =>
+          {
+            "number": blockNumber,
+            "timestamp": blockNumber * 15,
+            "hash": `0x${blockNumber->Int.toString}`,
+            "parentHash": `0x${(blockNumber - 1)->Int.toString}`,
+          }->(Utils.magic: {..} => Internal.eventBlock)
+        )
+        ->Ok
+        ->Promise.resolve
+      },
+      getHeightOrThrow: () => Promise.resolve(100),
+      fetchBlockRange: (
+        ~fromBlock as _,
+        ~toBlock as _,
+        ~contractAddressMapping as _,
+        ~currentBlockHeight as _,
+        ~partitionId as _,
+        ~selection as _,
+        ~logger as _,
+      ) => Js.Exn.raiseError("Block handlers shouldn't query logs"),
+    }
+
+    let handledBlocks = []
+    let blockHandler: FetchState.blockHandler = {
+      name: "EveryTenBlocks",
+      interval: 10,
+      startBlock: 5,
+      endBlock: None,
+      makeItem: (~block) =>
+        ChainFetcher.makeBlockHandlerItem(
+          ~chain,
+          ~name="EveryTenBlocks",
+          ~logIndex=ChainFetcher.maxLogIndex,
+          ~handler=async ({block}) => handledBlocks->Js.Array2.push(block)->ignore,
+          ~block,
+        ),
+    }
+
+    let response = switch await source->Source.fetchBlockRange(
+      ~fromBlock=10,
+      ~toBlock=Some(30),
+      ~contractAddressMapping=ContractAddressingMap.make(),
+      ~partitionId="0",
+      ~chain,
+      ~currentBlockHeight=100,
+      ~selection={isWildcard: true, eventConfigs: [], blockHandlers: [blockHandler]},
+      ~logger=Logging.logger,
+    ) {
+    | Ok(response) => response
+    | Error(_) => Js.Exn.raiseError("Expected the blocks of the block handlers to be fetched")
+    }
+
+    Assert.deepEqual(
+      getBlocksCalls->Array.map(blockNumbers =>
+        blockNumbers->Js.Array2.sortInPlaceWith((a, b) => a - b)
+      ),
+      [[9, 15, 25, 30]],
+      ~message="Should fetch the blocks of the handler, the last block and the parent of the first one",
+    )
+    Assert.deepEqual(
+      response.parsedQueueItems->Array.map(item => (item.blockNumber, item.logIndex, item.timestamp)),
+      [(15, 65535, 225), (25, 65535, 375)],
+    )
+    Assert.deepEqual(response.latestFetchedBlockNumber, 30)
+    Assert.deepEqual(response.reorgGuard.lastBlockScannedData.blockHash, "0x30")
+    Assert.deepEqual(
+      response.reorgGuard.firstBlockParentNumberAndHash->Option.map(block => block.blockHash),
+      Some("0x9"),
+    )
+
+    switch response.parsedQueueItems->Array.get(0) {
+    | Some({handler: Some(handler), event}) =>
+      await handler({
+        event,
+        context: Utils.magic("Mock context"),
+        loaderReturn: %raw(`undefined`),
+      })
+    | _ => Js.Exn.raiseError("Expected the block handler item to have a handler")
+    }
+    Assert.deepEqual(
+      handledBlocks->(Utils.magic: array<Types.BlockHandlers.block> => array<Js.Json.t>),
+      [
+        {
+          "number": 15,
+          "timestamp": 225,
+          "hash": "0x15",
+          "parentHash": "0x14",
+        }->(Utils.magic: {..} => Js.Json.t),
+      ],
+      ~message="Should run the handler with the block fields returned by the source",
+    )
+  })
+})
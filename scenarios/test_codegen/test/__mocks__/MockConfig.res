@@ -34,24 +34,31 @@ let mockChainConfig: Config.chainConfig = {
   endBlock: None,
   chain: chain1337,
   contracts,
-  source: RpcSource.make({
-    chain: chain1337,
-    contracts,
-    syncConfig: Config.getSyncConfig({
-      initialBlockInterval: 10000,
-      backoffMultiplicative: 10000.0,
-      accelerationAdditive: 10000,
-      intervalCeiling: 10000,
-      backoffMillis: 10000,
-      queryTimeoutMillis: 10000,
-    }),
-    provider: Ethers.JsonRpcProvider.make(
-      ~rpcUrls=["http://localhost:8545"],
-      ~chainId=1337,
-      ~fallbackStallTimeout=3,
-    ),
-    eventRouter: contracts
-    ->Belt.Array.flatMap(contract => contract.events)
-    ->EventRouter.fromEvmEventModsOrThrow(~chain=chain1337),
-  }),
+  blockHandlers: [],
+  sources: [
+    {
+      role: Primary,
+      source: RpcSource.make({
+        chain: chain1337,
+        contracts,
+        syncConfig: Config.getSyncConfig({
+          initialBlockInterval: 10000,
+          backoffMultiplicative: 10000.0,
+          accelerationAdditive: 10000,
+          intervalCeiling: 10000,
+          backoffMillis: 10000,
+          queryTimeoutMillis: 10000,
+        }),
+        provider: Ethers.JsonRpcProvider.make(
+          ~rpcUrls=["http://localhost:8545"],
+          ~chainId=1337,
+          ~fallbackStallTimeout=3,
+        ),
+        eventRouter: contracts
+        ->Belt.Array.flatMap(contract => contract.events)
+        ->EventRouter.fromEvmEventModsOrThrow(~chain=chain1337),
+      }),
+    },
+  ],
+  sourceStallTimeout: 20_000,
 }
@@ -11,6 +11,7 @@ module TestEntity = {
   }
 
   let name = "TestEntity"->(Utils.magic: string => Enums.EntityType.t)
+  let isImmutable = false
   let schema = S.schema(s => {
     id: s.matches(S.string),
     fieldA: s.matches(S.int),
@@ -32,7 +33,10 @@ module TestEntity = {
 }
 
 type testEntityHistory = EntityHistory.historyRow<TestEntity.t>
-let testEntityHistorySchema = EntityHistory.makeHistoryRowSchema(TestEntity.schema)
+let testEntityHistorySchema = EntityHistory.makeHistoryRowSchema(
+  TestEntity.schema,
+  ~entityTable=TestEntity.table,
+)
 
 let batchSetMockEntity = Table.PostgresInterop.makeBatchSetFn(
   ~table=TestEntity.table,
@@ -377,6 +377,128 @@ describe("FetchState.make", () => {
       )
     },
   )
+  it(
+    "Creates partitions per contract start block split by the maxAddrInPartition limit",
+    () => {
+      let fetchState = FetchState.make(
+        ~eventConfigs=[
+          {
+            contractName: "ContractA",
+            eventId: "0",
+            isWildcard: false,
+          },
+          {
+            contractName: "Gravatar",
+            eventId: "0",
+            isWildcard: false,
+          },
+        ],
+        ~staticContracts=Js.Dict.fromArray([
+          ("Gravatar", [mockAddress0]),
+          ("ContractA", [mockAddress1, mockAddress2, mockAddress3]),
+        ]),
+        ~contractStartBlocks=Js.Dict.fromArray([("ContractA", 10)]),
+        ~dynamicContracts=[],
+        ~startBlock=0,
+        ~endBlock=None,
+        ~maxAddrInPartition=2,
+      )
+
+      Assert.deepEqual(
+        fetchState.partitions,
+        [
+          {
+            id: "0",
+            status: {fetchingStateId: None},
+            latestFetchedBlock: {
+              blockNumber: 0,
+              blockTimestamp: 0,
+            },
+            selection: fetchState.normalSelection,
+            contractAddressMapping: ContractAddressingMap.fromArray([(mockAddress0, "Gravatar")]),
+            dynamicContracts: [],
+            fetchedEventQueue: [],
+          },
+          {
+            id: "1",
+            status: {fetchingStateId: None},
+            latestFetchedBlock: {
+              blockNumber: 9,
+              blockTimestamp: 0,
+            },
+            selection: fetchState.normalSelection,
+            contractAddressMapping: ContractAddressingMap.fromArray([
+              (mockAddress1, "ContractA"),
+              (mockAddress2, "ContractA"),
+            ]),
+            dynamicContracts: [],
+            fetchedEventQueue: [],
+          },
+          {
+            id: "2",
+            status: {fetchingStateId: None},
+            latestFetchedBlock: {
+              blockNumber: 9,
+              blockTimestamp: 0,
+            },
+            selection: fetchState.normalSelection,
+            contractAddressMapping: ContractAddressingMap.fromArray([(mockAddress3, "ContractA")]),
+            dynamicContracts: [],
+            fetchedEventQueue: [],
+          },
+        ],
+        ~message=`Late contracts shouldn't exceed the maxAddrInPartition limit`,
+      )
+      Assert.equal(fetchState.nextPartitionIndex, 3)
+    },
+  )
+
+  it("Doesn't create a late partition for contracts starting at the chain start block", () => {
+    let fetchState = FetchState.make(
+      ~eventConfigs=[
+        {
+          contractName: "ContractA",
+          eventId: "0",
+          isWildcard: false,
+        },
+        {
+          contractName: "Gravatar",
+          eventId: "0",
+          isWildcard: false,
+        },
+      ],
+      ~staticContracts=Js.Dict.fromArray([
+        ("Gravatar", [mockAddress0]),
+        ("ContractA", [mockAddress1]),
+      ]),
+      ~contractStartBlocks=Js.Dict.fromArray([("ContractA", 5)]),
+      ~dynamicContracts=[],
+      ~startBlock=5,
+      ~endBlock=None,
+      ~maxAddrInPartition=2,
+    )
+
+    Assert.deepEqual(
+      fetchState.partitions,
+      [
+        {
+          id: "0",
+          status: {fetchingStateId: None},
+          latestFetchedBlock: {
+            blockNumber: 4,
+            blockTimestamp: 0,
+          },
+          selection: fetchState.normalSelection,
+          contractAddressMapping: ContractAddressingMap.fromArray([
+            (mockAddress0, "Gravatar"),
+            (mockAddress1, "ContractA"),
+          ]),
+          dynamicContracts: [],
+          fetchedEventQueue: [],
+        },
+      ],
+    )
+  })
 })
 
 describe("FetchState.registerDynamicContracts", () => {
@@ -2482,3 +2604,110 @@ describe("Test queue item", () => {
     )
   })
 })
+
+describe("FetchState block handlers", () => {
+  let makeItem = (~block) => mockEvent(~blockNumber=block->Types.Block.getNumber, ~logIndex=65535)
+  let everyTenBlocks: FetchState.blockHandler = {
+    name: "EveryTenBlocks",
+    interval: 10,
+    startBlock: 5,
+    endBlock: None,
+    makeItem,
+  }
+  let everyFourBlocks: FetchState.blockHandler = {
+    name: "EveryFourBlocks",
+    interval: 4,
+    startBlock: 0,
+    endBlock: Some(20),
+    makeItem,
+  }
+
+  it("Gets the blocks of the block handlers in the range", () => {
+    Assert.deepEqual(
+      [everyTenBlocks, everyFourBlocks]->FetchState.getBlockHandlerBlockNumbers(
+        ~fromBlock=12,
+        ~toBlock=30,
+        ~maxBlocks=100,
+      ),
+      ([12, 15, 16, 20, 25], 30),
+    )
+    Assert.deepEqual(
+      [everyTenBlocks, everyFourBlocks]->FetchState.getBlockHandlerBlockNumbers(
+        ~fromBlock=12,
+        ~toBlock=30,
+        ~maxBlocks=3,
+      ),
+      ([12, 15, 16], 16),
+      ~message="Should cut the range at the last block within the limit",
+    )
+    Assert.deepEqual(
+      [everyFourBlocks]->FetchState.getBlockHandlerBlockNumbers(
+        ~fromBlock=21,
+        ~toBlock=30,
+        ~maxBlocks=100,
+      ),
+      ([], 30),
+      ~message="Shouldn't return blocks after the end block",
+    )
+  })
+
+  it("Fetches the block handlers in a separate partition and rolls it back", () => {
+    let fetchState = FetchState.make(
+      ~eventConfigs=[],
+      ~staticContracts=Js.Dict.empty(),
+      ~dynamicContracts=[],
+      ~blockHandlers=[everyTenBlocks],
+      ~startBlock=0,
+      ~endBlock=None,
+      ~maxAddrInPartition=3,
+    )
+
+    let partition = switch fetchState.partitions {
+    | [partition] => partition
+    | _ => Js.Exn.raiseError("Expected a single partition for the block handlers")
+    }
+    Assert.deepEqual(partition.selection.blockHandlers, Some([everyTenBlocks]))
+
+    let query = switch fetchState->FetchState.getNextQuery(
+      ~concurrencyLimit=10,
+      ~maxQueueSize=100,
+      ~currentBlockHeight=30,
+      ~stateId=0,
+    ) {
+    | Ready([query]) => query
+    | _ => Js.Exn.raiseError("Expected a single query for the block handlers")
+    }
+    Assert.deepEqual(query.fromBlock, 0)
+    Assert.deepEqual(query.target, FetchState.Head)
+
+    let fetchState =
+      fetchState
+      ->FetchState.setQueryResponse(
+        ~query,
+        ~latestFetchedBlock=getBlockData(~blockNumber=30),
+        ~newItems=[
+          mockEvent(~blockNumber=5, ~logIndex=65535),
+          mockEvent(~blockNumber=15, ~logIndex=65535),
+          mockEvent(~blockNumber=25, ~logIndex=65535),
+        ],
+        ~currentBlockHeight=30,
+      )
+      ->Result.getExn
+    Assert.deepEqual(fetchState.queueSize, 3)
+
+    Assert.deepEqual(
+      (fetchState->FetchState.rollback(~firstChangeEvent={blockNumber: 15, logIndex: 0})).partitions,
+      [
+        {
+          ...partition,
+          latestFetchedBlock: {
+            blockNumber: 14,
+            blockTimestamp: 0,
+          },
+          fetchedEventQueue: [mockEvent(~blockNumber=5, ~logIndex=65535)],
+        },
+      ],
+      ~message="Should remove the reorged blocks and fetch them again",
+    )
+  })
+})
@@ -132,7 +132,12 @@ describe("SourceManager fetchNext", () => {
   Async.it(
     "Executes full partitions in any order when we didn't reach concurency limit",
     async () => {
-      let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+      let sourceManager = SourceManager.make(
+        ~sources=MockConfig.mockChainConfig.sources,
+        ~stallTimeoutMillis=20_000,
+        ~maxPartitionConcurrency=10,
+        ~logger=Logging.logger,
+      )
 
       let partition0 = mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=4)
       let partition1 = mockFullPartition(~partitionIndex=1, ~latestFetchedBlockNumber=5)
@@ -195,7 +200,12 @@ describe("SourceManager fetchNext", () => {
   Async.it(
     "Slices full partitions to the concurrency limit, takes the earliest queries first",
     async () => {
-      let sourceManager = SourceManager.make(~maxPartitionConcurrency=2, ~logger=Logging.logger)
+      let sourceManager = SourceManager.make(
+        ~sources=MockConfig.mockChainConfig.sources,
+        ~stallTimeoutMillis=20_000,
+        ~maxPartitionConcurrency=2,
+        ~logger=Logging.logger,
+      )
 
       let partition0 = mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=4)
       let partition1 = mockFullPartition(~partitionIndex=1, ~latestFetchedBlockNumber=5)
@@ -251,7 +261,12 @@ describe("SourceManager fetchNext", () => {
   Async.it(
     "Skips full partitions at the chain last block and the ones at the endBlock",
     async () => {
-      let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+      let sourceManager = SourceManager.make(
+        ~sources=MockConfig.mockChainConfig.sources,
+        ~stallTimeoutMillis=20_000,
+        ~maxPartitionConcurrency=10,
+        ~logger=Logging.logger,
+      )
 
       let p0 = mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=4)
       let p1 = mockFullPartition(~partitionIndex=1, ~latestFetchedBlockNumber=5)
@@ -286,7 +301,12 @@ describe("SourceManager fetchNext", () => {
   )
 
   Async.it("Starts indexing from the initial state", async () => {
-    let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+    let sourceManager = SourceManager.make(
+      ~sources=MockConfig.mockChainConfig.sources,
+      ~stallTimeoutMillis=20_000,
+      ~maxPartitionConcurrency=10,
+      ~logger=Logging.logger,
+    )
 
     let waitForNewBlockMock = waitForNewBlockMock()
     let onNewBlockMock = onNewBlockMock()
@@ -336,7 +356,12 @@ describe("SourceManager fetchNext", () => {
   Async.it(
     "Waits for new block with currentBlockHeight=0 even when all partitions are done",
     async () => {
-      let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+      let sourceManager = SourceManager.make(
+        ~sources=MockConfig.mockChainConfig.sources,
+        ~stallTimeoutMillis=20_000,
+        ~maxPartitionConcurrency=10,
+        ~logger=Logging.logger,
+      )
 
       let waitForNewBlockMock = waitForNewBlockMock()
       let onNewBlockMock = onNewBlockMock()
@@ -365,7 +390,12 @@ describe("SourceManager fetchNext", () => {
   )
 
   Async.it("Waits for new block when all partitions are at the currentBlockHeight", async () => {
-    let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+    let sourceManager = SourceManager.make(
+      ~sources=MockConfig.mockChainConfig.sources,
+      ~stallTimeoutMillis=20_000,
+      ~maxPartitionConcurrency=10,
+      ~logger=Logging.logger,
+    )
 
     let p0 = mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=5)
     let p1 = mockFullPartition(~partitionIndex=1, ~latestFetchedBlockNumber=5)
@@ -410,7 +440,12 @@ describe("SourceManager fetchNext", () => {
   })
 
   Async.it("Restarts waiting for new block after a rollback", async () => {
-    let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+    let sourceManager = SourceManager.make(
+      ~sources=MockConfig.mockChainConfig.sources,
+      ~stallTimeoutMillis=20_000,
+      ~maxPartitionConcurrency=10,
+      ~logger=Logging.logger,
+    )
 
     let p0 = mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=5)
 
@@ -476,7 +511,12 @@ describe("SourceManager fetchNext", () => {
   })
 
   Async.it("Can add new partitions until the concurrency limit reached", async () => {
-    let sourceManager = SourceManager.make(~maxPartitionConcurrency=3, ~logger=Logging.logger)
+    let sourceManager = SourceManager.make(
+      ~sources=MockConfig.mockChainConfig.sources,
+      ~stallTimeoutMillis=20_000,
+      ~maxPartitionConcurrency=3,
+      ~logger=Logging.logger,
+    )
 
     let p0 = mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=4)
     let p1 = mockFullPartition(~partitionIndex=1, ~latestFetchedBlockNumber=5)
@@ -604,7 +644,12 @@ describe("SourceManager fetchNext", () => {
   })
 
   Async.it("Should not query partitions that are at max queue size", async () => {
-    let sourceManager = SourceManager.make(~maxPartitionConcurrency=10, ~logger=Logging.logger)
+    let sourceManager = SourceManager.make(
+      ~sources=MockConfig.mockChainConfig.sources,
+      ~stallTimeoutMillis=20_000,
+      ~maxPartitionConcurrency=10,
+      ~logger=Logging.logger,
+    )
 
     let executeQueryMock = executeQueryMock()
 
@@ -645,7 +690,12 @@ describe("SourceManager fetchNext", () => {
   })
 
   Async.it("Sorts after all the filtering is applied", async () => {
-    let sourceManager = SourceManager.make(~maxPartitionConcurrency=1, ~logger=Logging.logger)
+    let sourceManager = SourceManager.make(
+      ~sources=MockConfig.mockChainConfig.sources,
+      ~stallTimeoutMillis=20_000,
+      ~maxPartitionConcurrency=1,
+      ~logger=Logging.logger,
+    )
 
     let executeQueryMock = executeQueryMock()
 
@@ -681,4 +731,156 @@ describe("SourceManager fetchNext", () => {
 
     Assert.deepEqual(executeQueryMock.callIds, ["4"])
   })
+
+  let mockSource = (~name): Source.t => {"name": name}->Utils.magic
+  let mockQuery: FetchState.query = {"partitionId": "0"}->Utils.magic
+  let mockResponse: Source.blockRangeFetchResponse = {"fromBlockQueried": 0}->Utils.magic
+
+  Async.it("Switches to the fallback source when the active source stalls", async () => {
+    let primary = mockSource(~name="Primary")
+    let fallback = mockSource(~name="Fallback")
+    let sourceManager = SourceManager.make(
+      ~sources=[{role: Primary, source: primary}, {role: Fallback, source: fallback}],
+      ~stallTimeoutMillis=10,
+      ~maxPartitionConcurrency=10,
+      ~logger=Logging.logger,
+    )
+
+    Assert.equal(sourceManager->SourceManager.getActiveSource, primary)
+
+    let calledSources = []
+    let result = await sourceManager->SourceManager.executeQueryWithFailover(
+      ~query=mockQuery,
+      ~executeQuery=(_, ~source) => {
+        calledSources->Js.Array2.push(source.name)->ignore
+        source === primary ? Promise.make((_, _) => ()) : Promise.resolve(Ok(mockResponse))
+      },
+    )
+
+    Assert.deepEqual(result, Ok(mockResponse))
+    Assert.deepEqual(calledSources, ["Primary", "Fallback"])
+    Assert.equal(sourceManager->SourceManager.getActiveSource, fallback)
+  })
+
+  Async.it("Uses the realtime source once the head is reached", async () => {
+    let historical = mockSource(~name="Historical")
+    let realtime = mockSource(~name="Realtime")
+    let sourceManager = SourceManager.make(
+      ~sources=[{role: Historical, source: historical}, {role: Realtime, source: realtime}],
+      ~stallTimeoutMillis=10,
+      ~maxPartitionConcurrency=10,
+      ~logger=Logging.logger,
+    )
+
+    let waitedSources = []
+    let height = await sourceManager->SourceManager.waitForNewBlockWithFailover(
+      ~currentBlockHeight=0,
+      ~logger=Logging.logger,
+      ~waitForNewBlock=(source, ~currentBlockHeight as _, ~logger as _) => {
+        waitedSources->Js.Array2.push(source.name)->ignore
+        Promise.resolve(20)
+      },
+    )
+    Assert.equal(height, 20)
+    Assert.deepEqual(
+      waitedSources,
+      ["Historical"],
+      ~message="Should keep the historical source until fetchNext reaches the head",
+    )
+
+    await sourceManager->SourceManager.fetchNext(
+      ~fetchState=mockFetchState([
+        mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=0),
+      ]),
+      ~maxPerChainQueueSize=1000,
+      ~currentBlockHeight=0,
+      ~executeQuery=neverExecutePartitionQuery,
+      ~waitForNewBlock=(~currentBlockHeight, ~logger) =>
+        sourceManager->SourceManager.waitForNewBlockWithFailover(
+          ~currentBlockHeight,
+          ~logger,
+          ~waitForNewBlock=(source, ~currentBlockHeight as _, ~logger as _) => {
+            waitedSources->Js.Array2.push(source.name)->ignore
+            Promise.resolve(20)
+          },
+        ),
+      ~onNewBlock=(~currentBlockHeight as _) => (),
+      ~stateId=0,
+    )
+
+    Assert.deepEqual(waitedSources, ["Historical", "Realtime"])
+    Assert.equal(sourceManager->SourceManager.getActiveSource, realtime)
+  })
+
+  Async.it(
+    "Switches back to the historical source when the head gets far ahead of the fetched range",
+    async () => {
+      let historical = mockSource(~name="Historical")
+      let realtime = mockSource(~name="Realtime")
+      let sourceManager = SourceManager.make(
+        ~sources=[{role: Historical, source: historical}, {role: Realtime, source: realtime}],
+        ~stallTimeoutMillis=10,
+        ~maxPartitionConcurrency=10,
+        ~logger=Logging.logger,
+      )
+
+      // Reach the head at block 20
+      await sourceManager->SourceManager.fetchNext(
+        ~fetchState=mockFetchState([
+          mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=20),
+        ]),
+        ~maxPerChainQueueSize=1000,
+        ~currentBlockHeight=20,
+        ~executeQuery=neverExecutePartitionQuery,
+        ~waitForNewBlock=(~currentBlockHeight, ~logger) =>
+          sourceManager->SourceManager.waitForNewBlockWithFailover(
+            ~currentBlockHeight,
+            ~logger,
+            ~waitForNewBlock=(_, ~currentBlockHeight as _, ~logger as _) => Promise.resolve(21),
+          ),
+        ~onNewBlock=(~currentBlockHeight as _) => (),
+        ~stateId=0,
+      )
+      Assert.equal(sourceManager->SourceManager.getActiveSource, realtime)
+
+      let queriedSources = []
+      let executeQuery = query =>
+        sourceManager->SourceManager.executeQueryWithFailover(
+          ~query,
+          ~executeQuery=(_, ~source) => {
+            queriedSources->Js.Array2.push(source.name)->ignore
+            Promise.resolve(Ok(mockResponse))
+          },
+        )
+
+      // A new block right after the fetched range keeps the realtime source
+      await sourceManager->SourceManager.fetchNext(
+        ~fetchState=mockFetchState([
+          mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=20),
+        ]),
+        ~maxPerChainQueueSize=1000,
+        ~currentBlockHeight=21,
+        ~executeQuery,
+        ~waitForNewBlock=neverWaitForNewBlock,
+        ~onNewBlock=neverOnNewBlock,
+        ~stateId=0,
+      )
+      Assert.deepEqual(queriedSources, ["Realtime"])
+
+      // The head is more than maxRealtimeLagBlocks ahead after a long stall
+      await sourceManager->SourceManager.fetchNext(
+        ~fetchState=mockFetchState([
+          mockFullPartition(~partitionIndex=0, ~latestFetchedBlockNumber=21),
+        ]),
+        ~maxPerChainQueueSize=1000,
+        ~currentBlockHeight=100_000,
+        ~executeQuery,
+        ~waitForNewBlock=neverWaitForNewBlock,
+        ~onNewBlock=neverOnNewBlock,
+        ~stateId=0,
+      )
+      Assert.deepEqual(queriedSources, ["Realtime", "Historical"])
+      Assert.equal(sourceManager->SourceManager.getActiveSource, historical)
+    },
+  )
 })
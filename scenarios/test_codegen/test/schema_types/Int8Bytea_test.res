@@ -0,0 +1,149 @@
+open RescriptMocha
+
+describe("Load and save entities with Int8 and bytea from DB", () => {
+  let config = RegisterHandlers.registerAllHandlers()
+
+  Async.before(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  Async.after(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  let setEntity = (inMemoryStore, entity: Entities.EntityWithInt8AndBytea.t, ~blockNumber) => {
+    inMemoryStore
+    ->InMemoryStore.getInMemTable(
+      ~entityMod=module(Entities.EntityWithInt8AndBytea)->Entities.entityModToInternal,
+    )
+    ->InMemoryTable.Entity.set(
+      Set(entity->(Utils.magic: Entities.EntityWithInt8AndBytea.t => Entities.internalEntity))
+      ->Types.mkEntityUpdate(
+        ~eventIdentifier={
+          chainId: 1337,
+          blockTimestamp: blockNumber * 10,
+          blockNumber,
+          logIndex: 0,
+        },
+        ~entityId=entity.id,
+      ),
+      ~shouldSaveHistory=true,
+    )
+  }
+
+  let batchRead = DbFunctionsEntities.batchRead(~entityMod=module(Entities.EntityWithInt8AndBytea))
+  let read = async ids =>
+    (await Db.sql->batchRead(ids))->Js.Array2.sortInPlaceWith((
+      a: Entities.EntityWithInt8AndBytea.t,
+      b,
+    ) => compare(a.id, b.id))
+
+  //Larger than the max safe integer, so it's only kept as a bigint
+  let entity: Entities.EntityWithInt8AndBytea.t = {
+    id: "1",
+    amount: BigInt.fromStringUnsafe("9007199254740993"),
+    hash: "0xdeadbeef",
+    optHash: None,
+  }
+
+  let updatedEntity: Entities.EntityWithInt8AndBytea.t = {
+    id: "1",
+    amount: BigInt.fromStringUnsafe("-9007199254740993"),
+    hash: "0xcafe",
+    optHash: Some("0x00ff"),
+  }
+
+  let newEntity: Entities.EntityWithInt8AndBytea.t = {
+    id: "2",
+    amount: BigInt.fromInt(1),
+    hash: "0x01",
+    optHash: None,
+  }
+
+  Async.it("Writes and reads the Int8 and bytea fields", async () => {
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setEntity(entity, ~blockNumber=10)
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    Assert.deepEqual(await read(["1"]), [entity])
+
+    let rows: array<{"amount": string, "hash": string, "optHash": Js.Nullable.t<string>}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "amount"::TEXT AS "amount", encode("hash", 'hex') AS "hash", "optHash" FROM public."EntityWithInt8AndBytea";`,
+      )
+    Assert.deepEqual(
+      rows->Js.Array2.map(row => (
+        row["amount"],
+        row["hash"],
+        row["optHash"]->Js.Nullable.toOption,
+      )),
+      [("9007199254740993", "deadbeef", None)],
+      ~message="Should store the amount as a bigint and the hash as bytes",
+    )
+  })
+
+  Async.it("Serves the bytea fields as 0x prefixed hex strings", async () => {
+    let rows: array<{"hash": string, "optHash": Js.Nullable.t<string>}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT public."EntityWithInt8AndBytea_hash_hex"(e) AS "hash", public."EntityWithInt8AndBytea_optHash_hex"(e) AS "optHash" FROM public."EntityWithInt8AndBytea" e;`,
+      )
+    Assert.deepEqual(
+      rows->Js.Array2.map(row => (row["hash"], row["optHash"]->Js.Nullable.toOption)),
+      [("0xdeadbeef", None)],
+      ~message="Should serve the hex strings of the computed fields, or null for a missing value",
+    )
+  })
+
+  Async.it("Saves the fields in the entity history and rolls them back", async () => {
+    let inMemoryStore = InMemoryStore.make()
+    inMemoryStore->setEntity(updatedEntity, ~blockNumber=20)
+    inMemoryStore->setEntity(newEntity, ~blockNumber=20)
+    let _ = await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+
+    Assert.deepEqual(await read(["1", "2"]), [updatedEntity, newEntity])
+
+    let historyRows: array<{
+      "id": string,
+      "amount": string,
+      "hash": string,
+      "optHash": Js.Nullable.t<string>,
+    }> = await Db.sql->Postgres.unsafe(
+      `SELECT "id", "amount"::TEXT AS "amount", encode("hash", 'hex') AS "hash", encode("optHash", 'hex') AS "optHash"
+      FROM public."EntityWithInt8AndBytea_history" ORDER BY "entity_history_block_number", "id";`,
+    )
+    Assert.deepEqual(
+      historyRows->Js.Array2.map(row => (
+        row["id"],
+        row["amount"],
+        row["hash"],
+        row["optHash"]->Js.Nullable.toOption,
+      )),
+      [
+        ("1", "9007199254740993", "deadbeef", None),
+        ("1", "-9007199254740993", "cafe", Some("00ff")),
+        ("2", "1", "01", None),
+      ],
+      ~message="Should save the values of every change in the history",
+    )
+
+    let rollbackInMemoryStore = await IO.RollBack.rollBack(
+      ~chainId=1337,
+      ~blockTimestamp=150,
+      ~blockNumber=15,
+      ~logIndex=0,
+      ~isUnorderedMultichainMode=true,
+    )
+    let _ =
+      await Db.sql->IO.executeBatch(
+        ~inMemoryStore=rollbackInMemoryStore,
+        ~isInReorgThreshold=true,
+        ~config,
+      )
+
+    Assert.deepEqual(
+      await read(["1", "2"]),
+      [entity],
+      ~message="Should restore the values from before the reorg and delete the new entity",
+    )
+  })
+})
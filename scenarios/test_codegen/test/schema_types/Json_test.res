@@ -0,0 +1,124 @@
+open RescriptMocha
+
+describe("Load and save entities with Json from DB", () => {
+  Async.before(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  Async.after(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  let values = [
+    ("object", %raw(`{"kind": "swap", "amounts": [1, 2]}`)),
+    ("array", %raw(`["a", {"b": 1}]`)),
+    ("string", Js.Json.string("swap")),
+    ("number", Js.Json.number(1.5)),
+  ]
+
+  Async.it("Writes objects, arrays, strings and numbers as jsonb", async () => {
+    await DbFunctionsEntities.batchSet(~entityMod=module(Entities.EntityWithJson))(
+      Db.sql,
+      values->Js.Array2.map(((id, data)): Entities.EntityWithJson.t => {
+        id,
+        data,
+        optData: id == "object" ? Some(data) : None,
+      }),
+    )
+
+    let rows: array<{"id": string, "data": Js.Json.t, "optData": Js.Nullable.t<Js.Json.t>}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "id", "data", "optData" FROM public."EntityWithJson" ORDER BY "id";`,
+      )
+    Assert.deepEqual(
+      rows->Js.Array2.map(row => (row["id"], row["data"])),
+      values->Js.Array2.copy->Js.Array2.sortInPlaceWith(((a, _), (b, _)) => compare(a, b)),
+    )
+    Assert.deepEqual(
+      rows->Js.Array2.map(row => row["optData"]->Js.Nullable.toOption),
+      [None, None, values->Js.Array2.unsafe_get(0)->snd->Some, None],
+      ~message="Should write the missing optional values as null",
+    )
+
+    let typeRows: array<{"id": string, "type": string}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "id", jsonb_typeof("data") AS "type" FROM public."EntityWithJson" ORDER BY "id";`,
+      )
+    Assert.deepEqual(
+      typeRows->Js.Array2.map(row => row["type"]),
+      ["array", "number", "object", "string"],
+      ~message="Should keep the json type of every value",
+    )
+  })
+
+  Async.it("Queries the values with the gin index", async () => {
+    let indexRows: array<{"indexdef": string}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "indexdef" FROM pg_indexes WHERE "indexname" = 'EntityWithJson_data';`,
+      )
+    Assert.ok(
+      indexRows->Js.Array2.some(row => row["indexdef"]->Js.String2.includes("USING gin")),
+      ~message="Should create a gin index on the field",
+    )
+
+    let rows: array<{"id": string}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "id" FROM public."EntityWithJson" WHERE "data" @> '{"kind": "swap"}'::JSONB;`,
+      )
+    Assert.deepEqual(rows->Js.Array2.map(row => row["id"]), ["object"])
+  })
+
+  Async.it("Writes json values of entities with array fields", async () => {
+    await DbFunctionsEntities.batchSet(~entityMod=module(Entities.EntityWithJsonAndArray))(
+      Db.sql,
+      values->Js.Array2.map(((id, data)): Entities.EntityWithJsonAndArray.t => {
+        id,
+        data,
+        tags: [id],
+      }),
+    )
+
+    let rows: array<{"id": string, "data": Js.Json.t}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "id", "data" FROM public."EntityWithJsonAndArray" ORDER BY "id";`,
+      )
+    Assert.deepEqual(
+      rows->Js.Array2.map(row => (row["id"], row["data"])),
+      values->Js.Array2.copy->Js.Array2.sortInPlaceWith(((a, _), (b, _)) => compare(a, b)),
+    )
+  })
+
+  Async.it("Saves the json values in the entity history", async () => {
+    let inMemoryStore = InMemoryStore.make()
+    values->Js.Array2.forEach(((id, data)) => {
+      let entity: Entities.EntityWithJson.t = {id: `history-${id}`, data, optData: None}
+      inMemoryStore
+      ->InMemoryStore.getInMemTable(
+        ~entityMod=module(Entities.EntityWithJson)->Entities.entityModToInternal,
+      )
+      ->InMemoryTable.Entity.set(
+        Set(entity->(Utils.magic: Entities.EntityWithJson.t => Entities.internalEntity))
+        ->Types.mkEntityUpdate(
+          ~eventIdentifier={chainId: 1, blockTimestamp: 10, blockNumber: 1, logIndex: 0},
+          ~entityId=entity.id,
+        ),
+        ~shouldSaveHistory=true,
+      )
+    })
+    await Db.sql->IO.executeSetEntityWithHistory(
+      ~inMemoryStore,
+      ~entityMod=module(Entities.EntityWithJson),
+    )
+
+    let rows: array<{"id": string, "data": Js.Json.t}> =
+      await Db.sql->Postgres.unsafe(
+        `SELECT "id", "data" FROM public."EntityWithJson_history" ORDER BY "id";`,
+      )
+    Assert.deepEqual(
+      rows->Js.Array2.map(row => (row["id"], row["data"])),
+      values
+      ->Js.Array2.map(((id, data)) => (`history-${id}`, data))
+      ->Js.Array2.sortInPlaceWith(((a, _), (b, _)) => compare(a, b)),
+    )
+  })
+})